@@ -0,0 +1,23 @@
+-- Soft delete for contacts
+-- Deleting a contact only stamps deleted_at; emails, phones and contact_sources
+-- are kept so the contact can be restored. Rows are physically removed by the
+-- retention job once deleted_at is older than CONTACT_RETENTION_DAYS.
+
+ALTER TABLE contacts ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ NULL;
+
+-- Partial index used by the retention purge
+CREATE INDEX IF NOT EXISTS contacts_deleted_at_idx ON contacts(deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Include deleted_at in the strong ETag so delete/restore invalidate cached copies
+CREATE OR REPLACE FUNCTION compute_contact_etag(c contacts) RETURNS text AS $$
+BEGIN
+  RETURN encode(digest(
+    coalesce(c.full_name,'') || '|' ||
+    coalesce(c.status,'') || '|' ||
+    coalesce(c.unit_id::text,'') || '|' ||
+    coalesce(c.department_id::text,'') || '|' ||
+    coalesce(c.document,'') || '|' ||
+    coalesce(c.deleted_at::text,'')
+  ,'sha256'),'hex');
+END
+$$ LANGUAGE plpgsql IMMUTABLE;
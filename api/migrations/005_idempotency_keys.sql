@@ -0,0 +1,17 @@
+-- Idempotency-Key storage for POST endpoints
+-- A row is inserted when a request with a new key starts (status_code NULL) and
+-- completed with the response once the handler finishes. Replays within
+-- expires_at get the stored response; expired rows are reused or purged.
+
+CREATE TABLE IF NOT EXISTS idempotency_keys(
+  scope TEXT NOT NULL,
+  key TEXT NOT NULL,
+  request_hash TEXT NOT NULL,
+  status_code INT NULL,
+  response_headers JSONB NULL,
+  response_body BYTEA NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  expires_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY(scope, key)
+);
+CREATE INDEX IF NOT EXISTS idempotency_keys_expires_at_idx ON idempotency_keys(expires_at);
@@ -0,0 +1,14 @@
+-- Token revocation list
+-- A row revokes every token whose claim `kind` (jti, sid or sub) equals `value`.
+-- Rows are only needed until the revoked tokens expire; the cleanup job deletes
+-- them once expires_at has passed.
+
+CREATE TABLE IF NOT EXISTS revoked_tokens(
+  kind TEXT NOT NULL CHECK (kind IN ('jti','sid','sub')),
+  value TEXT NOT NULL,
+  reason TEXT NULL,
+  revoked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  expires_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY(kind, value)
+);
+CREATE INDEX IF NOT EXISTS revoked_tokens_expires_at_idx ON revoked_tokens(expires_at);
@@ -0,0 +1,17 @@
+-- API keys for machine-to-machine clients (ETL jobs, metrics scraper, webhooks)
+-- Only the SHA-256 of the key is stored; the plaintext is shown once when the
+-- key is issued or rotated. `prefix` is the public part of the key used for
+-- lookup. Revoked keys are kept (revoked_at set) for auditing.
+
+CREATE TABLE IF NOT EXISTS api_keys(
+  id UUID PRIMARY KEY,
+  name TEXT NOT NULL,
+  prefix TEXT NOT NULL UNIQUE,
+  key_hash TEXT NOT NULL,
+  scopes TEXT[] NOT NULL DEFAULT '{}',
+  created_by TEXT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  expires_at TIMESTAMPTZ NULL,
+  last_used_at TIMESTAMPTZ NULL,
+  revoked_at TIMESTAMPTZ NULL
+);
@@ -0,0 +1,13 @@
+-- Token buckets for rate limiting shared between replicas (RATE_LIMIT_STORE=postgres)
+-- `key` is "<route class>:<subject or ip:address>". `allowed` records the
+-- outcome of the last acquisition so the upsert can report it atomically.
+-- Idle buckets are already full and are purged periodically.
+
+CREATE TABLE IF NOT EXISTS rate_limit_buckets(
+  key TEXT PRIMARY KEY,
+  tokens DOUBLE PRECISION NOT NULL,
+  allowed BOOLEAN NOT NULL,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_buckets_updated_at ON rate_limit_buckets(updated_at);
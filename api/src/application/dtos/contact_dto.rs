@@ -5,8 +5,10 @@
 // Seguem o padrão de separação entre camadas da Clean Architecture
 
 use crate::domain::entities::Contact;
+use crate::domain::errors::DomainError;
 use crate::domain::value_objects::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // ============================================================================
@@ -15,7 +17,7 @@ use uuid::Uuid;
 
 /// DTO para criação de novos contatos
 /// Contém todos os dados necessários para criar um contato
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateContactRequest {
     /// Nome completo do contato
     pub full_name: String,
@@ -37,7 +39,7 @@ pub struct CreateContactRequest {
 
 /// DTO para atualização de contatos existentes
 /// Todos os campos são opcionais para permitir atualização parcial
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateContactRequest {
     /// ID do contato (não serializado, vem da URL)
     #[serde(skip)]
@@ -62,9 +64,33 @@ pub struct UpdateContactRequest {
     pub etag: String,
 }
 
+/// Documento editável de um contato, alvo dos patches RFC 7396 / RFC 6902
+/// Campos somente leitura (id, etag, datas) não fazem parte do documento
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContactPatchDocument {
+    /// Nome completo do contato
+    pub full_name: String,
+    /// Tipo do contato
+    pub contact_type: String,
+    /// Status do contato
+    pub status: String,
+    /// Documento de identificação (`null` limpa o campo)
+    pub document: Option<String>,
+    /// ID da unidade organizacional (`null` limpa o campo)
+    pub unit_id: Option<Uuid>,
+    /// ID do departamento (`null` limpa o campo)
+    pub department_id: Option<Uuid>,
+    /// Lista de emails
+    pub emails: Vec<Email>,
+    /// Lista de telefones
+    pub phones: Vec<Phone>,
+}
+
 /// DTO para busca de contatos com filtros
 /// Suporta paginação e filtros diversos
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ContactSearchRequest {
     /// Termo de busca geral (nome, documento, etc.)
     pub search_term: Option<String>,
@@ -76,19 +102,125 @@ pub struct ContactSearchRequest {
     pub unit_id: Option<Uuid>,
     /// Filtro por departamento
     pub department_id: Option<Uuid>,
+    /// Inclui contatos excluídos logicamente (somente administradores)
+    pub include_deleted: Option<bool>,
     /// Limite de resultados por página
     pub limit: Option<i64>,
     /// Offset para paginação
     pub offset: Option<i64>,
 }
 
+/// DTO para consulta de estatísticas de contatos
+/// Define o intervalo (datas UTC, inclusivas) e a granularidade das séries temporais
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContactStatisticsRequest {
+    /// Data inicial (padrão: 29 dias antes de `to`)
+    pub from: Option<chrono::NaiveDate>,
+    /// Data final (padrão: hoje)
+    pub to: Option<chrono::NaiveDate>,
+    /// Granularidade: day, week ou month (padrão: day)
+    pub bucket: Option<String>,
+}
+
+// ============================================================================
+// BATCH DTOs - OPERAÇÕES EM LOTE
+// ============================================================================
+
+/// Número máximo de operações aceitas em um único lote
+pub const MAX_CONTACT_BATCH_OPERATIONS: usize = 1000;
+
+/// Modo de execução de um lote de operações
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// Tudo ou nada: qualquer falha impede a aplicação de todo o lote
+    #[default]
+    Transaction,
+    /// Cada operação é aplicada (ou falha) de forma independente
+    PerItem,
+}
+
+/// DTO para execução de operações em lote sobre contatos
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ContactBatchRequest {
+    /// Modo de execução (padrão: transaction)
+    #[serde(default)]
+    pub mode: BatchMode,
+    /// Operações a executar, na ordem informada
+    pub operations: Vec<ContactBatchOperation>,
+}
+
+/// Operação individual de um lote; cada item carrega o ETag do contato
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ContactBatchOperation {
+    /// Atualização parcial, com a mesma semântica do PATCH em JSON
+    Update {
+        id: Uuid,
+        etag: String,
+        changes: ContactBatchChanges,
+    },
+    /// Exclusão lógica
+    Delete { id: Uuid, etag: String },
+    /// Alteração de status
+    SetStatus {
+        id: Uuid,
+        etag: String,
+        status: String,
+    },
+    /// Movimentação para outra unidade e/ou departamento
+    Move {
+        id: Uuid,
+        etag: String,
+        unit_id: Option<Uuid>,
+        department_id: Option<Uuid>,
+    },
+}
+
+/// Campos alteráveis por uma operação `update` em lote
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContactBatchChanges {
+    pub full_name: Option<String>,
+    pub contact_type: Option<String>,
+    pub status: Option<String>,
+    pub document: Option<String>,
+    pub unit_id: Option<Uuid>,
+    pub department_id: Option<Uuid>,
+    pub emails: Option<Vec<Email>>,
+    pub phones: Option<Vec<Phone>>,
+}
+
+impl ContactBatchOperation {
+    /// ID do contato alvo da operação
+    pub fn id(&self) -> Uuid {
+        match self {
+            ContactBatchOperation::Update { id, .. }
+            | ContactBatchOperation::Delete { id, .. }
+            | ContactBatchOperation::SetStatus { id, .. }
+            | ContactBatchOperation::Move { id, .. } => *id,
+        }
+    }
+
+    /// Nome da operação, como informado no campo `op`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContactBatchOperation::Update { .. } => "update",
+            ContactBatchOperation::Delete { .. } => "delete",
+            ContactBatchOperation::SetStatus { .. } => "set_status",
+            ContactBatchOperation::Move { .. } => "move",
+        }
+    }
+}
+
 // ============================================================================
 // RESPONSE DTOs - DADOS DE SAÍDA
 // ============================================================================
 
 /// DTO de resposta para contatos individuais
 /// Contém todos os dados de um contato para resposta da API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ContactResponse {
     /// ID único do contato
     pub id: Uuid,
@@ -114,11 +246,14 @@ pub struct ContactResponse {
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Data da última atualização
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Data da exclusão lógica (ausente para contatos ativos na base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// DTO de resposta para busca de contatos
 /// Contém lista paginada de contatos e metadados
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ContactSearchResponse {
     /// Lista de contatos encontrados
     pub items: Vec<ContactResponse>,
@@ -128,7 +263,7 @@ pub struct ContactSearchResponse {
 
 /// DTO de resposta para estatísticas de contatos
 /// Contém dados agregados para dashboards e relatórios
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ContactStatisticsResponse {
     /// Total de contatos
     pub total_contacts: i64,
@@ -140,8 +275,133 @@ pub struct ContactStatisticsResponse {
     pub persons: i64,
     /// Organizações
     pub organizations: i64,
-    /// Departamentos
+    /// Departamentos cadastrados
     pub departments: i64,
+    /// Contatos do tipo DEPARTMENT
+    pub department_contacts: i64,
+    /// Contatos por unidade organizacional
+    pub by_org_unit: Vec<ContactOrgUnitCountResponse>,
+    /// Contatos por departamento
+    pub by_department: Vec<ContactDepartmentCountResponse>,
+    /// Contatos por status
+    pub by_status: std::collections::BTreeMap<String, i64>,
+    /// Contatos por sistema de origem
+    pub by_source: std::collections::BTreeMap<String, i64>,
+    /// Séries temporais de criação e atualização
+    pub time_series: ContactTimeSeriesResponse,
+    /// Indicadores de qualidade dos dados
+    pub data_quality: ContactDataQualityResponse,
+    /// Momento em que as estatísticas foram calculadas (podem vir do cache)
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Contagem de contatos de uma unidade organizacional
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactOrgUnitCountResponse {
+    /// ID da unidade organizacional
+    pub unit_id: Uuid,
+    /// Contatos vinculados diretamente à unidade
+    pub contacts: i64,
+    /// Contatos da unidade e de todas as suas descendentes
+    pub contacts_including_descendants: i64,
+}
+
+/// Contagem de contatos de um departamento
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactDepartmentCountResponse {
+    /// ID do departamento
+    pub department_id: Uuid,
+    /// Contatos vinculados ao departamento
+    pub contacts: i64,
+}
+
+/// Séries temporais sobre o intervalo consultado
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactTimeSeriesResponse {
+    /// Início do intervalo (inclusivo)
+    pub from: chrono::DateTime<chrono::Utc>,
+    /// Fim do intervalo (exclusivo)
+    pub to: chrono::DateTime<chrono::Utc>,
+    /// Granularidade dos intervalos
+    pub bucket: StatisticsBucket,
+    /// Contatos criados por intervalo
+    pub created: Vec<TimeSeriesPointResponse>,
+    /// Contatos atualizados por intervalo
+    pub updated: Vec<TimeSeriesPointResponse>,
+}
+
+/// Ponto de uma série temporal
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TimeSeriesPointResponse {
+    /// Início do intervalo
+    pub bucket_start: chrono::DateTime<chrono::Utc>,
+    /// Quantidade no intervalo
+    pub count: i64,
+}
+
+/// Indicadores de qualidade dos dados de contatos
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactDataQualityResponse {
+    /// Contatos sem email primário
+    pub missing_primary_email: i64,
+    /// Contatos sem telefone primário
+    pub missing_primary_phone: i64,
+    /// Contatos com documento que não é um CPF/CNPJ válido
+    pub invalid_document: i64,
+}
+
+/// Resultado de uma operação de lote, antes do mapeamento para HTTP
+#[derive(Debug, Clone)]
+pub enum ContactBatchItemResult {
+    /// Operação aplicada; contém o contato resultante (ausente em `delete`)
+    Applied(Option<Box<ContactResponse>>),
+    /// Operação rejeitada pelo caso de uso ou pelo repositório
+    Failed(DomainError),
+    /// Operação válida, mas descartada porque outra operação do lote falhou
+    NotApplied,
+}
+
+/// Resultado de um lote, alinhado por índice com as operações recebidas
+#[derive(Debug, Clone)]
+pub struct ContactBatchResult {
+    pub mode: BatchMode,
+    /// Indica se alguma alteração foi persistida
+    pub committed: bool,
+    pub items: Vec<(ContactBatchOperation, ContactBatchItemResult)>,
+}
+
+/// DTO de resposta para um item do lote
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactBatchItemResponse {
+    /// Posição da operação no lote
+    pub index: usize,
+    /// Operação executada
+    pub op: String,
+    /// ID do contato alvo
+    pub id: Uuid,
+    /// Código de status HTTP do item
+    pub status: u16,
+    /// Contato resultante (operações aplicadas, exceto `delete`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<ContactResponse>,
+    /// Erro do item, no mesmo formato das respostas de erro da API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+}
+
+/// DTO de resposta para execução de operações em lote
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContactBatchResponse {
+    /// Modo de execução utilizado
+    pub mode: BatchMode,
+    /// Indica se alguma alteração foi persistida
+    pub committed: bool,
+    /// Quantidade de operações aplicadas
+    pub succeeded: usize,
+    /// Quantidade de operações não aplicadas
+    pub failed: usize,
+    /// Resultado por operação, na ordem do lote
+    pub results: Vec<ContactBatchItemResponse>,
 }
 
 // ============================================================================
@@ -165,6 +425,84 @@ impl From<Contact> for ContactResponse {
             etag: contact.etag,
             created_at: contact.created_at,
             updated_at: contact.updated_at,
+            deleted_at: contact.deleted_at,
+        }
+    }
+}
+
+/// Constrói o documento editável a partir da entidade de domínio
+impl From<&Contact> for ContactPatchDocument {
+    fn from(contact: &Contact) -> Self {
+        ContactPatchDocument {
+            full_name: contact.full_name.clone(),
+            contact_type: contact.contact_type.to_string(),
+            status: contact.status.to_string(),
+            document: contact.document.clone(),
+            unit_id: contact.unit_id.as_ref().map(|id| id.0),
+            department_id: contact.department_id.as_ref().map(|id| id.0),
+            emails: contact.emails.clone(),
+            phones: contact.phones.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_batch_request_defaults_to_transaction_mode() {
+        let request: ContactBatchRequest = serde_json::from_value(json!({
+            "operations": [
+                {"op": "delete", "id": Uuid::nil(), "etag": "abc"}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(request.mode, BatchMode::Transaction);
+        assert_eq!(request.operations[0].name(), "delete");
+    }
+
+    #[test]
+    fn test_batch_operations_are_tagged_by_op() {
+        let request: ContactBatchRequest = serde_json::from_value(json!({
+            "mode": "per_item",
+            "operations": [
+                {"op": "set_status", "id": Uuid::nil(), "etag": "a", "status": "INACTIVE"},
+                {"op": "move", "id": Uuid::nil(), "etag": "b", "department_id": Uuid::nil()},
+                {"op": "update", "id": Uuid::nil(), "etag": "c", "changes": {"full_name": "Ana"}}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(request.mode, BatchMode::PerItem);
+        assert!(matches!(
+            &request.operations[0],
+            ContactBatchOperation::SetStatus { status, .. } if status == "INACTIVE"
+        ));
+        assert!(matches!(
+            &request.operations[1],
+            ContactBatchOperation::Move {
+                unit_id: None,
+                department_id: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &request.operations[2],
+            ContactBatchOperation::Update { changes, .. } if changes.full_name.as_deref() == Some("Ana")
+        ));
+    }
+
+    #[test]
+    fn test_batch_update_rejects_unknown_fields() {
+        let result: Result<ContactBatchRequest, _> = serde_json::from_value(json!({
+            "operations": [
+                {"op": "update", "id": Uuid::nil(), "etag": "c", "changes": {"etag": "x"}}
+            ]
+        }));
+
+        assert!(result.is_err());
+    }
+}
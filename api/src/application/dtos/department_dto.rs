@@ -7,6 +7,7 @@
 use crate::domain::entities::Department;
 use crate::domain::value_objects::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // ============================================================================
@@ -15,7 +16,7 @@ use uuid::Uuid;
 
 /// DTO para criação de novos departamentos
 /// Contém dados necessários para criar um departamento
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDepartmentRequest {
     /// ID da unidade organizacional à qual o departamento pertence
     pub unit_id: Uuid,
@@ -25,7 +26,7 @@ pub struct CreateDepartmentRequest {
 
 /// DTO para atualização de departamentos existentes
 /// Todos os campos são opcionais para permitir atualização parcial
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDepartmentRequest {
     /// ID do departamento (não serializado, vem da URL)
     #[serde(skip)]
@@ -36,9 +37,20 @@ pub struct UpdateDepartmentRequest {
     pub name: Option<String>,
 }
 
+/// Documento editável de um departamento, alvo dos patches RFC 7396 / RFC 6902
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DepartmentPatchDocument {
+    /// ID da unidade organizacional (obrigatório, não pode ser limpo)
+    pub unit_id: Uuid,
+    /// Nome do departamento
+    pub name: String,
+}
+
 /// DTO para busca de departamentos com filtros
 /// Suporta paginação e filtros diversos
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DepartmentSearchRequest {
     /// Termo de busca geral (nome do departamento)
     pub search_term: Option<String>,
@@ -56,7 +68,7 @@ pub struct DepartmentSearchRequest {
 
 /// DTO de resposta para departamentos individuais
 /// Contém todos os dados de um departamento para resposta da API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DepartmentResponse {
     /// ID único do departamento
     pub id: Uuid,
@@ -72,7 +84,7 @@ pub struct DepartmentResponse {
 
 /// DTO de resposta para busca de departamentos
 /// Contém lista paginada de departamentos e metadados
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DepartmentSearchResponse {
     /// Lista de departamentos encontrados
     pub items: Vec<DepartmentResponse>,
@@ -82,7 +94,7 @@ pub struct DepartmentSearchResponse {
 
 /// DTO de resposta para estatísticas de departamentos
 /// Contém dados agregados para dashboards e relatórios
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DepartmentStatisticsResponse {
     /// Total de departamentos
     pub total_departments: i64,
@@ -107,3 +119,13 @@ impl From<Department> for DepartmentResponse {
         }
     }
 }
+
+/// Constrói o documento editável a partir da entidade de domínio
+impl From<&Department> for DepartmentPatchDocument {
+    fn from(department: &Department) -> Self {
+        DepartmentPatchDocument {
+            unit_id: department.unit_id.0,
+            name: department.name.value.clone(),
+        }
+    }
+}
@@ -27,3 +27,7 @@ pub use audit_event_dto::*;
 // ===== MERGE DTOs =====
 pub mod merge_dto;
 pub use merge_dto::*;
+
+// ===== PATCH DTOs =====
+pub mod patch_dto;
+pub use patch_dto::*;
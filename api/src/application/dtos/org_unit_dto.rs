@@ -7,6 +7,7 @@
 use crate::domain::entities::OrgUnit;
 use crate::domain::value_objects::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // ============================================================================
@@ -15,7 +16,7 @@ use uuid::Uuid;
 
 /// DTO para criação de novas unidades organizacionais
 /// Contém dados necessários para criar uma unidade organizacional
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateOrgUnitRequest {
     /// Nome da unidade organizacional
     pub name: String,
@@ -25,7 +26,7 @@ pub struct CreateOrgUnitRequest {
 
 /// DTO para atualização de unidades organizacionais existentes
 /// Todos os campos são opcionais para permitir atualização parcial
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateOrgUnitRequest {
     /// ID da unidade organizacional (não serializado, vem da URL)
     #[serde(skip)]
@@ -36,9 +37,20 @@ pub struct UpdateOrgUnitRequest {
     pub parent_id: Option<Uuid>,
 }
 
+/// Documento editável de uma unidade organizacional, alvo dos patches RFC 7396 / RFC 6902
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrgUnitPatchDocument {
+    /// Nome da unidade organizacional
+    pub name: String,
+    /// ID da unidade pai (`null` transforma a unidade em raiz)
+    pub parent_id: Option<Uuid>,
+}
+
 /// DTO para busca de unidades organizacionais com filtros
 /// Suporta paginação e filtros diversos
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct OrgUnitSearchRequest {
     /// Termo de busca geral (nome da unidade)
     pub search_term: Option<String>,
@@ -56,7 +68,7 @@ pub struct OrgUnitSearchRequest {
 
 /// DTO de resposta para unidades organizacionais individuais
 /// Contém todos os dados de uma unidade organizacional para resposta da API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct OrgUnitResponse {
     /// ID único da unidade organizacional
     pub id: Uuid,
@@ -72,7 +84,7 @@ pub struct OrgUnitResponse {
 
 /// DTO de resposta para busca de unidades organizacionais
 /// Contém lista paginada de unidades e metadados
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct OrgUnitSearchResponse {
     /// Lista de unidades organizacionais encontradas
     pub items: Vec<OrgUnitResponse>,
@@ -82,7 +94,7 @@ pub struct OrgUnitSearchResponse {
 
 /// DTO de resposta para hierarquia de unidades organizacionais
 /// Contém estrutura hierárquica com unidades e seus filhos
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct OrgUnitHierarchyResponse {
     /// Lista de unidades organizacionais
     pub items: Vec<OrgUnitResponse>,
@@ -107,3 +119,13 @@ impl From<OrgUnit> for OrgUnitResponse {
         }
     }
 }
+
+/// Constrói o documento editável a partir da entidade de domínio
+impl From<&OrgUnit> for OrgUnitPatchDocument {
+    fn from(org_unit: &OrgUnit) -> Self {
+        OrgUnitPatchDocument {
+            name: org_unit.name.value.clone(),
+            parent_id: org_unit.parent_id.as_ref().map(|id| id.0),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// ============================================================================
+// PATCH DTOs - JSON MERGE PATCH (RFC 7396) E JSON PATCH (RFC 6902)
+// ============================================================================
+// DTOs para atualizações via patch genérico em contatos, unidades e departamentos
+// O patch é aplicado sobre o documento editável da entidade e depois revalidado
+
+use crate::domain::errors::DomainError;
+use serde::{de::DeserializeOwned, Serialize};
+
+// ============================================================================
+// MEDIA TYPES
+// ============================================================================
+
+/// Media type de JSON Merge Patch (RFC 7396)
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// Media type de JSON Patch (RFC 6902)
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+// ============================================================================
+// REQUEST DTOs - DADOS DE ENTRADA
+// ============================================================================
+
+/// Documento de patch recebido do cliente
+#[derive(Debug, Clone)]
+pub enum EntityPatch {
+    /// JSON Merge Patch: `null` remove (limpa) o campo
+    Merge(serde_json::Value),
+    /// JSON Patch: lista ordenada de operações (add, remove, replace, move, copy, test)
+    Json(json_patch::Patch),
+}
+
+/// DTO para aplicação de patch em uma entidade existente
+#[derive(Debug, Clone)]
+pub struct PatchEntityRequest {
+    /// ID da entidade (vem da URL)
+    pub id: String,
+    /// Documento de patch
+    pub patch: EntityPatch,
+    /// ETag informado no header `If-Match` (opcional)
+    pub etag: Option<String>,
+}
+
+impl EntityPatch {
+    /// Aplica o patch sobre o documento editável `current` e devolve o documento resultante
+    ///
+    /// O resultado é desserializado novamente no tipo `T`, de modo que campos
+    /// desconhecidos, somente leitura ou com tipo inválido são rejeitados com
+    /// `DomainError::ValidationError`.
+    pub fn apply_to<T>(&self, current: &T) -> Result<T, DomainError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut document = serde_json::to_value(current)?;
+
+        match self {
+            EntityPatch::Merge(patch) => {
+                if !patch.is_object() {
+                    return Err(DomainError::ValidationError(
+                        "Merge patch document must be a JSON object".to_string(),
+                    ));
+                }
+                json_patch::merge(&mut document, patch);
+            }
+            EntityPatch::Json(patch) => {
+                json_patch::patch(&mut document, patch).map_err(|e| {
+                    DomainError::ValidationError(format!("Invalid JSON Patch: {}", e))
+                })?;
+            }
+        }
+
+        serde_json::from_value(document)
+            .map_err(|e| DomainError::ValidationError(format!("Invalid patched document: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Document {
+        name: String,
+        document: Option<String>,
+    }
+
+    fn current() -> Document {
+        Document {
+            name: "Alice".to_string(),
+            document: Some("123".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_merge_patch_null_clears_field() {
+        let patch = EntityPatch::Merge(json!({"document": null}));
+        let patched = patch.apply_to(&current()).unwrap();
+
+        assert_eq!(patched.name, "Alice");
+        assert_eq!(patched.document, None);
+    }
+
+    #[test]
+    fn test_merge_patch_omitted_field_is_kept() {
+        let patch = EntityPatch::Merge(json!({"name": "Bob"}));
+        let patched = patch.apply_to(&current()).unwrap();
+
+        assert_eq!(patched.name, "Bob");
+        assert_eq!(patched.document, Some("123".to_string()));
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_unknown_field() {
+        let patch = EntityPatch::Merge(json!({"etag": "abc"}));
+        assert!(matches!(
+            patch.apply_to(&current()),
+            Err(DomainError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_non_object() {
+        let patch = EntityPatch::Merge(json!(["name"]));
+        assert!(patch.apply_to(&current()).is_err());
+    }
+
+    #[test]
+    fn test_json_patch_remove_and_replace() {
+        let ops: json_patch::Patch = serde_json::from_value(json!([
+            {"op": "remove", "path": "/document"},
+            {"op": "replace", "path": "/name", "value": "Carol"}
+        ]))
+        .unwrap();
+        let patched = EntityPatch::Json(ops).apply_to(&current()).unwrap();
+
+        assert_eq!(
+            patched,
+            Document {
+                name: "Carol".to_string(),
+                document: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_json_patch_failed_test_operation() {
+        let ops: json_patch::Patch = serde_json::from_value(json!([
+            {"op": "test", "path": "/name", "value": "Someone else"},
+            {"op": "replace", "path": "/name", "value": "Carol"}
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            EntityPatch::Json(ops).apply_to(&current()),
+            Err(DomainError::ValidationError(_))
+        ));
+    }
+}
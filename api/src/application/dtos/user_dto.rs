@@ -7,6 +7,7 @@
 use crate::domain::entities::User;
 use crate::domain::value_objects::*;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // ============================================================================
@@ -15,7 +16,7 @@ use uuid::Uuid;
 
 /// DTO para criação de novos usuários
 /// Contém dados necessários para criar um usuário
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
     /// Nome de usuário único
     pub username: String,
@@ -29,7 +30,7 @@ pub struct CreateUserRequest {
 
 /// DTO para atualização de usuários existentes
 /// Todos os campos são opcionais para permitir atualização parcial
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateUserRequest {
     /// ID do usuário (não serializado, vem da URL)
     #[serde(skip)]
@@ -46,7 +47,8 @@ pub struct UpdateUserRequest {
 
 /// DTO para busca de usuários com filtros
 /// Suporta paginação e filtros diversos
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct UserSearchRequest {
     /// Termo de busca geral (username, email)
     pub search_term: Option<String>,
@@ -64,7 +66,7 @@ pub struct UserSearchRequest {
 
 /// DTO de resposta para usuários individuais
 /// Contém dados de um usuário para resposta da API (sem senha)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserResponse {
     /// ID único do usuário
     pub id: Uuid,
@@ -82,7 +84,7 @@ pub struct UserResponse {
 
 /// DTO de resposta para busca de usuários
 /// Contém lista paginada de usuários e metadados
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct UserSearchResponse {
     /// Lista de usuários encontrados
     pub items: Vec<UserResponse>,
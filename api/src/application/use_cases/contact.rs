@@ -1,9 +1,15 @@
 use crate::application::dto::*;
 use crate::domain::entities::Contact;
-use crate::domain::errors::DomainError;
-use crate::domain::repositories::{ContactRepository, ContactSearchCriteria};
+use crate::domain::errors::{DomainError, FieldError};
+use crate::domain::repositories::{
+    ContactChange, ContactRepository, ContactSearchCriteria, ContactStatisticsQuery,
+};
 use crate::domain::value_objects::*;
+use crate::shared::base_traits::Cache;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use uuid::Uuid;
 
 pub struct CreateContactUseCase<'a> {
     contact_repository: &'a dyn ContactRepository,
@@ -18,10 +24,21 @@ impl<'a> CreateContactUseCase<'a> {
         &self,
         request: CreateContactRequest,
     ) -> Result<ContactResponse, DomainError> {
+        // Todos os campos são validados antes de responder, para listar cada erro
+        let mut errors = Vec::new();
+        if request.full_name.trim().is_empty() {
+            errors.push(FieldError::new("full_name", "Full name cannot be empty"));
+        }
         let contact_type = ContactType::from_str(&request.contact_type)
-            .map_err(|e| DomainError::ValidationError(e))?;
+            .map_err(|e| errors.push(FieldError::new("contact_type", e)))
+            .ok();
         let status = ContactStatus::from_str(&request.status)
-            .map_err(|e| DomainError::ValidationError(e))?;
+            .map_err(|e| errors.push(FieldError::new("status", e)))
+            .ok();
+        let (contact_type, status) = match (contact_type, status) {
+            (Some(contact_type), Some(status)) if errors.is_empty() => (contact_type, status),
+            _ => return Err(DomainError::InvalidFields(errors)),
+        };
 
         let unit_id = request.unit_id.map(OrgUnitId);
         let department_id = request.department_id.map(DepartmentId);
@@ -55,6 +72,14 @@ impl<'a> UpdateContactUseCase<'a> {
         &self,
         request: UpdateContactRequest,
     ) -> Result<ContactResponse, DomainError> {
+        let contact = self.prepare(request).await?;
+        let updated_contact = self.contact_repository.update(&contact).await?;
+        Ok(updated_contact.into())
+    }
+
+    /// Loads the contact, checks the ETag and applies the changes in memory
+    /// without persisting them
+    pub async fn prepare(&self, request: UpdateContactRequest) -> Result<Contact, DomainError> {
         let contact_id = ContactId::from_string(&request.id)
             .map_err(|e| DomainError::ValidationError(format!("Invalid contact ID: {}", e)))?;
 
@@ -62,6 +87,7 @@ impl<'a> UpdateContactUseCase<'a> {
             .contact_repository
             .find_by_id(&contact_id)
             .await?
+            .filter(|contact| !contact.is_deleted())
             .ok_or_else(|| {
                 DomainError::NotFound(format!("Contact with ID {} not found", request.id))
             })?;
@@ -79,13 +105,13 @@ impl<'a> UpdateContactUseCase<'a> {
 
         if let Some(contact_type_str) = request.contact_type {
             let contact_type = ContactType::from_str(&contact_type_str)
-                .map_err(|e| DomainError::ValidationError(e))?;
+                .map_err(|e| DomainError::invalid_field("contact_type", e))?;
             contact.update_contact_type(contact_type);
         }
 
         if let Some(status_str) = request.status {
             let status = ContactStatus::from_str(&status_str)
-                .map_err(|e| DomainError::ValidationError(e))?;
+                .map_err(|e| DomainError::invalid_field("status", e))?;
             contact.update_status(status);
         }
 
@@ -109,6 +135,87 @@ impl<'a> UpdateContactUseCase<'a> {
             contact.phones = phones;
         }
 
+        Ok(contact)
+    }
+}
+
+pub struct PatchContactUseCase<'a> {
+    contact_repository: &'a dyn ContactRepository,
+}
+
+impl<'a> PatchContactUseCase<'a> {
+    pub fn new(contact_repository: &'a dyn ContactRepository) -> Self {
+        PatchContactUseCase { contact_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        request: PatchEntityRequest,
+    ) -> Result<ContactResponse, DomainError> {
+        let contact_id = ContactId::from_string(&request.id)
+            .map_err(|e| DomainError::ValidationError(format!("Invalid contact ID: {}", e)))?;
+
+        let mut contact = self
+            .contact_repository
+            .find_by_id(&contact_id)
+            .await?
+            .filter(|contact| !contact.is_deleted())
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Contact with ID {} not found", request.id))
+            })?;
+
+        // Check ETag for optimistic concurrency control
+        let etag = request.etag.as_deref().ok_or_else(|| {
+            DomainError::ValidationError(
+                "If-Match header with the current ETag is required".to_string(),
+            )
+        })?;
+        if contact.etag != etag {
+            return Err(DomainError::Conflict(
+                "ETag mismatch - contact was modified by another user".to_string(),
+            ));
+        }
+
+        let current = ContactPatchDocument::from(&contact);
+        let patched = request.patch.apply_to(&current)?;
+
+        // Only fields that actually changed go through the entity update methods
+        if patched.full_name != current.full_name {
+            contact.update_full_name(patched.full_name)?;
+        }
+
+        let contact_type =
+            ContactType::from_str(&patched.contact_type).map_err(DomainError::ValidationError)?;
+        if contact_type != contact.contact_type {
+            contact.update_contact_type(contact_type);
+        }
+
+        let status =
+            ContactStatus::from_str(&patched.status).map_err(DomainError::ValidationError)?;
+        if status != contact.status {
+            contact.update_status(status);
+        }
+
+        if patched.document != current.document {
+            contact.update_document(patched.document);
+        }
+
+        if patched.unit_id != current.unit_id {
+            contact.update_unit_id(patched.unit_id.map(OrgUnitId));
+        }
+
+        if patched.department_id != current.department_id {
+            contact.update_department_id(patched.department_id.map(DepartmentId));
+        }
+
+        if patched.emails != current.emails {
+            contact.replace_emails(patched.emails)?;
+        }
+
+        if patched.phones != current.phones {
+            contact.replace_phones(patched.phones)?;
+        }
+
         let updated_contact = self.contact_repository.update(&contact).await?;
         Ok(updated_contact.into())
     }
@@ -124,17 +231,291 @@ impl<'a> DeleteContactUseCase<'a> {
     }
 
     pub async fn execute(&self, id: &str) -> Result<(), DomainError> {
+        let contact_id = self.prepare(id, None).await?;
+
+        // Soft delete: emails, phones and sources are kept until the retention purge
+        self.contact_repository.soft_delete(&contact_id).await?;
+        Ok(())
+    }
+
+    /// Checks that the contact exists, was not deleted yet and, when given,
+    /// that the ETag still matches
+    pub async fn prepare(&self, id: &str, etag: Option<&str>) -> Result<ContactId, DomainError> {
         let contact_id = ContactId::from_string(id)
             .map_err(|e| DomainError::ValidationError(format!("Invalid contact ID: {}", e)))?;
 
-        // Check if contact exists
-        self.contact_repository
+        let contact = self
+            .contact_repository
             .find_by_id(&contact_id)
             .await?
+            .filter(|contact| !contact.is_deleted())
             .ok_or_else(|| DomainError::NotFound(format!("Contact with ID {} not found", id)))?;
 
-        self.contact_repository.delete(&contact_id).await?;
-        Ok(())
+        if etag.is_some_and(|etag| etag != contact.etag) {
+            return Err(DomainError::Conflict(
+                "ETag mismatch - contact was modified by another user".to_string(),
+            ));
+        }
+
+        Ok(contact_id)
+    }
+}
+
+pub struct BatchContactsUseCase<'a> {
+    contact_repository: &'a dyn ContactRepository,
+}
+
+impl<'a> BatchContactsUseCase<'a> {
+    pub fn new(contact_repository: &'a dyn ContactRepository) -> Self {
+        BatchContactsUseCase { contact_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        request: ContactBatchRequest,
+    ) -> Result<ContactBatchResult, DomainError> {
+        if request.operations.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Batch must contain at least one operation".to_string(),
+            ));
+        }
+        if request.operations.len() > MAX_CONTACT_BATCH_OPERATIONS {
+            return Err(DomainError::ValidationError(format!(
+                "Batch cannot contain more than {} operations",
+                MAX_CONTACT_BATCH_OPERATIONS
+            )));
+        }
+
+        // Every item is checked against the stored ETag, so a contact may appear only once
+        let mut ids = HashSet::new();
+        if let Some(duplicate) = request.operations.iter().find(|op| !ids.insert(op.id())) {
+            return Err(DomainError::ValidationError(format!(
+                "Contact {} appears more than once in the batch",
+                duplicate.id()
+            )));
+        }
+
+        match request.mode {
+            BatchMode::Transaction => self.execute_transaction(request.operations).await,
+            BatchMode::PerItem => self.execute_per_item(request.operations).await,
+        }
+    }
+
+    async fn execute_transaction(
+        &self,
+        operations: Vec<ContactBatchOperation>,
+    ) -> Result<ContactBatchResult, DomainError> {
+        // Validate every item first; nothing is written unless all of them pass
+        let mut prepared = Vec::with_capacity(operations.len());
+        for operation in &operations {
+            prepared.push(self.prepare(operation).await);
+        }
+
+        let results = if prepared.iter().all(Result::is_ok) {
+            let changes: Vec<ContactChange> = prepared.into_iter().flatten().collect();
+            match self.contact_repository.apply_batch(&changes).await {
+                Ok(saved) => saved
+                    .into_iter()
+                    .map(|contact| {
+                        ContactBatchItemResult::Applied(contact.map(|c| Box::new(c.into())))
+                    })
+                    .collect(),
+                Err(err) => vec![ContactBatchItemResult::Failed(err); changes.len()],
+            }
+        } else {
+            prepared
+                .into_iter()
+                .map(|result| match result {
+                    Ok(_) => ContactBatchItemResult::NotApplied,
+                    Err(err) => ContactBatchItemResult::Failed(err),
+                })
+                .collect()
+        };
+
+        let committed = results
+            .iter()
+            .all(|result| matches!(result, ContactBatchItemResult::Applied(_)));
+
+        Ok(ContactBatchResult {
+            mode: BatchMode::Transaction,
+            committed,
+            items: operations.into_iter().zip(results).collect(),
+        })
+    }
+
+    async fn execute_per_item(
+        &self,
+        operations: Vec<ContactBatchOperation>,
+    ) -> Result<ContactBatchResult, DomainError> {
+        let mut items = Vec::with_capacity(operations.len());
+        for operation in operations {
+            let result = match self.apply(&operation).await {
+                Ok(contact) => ContactBatchItemResult::Applied(contact),
+                Err(err) => ContactBatchItemResult::Failed(err),
+            };
+            items.push((operation, result));
+        }
+
+        let committed = items
+            .iter()
+            .any(|(_, result)| matches!(result, ContactBatchItemResult::Applied(_)));
+
+        Ok(ContactBatchResult {
+            mode: BatchMode::PerItem,
+            committed,
+            items,
+        })
+    }
+
+    async fn apply(
+        &self,
+        operation: &ContactBatchOperation,
+    ) -> Result<Option<Box<ContactResponse>>, DomainError> {
+        let change = self.prepare(operation).await?;
+        let saved = self
+            .contact_repository
+            .apply_batch(std::slice::from_ref(&change))
+            .await?;
+        Ok(saved
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|c| Box::new(c.into())))
+    }
+
+    /// Translates a batch item into a pending change using the single-contact use cases
+    async fn prepare(
+        &self,
+        operation: &ContactBatchOperation,
+    ) -> Result<ContactChange, DomainError> {
+        let update = |id: &Uuid, etag: &String| UpdateContactRequest {
+            id: id.to_string(),
+            full_name: None,
+            contact_type: None,
+            status: None,
+            document: None,
+            unit_id: None,
+            department_id: None,
+            emails: None,
+            phones: None,
+            etag: etag.clone(),
+        };
+
+        let request = match operation {
+            ContactBatchOperation::Delete { id, etag } => {
+                return DeleteContactUseCase::new(self.contact_repository)
+                    .prepare(&id.to_string(), Some(etag))
+                    .await
+                    .map(|id| ContactChange::SoftDelete {
+                        id,
+                        etag: etag.clone(),
+                    });
+            }
+            ContactBatchOperation::Update { id, etag, changes } => UpdateContactRequest {
+                full_name: changes.full_name.clone(),
+                contact_type: changes.contact_type.clone(),
+                status: changes.status.clone(),
+                document: changes.document.clone(),
+                unit_id: changes.unit_id,
+                department_id: changes.department_id,
+                emails: changes.emails.clone(),
+                phones: changes.phones.clone(),
+                ..update(id, etag)
+            },
+            ContactBatchOperation::SetStatus { id, etag, status } => UpdateContactRequest {
+                status: Some(status.clone()),
+                ..update(id, etag)
+            },
+            ContactBatchOperation::Move {
+                id,
+                etag,
+                unit_id,
+                department_id,
+            } => {
+                if unit_id.is_none() && department_id.is_none() {
+                    return Err(DomainError::ValidationError(
+                        "Move requires unit_id or department_id".to_string(),
+                    ));
+                }
+                UpdateContactRequest {
+                    unit_id: *unit_id,
+                    department_id: *department_id,
+                    ..update(id, etag)
+                }
+            }
+        };
+
+        let etag = request.etag.clone();
+        UpdateContactUseCase::new(self.contact_repository)
+            .prepare(request)
+            .await
+            .map(|contact| ContactChange::Update { contact, etag })
+    }
+}
+
+pub struct RestoreContactUseCase<'a> {
+    contact_repository: &'a dyn ContactRepository,
+}
+
+impl<'a> RestoreContactUseCase<'a> {
+    pub fn new(contact_repository: &'a dyn ContactRepository) -> Self {
+        RestoreContactUseCase { contact_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        id: &str,
+        principal: &Principal,
+    ) -> Result<ContactResponse, DomainError> {
+        require_admin(principal)?;
+
+        let contact_id = ContactId::from_string(id)
+            .map_err(|e| DomainError::ValidationError(format!("Invalid contact ID: {}", e)))?;
+
+        let contact = self
+            .contact_repository
+            .find_by_id(&contact_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Contact with ID {} not found", id)))?;
+
+        if !contact.is_deleted() {
+            return Err(DomainError::Conflict(format!(
+                "Contact with ID {} is not deleted",
+                id
+            )));
+        }
+
+        self.contact_repository.restore(&contact_id).await?;
+
+        // Re-read so the response carries the ETag computed by the database
+        let restored = self
+            .contact_repository
+            .find_by_id(&contact_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Contact with ID {} not found", id)))?;
+        Ok(restored.into())
+    }
+}
+
+pub struct PurgeDeletedContactsUseCase<'a> {
+    contact_repository: &'a dyn ContactRepository,
+}
+
+impl<'a> PurgeDeletedContactsUseCase<'a> {
+    pub fn new(contact_repository: &'a dyn ContactRepository) -> Self {
+        PurgeDeletedContactsUseCase { contact_repository }
+    }
+
+    /// Remove definitivamente os contatos excluídos há mais de `retention`
+    pub async fn execute(&self, retention: chrono::Duration) -> Result<u64, DomainError> {
+        if retention < chrono::Duration::zero() {
+            return Err(DomainError::ValidationError(
+                "Retention period cannot be negative".to_string(),
+            ));
+        }
+
+        let deleted_before = chrono::Utc::now() - retention;
+        self.contact_repository.purge_deleted(deleted_before).await
     }
 }
 
@@ -150,15 +531,24 @@ impl<'a> GetContactsUseCase<'a> {
     pub async fn execute(
         &self,
         request: ContactSearchRequest,
+        principal: &Principal,
     ) -> Result<ContactSearchResponse, DomainError> {
+        // Contatos excluídos logicamente: somente administradores
+        if request.include_deleted == Some(true) {
+            require_admin(principal)?;
+        }
+
         let contact_type = if let Some(ct) = request.contact_type {
-            Some(ContactType::from_str(&ct).map_err(|e| DomainError::ValidationError(e))?)
+            Some(
+                ContactType::from_str(&ct)
+                    .map_err(|e| DomainError::invalid_field("contact_type", e))?,
+            )
         } else {
             None
         };
 
         let status = if let Some(s) = request.status {
-            Some(ContactStatus::from_str(&s).map_err(|e| DomainError::ValidationError(e))?)
+            Some(ContactStatus::from_str(&s).map_err(|e| DomainError::invalid_field("status", e))?)
         } else {
             None
         };
@@ -172,6 +562,7 @@ impl<'a> GetContactsUseCase<'a> {
             status,
             unit_id,
             department_id,
+            include_deleted: request.include_deleted.unwrap_or(false),
             limit: request.limit,
             offset: request.offset,
         };
@@ -194,29 +585,295 @@ impl<'a> GetContactsUseCase<'a> {
             .contact_repository
             .find_by_id(id)
             .await?
+            .filter(|contact| !contact.is_deleted())
             .ok_or_else(|| DomainError::NotFound(format!("Contact with ID {} not found", id)))?;
         Ok(contact.into())
     }
 }
 
+/// Maximum number of buckets a single statistics request may produce
+pub const MAX_STATISTICS_BUCKETS: usize = 400;
+
 pub struct GetContactStatisticsUseCase<'a> {
     contact_repository: &'a dyn ContactRepository,
+    cache: &'a dyn Cache<ContactStatisticsResponse>,
+    cache_ttl: std::time::Duration,
 }
 
 impl<'a> GetContactStatisticsUseCase<'a> {
-    pub fn new(contact_repository: &'a dyn ContactRepository) -> Self {
-        GetContactStatisticsUseCase { contact_repository }
+    pub fn new(
+        contact_repository: &'a dyn ContactRepository,
+        cache: &'a dyn Cache<ContactStatisticsResponse>,
+        cache_ttl: std::time::Duration,
+    ) -> Self {
+        GetContactStatisticsUseCase {
+            contact_repository,
+            cache,
+            cache_ttl,
+        }
     }
 
-    pub async fn execute(&self) -> Result<ContactStatisticsResponse, DomainError> {
-        let stats = self.contact_repository.get_statistics().await?;
-        Ok(ContactStatisticsResponse {
+    pub async fn execute(
+        &self,
+        request: ContactStatisticsRequest,
+    ) -> Result<ContactStatisticsResponse, DomainError> {
+        let query = statistics_query(&request, Utc::now().date_naive())?;
+        let cache_key = format!(
+            "contacts:statistics:{}:{}:{}",
+            query.from.to_rfc3339(),
+            query.to.to_rfc3339(),
+            query.bucket
+        );
+
+        match self.cache.get(&cache_key).await {
+            Ok(Some(cached)) => return Ok(cached),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "contact statistics cache read failed"),
+        }
+
+        let stats = self.contact_repository.get_statistics(&query).await?;
+        let response = ContactStatisticsResponse {
             total_contacts: stats.total_contacts,
             active_contacts: stats.active_contacts,
             inactive_contacts: stats.inactive_contacts,
             persons: stats.persons,
             organizations: stats.organizations,
             departments: stats.departments,
-        })
+            department_contacts: stats.department_contacts,
+            by_org_unit: stats
+                .by_org_unit
+                .into_iter()
+                .map(|unit| ContactOrgUnitCountResponse {
+                    unit_id: unit.unit_id.0,
+                    contacts: unit.contacts,
+                    contacts_including_descendants: unit.contacts_including_descendants,
+                })
+                .collect(),
+            by_department: stats
+                .by_department
+                .into_iter()
+                .map(|(department_id, contacts)| ContactDepartmentCountResponse {
+                    department_id: department_id.0,
+                    contacts,
+                })
+                .collect(),
+            by_status: stats
+                .by_status
+                .into_iter()
+                .map(|(status, count)| (status.to_string(), count))
+                .collect(),
+            by_source: stats.by_source.into_iter().collect(),
+            time_series: ContactTimeSeriesResponse {
+                from: query.from,
+                to: query.to,
+                bucket: query.bucket,
+                created: fill_series(&query, &stats.created_series),
+                updated: fill_series(&query, &stats.updated_series),
+            },
+            data_quality: ContactDataQualityResponse {
+                missing_primary_email: stats.data_quality.missing_primary_email,
+                missing_primary_phone: stats.data_quality.missing_primary_phone,
+                invalid_document: stats.data_quality.invalid_document,
+            },
+            generated_at: Utc::now(),
+        };
+
+        if let Err(e) = self
+            .cache
+            .set(&cache_key, &response, Some(self.cache_ttl))
+            .await
+        {
+            tracing::warn!(error = %e, "contact statistics cache write failed");
+        }
+
+        Ok(response)
+    }
+}
+
+/// Resolves the requested date range into a half-open UTC range aligned to whole days
+fn statistics_query(
+    request: &ContactStatisticsRequest,
+    today: NaiveDate,
+) -> Result<ContactStatisticsQuery, DomainError> {
+    let bucket = match request.bucket.as_deref() {
+        Some(bucket) => StatisticsBucket::from_str(bucket).map_err(DomainError::ValidationError)?,
+        None => StatisticsBucket::Day,
+    };
+    let out_of_range = || DomainError::ValidationError("Date range is out of bounds".to_string());
+    let to = request.to.unwrap_or(today);
+    let from = match request.from {
+        Some(from) => from,
+        None => to
+            .checked_sub_signed(chrono::Duration::days(29))
+            .ok_or_else(out_of_range)?,
+    };
+    if from > to {
+        return Err(DomainError::ValidationError(
+            "'from' must not be after 'to'".to_string(),
+        ));
+    }
+    let end = to
+        .checked_add_signed(chrono::Duration::days(1))
+        .ok_or_else(out_of_range)?;
+
+    let query = ContactStatisticsQuery {
+        from: from.and_time(NaiveTime::MIN).and_utc(),
+        to: end.and_time(NaiveTime::MIN).and_utc(),
+        bucket,
+    };
+
+    let mut buckets = 0;
+    let mut start = bucket.truncate(query.from);
+    while start < query.to {
+        buckets += 1;
+        if buckets > MAX_STATISTICS_BUCKETS {
+            return Err(DomainError::ValidationError(format!(
+                "Requested range produces more than {} {} buckets",
+                MAX_STATISTICS_BUCKETS, bucket
+            )));
+        }
+        start = bucket.next(start);
+    }
+
+    Ok(query)
+}
+
+/// Expands a sparse series into one point per bucket, filling gaps with zero
+fn fill_series(
+    query: &ContactStatisticsQuery,
+    points: &[(DateTime<Utc>, i64)],
+) -> Vec<TimeSeriesPointResponse> {
+    let mut counts: HashMap<DateTime<Utc>, i64> = HashMap::new();
+    for (at, count) in points {
+        *counts.entry(query.bucket.truncate(*at)).or_default() += count;
+    }
+
+    let mut series = Vec::new();
+    let mut start = query.bucket.truncate(query.from);
+    while start < query.to {
+        series.push(TimeSeriesPointResponse {
+            bucket_start: start,
+            count: counts.get(&start).copied().unwrap_or(0),
+        });
+        start = query.bucket.next(start);
+    }
+    series
+}
+
+/// Garante que o usuário autenticado possui o papel `admin`
+fn require_admin(principal: &Principal) -> Result<(), DomainError> {
+    if principal.is_admin() {
+        Ok(())
+    } else {
+        Err(DomainError::Forbidden("Admin role required".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(from: &str, to: &str, bucket: &str) -> ContactStatisticsRequest {
+        ContactStatisticsRequest {
+            from: Some(from.parse().unwrap()),
+            to: Some(to.parse().unwrap()),
+            bucket: Some(bucket.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_require_admin() {
+        let admin = Principal {
+            permissions: ["admin".to_string()].into(),
+            ..Principal::default()
+        };
+        assert!(require_admin(&admin).is_ok());
+        assert!(matches!(
+            require_admin(&Principal::default()),
+            Err(DomainError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn test_statistics_query_defaults_to_last_30_days() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let query = statistics_query(&ContactStatisticsRequest::default(), today).unwrap();
+
+        assert_eq!(query.bucket, StatisticsBucket::Day);
+        assert_eq!(
+            query.from,
+            Utc.with_ymd_and_hms(2025, 3, 2, 0, 0, 0).unwrap()
+        );
+        assert_eq!(query.to, Utc.with_ymd_and_hms(2025, 4, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_statistics_query_rejects_inverted_range() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let result = statistics_query(&request("2025-03-10", "2025-03-01", "day"), today);
+
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_statistics_query_limits_bucket_count() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+
+        assert!(statistics_query(&request("2020-01-01", "2025-01-01", "day"), today).is_err());
+        assert!(statistics_query(&request("2020-01-01", "2025-01-01", "month"), today).is_ok());
+    }
+
+    #[test]
+    fn test_statistics_query_handles_calendar_bounds() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let latest = ContactStatisticsRequest {
+            to: Some(NaiveDate::MAX),
+            ..ContactStatisticsRequest::default()
+        };
+        let earliest = ContactStatisticsRequest {
+            to: Some(NaiveDate::MIN),
+            ..ContactStatisticsRequest::default()
+        };
+
+        assert!(matches!(
+            statistics_query(&latest, today),
+            Err(DomainError::ValidationError(_))
+        ));
+        assert!(matches!(
+            statistics_query(&earliest, today),
+            Err(DomainError::ValidationError(_))
+        ));
+        for bucket in ["day", "week", "month"] {
+            let request = ContactStatisticsRequest {
+                from: NaiveDate::MAX.pred_opt(),
+                to: NaiveDate::MAX.pred_opt(),
+                bucket: Some(bucket.to_string()),
+            };
+            assert!(statistics_query(&request, today).is_ok());
+            let request = ContactStatisticsRequest {
+                from: Some(NaiveDate::MIN),
+                to: Some(NaiveDate::MIN),
+                bucket: Some(bucket.to_string()),
+            };
+            assert!(statistics_query(&request, today).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_fill_series_fills_gaps_with_zero() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let query = statistics_query(&request("2025-01-01", "2025-03-15", "month"), today).unwrap();
+        let points = vec![
+            (Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(), 3),
+            (Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap(), 5),
+        ];
+
+        let counts: Vec<i64> = fill_series(&query, &points)
+            .iter()
+            .map(|point| point.count)
+            .collect();
+
+        assert_eq!(counts, vec![3, 0, 5]);
     }
 }
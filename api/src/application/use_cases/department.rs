@@ -21,7 +21,7 @@ impl<'a> CreateDepartmentUseCase<'a> {
     ) -> Result<DepartmentResponse, DomainError> {
         let unit_id = OrgUnitId(request.unit_id);
         let name =
-            DepartmentName::new(request.name).map_err(|e| DomainError::ValidationError(e))?;
+            DepartmentName::new(request.name).map_err(|e| DomainError::invalid_field("name", e))?;
 
         let department = Department::new(unit_id, name);
         let saved_department = self.department_repository.save(&department).await?;
@@ -57,7 +57,7 @@ impl<'a> UpdateDepartmentUseCase<'a> {
 
         if let Some(name) = request.name {
             let department_name =
-                DepartmentName::new(name).map_err(|e| DomainError::ValidationError(e))?;
+                DepartmentName::new(name).map_err(|e| DomainError::invalid_field("name", e))?;
             department.update_name(department_name);
         }
 
@@ -70,6 +70,50 @@ impl<'a> UpdateDepartmentUseCase<'a> {
     }
 }
 
+pub struct PatchDepartmentUseCase<'a> {
+    department_repository: &'a dyn DepartmentRepository,
+}
+
+impl<'a> PatchDepartmentUseCase<'a> {
+    pub fn new(department_repository: &'a dyn DepartmentRepository) -> Self {
+        PatchDepartmentUseCase {
+            department_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: PatchEntityRequest,
+    ) -> Result<DepartmentResponse, DomainError> {
+        let department_id = DepartmentId::from_string(&request.id)
+            .map_err(|e| DomainError::ValidationError(format!("Invalid department ID: {}", e)))?;
+
+        let mut department = self
+            .department_repository
+            .find_by_id(&department_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Department with ID {} not found", request.id))
+            })?;
+
+        let current = DepartmentPatchDocument::from(&department);
+        let patched = request.patch.apply_to(&current)?;
+
+        if patched.name != current.name {
+            let department_name =
+                DepartmentName::new(patched.name).map_err(DomainError::ValidationError)?;
+            department.update_name(department_name);
+        }
+
+        if patched.unit_id != current.unit_id {
+            department.update_unit(OrgUnitId(patched.unit_id));
+        }
+
+        let updated_department = self.department_repository.update(&department).await?;
+        Ok(updated_department.into())
+    }
+}
+
 pub struct DeleteDepartmentUseCase<'a> {
     department_repository: &'a dyn DepartmentRepository,
 }
@@ -19,7 +19,8 @@ impl<'a> CreateOrgUnitUseCase<'a> {
         &self,
         request: CreateOrgUnitRequest,
     ) -> Result<OrgUnitResponse, DomainError> {
-        let name = OrgUnitName::new(request.name).map_err(|e| DomainError::ValidationError(e))?;
+        let name =
+            OrgUnitName::new(request.name).map_err(|e| DomainError::invalid_field("name", e))?;
 
         let parent_id = request.parent_id.map(OrgUnitId);
 
@@ -57,7 +58,7 @@ impl<'a> UpdateOrgUnitUseCase<'a> {
 
         if let Some(name) = request.name {
             let org_unit_name =
-                OrgUnitName::new(name).map_err(|e| DomainError::ValidationError(e))?;
+                OrgUnitName::new(name).map_err(|e| DomainError::invalid_field("name", e))?;
             org_unit.update_name(org_unit_name);
         }
 
@@ -70,6 +71,55 @@ impl<'a> UpdateOrgUnitUseCase<'a> {
     }
 }
 
+pub struct PatchOrgUnitUseCase<'a> {
+    org_unit_repository: &'a dyn OrgUnitRepository,
+}
+
+impl<'a> PatchOrgUnitUseCase<'a> {
+    pub fn new(org_unit_repository: &'a dyn OrgUnitRepository) -> Self {
+        PatchOrgUnitUseCase {
+            org_unit_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: PatchEntityRequest,
+    ) -> Result<OrgUnitResponse, DomainError> {
+        let org_unit_id = OrgUnitId::from_string(&request.id)
+            .map_err(|e| DomainError::ValidationError(format!("Invalid org unit ID: {}", e)))?;
+
+        let mut org_unit = self
+            .org_unit_repository
+            .find_by_id(&org_unit_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("OrgUnit with ID {} not found", request.id))
+            })?;
+
+        let current = OrgUnitPatchDocument::from(&org_unit);
+        let patched = request.patch.apply_to(&current)?;
+
+        if patched.name != current.name {
+            let org_unit_name =
+                OrgUnitName::new(patched.name).map_err(DomainError::ValidationError)?;
+            org_unit.update_name(org_unit_name);
+        }
+
+        if patched.parent_id != current.parent_id {
+            if patched.parent_id == Some(org_unit.id.0) {
+                return Err(DomainError::BusinessRuleViolation(
+                    "OrgUnit cannot be its own parent".to_string(),
+                ));
+            }
+            org_unit.set_parent(patched.parent_id.map(OrgUnitId));
+        }
+
+        let updated_org_unit = self.org_unit_repository.update(&org_unit).await?;
+        Ok(updated_org_unit.into())
+    }
+}
+
 pub struct DeleteOrgUnitUseCase<'a> {
     org_unit_repository: &'a dyn OrgUnitRepository,
 }
@@ -1,6 +1,6 @@
 use crate::application::dto::*;
 use crate::domain::entities::User;
-use crate::domain::errors::DomainError;
+use crate::domain::errors::{DomainError, FieldError};
 use crate::domain::repositories::UserRepository;
 use crate::domain::value_objects::*;
 
@@ -14,18 +14,31 @@ impl<'a> CreateUserUseCase<'a> {
     }
 
     pub async fn execute(&self, request: CreateUserRequest) -> Result<UserResponse, DomainError> {
-        let username =
-            Username::new(request.username).map_err(|e| DomainError::ValidationError(e))?;
-        let email = UserEmail::new(request.email).map_err(|e| DomainError::ValidationError(e))?;
-        let password =
-            Password::new(request.password).map_err(|e| DomainError::ValidationError(e))?;
-
+        // Todos os campos são validados antes de responder, para listar cada erro
+        let mut errors = Vec::new();
+        let username = Username::new(request.username)
+            .map_err(|e| errors.push(FieldError::new("username", e)))
+            .ok();
+        let email = UserEmail::new(request.email)
+            .map_err(|e| errors.push(FieldError::new("email", e)))
+            .ok();
+        let password = Password::new(request.password)
+            .map_err(|e| errors.push(FieldError::new("password", e)))
+            .ok();
         let roles = request
             .roles
             .into_iter()
             .map(|role_str| Role::new(role_str))
             .collect::<Result<Vec<Role>, String>>()
-            .map_err(|e| DomainError::ValidationError(e))?;
+            .map_err(|e| errors.push(FieldError::new("roles", e)))
+            .ok();
+
+        let (username, email, password, roles) = match (username, email, password, roles) {
+            (Some(username), Some(email), Some(password), Some(roles)) => {
+                (username, email, password, roles)
+            }
+            _ => return Err(DomainError::InvalidFields(errors)),
+        };
 
         let user = User::new(username, email, password, roles);
         let saved_user = self.user_repository.save(&user).await?;
@@ -56,18 +69,19 @@ impl<'a> UpdateUserUseCase<'a> {
 
         if let Some(username) = request.username {
             let username_vo =
-                Username::new(username).map_err(|e| DomainError::ValidationError(e))?;
+                Username::new(username).map_err(|e| DomainError::invalid_field("username", e))?;
             user.update_username(username_vo);
         }
 
         if let Some(email) = request.email {
-            let email_vo = UserEmail::new(email).map_err(|e| DomainError::ValidationError(e))?;
+            let email_vo =
+                UserEmail::new(email).map_err(|e| DomainError::invalid_field("email", e))?;
             user.update_email(email_vo);
         }
 
         if let Some(password) = request.password {
             let password_vo =
-                Password::new(password).map_err(|e| DomainError::ValidationError(e))?;
+                Password::new(password).map_err(|e| DomainError::invalid_field("password", e))?;
             user.update_password(password_vo);
         }
 
@@ -76,7 +90,7 @@ impl<'a> UpdateUserUseCase<'a> {
                 .into_iter()
                 .map(|role_str| Role::new(role_str))
                 .collect::<Result<Vec<Role>, String>>()
-                .map_err(|e| DomainError::ValidationError(e))?;
+                .map_err(|e| DomainError::invalid_field("roles", e))?;
             user.roles = role_vos;
         }
 
@@ -179,3 +193,47 @@ impl<'a> GetUsersUseCase<'a> {
         Ok(UserSearchResponse { items, total })
     }
 }
+
+pub struct AuthenticateUserUseCase<'a> {
+    user_repository: &'a dyn UserRepository,
+}
+
+impl<'a> AuthenticateUserUseCase<'a> {
+    pub fn new(user_repository: &'a dyn UserRepository) -> Self {
+        AuthenticateUserUseCase { user_repository }
+    }
+
+    /// Confere usuário e senha; falhas não revelam se o usuário existe
+    pub async fn execute(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<UserResponse, DomainError> {
+        let username = username.trim();
+        if username.is_empty() || password.is_empty() {
+            return Err(invalid_credentials());
+        }
+
+        let user = self
+            .user_repository
+            .verify_credentials(username, password)
+            .await?
+            .ok_or_else(invalid_credentials)?;
+        Ok(user.into())
+    }
+
+    /// Recarrega o usuário de um refresh token (roles atualizadas; usuário removido é rejeitado)
+    pub async fn execute_refresh(&self, id: &str) -> Result<UserResponse, DomainError> {
+        let user_id = UserId::from_string(id).map_err(|_| invalid_credentials())?;
+        let user = self
+            .user_repository
+            .find_by_id(&user_id)
+            .await?
+            .ok_or_else(invalid_credentials)?;
+        Ok(user.into())
+    }
+}
+
+fn invalid_credentials() -> DomainError {
+    DomainError::Unauthorized("Invalid credentials".to_string())
+}
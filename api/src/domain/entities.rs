@@ -21,6 +21,8 @@ pub struct Contact {
     pub etag: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>, // Soft delete: preenchido enquanto o contato está na lixeira
 }
 
 impl Contact {
@@ -35,8 +37,9 @@ impl Contact {
         phones: Vec<Phone>,
     ) -> Result<Self, DomainError> {
         if full_name.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Full name cannot be empty".to_string(),
+            return Err(DomainError::invalid_field(
+                "full_name",
+                "Full name cannot be empty",
             ));
         }
 
@@ -53,13 +56,15 @@ impl Contact {
             etag: Uuid::new_v4().to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            deleted_at: None,
         })
     }
 
     pub fn update_full_name(&mut self, full_name: String) -> Result<(), DomainError> {
         if full_name.trim().is_empty() {
-            return Err(DomainError::ValidationError(
-                "Full name cannot be empty".to_string(),
+            return Err(DomainError::invalid_field(
+                "full_name",
+                "Full name cannot be empty",
             ));
         }
         self.full_name = full_name;
@@ -130,6 +135,39 @@ impl Contact {
         Ok(())
     }
 
+    pub fn replace_emails(&mut self, emails: Vec<Email>) -> Result<(), DomainError> {
+        let mut contact = self.clone();
+        contact.emails.clear();
+        for email in emails {
+            let email =
+                Email::new(email.value, email.is_primary).map_err(DomainError::ValidationError)?;
+            contact.add_email(email)?;
+        }
+        self.emails = contact.emails;
+        self.etag = Uuid::new_v4().to_string();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn replace_phones(&mut self, phones: Vec<Phone>) -> Result<(), DomainError> {
+        let mut contact = self.clone();
+        contact.phones.clear();
+        for phone in phones {
+            let phone = Phone::new(
+                phone.e164,
+                phone.extension,
+                phone.phone_type,
+                phone.is_primary,
+            )
+            .map_err(DomainError::ValidationError)?;
+            contact.add_phone(phone)?;
+        }
+        self.phones = contact.phones;
+        self.etag = Uuid::new_v4().to_string();
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     pub fn remove_email(&mut self, email_value: &str) {
         self.emails.retain(|e| e.value != email_value);
         self.etag = Uuid::new_v4().to_string();
@@ -146,6 +184,10 @@ impl Contact {
         self.status == ContactStatus::Active
     }
 
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     pub fn get_primary_email(&self) -> Option<&Email> {
         self.emails.iter().find(|e| e.is_primary)
     }
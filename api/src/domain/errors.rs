@@ -1,4 +1,7 @@
+use serde::Serialize;
+use std::fmt;
 use thiserror::Error;
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Error)]
 pub enum DomainError {
@@ -6,6 +9,8 @@ pub enum DomainError {
     NotFound(String),
     #[error("Invalid input: {0}")]
     ValidationError(String),
+    #[error("Invalid input: {}", FieldErrors(.0))]
+    InvalidFields(Vec<FieldError>),
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
     #[error("Forbidden: {0}")]
@@ -22,22 +27,73 @@ pub enum DomainError {
     BusinessRuleViolation(String),
 }
 
+/// Invalid value for a single request field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        FieldError {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+struct FieldErrors<'a>(&'a [FieldError]);
+
+impl fmt::Display for FieldErrors<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl DomainError {
+    /// Validation error for a single field
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        DomainError::InvalidFields(vec![FieldError::new(field, message)])
+    }
+}
+
 impl From<sqlx::Error> for DomainError {
     fn from(err: sqlx::Error) -> Self {
+        use sqlx::error::ErrorKind;
+
+        // Driver messages (SQL, constraint and column names) stay in DatabaseError,
+        // which is logged but never returned to clients
         match err {
             sqlx::Error::RowNotFound => {
                 DomainError::NotFound("Entity not found in database".to_string())
             }
-            sqlx::Error::Database(db_err) => {
-                if db_err.constraint().is_some() {
-                    DomainError::Conflict(format!(
-                        "Database constraint violation: {}",
-                        db_err.message()
-                    ))
-                } else {
-                    DomainError::DatabaseError(db_err.message().to_string())
+            sqlx::Error::Database(db_err) => match db_err.kind() {
+                ErrorKind::UniqueViolation => {
+                    DomainError::Conflict("Resource already exists".to_string())
                 }
-            }
+                ErrorKind::ForeignKeyViolation => DomainError::Conflict(
+                    "Referenced resource does not exist or is still in use".to_string(),
+                ),
+                ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+                    DomainError::ValidationError(
+                        "Value violates a data integrity constraint".to_string(),
+                    )
+                }
+                _ => DomainError::DatabaseError(db_err.message().to_string()),
+            },
             _ => DomainError::DatabaseError(err.to_string()),
         }
     }
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::domain::entities::*;
@@ -16,6 +17,15 @@ pub trait ContactRepository: Send + Sync {
     async fn save(&self, contact: &Contact) -> Result<Contact, DomainError>;
     async fn update(&self, contact: &Contact) -> Result<Contact, DomainError>;
     async fn delete(&self, id: &ContactId) -> Result<(), DomainError>;
+    async fn soft_delete(&self, id: &ContactId) -> Result<(), DomainError>;
+    async fn restore(&self, id: &ContactId) -> Result<(), DomainError>;
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, DomainError>;
+    /// Persists all changes atomically; results are aligned with `changes`
+    /// (`Some` with the stored contact for updates, `None` for deletions)
+    async fn apply_batch(
+        &self,
+        changes: &[ContactChange],
+    ) -> Result<Vec<Option<Contact>>, DomainError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<Contact>, DomainError>;
     async fn find_by_document(&self, document: &str) -> Result<Option<Contact>, DomainError>;
     async fn find_by_name(&self, name: &str) -> Result<Vec<Contact>, DomainError>;
@@ -26,7 +36,18 @@ pub trait ContactRepository: Send + Sync {
     ) -> Result<Vec<Contact>, DomainError>;
     async fn count_by_status(&self, status: &ContactStatus) -> Result<i64, DomainError>;
     async fn count_by_type(&self, contact_type: &ContactType) -> Result<i64, DomainError>;
-    async fn get_statistics(&self) -> Result<ContactStatistics, DomainError>;
+    async fn get_statistics(
+        &self,
+        query: &ContactStatisticsQuery,
+    ) -> Result<ContactStatistics, DomainError>;
+}
+
+/// Alteração de um lote; `etag` é o ETag verificado ao preparar a alteração, e o
+/// contato só é gravado se ainda o tiver (senão, `DomainError::Conflict`)
+#[derive(Debug, Clone)]
+pub enum ContactChange {
+    Update { contact: Contact, etag: String },
+    SoftDelete { id: ContactId, etag: String },
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +57,7 @@ pub struct ContactSearchCriteria {
     pub status: Option<ContactStatus>,
     pub unit_id: Option<OrgUnitId>,
     pub department_id: Option<DepartmentId>,
+    pub include_deleted: bool,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -46,6 +68,16 @@ pub struct ContactSearchResult {
     pub total: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactStatisticsQuery {
+    /// Inclusive start of the time series range
+    pub from: DateTime<Utc>,
+    /// Exclusive end of the time series range
+    pub to: DateTime<Utc>,
+    pub bucket: StatisticsBucket,
+}
+
+/// Aggregated statistics over non-deleted contacts
 #[derive(Debug, Clone)]
 pub struct ContactStatistics {
     pub total_contacts: i64,
@@ -53,7 +85,36 @@ pub struct ContactStatistics {
     pub inactive_contacts: i64,
     pub persons: i64,
     pub organizations: i64,
+    /// Contacts of type DEPARTMENT
+    pub department_contacts: i64,
+    /// Departments registered in the directory
     pub departments: i64,
+    pub by_org_unit: Vec<OrgUnitContactCount>,
+    pub by_department: Vec<(DepartmentId, i64)>,
+    pub by_status: Vec<(ContactStatus, i64)>,
+    /// Contacts per source system; contacts without a source are not counted
+    pub by_source: Vec<(String, i64)>,
+    /// Sparse series keyed by bucket start; empty buckets are omitted
+    pub created_series: Vec<(DateTime<Utc>, i64)>,
+    pub updated_series: Vec<(DateTime<Utc>, i64)>,
+    pub data_quality: ContactDataQuality,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrgUnitContactCount {
+    pub unit_id: OrgUnitId,
+    /// Contacts assigned directly to the unit
+    pub contacts: i64,
+    /// Contacts assigned to the unit or to any of its descendants
+    pub contacts_including_descendants: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContactDataQuality {
+    pub missing_primary_email: i64,
+    pub missing_primary_phone: i64,
+    /// Documents that are not a valid CPF or CNPJ
+    pub invalid_document: i64,
 }
 
 // OrgUnit Repository
@@ -137,6 +198,12 @@ pub trait UserRepository: Send + Sync {
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, DomainError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError>;
     async fn find_by_role(&self, role: &str) -> Result<Vec<User>, DomainError>;
+    /// Busca o usuário pelo username e confere a senha contra o hash armazenado (bcrypt)
+    async fn verify_credentials(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<User>, DomainError>;
 }
 
 #[derive(Debug, Clone)]
@@ -168,6 +235,8 @@ pub trait AuditEventRepository: Send + Sync {
 }
 
 // SourceRecord Repository
+// Fontes e deduplicação ainda não têm casos de uso; por ora só os testes os usam
+#[allow(dead_code)]
 #[async_trait]
 pub trait SourceRecordRepository: Send + Sync {
     async fn find_by_id(&self, id: &SourceRecordId) -> Result<Option<SourceRecord>, DomainError>;
@@ -181,6 +250,7 @@ pub trait SourceRecordRepository: Send + Sync {
 }
 
 // ContactSource Repository
+#[allow(dead_code)]
 #[async_trait]
 pub trait ContactSourceRepository: Send + Sync {
     async fn save(&self, contact_source: &ContactSource) -> Result<ContactSource, DomainError>;
@@ -200,6 +270,7 @@ pub trait ContactSourceRepository: Send + Sync {
 }
 
 // MergeCandidate Repository
+#[allow(dead_code)]
 #[async_trait]
 pub trait MergeCandidateRepository: Send + Sync {
     async fn save(&self, candidate: &MergeCandidate) -> Result<MergeCandidate, DomainError>;
@@ -213,6 +284,7 @@ pub trait MergeCandidateRepository: Send + Sync {
 }
 
 // MergeDecision Repository
+#[allow(dead_code)]
 #[async_trait]
 pub trait MergeDecisionRepository: Send + Sync {
     async fn save(&self, decision: &MergeDecision) -> Result<MergeDecision, DomainError>;
@@ -229,4 +301,5 @@ pub trait MergeDecisionRepository: Send + Sync {
 pub trait WebhookReceiptRepository: Send + Sync {
     async fn save(&self, receipt: &WebhookReceipt) -> Result<WebhookReceipt, DomainError>;
     async fn exists(&self, source: &str, nonce: &str) -> Result<bool, DomainError>;
+    async fn delete(&self, source: &str, nonce: &str) -> Result<(), DomainError>;
 }
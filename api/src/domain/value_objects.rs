@@ -1,7 +1,10 @@
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::str::FromStr;
 use thiserror::Error;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // Common Value Objects
@@ -56,7 +59,7 @@ impl fmt::Display for ContactId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct Email {
     pub value: String,
     pub is_primary: bool,
@@ -71,7 +74,7 @@ impl Email {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct Phone {
     pub e164: String,
     pub extension: Option<String>,
@@ -98,7 +101,7 @@ impl Phone {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum PhoneType {
     Mobile,
     Work,
@@ -191,6 +194,105 @@ impl FromStr for ContactStatus {
     }
 }
 
+/// Valida o documento de um contato: CPF (11 dígitos) ou CNPJ (14 dígitos)
+///
+/// Aceita a pontuação usual (`.`, `-`, `/`) e confere os dígitos verificadores.
+/// Sequências de um único dígito repetido são rejeitadas.
+pub fn is_valid_document(document: &str) -> bool {
+    if document
+        .chars()
+        .any(|c| !c.is_ascii_digit() && !matches!(c, '.' | '-' | '/' | ' '))
+    {
+        return false;
+    }
+
+    let digits: Vec<u32> = document.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.windows(2).all(|w| w[0] == w[1]) {
+        return false;
+    }
+
+    // Dígito verificador módulo 11 para os `weights.len()` primeiros dígitos
+    let check_digit = |weights: &[u32]| {
+        let sum: u32 = digits.iter().zip(weights).map(|(d, w)| d * w).sum();
+        match sum % 11 {
+            0 | 1 => 0,
+            r => 11 - r,
+        }
+    };
+
+    match digits.len() {
+        11 => {
+            check_digit(&[10, 9, 8, 7, 6, 5, 4, 3, 2]) == digits[9]
+                && check_digit(&[11, 10, 9, 8, 7, 6, 5, 4, 3, 2]) == digits[10]
+        }
+        14 => {
+            check_digit(&[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]) == digits[12]
+                && check_digit(&[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]) == digits[13]
+        }
+        _ => false,
+    }
+}
+
+// Statistics Value Objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatisticsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl StatisticsBucket {
+    /// Início do intervalo que contém `at` (semanas começam na segunda-feira,
+    /// como o `date_trunc` do PostgreSQL)
+    pub fn truncate(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let start = match self {
+            StatisticsBucket::Day => date,
+            StatisticsBucket::Week => date
+                .checked_sub_signed(Duration::days(i64::from(
+                    date.weekday().num_days_from_monday(),
+                )))
+                .unwrap_or(NaiveDate::MIN),
+            StatisticsBucket::Month => date.with_day(1).unwrap_or(date),
+        };
+        start.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// Início do intervalo seguinte ao que começa em `start`
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            StatisticsBucket::Day => start.checked_add_signed(Duration::days(1)),
+            StatisticsBucket::Week => start.checked_add_signed(Duration::weeks(1)),
+            StatisticsBucket::Month => start.checked_add_months(Months::new(1)),
+        }
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+impl fmt::Display for StatisticsBucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatisticsBucket::Day => write!(f, "day"),
+            StatisticsBucket::Week => write!(f, "week"),
+            StatisticsBucket::Month => write!(f, "month"),
+        }
+    }
+}
+
+impl FromStr for StatisticsBucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(StatisticsBucket::Day),
+            "week" => Ok(StatisticsBucket::Week),
+            "month" => Ok(StatisticsBucket::Month),
+            _ => Err(format!("'{}' is not a valid StatisticsBucket", s)),
+        }
+    }
+}
+
 // OrgUnit Value Objects
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OrgUnitId(pub Uuid);
@@ -508,6 +610,39 @@ impl Nonce {
     }
 }
 
+// Principal - usuário autenticado que executa a operação
+// Montado a partir do token JWT pela camada de apresentação e repassado aos
+// casos de uso para decisões de autorização (propriedade, dados pessoais)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Principal {
+    pub subject: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub session_id: Option<String>,
+    pub realm_roles: Vec<String>,
+    /// Roles por client (`resource_access.<client>.roles`)
+    pub client_roles: BTreeMap<String, Vec<String>>,
+    pub scopes: Vec<String>,
+    /// Permissões efetivas (roles, roles de client e escopos já mapeados)
+    pub permissions: BTreeSet<String>,
+}
+
+impl Principal {
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.contains(permission)
+    }
+
+    pub fn has_any_permission<S: AsRef<str>>(&self, permissions: &[S]) -> bool {
+        permissions
+            .iter()
+            .any(|permission| self.has_permission(permission.as_ref()))
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.has_permission("admin")
+    }
+}
+
 // Common Value Object Errors
 #[derive(Debug, Error)]
 pub enum ValueObjectError {
@@ -520,3 +655,70 @@ pub enum ValueObjectError {
     #[error("Value too long: maximum {0} characters allowed")]
     TooLong(usize),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_valid_cpf_and_cnpj() {
+        assert!(is_valid_document("529.982.247-25"));
+        assert!(is_valid_document("52998224725"));
+        assert!(is_valid_document("11.222.333/0001-81"));
+    }
+
+    #[test]
+    fn test_invalid_documents() {
+        assert!(!is_valid_document("529.982.247-24"));
+        assert!(!is_valid_document("11.222.333/0001-80"));
+        assert!(!is_valid_document("111.111.111-11"));
+        assert!(!is_valid_document("12345"));
+        assert!(!is_valid_document("ABC.982.247-25"));
+    }
+
+    #[test]
+    fn test_bucket_truncate() {
+        // 2025-01-15 is a Wednesday
+        let at = Utc.with_ymd_and_hms(2025, 1, 15, 13, 45, 0).unwrap();
+
+        assert_eq!(
+            StatisticsBucket::Day.truncate(at),
+            Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            StatisticsBucket::Week.truncate(at),
+            Utc.with_ymd_and_hms(2025, 1, 13, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            StatisticsBucket::Month.truncate(at),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bucket_next_month_handles_month_lengths() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let next = StatisticsBucket::Month.next(start);
+
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            StatisticsBucket::Month.next(next),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_principal_permissions() {
+        let principal = Principal {
+            subject: "user-1".to_string(),
+            permissions: BTreeSet::from(["directory.write".to_string()]),
+            ..Principal::default()
+        };
+
+        assert!(principal.has_permission("directory.write"));
+        assert!(principal.has_any_permission(&["admin", "directory.write"]));
+        assert!(!principal.has_any_permission(&["admin"]));
+        assert!(!principal.is_admin());
+    }
+}
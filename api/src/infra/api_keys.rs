@@ -0,0 +1,388 @@
+//! =============================================================================
+//! API KEY STORE
+//! =============================================================================
+//! Persistência das API keys de clientes máquina-a-máquina
+//! Apenas o hash SHA-256 da chave é armazenado, na tabela `api_keys` do PostgreSQL
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Prefixo fixo das chaves emitidas (`sut_<prefix>_<secret>`)
+pub const API_KEY_PREFIX: &str = "sut";
+
+/// API key registrada (sem o segredo)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// Parte pública da chave, usada para localizá-la
+    pub prefix: String,
+    /// SHA-256 (hex) da chave completa
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Chave não revogada e não expirada
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// Chave recém-gerada; `key` só existe em memória e é exibida uma única vez
+#[derive(Debug, Clone)]
+pub struct GeneratedApiKey {
+    pub prefix: String,
+    pub key: String,
+    pub key_hash: String,
+}
+
+/// Gera uma nova chave aleatória (48 bits de prefixo, 256 bits de segredo)
+pub fn generate_api_key() -> GeneratedApiKey {
+    let mut rng = rand::thread_rng();
+    let mut prefix = [0u8; 6];
+    let mut secret = [0u8; 32];
+    rng.fill_bytes(&mut prefix);
+    rng.fill_bytes(&mut secret);
+
+    let prefix = hex::encode(prefix);
+    let key = format!("{}_{}_{}", API_KEY_PREFIX, prefix, hex::encode(secret));
+    let key_hash = hash_api_key(&key);
+    GeneratedApiKey {
+        prefix,
+        key,
+        key_hash,
+    }
+}
+
+/// SHA-256 (hex) da chave completa
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Extrai o prefixo público de uma chave no formato `sut_<prefix>_<secret>`
+pub fn api_key_prefix(key: &str) -> Option<&str> {
+    let mut parts = key.splitn(3, '_');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(API_KEY_PREFIX), Some(prefix), Some(secret))
+            if !prefix.is_empty() && !secret.is_empty() =>
+        {
+            Some(prefix)
+        }
+        _ => None,
+    }
+}
+
+/// Armazenamento de API keys
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Registra uma nova chave
+    async fn create(&self, key: &ApiKey) -> anyhow::Result<()>;
+
+    /// Lista todas as chaves, inclusive revogadas
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>>;
+
+    /// Busca uma chave pelo prefixo público
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<ApiKey>>;
+
+    /// Substitui o segredo de uma chave não revogada
+    async fn rotate(
+        &self,
+        id: Uuid,
+        prefix: &str,
+        key_hash: &str,
+    ) -> anyhow::Result<Option<ApiKey>>;
+
+    /// Revoga uma chave, retornando `false` se ela não existe ou já foi revogada
+    async fn revoke(&self, id: Uuid) -> anyhow::Result<bool>;
+
+    /// Registra o uso da chave
+    async fn touch(&self, id: Uuid) -> anyhow::Result<()>;
+}
+
+/// Implementação em memória (testes e modo demo)
+#[derive(Default)]
+pub struct InMemoryApiKeyStore {
+    keys: Mutex<Vec<ApiKey>>,
+}
+
+impl InMemoryApiKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn create(&self, key: &ApiKey) -> anyhow::Result<()> {
+        let mut keys = self.keys.lock();
+        if keys
+            .iter()
+            .any(|k| k.id == key.id || k.prefix == key.prefix)
+        {
+            anyhow::bail!("API key {} already exists", key.id);
+        }
+        keys.push(key.clone());
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+        let mut keys = self.keys.lock().clone();
+        keys.sort_by_key(|k| k.created_at);
+        Ok(keys)
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<ApiKey>> {
+        Ok(self
+            .keys
+            .lock()
+            .iter()
+            .find(|k| k.prefix == prefix)
+            .cloned())
+    }
+
+    async fn rotate(
+        &self,
+        id: Uuid,
+        prefix: &str,
+        key_hash: &str,
+    ) -> anyhow::Result<Option<ApiKey>> {
+        let mut keys = self.keys.lock();
+        let Some(key) = keys
+            .iter_mut()
+            .find(|k| k.id == id && k.revoked_at.is_none())
+        else {
+            return Ok(None);
+        };
+        key.prefix = prefix.to_string();
+        key.key_hash = key_hash.to_string();
+        key.last_used_at = None;
+        Ok(Some(key.clone()))
+    }
+
+    async fn revoke(&self, id: Uuid) -> anyhow::Result<bool> {
+        let mut keys = self.keys.lock();
+        match keys
+            .iter_mut()
+            .find(|k| k.id == id && k.revoked_at.is_none())
+        {
+            Some(key) => {
+                key.revoked_at = Some(Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn touch(&self, id: Uuid) -> anyhow::Result<()> {
+        for key in self.keys.lock().iter_mut().filter(|k| k.id == id) {
+            key.last_used_at = Some(Utc::now());
+        }
+        Ok(())
+    }
+}
+
+/// Implementação em PostgreSQL
+pub struct PgApiKeyStore {
+    pool: crate::infra::pg::PgPoolHandle,
+}
+
+impl PgApiKeyStore {
+    pub fn new(pool: crate::infra::pg::PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+const API_KEY_COLUMNS: &str = "id, name, prefix, key_hash, scopes, created_by, created_at, \
+                               expires_at, last_used_at, revoked_at";
+
+type ApiKeyRow = (
+    Uuid,
+    String,
+    String,
+    String,
+    Vec<String>,
+    Option<String>,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    Option<DateTime<Utc>>,
+    Option<DateTime<Utc>>,
+);
+
+fn api_key_from_row(row: ApiKeyRow) -> ApiKey {
+    let (
+        id,
+        name,
+        prefix,
+        key_hash,
+        scopes,
+        created_by,
+        created_at,
+        expires_at,
+        last_used_at,
+        revoked_at,
+    ) = row;
+    ApiKey {
+        id,
+        name,
+        prefix,
+        key_hash,
+        scopes,
+        created_by,
+        created_at,
+        expires_at,
+        last_used_at,
+        revoked_at,
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for PgApiKeyStore {
+    async fn create(&self, key: &ApiKey) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO api_keys (id, name, prefix, key_hash, scopes, created_by, created_at, expires_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(key.id)
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(&key.scopes)
+        .bind(&key.created_by)
+        .bind(key.created_at)
+        .bind(key.expires_at)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<ApiKey>> {
+        let rows: Vec<ApiKeyRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM api_keys ORDER BY created_at",
+            API_KEY_COLUMNS
+        ))
+        .fetch_all(&self.pool.get())
+        .await?;
+        Ok(rows.into_iter().map(api_key_from_row).collect())
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> anyhow::Result<Option<ApiKey>> {
+        let row: Option<ApiKeyRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM api_keys WHERE prefix = $1",
+            API_KEY_COLUMNS
+        ))
+        .bind(prefix)
+        .fetch_optional(&self.pool.get())
+        .await?;
+        Ok(row.map(api_key_from_row))
+    }
+
+    async fn rotate(
+        &self,
+        id: Uuid,
+        prefix: &str,
+        key_hash: &str,
+    ) -> anyhow::Result<Option<ApiKey>> {
+        let row: Option<ApiKeyRow> = sqlx::query_as(&format!(
+            "UPDATE api_keys SET prefix = $2, key_hash = $3, last_used_at = NULL \
+             WHERE id = $1 AND revoked_at IS NULL RETURNING {}",
+            API_KEY_COLUMNS
+        ))
+        .bind(id)
+        .bind(prefix)
+        .bind(key_hash)
+        .fetch_optional(&self.pool.get())
+        .await?;
+        Ok(row.map(api_key_from_row))
+    }
+
+    async fn revoke(&self, id: Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = now() WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch(&self, id: Uuid) -> anyhow::Result<()> {
+        // Grava no máximo uma vez por minuto para não gerar uma escrita por requisição
+        sqlx::query(
+            "UPDATE api_keys SET last_used_at = now() WHERE id = $1 \
+             AND (last_used_at IS NULL OR last_used_at < now() - interval '1 minute')",
+        )
+        .bind(id)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_round_trip() {
+        let generated = generate_api_key();
+        assert!(generated.key.starts_with("sut_"));
+        assert_eq!(
+            api_key_prefix(&generated.key),
+            Some(generated.prefix.as_str())
+        );
+        assert_eq!(hash_api_key(&generated.key), generated.key_hash);
+        assert_ne!(generate_api_key().key, generated.key);
+    }
+
+    #[test]
+    fn test_api_key_prefix_rejects_malformed_keys() {
+        assert_eq!(api_key_prefix("abc_0123_secret"), None);
+        assert_eq!(api_key_prefix("sut_0123"), None);
+        assert_eq!(api_key_prefix("sut__secret"), None);
+        assert_eq!(api_key_prefix("sut_0123_"), None);
+        assert_eq!(api_key_prefix("sut_0123_se_cret"), Some("0123"));
+    }
+
+    #[test]
+    fn test_is_active_checks_revocation_and_expiry() {
+        let now = Utc::now();
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            name: "etl".to_string(),
+            prefix: "0123".to_string(),
+            key_hash: String::new(),
+            scopes: vec![],
+            created_by: None,
+            created_at: now,
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        assert!(key.is_active(now));
+        assert!(ApiKey {
+            expires_at: Some(now + chrono::Duration::hours(1)),
+            ..key.clone()
+        }
+        .is_active(now));
+        assert!(!ApiKey {
+            expires_at: Some(now),
+            ..key.clone()
+        }
+        .is_active(now));
+        assert!(!ApiKey {
+            revoked_at: Some(now),
+            ..key
+        }
+        .is_active(now));
+    }
+}
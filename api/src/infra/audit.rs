@@ -1,3 +1,5 @@
+use crate::domain::entities::AuditEvent;
+use crate::domain::value_objects::{Action, EntityType};
 use crate::AppState;
 use axum::http::StatusCode;
 
@@ -10,13 +12,21 @@ pub async fn log_audit(
     before: Option<serde_json::Value>,
     after: Option<serde_json::Value>,
 ) -> Result<(), StatusCode> {
-    let _ = sqlx::query("INSERT INTO audit_events (actor_sub, action, entity_type, entity_id, before, after) VALUES ($1,$2,$3,$4,$5,$6)")
-    .bind(actor_sub.map(|s| s.to_string()))
-    .bind(action)
-    .bind(entity_type)
-    .bind(entity_id)
-    .bind(before)
-    .bind(after)
-    .execute(&st.pg).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let event = AuditEvent::new(
+        actor_sub.map(|s| s.to_string()),
+        Action {
+            value: action.to_string(),
+        },
+        EntityType {
+            value: entity_type.to_string(),
+        },
+        entity_id.to_string(),
+        before,
+        after,
+    );
+    st.audit_event_repository
+        .save(&event)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(())
 }
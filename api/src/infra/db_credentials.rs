@@ -0,0 +1,474 @@
+//! =============================================================================
+//! DYNAMIC DATABASE CREDENTIALS
+//! =============================================================================
+//! Credenciais PostgreSQL de curta duração emitidas pelo Vault (`database/creds/<role>`)
+//! O lease é renovado antes de expirar; quando não pode mais ser renovado
+//! (max_ttl atingido ou falha na renovação), novas credenciais são emitidas e um
+//! novo pool substitui o atual sem interromper as requisições em andamento
+
+use crate::infra::pg::{self, PgPoolHandle};
+use crate::infra::vault::{DatabaseLease, VaultClient};
+use crate::shared::config::DatabaseConfig;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use sqlx::postgres::PgConnectOptions;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Tempo em que o pool anterior continua aberto após a troca, para as
+/// requisições em andamento terminarem
+const POOL_DRAIN_GRACE: Duration = Duration::from_secs(30);
+
+/// Intervalo mínimo entre ciclos (evita laço apertado com leases muito curtos)
+const MIN_RENEWAL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Espera antes de tentar novamente após uma falha de rotação
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Estado do lease atual, exposto em `/ready`
+#[derive(Debug, Clone, Serialize)]
+pub struct LeaseStatus {
+    pub lease_id: String,
+    pub username: String,
+    pub renewable: bool,
+    /// Validade concedida na emissão (referência para decidir a rotação)
+    pub lease_duration_secs: u64,
+    pub expires_at: DateTime<Utc>,
+    pub last_renewed_at: Option<DateTime<Utc>>,
+    /// Quantas vezes as credenciais foram trocadas desde a inicialização
+    pub rotations: u64,
+    pub last_error: Option<String>,
+}
+
+impl LeaseStatus {
+    fn from_lease(lease: &DatabaseLease, rotations: u64) -> Self {
+        Self {
+            lease_id: lease.lease_id.clone(),
+            username: lease.credentials.username.clone(),
+            renewable: lease.renewable,
+            lease_duration_secs: lease.lease_duration,
+            expires_at: Utc::now() + chrono::Duration::seconds(lease.lease_duration as i64),
+            last_renewed_at: None,
+            rotations,
+            last_error: None,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Segundos até a expiração (negativo se já expirou)
+    pub fn expires_in_secs(&self, now: DateTime<Utc>) -> i64 {
+        (self.expires_at - now).num_seconds()
+    }
+}
+
+/// Próximo ciclo após 2/3 da validade restante
+fn renewal_delay(remaining: Duration) -> Duration {
+    (remaining * 2 / 3).max(MIN_RENEWAL_INTERVAL)
+}
+
+/// Renovação que já não cobre 1/3 da validade original indica max_ttl próximo
+fn renewal_is_short(renewed_secs: u64, original_secs: u64) -> bool {
+    renewed_secs < original_secs / 3
+}
+
+/// Aplica as credenciais do lease sobre as opções de conexão do PG_DSN
+fn with_credentials(base: &PgConnectOptions, lease: &DatabaseLease) -> PgConnectOptions {
+    base.clone()
+        .username(&lease.credentials.username)
+        .password(&lease.credentials.password)
+}
+
+/// Gerencia o lease das credenciais dinâmicas e o pool construído com elas
+pub struct DbCredentialManager {
+    vault: VaultClient,
+    role: String,
+    database: DatabaseConfig,
+    base_options: PgConnectOptions,
+    pool: PgPoolHandle,
+    status: RwLock<LeaseStatus>,
+    /// Tempo de drenagem do pool anterior (`POOL_DRAIN_GRACE`)
+    drain_grace: Duration,
+}
+
+impl DbCredentialManager {
+    /// Emite as primeiras credenciais e conecta o pool
+    ///
+    /// Host, porta, banco e parâmetros vêm de `database.connection_string`;
+    /// usuário e senha vêm do Vault.
+    pub async fn connect(
+        vault: VaultClient,
+        role: String,
+        database: &DatabaseConfig,
+    ) -> anyhow::Result<Arc<Self>> {
+        let base_options = PgConnectOptions::from_str(&database.connection_string)
+            .context("invalid PG_DSN connection string")?;
+        let lease = vault.get_database_credentials(&role).await?;
+        let pool = pg::pool_with_options(database, with_credentials(&base_options, &lease)).await?;
+        info!(role = %role, username = %lease.credentials.username, lease_duration = lease.lease_duration, "connected with Vault dynamic database credentials");
+        Ok(Arc::new(Self::new(
+            vault,
+            role,
+            database.clone(),
+            base_options,
+            &lease,
+            PgPoolHandle::new(pool),
+        )))
+    }
+
+    fn new(
+        vault: VaultClient,
+        role: String,
+        database: DatabaseConfig,
+        base_options: PgConnectOptions,
+        lease: &DatabaseLease,
+        pool: PgPoolHandle,
+    ) -> Self {
+        Self {
+            vault,
+            role,
+            database,
+            base_options,
+            pool,
+            status: RwLock::new(LeaseStatus::from_lease(lease, 0)),
+            drain_grace: POOL_DRAIN_GRACE,
+        }
+    }
+
+    /// Handle do pool (sempre aponta para o pool com as credenciais atuais)
+    pub fn pool(&self) -> PgPoolHandle {
+        self.pool.clone()
+    }
+
+    /// Estado do lease atual
+    pub fn status(&self) -> LeaseStatus {
+        self.status.read().clone()
+    }
+
+    /// Executa o ciclo de renovação/rotação em background
+    pub fn spawn_renewal(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(self.next_delay()).await;
+                self.renew_or_rotate().await;
+            }
+        })
+    }
+
+    fn next_delay(&self) -> Duration {
+        let status = self.status.read();
+        if status.last_error.is_some() {
+            return RETRY_INTERVAL;
+        }
+        let remaining = status.expires_in_secs(Utc::now()).max(0) as u64;
+        renewal_delay(Duration::from_secs(remaining))
+    }
+
+    /// Renova o lease; se não for possível, emite novas credenciais e troca o pool
+    async fn renew_or_rotate(&self) {
+        let (lease_id, renewable, original) = {
+            let status = self.status.read();
+            (
+                status.lease_id.clone(),
+                status.renewable,
+                status.lease_duration_secs,
+            )
+        };
+
+        if renewable {
+            match self.vault.renew_lease(&lease_id, original).await {
+                Ok(renewal) if !renewal_is_short(renewal.lease_duration, original) => {
+                    let now = Utc::now();
+                    let mut status = self.status.write();
+                    status.expires_at =
+                        now + chrono::Duration::seconds(renewal.lease_duration as i64);
+                    status.renewable = renewal.renewable;
+                    status.last_renewed_at = Some(now);
+                    status.last_error = None;
+                    info!(
+                        lease_duration = renewal.lease_duration,
+                        "database credential lease renewed"
+                    );
+                    return;
+                }
+                Ok(renewal) => info!(
+                    lease_duration = renewal.lease_duration,
+                    "database credential lease close to max_ttl; rotating credentials"
+                ),
+                Err(e) => {
+                    warn!(error = ?e, "database credential lease renewal failed; rotating credentials")
+                }
+            }
+        }
+
+        if let Err(e) = self.rotate().await {
+            error!(error = ?e, "database credential rotation failed; keeping current pool");
+            self.status.write().last_error = Some(e.to_string());
+        }
+    }
+
+    /// Emite novas credenciais, conecta um novo pool e substitui o atual
+    async fn rotate(&self) -> anyhow::Result<()> {
+        let lease = self.vault.get_database_credentials(&self.role).await?;
+        let pool = match pg::pool_with_options(
+            &self.database,
+            with_credentials(&self.base_options, &lease),
+        )
+        .await
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                // Credenciais que não conectaram não devem ficar válidas no Vault
+                if let Err(revoke_error) = self.vault.revoke_lease(&lease.lease_id).await {
+                    warn!(error = ?revoke_error, "failed to revoke unused database lease");
+                }
+                return Err(e);
+            }
+        };
+
+        let previous_pool = self.pool.replace(pool);
+        let previous = {
+            let mut status = self.status.write();
+            let rotations = status.rotations + 1;
+            std::mem::replace(&mut *status, LeaseStatus::from_lease(&lease, rotations))
+        };
+        info!(username = %lease.credentials.username, lease_duration = lease.lease_duration, "database credentials rotated");
+
+        // Drenagem: requisições em andamento terminam no pool anterior, que só
+        // então é fechado e tem o lease revogado
+        let vault = self.vault.clone();
+        let drain_grace = self.drain_grace;
+        tokio::spawn(async move {
+            tokio::time::sleep(drain_grace).await;
+            previous_pool.close().await;
+            if let Err(e) = vault.revoke_lease(&previous.lease_id).await {
+                warn!(error = ?e, "failed to revoke previous database lease");
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::vault::{DatabaseCredentials, VaultConfig};
+
+    fn lease(id: &str, duration: u64) -> DatabaseLease {
+        DatabaseLease {
+            lease_id: id.to_string(),
+            lease_duration: duration,
+            renewable: true,
+            credentials: DatabaseCredentials {
+                username: "v-sut-1".to_string(),
+                password: "pw".to_string(),
+            },
+        }
+    }
+
+    /// Gerenciador com pool "lazy" apontando para uma porta sem PostgreSQL
+    fn manager(vault_url: String) -> DbCredentialManager {
+        manager_on(vault_url, "postgres://127.0.0.1:1/sut")
+    }
+
+    /// Gerenciador com pool "lazy" sobre `connection_string`
+    fn manager_on(vault_url: String, connection_string: &str) -> DbCredentialManager {
+        let database = DatabaseConfig {
+            connection_string: connection_string.to_string(),
+            pool_size: 1,
+            connection_timeout_secs: 1,
+            connect_attempts: 1,
+            connect_backoff_ms: 0,
+            vault_role: Some("sut-app".to_string()),
+        };
+        let vault = VaultClient::new(VaultConfig {
+            addr: vault_url,
+            token: "test-token".to_string(),
+            timeout: Duration::from_secs(5),
+            max_retries: 1,
+            retry_delay: Duration::from_millis(10),
+        })
+        .unwrap();
+        let base_options = PgConnectOptions::from_str(&database.connection_string).unwrap();
+        let pool = pg::pool_options(&database).connect_lazy_with(base_options.clone());
+        DbCredentialManager::new(
+            vault,
+            "sut-app".to_string(),
+            database,
+            base_options,
+            &lease("database/creds/sut-app/first", 3600),
+            PgPoolHandle::new(pool),
+        )
+    }
+
+    #[test]
+    fn test_renewal_schedule() {
+        assert_eq!(
+            renewal_delay(Duration::from_secs(3600)),
+            Duration::from_secs(2400)
+        );
+        assert_eq!(renewal_delay(Duration::ZERO), MIN_RENEWAL_INTERVAL);
+        assert!(!renewal_is_short(1200, 3600));
+        assert!(renewal_is_short(1199, 3600));
+    }
+
+    #[tokio::test]
+    async fn test_lease_is_renewed_in_place() {
+        let mut server = mockito::Server::new_async().await;
+        let renew = server
+            .mock("PUT", "/v1/sys/leases/renew")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"lease_id": "database/creds/sut-app/first", "lease_duration": 3600, "renewable": true, "data": null}"#)
+            .create_async()
+            .await;
+        let manager = manager(server.url());
+        manager.status.write().expires_at = Utc::now();
+
+        manager.renew_or_rotate().await;
+
+        renew.assert_async().await;
+        let status = manager.status();
+        assert_eq!(status.lease_id, "database/creds/sut-app/first");
+        assert_eq!(status.rotations, 0);
+        assert!(status.last_renewed_at.is_some());
+        assert!(status.expires_in_secs(Utc::now()) > 3500);
+    }
+
+    #[tokio::test]
+    async fn test_failed_rotation_keeps_current_pool_and_revokes_new_lease() {
+        let mut server = mockito::Server::new_async().await;
+        // Renovação limitada pelo max_ttl força a rotação
+        let _renew = server
+            .mock("PUT", "/v1/sys/leases/renew")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"lease_id": "database/creds/sut-app/first", "lease_duration": 60, "renewable": false, "data": null}"#)
+            .create_async()
+            .await;
+        let _creds = server
+            .mock("GET", "/v1/database/creds/sut-app")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"lease_id": "database/creds/sut-app/second", "lease_duration": 3600, "renewable": true, "data": {"username": "v-sut-2", "password": "pw2"}}"#)
+            .create_async()
+            .await;
+        let revoke = server
+            .mock("PUT", "/v1/sys/leases/revoke")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "lease_id": "database/creds/sut-app/second"
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let manager = manager(server.url());
+
+        manager.renew_or_rotate().await;
+
+        revoke.assert_async().await;
+        let status = manager.status();
+        assert_eq!(status.username, "v-sut-1");
+        assert_eq!(status.rotations, 0);
+        assert!(status.last_error.is_some());
+        assert_eq!(manager.next_delay(), RETRY_INTERVAL);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires TEST_PG_DSN"]
+    async fn test_rotation_swaps_pool_and_revokes_previous_lease_after_drain() {
+        let dsn = std::env::var("TEST_PG_DSN")
+            .expect("TEST_PG_DSN must point to a PostgreSQL server to run the postgres tests");
+        let admin = PgConnectOptions::from_str(&dsn).expect("invalid TEST_PG_DSN");
+        // Usuário criado pelo "Vault" para as novas credenciais
+        let username = format!("v_sut_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+        let mut connection = sqlx::ConnectOptions::connect(&admin).await.unwrap();
+        sqlx::Executor::execute(
+            &mut connection,
+            format!("CREATE ROLE {username} LOGIN PASSWORD 'pw2'").as_str(),
+        )
+        .await
+        .unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        // Renovação limitada pelo max_ttl força a rotação
+        let _renew = server
+            .mock("PUT", "/v1/sys/leases/renew")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"lease_id": "database/creds/sut-app/first", "lease_duration": 60, "renewable": false, "data": null}"#)
+            .create_async()
+            .await;
+        let _creds = server
+            .mock("GET", "/v1/database/creds/sut-app")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "lease_id": "database/creds/sut-app/second",
+                    "lease_duration": 3600,
+                    "renewable": true,
+                    "data": { "username": username, "password": "pw2" },
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let revoke = server
+            .mock("PUT", "/v1/sys/leases/revoke")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "lease_id": "database/creds/sut-app/first"
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let mut manager = manager_on(server.url(), &dsn);
+        manager.drain_grace = Duration::from_millis(200);
+
+        // Requisição em andamento no pool anterior durante a troca
+        let previous = manager.pool().get();
+        let mut in_flight = previous.acquire().await.unwrap();
+
+        manager.renew_or_rotate().await;
+
+        let status = manager.status();
+        assert_eq!(status.lease_id, "database/creds/sut-app/second");
+        assert_eq!(status.username, username);
+        assert_eq!(status.rotations, 1);
+        assert!(status.last_error.is_none());
+        let current_user: String = sqlx::query_scalar("SELECT current_user::text")
+            .fetch_one(&manager.pool().get())
+            .await
+            .unwrap();
+        assert_eq!(current_user, username);
+
+        // Após a carência, o pool anterior espera a requisição terminar antes de fechar
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let one: i32 = sqlx::query_scalar("SELECT 1")
+            .fetch_one(&mut *in_flight)
+            .await
+            .unwrap();
+        assert_eq!(one, 1);
+        assert!(!revoke.matched_async().await);
+        drop(in_flight);
+
+        for _ in 0..50 {
+            if revoke.matched_async().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        revoke.assert_async().await;
+        assert!(previous.is_closed());
+
+        manager.pool().get().close().await;
+        sqlx::Executor::execute(&mut connection, format!("DROP ROLE {username}").as_str())
+            .await
+            .unwrap();
+    }
+}
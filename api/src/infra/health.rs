@@ -0,0 +1,208 @@
+//! =============================================================================
+//! HEALTH CHECKS DE INFRAESTRUTURA
+//! =============================================================================
+//! Verificações de PostgreSQL (latência e saturação do pool), versão das
+//! migrações, selagem do Vault e lease das credenciais dinâmicas, registradas
+//! no `HealthRegistry` (ver `shared::health`)
+//!
+//! Usuário do banco, versão do Vault e erros brutos vão em `diagnostics`, que o
+//! `/ready` público omite.
+
+use crate::infra::db_credentials::DbCredentialManager;
+use crate::infra::pg::{self, PgPoolHandle};
+use crate::infra::vault::VaultClient;
+use crate::shared::health::{CheckOutcome, HealthCheck};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Latência de `SELECT 1` a partir da qual o banco é considerado degradado
+const SLOW_QUERY: Duration = Duration::from_millis(250);
+
+/// Fração de conexões em uso a partir da qual o pool é considerado saturado
+const POOL_SATURATION: f64 = 0.9;
+
+/// Lease com menos tempo que isso é sinalizado (a renovação deveria ter ocorrido)
+const LEASE_EXPIRY_WARNING_SECS: i64 = 60;
+
+/// Latência e uso do pool do PostgreSQL
+pub struct PostgresCheck {
+    pool: PgPoolHandle,
+}
+
+impl PostgresCheck {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for PostgresCheck {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let pool = self.pool.get();
+        let started = Instant::now();
+        let result = sqlx::query_scalar::<_, i32>("SELECT 1")
+            .fetch_one(&pool)
+            .await;
+        let latency = started.elapsed();
+
+        let max = pool.options().get_max_connections();
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        let in_use = size.saturating_sub(idle);
+        let saturation = f64::from(in_use) / f64::from(max.max(1));
+        let details = json!({
+            "latency_ms": latency.as_millis() as u64,
+            "pool_size": size,
+            "pool_idle": idle,
+            "pool_in_use": in_use,
+            "pool_max": max,
+        });
+
+        match result {
+            Err(e) => CheckOutcome::fail(details, "query failed")
+                .with_diagnostics(json!({ "error": e.to_string() })),
+            Ok(_) if saturation >= POOL_SATURATION => CheckOutcome::warn(
+                details,
+                format!("connection pool saturated ({in_use}/{max} in use)"),
+            ),
+            Ok(_) if latency >= SLOW_QUERY => CheckOutcome::warn(
+                details,
+                format!("slow database ({}ms)", latency.as_millis()),
+            ),
+            Ok(_) => CheckOutcome::pass(details),
+        }
+    }
+}
+
+/// Migrações aplicadas em relação às embutidas no binário
+pub struct MigrationCheck {
+    pool: PgPoolHandle,
+}
+
+impl MigrationCheck {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for MigrationCheck {
+    fn name(&self) -> &str {
+        "migrations"
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let expected = pg::latest_migration_version();
+        let (applied, dirty) = match pg::applied_migration_version(&self.pool.get()).await {
+            Ok(applied) => applied,
+            Err(e) => {
+                return CheckOutcome::fail(
+                    json!({ "expected": expected }),
+                    "could not read the applied migrations",
+                )
+                .with_diagnostics(json!({ "error": format!("{e:#}") }))
+            }
+        };
+        let details = json!({ "applied": applied, "expected": expected, "dirty": dirty });
+
+        if dirty {
+            CheckOutcome::fail(details, "a migration failed partway")
+        } else if applied.unwrap_or(0) < expected {
+            CheckOutcome::fail(details, "database schema is behind this build")
+        } else {
+            // Versão maior que a esperada: outra réplica mais nova já migrou
+            CheckOutcome::pass(details)
+        }
+    }
+}
+
+/// Selagem do Vault (não crítica: segredos já carregados continuam em uso)
+pub struct VaultCheck {
+    vault: VaultClient,
+}
+
+impl VaultCheck {
+    pub fn new(vault: VaultClient) -> Self {
+        Self { vault }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for VaultCheck {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let status = match self.vault.seal_status().await {
+            Ok(status) => status,
+            Err(e) => {
+                return CheckOutcome::fail(serde_json::Value::Null, "vault is unreachable")
+                    .with_diagnostics(json!({ "error": format!("{e:#}") }))
+            }
+        };
+        let details = json!({
+            "initialized": status.initialized,
+            "sealed": status.sealed,
+            "standby": status.standby,
+        });
+
+        let outcome = if !status.initialized {
+            CheckOutcome::fail(details, "vault is not initialized")
+        } else if status.sealed {
+            CheckOutcome::fail(details, "vault is sealed")
+        } else {
+            CheckOutcome::pass(details)
+        };
+        outcome.with_diagnostics(json!({ "version": status.version }))
+    }
+}
+
+/// Lease das credenciais dinâmicas do PostgreSQL (`PG_VAULT_ROLE`)
+pub struct DbLeaseCheck {
+    manager: Arc<DbCredentialManager>,
+}
+
+impl DbLeaseCheck {
+    pub fn new(manager: Arc<DbCredentialManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for DbLeaseCheck {
+    fn name(&self) -> &str {
+        "db_lease"
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let now = chrono::Utc::now();
+        let lease = self.manager.status();
+        let expires_in = lease.expires_in_secs(now);
+        let details = json!({ "expires_in_secs": expires_in });
+
+        let outcome = if lease.is_expired(now) {
+            CheckOutcome::fail(details, "database lease expired")
+        } else if expires_in < LEASE_EXPIRY_WARNING_SECS || lease.last_error.is_some() {
+            CheckOutcome::warn(details, "database lease renewal is failing")
+        } else {
+            CheckOutcome::pass(details)
+        };
+        outcome.with_diagnostics(json!({
+            "username": lease.username,
+            "renewable": lease.renewable,
+            "rotations": lease.rotations,
+            "last_error": lease.last_error,
+        }))
+    }
+}
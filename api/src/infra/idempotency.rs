@@ -0,0 +1,246 @@
+//! =============================================================================
+//! IDEMPOTENCY STORE
+//! =============================================================================
+//! Persistência das chaves `Idempotency-Key` e das respostas originais
+//! A implementação padrão usa a tabela `idempotency_keys` do PostgreSQL
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// Resposta armazenada para reprodução em requisições repetidas
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// Estado de uma chave no início de uma requisição
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyBegin {
+    /// Chave nova (ou expirada): a requisição deve ser processada
+    Started,
+    /// Requisição original ainda em andamento
+    InProgress { request_hash: String },
+    /// Requisição original concluída; contém a resposta a reproduzir
+    Completed {
+        request_hash: String,
+        response: StoredResponse,
+    },
+}
+
+/// Armazenamento de chaves de idempotência
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Reserva a chave para a requisição ou devolve o estado já registrado
+    async fn begin(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<IdempotencyBegin>;
+
+    /// Registra a resposta da requisição original
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> anyhow::Result<()>;
+
+    /// Libera a chave (ex.: erro 5xx) para que o cliente possa tentar novamente
+    async fn release(&self, scope: &str, key: &str) -> anyhow::Result<()>;
+
+    /// Remove chaves expiradas, retornando quantas foram removidas
+    async fn purge_expired(&self) -> anyhow::Result<u64>;
+}
+
+/// Chave registrada: hash da requisição, resposta (quando concluída) e validade
+type IdempotencyEntry = (String, Option<StoredResponse>, DateTime<Utc>);
+
+/// Implementação em memória (testes e modo demo)
+#[derive(Default)]
+pub struct InMemoryIdempotencyStore {
+    entries: Mutex<HashMap<(String, String), IdempotencyEntry>>,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn begin(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        let mut entries = self.entries.lock();
+        let entry_key = (scope.to_string(), key.to_string());
+        Ok(match entries.get(&entry_key) {
+            // Uma chave expirada é reaproveitada como se fosse nova
+            Some((hash, response, expires)) if *expires > Utc::now() => match response {
+                None => IdempotencyBegin::InProgress {
+                    request_hash: hash.clone(),
+                },
+                Some(response) => IdempotencyBegin::Completed {
+                    request_hash: hash.clone(),
+                    response: response.clone(),
+                },
+            },
+            _ => {
+                entries.insert(entry_key, (request_hash.to_string(), None, expires_at));
+                IdempotencyBegin::Started
+            }
+        })
+    }
+
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> anyhow::Result<()> {
+        if let Some(entry) = self
+            .entries
+            .lock()
+            .get_mut(&(scope.to_string(), key.to_string()))
+        {
+            entry.1 = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    async fn release(&self, scope: &str, key: &str) -> anyhow::Result<()> {
+        self.entries
+            .lock()
+            .remove(&(scope.to_string(), key.to_string()));
+        Ok(())
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        let now = Utc::now();
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        entries.retain(|_, (_, _, expires_at)| *expires_at > now);
+        Ok((before - entries.len()) as u64)
+    }
+}
+
+/// Implementação em PostgreSQL
+pub struct PgIdempotencyStore {
+    pool: crate::infra::pg::PgPoolHandle,
+}
+
+impl PgIdempotencyStore {
+    pub fn new(pool: crate::infra::pg::PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for PgIdempotencyStore {
+    async fn begin(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        // Insere a chave; uma chave expirada é reaproveitada como se fosse nova
+        let started: Option<bool> = sqlx::query_scalar(
+            "INSERT INTO idempotency_keys (scope, key, request_hash, expires_at) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (scope, key) DO UPDATE SET request_hash = EXCLUDED.request_hash, \
+             status_code = NULL, response_headers = NULL, response_body = NULL, \
+             created_at = now(), expires_at = EXCLUDED.expires_at \
+             WHERE idempotency_keys.expires_at <= now() \
+             RETURNING true",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(request_hash)
+        .bind(expires_at)
+        .fetch_optional(&self.pool.get())
+        .await?;
+
+        if started.is_some() {
+            return Ok(IdempotencyBegin::Started);
+        }
+
+        let row: Option<(
+            String,
+            Option<i32>,
+            Option<serde_json::Value>,
+            Option<Vec<u8>>,
+        )> = sqlx::query_as(
+            "SELECT request_hash, status_code, response_headers, response_body \
+             FROM idempotency_keys WHERE scope = $1 AND key = $2",
+        )
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(&self.pool.get())
+        .await?;
+
+        match row {
+            // A chave foi liberada entre as duas consultas
+            None => Ok(IdempotencyBegin::InProgress {
+                request_hash: request_hash.to_string(),
+            }),
+            Some((request_hash, None, _, _)) => Ok(IdempotencyBegin::InProgress { request_hash }),
+            Some((request_hash, Some(status), headers, body)) => Ok(IdempotencyBegin::Completed {
+                request_hash,
+                response: StoredResponse {
+                    status: u16::try_from(status)?,
+                    headers: headers
+                        .map(serde_json::from_value)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    body: body.unwrap_or_default(),
+                },
+            }),
+        }
+    }
+
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE idempotency_keys SET status_code = $3, response_headers = $4, response_body = $5 \
+             WHERE scope = $1 AND key = $2",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(i32::from(response.status))
+        .bind(serde_json::to_value(&response.headers)?)
+        .bind(&response.body)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(())
+    }
+
+    async fn release(&self, scope: &str, key: &str) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE scope = $1 AND key = $2")
+            .bind(scope)
+            .bind(key)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= now()")
+            .execute(&self.pool.get())
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
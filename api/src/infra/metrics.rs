@@ -0,0 +1,122 @@
+//! =============================================================================
+//! MÉTRICAS DE INFRAESTRUTURA E DOMÍNIO
+//! =============================================================================
+//! Fontes do `MetricsCollector` (ver `shared::metrics`): contatos por status e
+//! tipo, candidatos a merge pendentes, uso do pool do PostgreSQL e cache do Vault
+
+use crate::infra::pg::PgPoolHandle;
+use crate::infra::vault::VaultClient;
+use crate::shared::metrics::{self, MetricsSource};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+
+/// Contatos não excluídos por status e tipo e candidatos a merge sem decisão
+pub struct ContactMetrics {
+    pool: PgPoolHandle,
+    /// Combinações (status, tipo) já exportadas; as que somem voltam a zero
+    seen: Mutex<HashSet<(String, String)>>,
+}
+
+impl ContactMetrics {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self {
+            pool,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl MetricsSource for ContactMetrics {
+    fn name(&self) -> &str {
+        "contacts"
+    }
+
+    async fn collect(&self) -> anyhow::Result<()> {
+        let pool = self.pool.get();
+        let counts = sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT status, type, count(*) FROM contacts WHERE deleted_at IS NULL GROUP BY status, type",
+        )
+        .fetch_all(&pool)
+        .await?;
+        // Par decidido em qualquer ordem (primary/duplicate) não está mais pendente
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT count(*) FROM merge_candidates mc
+             WHERE NOT EXISTS (
+               SELECT 1 FROM merge_decisions md
+               WHERE (md.primary_contact = mc.contact_a AND md.duplicate_contact = mc.contact_b)
+                  OR (md.primary_contact = mc.contact_b AND md.duplicate_contact = mc.contact_a)
+             )",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let mut seen = self.seen.lock();
+        let current: HashSet<(String, String)> = counts
+            .iter()
+            .map(|(status, kind, _)| (status.clone(), kind.clone()))
+            .collect();
+        for (status, kind) in seen.difference(&current) {
+            metrics::set_contacts(status, kind, 0);
+        }
+        for (status, kind, count) in &counts {
+            metrics::set_contacts(status, kind, *count);
+        }
+        *seen = current;
+        metrics::set_pending_merge_candidates(pending);
+        Ok(())
+    }
+}
+
+/// Conexões do pool do PostgreSQL (o pool atual, após rotações de credenciais)
+pub struct DbPoolMetrics {
+    pool: PgPoolHandle,
+}
+
+impl DbPoolMetrics {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MetricsSource for DbPoolMetrics {
+    fn name(&self) -> &str {
+        "db_pool"
+    }
+
+    async fn collect(&self) -> anyhow::Result<()> {
+        let pool = self.pool.get();
+        metrics::set_db_pool(
+            pool.size(),
+            pool.num_idle() as u32,
+            pool.options().get_max_connections(),
+        );
+        Ok(())
+    }
+}
+
+/// Entradas e taxa de acerto do cache de secrets do cliente Vault
+pub struct VaultCacheMetrics {
+    vault: VaultClient,
+}
+
+impl VaultCacheMetrics {
+    pub fn new(vault: VaultClient) -> Self {
+        Self { vault }
+    }
+}
+
+#[async_trait]
+impl MetricsSource for VaultCacheMetrics {
+    fn name(&self) -> &str {
+        "vault_cache"
+    }
+
+    async fn collect(&self) -> anyhow::Result<()> {
+        let stats = self.vault.cache_stats().await;
+        metrics::set_vault_cache(stats.entries, stats.expired, stats.hit_ratio());
+        Ok(())
+    }
+}
@@ -4,8 +4,16 @@
 //! Módulo de infraestrutura para integração com serviços externos
 //! Inclui integração com Vault, PostgreSQL, auditoria e outros serviços
 
+pub mod api_keys;
 pub mod audit;
+pub mod db_credentials;
+pub mod health;
+pub mod idempotency;
+pub mod metrics;
 pub mod pg;
+pub mod rate_limit;
+pub mod revocation;
+pub mod signing_keys;
 pub mod vault;
 
 pub use audit::*;
@@ -1,21 +1,29 @@
+use crate::shared::config::DatabaseConfig;
 use anyhow::Context;
-use sqlx::postgres::PgPoolOptions;
+use parking_lot::RwLock;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::str::FromStr;
+use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 
-pub async fn pool(dsn: &str) -> anyhow::Result<sqlx::Pool<sqlx::Postgres>> {
-    let max_attempts = std::env::var("PG_CONNECT_ATTEMPTS")
-        .ok()
-        .and_then(|v| v.parse::<u32>().ok())
-        .unwrap_or(10);
-    let backoff_ms = std::env::var("PG_CONNECT_BACKOFF_MS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(1_000);
+pub async fn pool(config: &DatabaseConfig) -> anyhow::Result<sqlx::Pool<sqlx::Postgres>> {
+    let options = PgConnectOptions::from_str(&config.connection_string)
+        .context("invalid PG_DSN connection string")?;
+    pool_with_options(config, options).await
+}
+
+/// Cria o pool com opções de conexão explícitas (ex.: credenciais dinâmicas do Vault)
+pub async fn pool_with_options(
+    config: &DatabaseConfig,
+    options: PgConnectOptions,
+) -> anyhow::Result<sqlx::Pool<sqlx::Postgres>> {
+    let max_attempts = config.connect_attempts;
+    let backoff_ms = config.connect_backoff_ms;
 
     let mut attempt = 0;
     loop {
         attempt += 1;
-        match PgPoolOptions::new().max_connections(10).connect(dsn).await {
+        match pool_options(config).connect_with(options.clone()).await {
             Ok(pool) => return Ok(pool),
             Err(err) if attempt < max_attempts => {
                 tracing::warn!(attempt, max_attempts, error = %err, "failed to connect to postgres; retrying");
@@ -30,10 +38,70 @@ pub async fn pool(dsn: &str) -> anyhow::Result<sqlx::Pool<sqlx::Postgres>> {
     }
 }
 
+/// Opções do pool derivadas da configuração
+pub fn pool_options(config: &DatabaseConfig) -> PgPoolOptions {
+    PgPoolOptions::new()
+        .max_connections(config.pool_size)
+        .acquire_timeout(Duration::from_secs(config.connection_timeout_secs))
+}
+
+/// Pool PostgreSQL substituível em tempo de execução (rotação de credenciais)
+///
+/// Cada operação obtém o pool atual com `get()`; quem já obteve o pool anterior
+/// continua usando-o até terminar, e ele só é fechado após o período de drenagem.
+#[derive(Clone)]
+pub struct PgPoolHandle {
+    current: Arc<RwLock<sqlx::PgPool>>,
+}
+
+impl PgPoolHandle {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(pool)),
+        }
+    }
+
+    /// Pool atual
+    pub fn get(&self) -> sqlx::PgPool {
+        self.current.read().clone()
+    }
+
+    /// Substitui o pool, retornando o anterior para ser drenado pelo chamador
+    pub fn replace(&self, pool: sqlx::PgPool) -> sqlx::PgPool {
+        std::mem::replace(&mut *self.current.write(), pool)
+    }
+}
+
+impl From<sqlx::PgPool> for PgPoolHandle {
+    fn from(pool: sqlx::PgPool) -> Self {
+        Self::new(pool)
+    }
+}
+
+static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
 pub async fn migrate(pool: &sqlx::Pool<sqlx::Postgres>) -> anyhow::Result<()> {
-    static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
     MIGRATOR
         .run(pool)
         .await
         .context("running database migrations")
 }
+
+/// Versão da migração mais recente embutida no binário
+pub fn latest_migration_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// Versão mais recente aplicada com sucesso no banco e se há migração pela metade
+pub async fn applied_migration_version(
+    pool: &sqlx::Pool<sqlx::Postgres>,
+) -> anyhow::Result<(Option<i64>, bool)> {
+    let (version, dirty): (Option<i64>, bool) = sqlx::query_as(
+        "SELECT max(version) FILTER (WHERE success), coalesce(bool_or(NOT success), false) \
+         FROM _sqlx_migrations",
+    )
+    .fetch_one(pool)
+    .await
+    .context("reading applied migrations")?;
+    Ok((version, dirty))
+}
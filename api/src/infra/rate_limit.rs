@@ -0,0 +1,230 @@
+//! =============================================================================
+//! RATE LIMIT STORE
+//! =============================================================================
+//! Token buckets do rate limiting: cada chave tem uma capacidade (rajada) que é
+//! reabastecida continuamente na taxa configurada
+//! A implementação em memória vale por réplica; a de PostgreSQL (tabela
+//! `rate_limit_buckets`) é compartilhada entre réplicas
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Capacidade e taxa de reabastecimento de um bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitPolicy {
+    /// Rajada máxima (tokens no bucket cheio)
+    pub capacity: u32,
+    /// Tokens reabastecidos por segundo
+    pub refill_per_sec: f64,
+}
+
+impl RateLimitPolicy {
+    pub fn per_minute(requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            capacity: burst.max(1),
+            refill_per_sec: f64::from(requests_per_minute.max(1)) / 60.0,
+        }
+    }
+
+    /// Tempo para um bucket vazio ficar cheio
+    pub fn full_refill(&self) -> Duration {
+        Duration::from_secs_f64(f64::from(self.capacity) / self.refill_per_sec)
+    }
+
+    /// Tokens após `elapsed` a partir de `tokens`, limitados à capacidade
+    fn refill(&self, tokens: f64, elapsed: Duration) -> f64 {
+        (tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(f64::from(self.capacity))
+    }
+
+    /// Resultado de uma aquisição, dado o saldo de tokens após ela
+    fn decision(&self, allowed: bool, tokens: f64) -> RateLimitDecision {
+        let capacity = f64::from(self.capacity);
+        RateLimitDecision {
+            allowed,
+            limit: self.capacity,
+            remaining: tokens.max(0.0).floor() as u32,
+            reset_after: Duration::from_secs_f64(
+                ((capacity - tokens).max(0.0) / self.refill_per_sec).ceil(),
+            ),
+            retry_after: if allowed {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64(((1.0 - tokens) / self.refill_per_sec).ceil().max(1.0))
+            },
+        }
+    }
+}
+
+/// Resultado da tentativa de consumir um token
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// Capacidade do bucket
+    pub limit: u32,
+    /// Requisições ainda disponíveis imediatamente
+    pub remaining: u32,
+    /// Tempo até o bucket ficar cheio novamente
+    pub reset_after: Duration,
+    /// Espera sugerida quando a requisição foi recusada
+    pub retry_after: Duration,
+}
+
+/// Armazenamento dos buckets
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Consome um token do bucket `key`, se houver
+    async fn acquire(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> anyhow::Result<RateLimitDecision>;
+
+    /// Remove buckets sem uso há mais de `idle` (já cheios), retornando quantos
+    async fn purge_idle(&self, idle: Duration) -> anyhow::Result<u64>;
+}
+
+/// Implementação em memória (por réplica)
+#[derive(Default)]
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn acquire_at(&self, key: &str, policy: &RateLimitPolicy, now: Instant) -> RateLimitDecision {
+        let mut buckets = self.buckets.lock();
+        let (tokens, updated_at) = buckets
+            .entry(key.to_string())
+            .or_insert((f64::from(policy.capacity), now));
+        let available = policy.refill(*tokens, now.saturating_duration_since(*updated_at));
+        let allowed = available >= 1.0;
+        *tokens = if allowed { available - 1.0 } else { available };
+        *updated_at = now;
+        policy.decision(allowed, *tokens)
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> anyhow::Result<RateLimitDecision> {
+        Ok(self.acquire_at(key, policy, Instant::now()))
+    }
+
+    async fn purge_idle(&self, idle: Duration) -> anyhow::Result<u64> {
+        let mut buckets = self.buckets.lock();
+        let before = buckets.len();
+        buckets.retain(|_, (_, updated_at)| updated_at.elapsed() < idle);
+        Ok((before - buckets.len()) as u64)
+    }
+}
+
+/// Implementação em PostgreSQL (compartilhada entre réplicas)
+pub struct PgRateLimitStore {
+    pool: crate::infra::pg::PgPoolHandle,
+}
+
+impl PgRateLimitStore {
+    pub fn new(pool: crate::infra::pg::PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for PgRateLimitStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        policy: &RateLimitPolicy,
+    ) -> anyhow::Result<RateLimitDecision> {
+        // Reabastece e consome em um único upsert (atômico entre réplicas)
+        let (tokens, allowed): (f64, bool) = sqlx::query_as(
+            "INSERT INTO rate_limit_buckets AS b (key, tokens, allowed, updated_at) \
+             VALUES ($1, $2 - 1, true, now()) \
+             ON CONFLICT (key) DO UPDATE SET \
+             tokens = CASE WHEN LEAST($2, b.tokens + EXTRACT(EPOCH FROM now() - b.updated_at)::float8 * $3) >= 1 \
+                      THEN LEAST($2, b.tokens + EXTRACT(EPOCH FROM now() - b.updated_at)::float8 * $3) - 1 \
+                      ELSE LEAST($2, b.tokens + EXTRACT(EPOCH FROM now() - b.updated_at)::float8 * $3) END, \
+             allowed = LEAST($2, b.tokens + EXTRACT(EPOCH FROM now() - b.updated_at)::float8 * $3) >= 1, \
+             updated_at = now() \
+             RETURNING tokens, allowed",
+        )
+        .bind(key)
+        .bind(f64::from(policy.capacity))
+        .bind(policy.refill_per_sec)
+        .fetch_one(&self.pool.get())
+        .await?;
+        Ok(policy.decision(allowed, tokens))
+    }
+
+    async fn purge_idle(&self, idle: Duration) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM rate_limit_buckets WHERE updated_at < now() - make_interval(secs => $1)",
+        )
+        .bind(idle.as_secs_f64())
+        .execute(&self.pool.get())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let store = InMemoryRateLimitStore::new();
+        let policy = RateLimitPolicy::per_minute(60, 3);
+        let start = Instant::now();
+
+        for remaining in [2, 1, 0] {
+            let decision = store.acquire_at("k", &policy, start);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+        let denied = store.acquire_at("k", &policy, start);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, Duration::from_secs(1));
+        assert_eq!(denied.reset_after, Duration::from_secs(3));
+
+        // 1 token por segundo
+        let decision = store.acquire_at("k", &policy, start + Duration::from_secs(1));
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        // Buckets são independentes por chave
+        assert!(store.acquire_at("other", &policy, start).allowed);
+    }
+
+    #[test]
+    fn test_refill_is_capped_at_capacity() {
+        let store = InMemoryRateLimitStore::new();
+        let policy = RateLimitPolicy::per_minute(600, 2);
+        let start = Instant::now();
+        store.acquire_at("k", &policy, start);
+
+        let decision = store.acquire_at("k", &policy, start + Duration::from_secs(3600));
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+        assert_eq!(policy.full_refill(), Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_purge_idle_buckets() {
+        let store = InMemoryRateLimitStore::new();
+        let policy = RateLimitPolicy::per_minute(60, 1);
+        store.acquire("k", &policy).await.unwrap();
+
+        assert_eq!(store.purge_idle(Duration::from_secs(60)).await.unwrap(), 0);
+        assert_eq!(store.purge_idle(Duration::ZERO).await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+//! =============================================================================
+//! TOKEN REVOCATION STORE
+//! =============================================================================
+//! Persistência da lista de tokens revogados (por `jti`, `sid` ou `sub`)
+//! A implementação padrão usa a tabela `revoked_tokens` do PostgreSQL
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Claim usada para identificar os tokens revogados
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RevocationKind {
+    /// Um único token (`jti`)
+    Jti,
+    /// Todos os tokens de uma sessão (`sid`)
+    Sid,
+    /// Todos os tokens de um usuário (`sub`); `apikey:<id>` revoga uma API key
+    Sub,
+}
+
+impl RevocationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevocationKind::Jti => "jti",
+            RevocationKind::Sid => "sid",
+            RevocationKind::Sub => "sub",
+        }
+    }
+}
+
+impl fmt::Display for RevocationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RevocationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jti" => Ok(RevocationKind::Jti),
+            "sid" => Ok(RevocationKind::Sid),
+            "sub" => Ok(RevocationKind::Sub),
+            other => Err(anyhow::anyhow!("unknown revocation kind: {}", other)),
+        }
+    }
+}
+
+/// Revogação registrada
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRevocation {
+    pub kind: RevocationKind,
+    pub value: String,
+    pub reason: Option<String>,
+    /// Após este instante os tokens revogados já expiraram e o registro pode ser removido
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Armazenamento da lista de revogação
+#[async_trait]
+pub trait RevocationStore: Send + Sync {
+    /// Registra (ou prorroga) uma revogação
+    async fn revoke(&self, revocation: &TokenRevocation) -> anyhow::Result<()>;
+
+    /// Lista as revogações ainda vigentes
+    async fn active(&self) -> anyhow::Result<Vec<TokenRevocation>>;
+
+    /// Remove revogações expiradas, retornando quantas foram removidas
+    async fn purge_expired(&self) -> anyhow::Result<u64>;
+}
+
+/// Implementação em memória (testes e modo demo)
+#[derive(Default)]
+pub struct InMemoryRevocationStore {
+    revocations: Mutex<HashMap<(RevocationKind, String), TokenRevocation>>,
+}
+
+impl InMemoryRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RevocationStore for InMemoryRevocationStore {
+    async fn revoke(&self, revocation: &TokenRevocation) -> anyhow::Result<()> {
+        let mut revocations = self.revocations.lock();
+        let entry = revocations
+            .entry((revocation.kind, revocation.value.clone()))
+            .or_insert_with(|| revocation.clone());
+        // Como no PostgreSQL, a revogação nunca é encurtada
+        entry.reason = revocation.reason.clone();
+        entry.expires_at = entry.expires_at.max(revocation.expires_at);
+        Ok(())
+    }
+
+    async fn active(&self) -> anyhow::Result<Vec<TokenRevocation>> {
+        let now = Utc::now();
+        Ok(self
+            .revocations
+            .lock()
+            .values()
+            .filter(|r| r.expires_at > now)
+            .cloned()
+            .collect())
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        let now = Utc::now();
+        let mut revocations = self.revocations.lock();
+        let before = revocations.len();
+        revocations.retain(|_, r| r.expires_at > now);
+        Ok((before - revocations.len()) as u64)
+    }
+}
+
+/// Implementação em PostgreSQL
+pub struct PgRevocationStore {
+    pool: crate::infra::pg::PgPoolHandle,
+}
+
+impl PgRevocationStore {
+    pub fn new(pool: crate::infra::pg::PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RevocationStore for PgRevocationStore {
+    async fn revoke(&self, revocation: &TokenRevocation) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO revoked_tokens (kind, value, reason, expires_at) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (kind, value) DO UPDATE SET reason = EXCLUDED.reason, \
+             revoked_at = now(), expires_at = GREATEST(revoked_tokens.expires_at, EXCLUDED.expires_at)",
+        )
+        .bind(revocation.kind.as_str())
+        .bind(&revocation.value)
+        .bind(&revocation.reason)
+        .bind(revocation.expires_at)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(())
+    }
+
+    async fn active(&self) -> anyhow::Result<Vec<TokenRevocation>> {
+        let rows: Vec<(String, String, Option<String>, DateTime<Utc>)> = sqlx::query_as(
+            "SELECT kind, value, reason, expires_at FROM revoked_tokens WHERE expires_at > now()",
+        )
+        .fetch_all(&self.pool.get())
+        .await?;
+
+        rows.into_iter()
+            .map(|(kind, value, reason, expires_at)| {
+                Ok(TokenRevocation {
+                    kind: kind.parse()?,
+                    value,
+                    reason,
+                    expires_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= now()")
+            .execute(&self.pool.get())
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
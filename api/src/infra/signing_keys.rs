@@ -0,0 +1,108 @@
+//! =============================================================================
+//! LOCAL SIGNING KEY
+//! =============================================================================
+//! Chave RSA usada para assinar os tokens emitidos pela própria API (RS256)
+//! A chave pública é exposta como JWK para o JWKS local (`/.well-known/jwks.json`)
+
+use anyhow::Context;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPrivateKey;
+use sha2::{Digest, Sha256};
+
+/// Tamanho das chaves geradas
+const RSA_KEY_BITS: usize = 2048;
+
+/// Algoritmo dos tokens assinados com a chave local
+pub const LOCAL_SIGNING_ALGORITHM: Algorithm = Algorithm::RS256;
+
+/// Chave privada de assinatura e sua representação pública (JWK)
+#[derive(Clone)]
+pub struct LocalSigningKey {
+    /// Identificador da chave (thumbprint RFC 7638)
+    pub kid: String,
+    pub encoding: EncodingKey,
+    pub decoding: DecodingKey,
+    /// Chave pública no formato JWK, publicada no JWKS local
+    pub jwk: serde_json::Value,
+}
+
+impl LocalSigningKey {
+    /// Carrega uma chave RSA em PEM (PKCS#8 ou PKCS#1)
+    pub fn from_pem(pem: &str) -> anyhow::Result<Self> {
+        let key = RsaPrivateKey::from_pkcs8_pem(pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+            .context("invalid RSA private key (expected PKCS#8 or PKCS#1 PEM)")?;
+        Self::from_private_key(&key)
+    }
+
+    /// Gera uma chave efêmera (válida apenas enquanto o processo estiver no ar)
+    pub fn generate() -> anyhow::Result<Self> {
+        let key = RsaPrivateKey::new(&mut rand::thread_rng(), RSA_KEY_BITS)
+            .context("failed to generate RSA key")?;
+        Self::from_private_key(&key)
+    }
+
+    fn from_private_key(key: &RsaPrivateKey) -> anyhow::Result<Self> {
+        let pem = key
+            .to_pkcs8_pem(LineEnding::LF)
+            .context("failed to encode RSA key")?;
+        let encoding = EncodingKey::from_rsa_pem(pem.as_bytes())?;
+
+        let n = URL_SAFE_NO_PAD.encode(key.n().to_bytes_be());
+        let e = URL_SAFE_NO_PAD.encode(key.e().to_bytes_be());
+        // Thumbprint RFC 7638: membros obrigatórios em ordem lexicográfica, sem espaços
+        let thumbprint = format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, e, n);
+        let kid = URL_SAFE_NO_PAD.encode(Sha256::digest(thumbprint.as_bytes()));
+        let decoding = DecodingKey::from_rsa_components(&n, &e)?;
+
+        let jwk = serde_json::json!({
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": kid,
+            "n": n,
+            "e": e,
+        });
+        Ok(Self {
+            kid,
+            encoding,
+            decoding,
+            jwk,
+        })
+    }
+
+    /// JWKS (`{"keys": [...]}`) com a chave pública
+    pub fn jwks(&self) -> serde_json::Value {
+        serde_json::json!({ "keys": [self.jwk] })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key_publishes_consumable_jwk() {
+        let key = LocalSigningKey::generate().unwrap();
+        assert_eq!(key.jwk["kid"], key.kid);
+
+        let jwk = crate::presentation::auth::JwkKey::from_jwk(&key.jwk).unwrap();
+        assert_eq!(jwk.algorithms, vec![LOCAL_SIGNING_ALGORITHM]);
+        assert_eq!(key.jwks()["keys"][0], key.jwk);
+    }
+
+    #[test]
+    fn test_from_pem_keeps_kid_stable() {
+        let private = RsaPrivateKey::new(&mut rand::thread_rng(), RSA_KEY_BITS).unwrap();
+        let pem = private.to_pkcs8_pem(LineEnding::LF).unwrap();
+
+        let first = LocalSigningKey::from_pem(&pem).unwrap();
+        let second = LocalSigningKey::from_pem(&pem).unwrap();
+        assert_eq!(first.kid, second.kid);
+        assert!(LocalSigningKey::from_pem("not a key").is_err());
+    }
+}
@@ -5,6 +5,7 @@
 //! Implementa gerenciamento de secrets, criptografia e autenticação
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -47,6 +48,8 @@ impl Default for VaultConfig {
 #[derive(Debug, Deserialize)]
 pub struct VaultResponse<T> {
     pub data: T,
+    #[serde(default)]
+    pub lease_id: Option<String>,
     pub lease_duration: Option<u64>,
     pub renewable: Option<bool>,
     pub warnings: Option<Vec<String>>,
@@ -72,10 +75,26 @@ pub struct KvMetadata {
 }
 
 /// Dados de credenciais do database
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct DatabaseCredentials {
     pub username: String,
     pub password: String,
+}
+
+/// Credenciais dinâmicas do database com o lease que as mantém válidas
+#[derive(Debug, Clone)]
+pub struct DatabaseLease {
+    pub lease_id: String,
+    /// Validade do lease (em segundos)
+    pub lease_duration: u64,
+    pub renewable: bool,
+    pub credentials: DatabaseCredentials,
+}
+
+/// Resultado da renovação de um lease
+#[derive(Debug, Clone)]
+pub struct LeaseRenewal {
+    /// Nova validade (pode ser menor que a pedida quando o max_ttl é atingido)
     pub lease_duration: u64,
     pub renewable: bool,
 }
@@ -104,6 +123,34 @@ pub struct TransitDecryptResponse {
     pub plaintext: String,
 }
 
+/// Resposta de `sys/health`
+#[derive(Debug, Clone, Deserialize)]
+pub struct VaultSealStatus {
+    pub initialized: bool,
+    pub sealed: bool,
+    #[serde(default)]
+    pub standby: bool,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Estatísticas do cache de secrets (acertos e falhas desde o início do processo)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VaultCacheStats {
+    pub entries: usize,
+    pub expired: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl VaultCacheStats {
+    /// Fração das leituras atendidas pelo cache (`None` antes da primeira leitura)
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
 /// Cliente Vault com cache e retry automático
 #[derive(Clone)]
 pub struct VaultClient {
@@ -111,6 +158,8 @@ pub struct VaultClient {
     client: Client,
     cache: Arc<RwLock<HashMap<String, (Value, std::time::Instant)>>>,
     cache_ttl: Duration,
+    cache_hits: Arc<AtomicU64>,
+    cache_misses: Arc<AtomicU64>,
 }
 
 impl VaultClient {
@@ -131,6 +180,8 @@ impl VaultClient {
             client,
             cache: Arc::new(RwLock::new(HashMap::new())),
             cache_ttl: Duration::from_secs(300), // 5 minutos
+            cache_hits: Arc::new(AtomicU64::new(0)),
+            cache_misses: Arc::new(AtomicU64::new(0)),
         })
     }
 
@@ -149,11 +200,31 @@ impl VaultClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        let response = self.send(method, path, body).await?;
+        let vault_response: VaultResponse<T> = response
+            .json()
+            .await
+            .context("Falha ao deserializar resposta do Vault")?;
+
+        debug!("Requisição Vault bem-sucedida: {}", path);
+        Ok(vault_response)
+    }
+
+    /// Enviar requisição autenticada, com retry em falhas de rede
+    #[tracing::instrument(
+        name = "vault.request",
+        skip(self, body),
+        fields(otel.kind = "client", http.request.method = %method, vault.path = path)
+    )]
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<reqwest::Response> {
         let url = format!("{}/v1/{}", self.config.addr, path.trim_start_matches('/'));
 
-        let mut request = self
-            .client
-            .request(method, &url)
+        let mut request = crate::shared::telemetry::propagate(self.client.request(method, &url))
             .header("X-Vault-Token", &self.config.token)
             .header("Content-Type", "application/json");
 
@@ -166,13 +237,7 @@ impl VaultClient {
             match request.try_clone().unwrap().send().await {
                 Ok(response) => {
                     if response.status().is_success() {
-                        let vault_response: VaultResponse<T> = response
-                            .json()
-                            .await
-                            .context("Falha ao deserializar resposta do Vault")?;
-
-                        debug!("Requisição Vault bem-sucedida: {}", path);
-                        return Ok(vault_response);
+                        return Ok(response);
                     } else {
                         let status = response.status();
                         let error_text = response.text().await.unwrap_or_default();
@@ -205,10 +270,12 @@ impl VaultClient {
             if let Some((value, timestamp)) = cache.get(path) {
                 if timestamp.elapsed() < self.cache_ttl {
                     debug!("Cache hit para secret: {}", path);
+                    self.cache_hits.fetch_add(1, Ordering::Relaxed);
                     return Ok(serde_json::from_value(value.clone())?);
                 }
             }
         }
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
 
         // Buscar no Vault
         let response: VaultResponse<KvData> = self
@@ -261,8 +328,8 @@ impl VaultClient {
         Ok(())
     }
 
-    /// Obter credenciais do database
-    pub async fn get_database_credentials(&self, role: &str) -> Result<DatabaseCredentials> {
+    /// Obter credenciais dinâmicas do database (secrets engine `database`)
+    pub async fn get_database_credentials(&self, role: &str) -> Result<DatabaseLease> {
         let response: VaultResponse<DatabaseCredentials> = self
             .request(
                 reqwest::Method::GET,
@@ -272,7 +339,45 @@ impl VaultClient {
             .await?;
 
         info!("Credenciais do database obtidas para role: {}", role);
-        Ok(response.data)
+        Ok(DatabaseLease {
+            lease_id: response
+                .lease_id
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Vault não retornou lease_id para {}", role))?,
+            lease_duration: response.lease_duration.unwrap_or_default(),
+            renewable: response.renewable.unwrap_or(false),
+            credentials: response.data,
+        })
+    }
+
+    /// Renovar um lease, pedindo `increment` segundos de validade
+    pub async fn renew_lease(&self, lease_id: &str, increment: u64) -> Result<LeaseRenewal> {
+        let response: VaultResponse<Option<Value>> = self
+            .request(
+                reqwest::Method::PUT,
+                "sys/leases/renew",
+                Some(serde_json::json!({ "lease_id": lease_id, "increment": increment })),
+            )
+            .await?;
+
+        debug!("Lease renovado: {}", lease_id);
+        Ok(LeaseRenewal {
+            lease_duration: response.lease_duration.unwrap_or_default(),
+            renewable: response.renewable.unwrap_or(false),
+        })
+    }
+
+    /// Revogar um lease (as credenciais deixam de valer imediatamente)
+    pub async fn revoke_lease(&self, lease_id: &str) -> Result<()> {
+        self.send(
+            reqwest::Method::PUT,
+            "sys/leases/revoke",
+            Some(serde_json::json!({ "lease_id": lease_id })),
+        )
+        .await?;
+
+        info!("Lease revogado: {}", lease_id);
+        Ok(())
     }
 
     /// Criptografar dados usando Transit
@@ -347,11 +452,12 @@ impl VaultClient {
 
     /// Verificar saúde do Vault
     pub async fn health_check(&self) -> Result<bool> {
-        match self
-            .client
-            .get(&format!("{}/v1/sys/health", self.config.addr))
-            .send()
-            .await
+        match crate::shared::telemetry::propagate(
+            self.client
+                .get(format!("{}/v1/sys/health", self.config.addr)),
+        )
+        .send()
+        .await
         {
             Ok(response) => {
                 let is_healthy = response.status().is_success();
@@ -369,6 +475,26 @@ impl VaultClient {
         }
     }
 
+    /// Estado de inicialização e selagem (`sys/health`, não exige token)
+    ///
+    /// Os códigos de status são normalizados para 200 para que o corpo seja
+    /// lido mesmo quando o Vault está selado ou em standby.
+    pub async fn seal_status(&self) -> Result<VaultSealStatus> {
+        let response = crate::shared::telemetry::propagate(self.client.get(format!(
+            "{}/v1/sys/health?standbyok=true&sealedcode=200&uninitcode=200&perfstandbyok=true",
+            self.config.addr
+        )))
+        .send()
+        .await
+        .context("Falha ao consultar sys/health do Vault")?;
+        response
+            .error_for_status()
+            .context("sys/health do Vault retornou erro")?
+            .json()
+            .await
+            .context("Resposta inválida de sys/health do Vault")
+    }
+
     /// Limpar cache
     pub async fn clear_cache(&self) {
         let mut cache = self.cache.write().await;
@@ -377,14 +503,18 @@ impl VaultClient {
     }
 
     /// Obter estatísticas do cache
-    pub async fn cache_stats(&self) -> (usize, usize) {
+    pub async fn cache_stats(&self) -> VaultCacheStats {
         let cache = self.cache.read().await;
-        let total = cache.len();
         let expired = cache
             .values()
             .filter(|(_, timestamp)| timestamp.elapsed() >= self.cache_ttl)
             .count();
-        (total, expired)
+        VaultCacheStats {
+            entries: cache.len(),
+            expired,
+            hits: self.cache_hits.load(Ordering::Relaxed),
+            misses: self.cache_misses.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -599,7 +729,6 @@ impl VaultService for SecretsManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockito::{mock, server_url};
 
     #[tokio::test]
     async fn test_vault_client_creation() {
@@ -617,14 +746,17 @@ mod tests {
 
     #[tokio::test]
     async fn test_health_check() {
-        let _m = mock("GET", "/v1/sys/health")
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v1/sys/health")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(r#"{"initialized": true, "sealed": false}"#)
-            .create();
+            .create_async()
+            .await;
 
         let config = VaultConfig {
-            addr: server_url(),
+            addr: server.url(),
             token: "test-token".to_string(),
             timeout: Duration::from_secs(5),
             max_retries: 1,
@@ -635,4 +767,122 @@ mod tests {
         let is_healthy = client.health_check().await.unwrap();
         assert!(is_healthy);
     }
+
+    #[tokio::test]
+    async fn test_seal_status_reads_sealed_vault() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v1/sys/health")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "sealedcode".into(),
+                "200".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"initialized": true, "sealed": true, "standby": false, "version": "1.15.0"}"#,
+            )
+            .create_async()
+            .await;
+
+        let client = VaultClient::new(VaultConfig {
+            addr: server.url(),
+            token: "test-token".to_string(),
+            timeout: Duration::from_secs(5),
+            max_retries: 1,
+            retry_delay: Duration::from_millis(100),
+        })
+        .unwrap();
+        let status = client.seal_status().await.unwrap();
+        assert!(status.initialized);
+        assert!(status.sealed);
+        assert_eq!(status.version.as_deref(), Some("1.15.0"));
+    }
+
+    #[tokio::test]
+    async fn test_cache_stats_count_hits_and_misses() {
+        let mut server = mockito::Server::new_async().await;
+        let kv = server
+            .mock("GET", "/v1/kv/data/sut")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": {"data": {"metrics_token": "t"}}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = VaultClient::new(VaultConfig {
+            addr: server.url(),
+            token: "test-token".to_string(),
+            timeout: Duration::from_secs(5),
+            max_retries: 1,
+            retry_delay: Duration::from_millis(100),
+        })
+        .unwrap();
+        assert_eq!(client.cache_stats().await.hit_ratio(), None);
+
+        for _ in 0..4 {
+            client.get_secret("sut").await.unwrap();
+        }
+        kv.assert_async().await;
+
+        let stats = client.cache_stats().await;
+        assert_eq!((stats.entries, stats.expired), (1, 0));
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!(stats.hit_ratio(), Some(0.75));
+    }
+
+    #[tokio::test]
+    async fn test_database_credentials_lease_lifecycle() {
+        let mut server = mockito::Server::new_async().await;
+        let _creds = server
+            .mock("GET", "/v1/database/creds/sut-app")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"lease_id": "database/creds/sut-app/abc", "lease_duration": 3600,
+                    "renewable": true, "data": {"username": "v-sut-1", "password": "pw"}}"#,
+            )
+            .create_async()
+            .await;
+        let _renew = server
+            .mock("PUT", "/v1/sys/leases/renew")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "lease_id": "database/creds/sut-app/abc", "increment": 3600
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"lease_id": "database/creds/sut-app/abc", "lease_duration": 1200,
+                    "renewable": true, "data": null}"#,
+            )
+            .create_async()
+            .await;
+        let revoke = server
+            .mock("PUT", "/v1/sys/leases/revoke")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let client = VaultClient::new(VaultConfig {
+            addr: server.url(),
+            token: "test-token".to_string(),
+            timeout: Duration::from_secs(5),
+            max_retries: 1,
+            retry_delay: Duration::from_millis(100),
+        })
+        .unwrap();
+
+        let lease = client.get_database_credentials("sut-app").await.unwrap();
+        assert_eq!(lease.lease_id, "database/creds/sut-app/abc");
+        assert_eq!(lease.lease_duration, 3600);
+        assert_eq!(lease.credentials.username, "v-sut-1");
+
+        let renewal = client.renew_lease(&lease.lease_id, 3600).await.unwrap();
+        assert_eq!(renewal.lease_duration, 1200);
+        assert!(renewal.renewable);
+
+        client.revoke_lease(&lease.lease_id).await.unwrap();
+        revoke.assert_async().await;
+    }
 }
@@ -0,0 +1,1085 @@
+use chrono::Duration;
+
+use super::{assert_conflict, at, contract_tests, Repositories};
+use crate::domain::entities::{
+    Contact, ContactSource, Department, MergeCandidate, MergeDecision, MergeDecisionType, OrgUnit,
+    SourceRecord,
+};
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{ContactChange, ContactSearchCriteria, ContactStatisticsQuery};
+use crate::domain::value_objects::*;
+
+contract_tests!(
+    crud_round_trips_every_field,
+    update_and_delete_of_missing_contact,
+    soft_delete_hides_until_restored,
+    purge_removes_only_expired_contacts,
+    search_applies_every_criterion,
+    pagination_total_counts_every_match,
+    uniqueness_and_reference_conflicts,
+    failed_save_leaves_no_partial_rows,
+    apply_batch_is_atomic,
+    apply_batch_rejects_stale_etags,
+    delete_cascades_and_merge_decisions_block_it,
+    statistics_aggregate_non_deleted_contacts,
+);
+
+fn contact(name: &str) -> Contact {
+    Contact::new(
+        name.to_string(),
+        ContactType::Person,
+        ContactStatus::Active,
+        None,
+        None,
+        None,
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+fn email(value: &str, is_primary: bool) -> Email {
+    Email::new(value.to_string(), is_primary).unwrap()
+}
+
+fn phone(e164: &str, extension: Option<&str>, is_primary: bool) -> Phone {
+    Phone::new(
+        e164.to_string(),
+        extension.map(str::to_string),
+        PhoneType::Work,
+        is_primary,
+    )
+    .unwrap()
+}
+
+fn criteria() -> ContactSearchCriteria {
+    ContactSearchCriteria {
+        full_name: None,
+        contact_type: None,
+        status: None,
+        unit_id: None,
+        department_id: None,
+        include_deleted: false,
+        limit: None,
+        offset: None,
+    }
+}
+
+fn names(contacts: &[Contact]) -> Vec<&str> {
+    contacts.iter().map(|c| c.full_name.as_str()).collect()
+}
+
+fn sorted_emails(contact: &Contact) -> Vec<(String, bool)> {
+    let mut emails: Vec<_> = contact
+        .emails
+        .iter()
+        .map(|e| (e.value.clone(), e.is_primary))
+        .collect();
+    emails.sort();
+    emails
+}
+
+fn sorted_phones(contact: &Contact) -> Vec<(String, Option<String>, bool)> {
+    let mut phones: Vec<_> = contact
+        .phones
+        .iter()
+        .map(|p| (p.e164.clone(), p.extension.clone(), p.is_primary))
+        .collect();
+    phones.sort();
+    phones
+}
+
+async fn unit(repos: &Repositories, name: &str, parent: Option<&OrgUnit>) -> OrgUnit {
+    let unit = OrgUnit::new(
+        OrgUnitName::new(name.to_string()).unwrap(),
+        parent.map(|p| p.id.clone()),
+    );
+    repos.org_units.save(&unit).await.unwrap()
+}
+
+async fn department(repos: &Repositories, unit: &OrgUnit, name: &str) -> Department {
+    let department = Department::new(
+        unit.id.clone(),
+        DepartmentName::new(name.to_string()).unwrap(),
+    );
+    repos.departments.save(&department).await.unwrap()
+}
+
+async fn crud_round_trips_every_field(repos: Repositories) {
+    let hq = unit(&repos, "Headquarters", None).await;
+    let engineering = department(&repos, &hq, "Engineering").await;
+    let new = Contact {
+        contact_type: ContactType::Organization,
+        status: ContactStatus::Inactive,
+        document: Some("11.222.333/0001-81".to_string()),
+        unit_id: Some(hq.id.clone()),
+        department_id: Some(engineering.id.clone()),
+        emails: vec![
+            email("ana@example.com", true),
+            email("ana@home.example", false),
+        ],
+        phones: vec![
+            phone("+5511999990000", None, true),
+            phone("+551133330000", Some("42"), false),
+        ],
+        created_at: at(2024, 3, 4, 10),
+        ..contact("Ana Lima")
+    };
+
+    let saved = repos.contacts.save(&new).await.unwrap();
+    assert!(!saved.etag.is_empty());
+    assert_eq!(saved.created_at, new.created_at);
+    assert!(saved.deleted_at.is_none());
+
+    let found = repos.contacts.find_by_id(&new.id).await.unwrap().unwrap();
+    assert_eq!(found.full_name, "Ana Lima");
+    assert_eq!(found.contact_type, ContactType::Organization);
+    assert_eq!(found.status, ContactStatus::Inactive);
+    assert_eq!(found.document.as_deref(), Some("11.222.333/0001-81"));
+    assert_eq!(found.unit_id, Some(hq.id.clone()));
+    assert_eq!(found.department_id, Some(engineering.id.clone()));
+    assert_eq!(sorted_emails(&found), sorted_emails(&new));
+    assert_eq!(sorted_phones(&found), sorted_phones(&new));
+    assert_eq!(found.etag, saved.etag);
+    assert_eq!(found.created_at, new.created_at);
+
+    let changed = Contact {
+        full_name: "Ana Lima Souza".to_string(),
+        department_id: None,
+        emails: vec![email("ana.souza@example.com", true)],
+        phones: vec![],
+        created_at: at(2030, 1, 1, 0),
+        ..found.clone()
+    };
+    let updated = repos.contacts.update(&changed).await.unwrap();
+    assert_eq!(updated.full_name, "Ana Lima Souza");
+    assert_ne!(updated.etag, saved.etag);
+    assert_eq!(
+        updated.created_at, new.created_at,
+        "created_at is immutable"
+    );
+    assert!(updated.updated_at >= saved.updated_at);
+
+    let found = repos.contacts.find_by_id(&new.id).await.unwrap().unwrap();
+    assert_eq!(found.department_id, None);
+    assert_eq!(sorted_emails(&found), sorted_emails(&changed));
+    assert!(found.phones.is_empty());
+
+    assert_eq!(
+        repos
+            .contacts
+            .find_by_email("ana.souza@example.com")
+            .await
+            .unwrap()
+            .map(|c| c.id),
+        Some(new.id.clone())
+    );
+    assert!(repos
+        .contacts
+        .find_by_email("ana@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        repos
+            .contacts
+            .find_by_document("11.222.333/0001-81")
+            .await
+            .unwrap()
+            .map(|c| c.id),
+        Some(new.id.clone())
+    );
+
+    repos.contacts.delete(&new.id).await.unwrap();
+    assert!(repos.contacts.find_by_id(&new.id).await.unwrap().is_none());
+}
+
+async fn update_and_delete_of_missing_contact(repos: Repositories) {
+    let missing = contact("Nobody");
+    assert!(matches!(
+        repos.contacts.update(&missing).await,
+        Err(DomainError::NotFound(_))
+    ));
+    repos.contacts.delete(&missing.id).await.unwrap();
+    repos.contacts.soft_delete(&missing.id).await.unwrap();
+    repos.contacts.restore(&missing.id).await.unwrap();
+}
+
+async fn soft_delete_hides_until_restored(repos: Repositories) {
+    let hq = unit(&repos, "Headquarters", None).await;
+    let engineering = department(&repos, &hq, "Engineering").await;
+    let ana = repos
+        .contacts
+        .save(&Contact {
+            unit_id: Some(hq.id.clone()),
+            department_id: Some(engineering.id.clone()),
+            ..contact("Ana")
+        })
+        .await
+        .unwrap();
+
+    repos.contacts.soft_delete(&ana.id).await.unwrap();
+    let deleted = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert!(deleted.deleted_at.is_some());
+    assert_ne!(deleted.etag, ana.etag, "deletion changes the ETag");
+
+    // Uma segunda exclusão não altera o instante original
+    repos.contacts.soft_delete(&ana.id).await.unwrap();
+    let again = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert_eq!(again.deleted_at, deleted.deleted_at);
+
+    assert_eq!(repos.contacts.find_all(&criteria()).await.unwrap().total, 0);
+    let with_deleted = repos
+        .contacts
+        .find_all(&ContactSearchCriteria {
+            include_deleted: true,
+            ..criteria()
+        })
+        .await
+        .unwrap();
+    assert_eq!(with_deleted.total, 1);
+    assert_eq!(names(&with_deleted.items), ["Ana"]);
+    assert!(repos.contacts.find_by_name("Ana").await.unwrap().is_empty());
+    assert!(repos
+        .contacts
+        .find_by_unit(&hq.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(repos
+        .contacts
+        .find_by_department(&engineering.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        repos
+            .contacts
+            .count_by_status(&ContactStatus::Active)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        repos
+            .contacts
+            .count_by_type(&ContactType::Person)
+            .await
+            .unwrap(),
+        0
+    );
+
+    repos.contacts.restore(&ana.id).await.unwrap();
+    let restored = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert!(restored.deleted_at.is_none());
+    assert_eq!(restored.etag, ana.etag, "the ETag follows the content");
+    assert_eq!(
+        names(&repos.contacts.find_by_unit(&hq.id).await.unwrap()),
+        ["Ana"]
+    );
+}
+
+async fn purge_removes_only_expired_contacts(repos: Repositories) {
+    let kept = repos.contacts.save(&contact("Kept")).await.unwrap();
+    let expired = repos.contacts.save(&contact("Expired")).await.unwrap();
+    repos.contacts.soft_delete(&expired.id).await.unwrap();
+
+    // Excluído depois do corte: permanece na lixeira
+    let cutoff = chrono::Utc::now() - Duration::hours(1);
+    assert_eq!(repos.contacts.purge_deleted(cutoff).await.unwrap(), 0);
+    assert!(repos
+        .contacts
+        .find_by_id(&expired.id)
+        .await
+        .unwrap()
+        .is_some());
+
+    let cutoff = chrono::Utc::now() + Duration::seconds(1);
+    assert_eq!(repos.contacts.purge_deleted(cutoff).await.unwrap(), 1);
+    assert!(repos
+        .contacts
+        .find_by_id(&expired.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repos.contacts.find_by_id(&kept.id).await.unwrap().is_some());
+}
+
+async fn search_applies_every_criterion(repos: Repositories) {
+    let hq = unit(&repos, "Headquarters", None).await;
+    let branch = unit(&repos, "Branch", None).await;
+    let engineering = department(&repos, &hq, "Engineering").await;
+    let hr = department(&repos, &hq, "HR").await;
+
+    let fixtures = [
+        (
+            "Alice Silva",
+            ContactType::Person,
+            ContactStatus::Active,
+            &hq,
+            Some(&engineering),
+        ),
+        (
+            "Bruno Souza",
+            ContactType::Person,
+            ContactStatus::Inactive,
+            &hq,
+            Some(&hr),
+        ),
+        (
+            "Silva & Filhos",
+            ContactType::Organization,
+            ContactStatus::Active,
+            &branch,
+            None,
+        ),
+        (
+            "100% Silva",
+            ContactType::Department,
+            ContactStatus::Active,
+            &branch,
+            None,
+        ),
+    ];
+    for (hour, (name, contact_type, status, unit, department)) in fixtures.into_iter().enumerate() {
+        repos
+            .contacts
+            .save(&Contact {
+                contact_type,
+                status,
+                unit_id: Some(unit.id.clone()),
+                department_id: department.map(|d| d.id.clone()),
+                created_at: at(2024, 1, 1, hour as u32),
+                ..contact(name)
+            })
+            .await
+            .unwrap();
+    }
+
+    let search = |criteria: ContactSearchCriteria| {
+        let repos = repos.clone();
+        async move {
+            let result = repos.contacts.find_all(&criteria).await.unwrap();
+            assert_eq!(result.total, result.items.len() as i64);
+            names(&result.items)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Sem critérios: todos, do mais recente para o mais antigo
+    assert_eq!(
+        search(criteria()).await,
+        ["100% Silva", "Silva & Filhos", "Bruno Souza", "Alice Silva"]
+    );
+    assert_eq!(
+        search(ContactSearchCriteria {
+            full_name: Some("SILVA".to_string()),
+            ..criteria()
+        })
+        .await,
+        ["100% Silva", "Silva & Filhos", "Alice Silva"]
+    );
+    // `%` e `_` são literais, não curingas
+    assert_eq!(
+        search(ContactSearchCriteria {
+            full_name: Some("0% s".to_string()),
+            ..criteria()
+        })
+        .await,
+        ["100% Silva"]
+    );
+    assert!(search(ContactSearchCriteria {
+        full_name: Some("a_i".to_string()),
+        ..criteria()
+    })
+    .await
+    .is_empty());
+    assert_eq!(
+        search(ContactSearchCriteria {
+            contact_type: Some(ContactType::Organization),
+            ..criteria()
+        })
+        .await,
+        ["Silva & Filhos"]
+    );
+    assert_eq!(
+        search(ContactSearchCriteria {
+            status: Some(ContactStatus::Inactive),
+            ..criteria()
+        })
+        .await,
+        ["Bruno Souza"]
+    );
+    assert_eq!(
+        search(ContactSearchCriteria {
+            unit_id: Some(branch.id.clone()),
+            ..criteria()
+        })
+        .await,
+        ["100% Silva", "Silva & Filhos"]
+    );
+    assert_eq!(
+        search(ContactSearchCriteria {
+            department_id: Some(engineering.id.clone()),
+            ..criteria()
+        })
+        .await,
+        ["Alice Silva"]
+    );
+    // Critérios combinados se somam
+    assert_eq!(
+        search(ContactSearchCriteria {
+            full_name: Some("silva".to_string()),
+            unit_id: Some(hq.id.clone()),
+            status: Some(ContactStatus::Active),
+            contact_type: Some(ContactType::Person),
+            ..criteria()
+        })
+        .await,
+        ["Alice Silva"]
+    );
+    assert!(search(ContactSearchCriteria {
+        full_name: Some("Bruno".to_string()),
+        status: Some(ContactStatus::Active),
+        ..criteria()
+    })
+    .await
+    .is_empty());
+
+    assert_eq!(
+        names(&repos.contacts.find_by_name("silva").await.unwrap()),
+        ["100% Silva", "Silva & Filhos", "Alice Silva"]
+    );
+    assert_eq!(
+        names(&repos.contacts.find_by_unit(&hq.id).await.unwrap()),
+        ["Bruno Souza", "Alice Silva"]
+    );
+    assert_eq!(
+        names(&repos.contacts.find_by_department(&hr.id).await.unwrap()),
+        ["Bruno Souza"]
+    );
+    assert_eq!(
+        repos
+            .contacts
+            .count_by_status(&ContactStatus::Active)
+            .await
+            .unwrap(),
+        3
+    );
+    assert_eq!(
+        repos
+            .contacts
+            .count_by_type(&ContactType::Person)
+            .await
+            .unwrap(),
+        2
+    );
+}
+
+async fn pagination_total_counts_every_match(repos: Repositories) {
+    for day in 1..=5 {
+        repos
+            .contacts
+            .save(&Contact {
+                status: if day % 2 == 0 {
+                    ContactStatus::Inactive
+                } else {
+                    ContactStatus::Active
+                },
+                created_at: at(2024, 1, day, 0),
+                ..contact(&format!("Contact {day}"))
+            })
+            .await
+            .unwrap();
+    }
+
+    let page = |limit: Option<i64>, offset: Option<i64>, status: Option<ContactStatus>| {
+        let repos = repos.clone();
+        async move {
+            let result = repos
+                .contacts
+                .find_all(&ContactSearchCriteria {
+                    status,
+                    limit,
+                    offset,
+                    ..criteria()
+                })
+                .await
+                .unwrap();
+            let names: Vec<String> = names(&result.items)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            (names, result.total)
+        }
+    };
+
+    assert_eq!(
+        page(Some(2), None, None).await,
+        (vec!["Contact 5".into(), "Contact 4".into()], 5)
+    );
+    assert_eq!(
+        page(Some(2), Some(2), None).await,
+        (vec!["Contact 3".into(), "Contact 2".into()], 5)
+    );
+    assert_eq!(
+        page(Some(2), Some(4), None).await,
+        (vec!["Contact 1".into()], 5)
+    );
+    assert_eq!(page(Some(2), Some(10), None).await, (vec![], 5));
+    assert_eq!(page(Some(0), None, None).await, (vec![], 5));
+    assert_eq!(page(None, Some(3), None).await.0.len(), 2);
+    // O total considera os filtros, não a página
+    assert_eq!(
+        page(Some(1), Some(1), Some(ContactStatus::Active)).await,
+        (vec!["Contact 3".into()], 3)
+    );
+}
+
+async fn uniqueness_and_reference_conflicts(repos: Repositories) {
+    let ana = repos
+        .contacts
+        .save(&Contact {
+            document: Some("529.982.247-25".to_string()),
+            ..contact("Ana")
+        })
+        .await
+        .unwrap();
+
+    // Mesmo id
+    assert_conflict(repos.contacts.save(&ana).await);
+    // Documento único
+    assert_conflict(
+        repos
+            .contacts
+            .save(&Contact {
+                document: Some("529.982.247-25".to_string()),
+                ..contact("Bia")
+            })
+            .await,
+    );
+    let bia = repos.contacts.save(&contact("Bia")).await.unwrap();
+    assert_conflict(
+        repos
+            .contacts
+            .update(&Contact {
+                document: Some("529.982.247-25".to_string()),
+                ..bia.clone()
+            })
+            .await,
+    );
+    // E-mails sem distinção de maiúsculas e telefones (número e ramal) por contato
+    assert_conflict(
+        repos
+            .contacts
+            .save(&Contact {
+                emails: vec![email("a@example.com", true), email("A@EXAMPLE.COM", false)],
+                ..contact("Caio")
+            })
+            .await,
+    );
+    assert_conflict(
+        repos
+            .contacts
+            .save(&Contact {
+                phones: vec![
+                    phone("+5511999990000", None, true),
+                    phone("+5511999990000", None, false),
+                ],
+                ..contact("Caio")
+            })
+            .await,
+    );
+    repos
+        .contacts
+        .save(&Contact {
+            phones: vec![
+                phone("+5511999990000", None, true),
+                phone("+5511999990000", Some("1"), false),
+            ],
+            ..contact("Caio")
+        })
+        .await
+        .unwrap();
+    // O mesmo e-mail pode pertencer a contatos diferentes
+    repos
+        .contacts
+        .update(&Contact {
+            emails: vec![email("shared@example.com", true)],
+            ..bia.clone()
+        })
+        .await
+        .unwrap();
+    repos
+        .contacts
+        .update(&Contact {
+            emails: vec![email("shared@example.com", true)],
+            ..ana.clone()
+        })
+        .await
+        .unwrap();
+
+    // Unidade e departamento precisam existir
+    assert_conflict(
+        repos
+            .contacts
+            .save(&Contact {
+                unit_id: Some(OrgUnitId::new()),
+                ..contact("Davi")
+            })
+            .await,
+    );
+    assert_conflict(
+        repos
+            .contacts
+            .update(&Contact {
+                department_id: Some(DepartmentId::new()),
+                ..bia
+            })
+            .await,
+    );
+}
+
+async fn failed_save_leaves_no_partial_rows(repos: Repositories) {
+    let rejected = Contact {
+        emails: vec![email("a@example.com", true), email("a@example.com", false)],
+        ..contact("Rejected")
+    };
+    assert_conflict(repos.contacts.save(&rejected).await);
+    assert!(repos
+        .contacts
+        .find_by_id(&rejected.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let ana = repos
+        .contacts
+        .save(&Contact {
+            emails: vec![email("ana@example.com", true)],
+            ..contact("Ana")
+        })
+        .await
+        .unwrap();
+    assert_conflict(
+        repos
+            .contacts
+            .update(&Contact {
+                full_name: "Ana Maria".to_string(),
+                emails: vec![email("x@example.com", true), email("X@example.com", false)],
+                ..ana.clone()
+            })
+            .await,
+    );
+    let stored = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert_eq!(stored.full_name, "Ana");
+    assert_eq!(sorted_emails(&stored), sorted_emails(&ana));
+    assert_eq!(stored.etag, ana.etag);
+}
+
+async fn apply_batch_is_atomic(repos: Repositories) {
+    let ana = repos
+        .contacts
+        .save(&Contact {
+            document: Some("1".to_string()),
+            ..contact("Ana")
+        })
+        .await
+        .unwrap();
+    let bia = repos
+        .contacts
+        .save(&Contact {
+            document: Some("2".to_string()),
+            ..contact("Bia")
+        })
+        .await
+        .unwrap();
+
+    // O último item viola o documento único: nada é gravado
+    let err = repos
+        .contacts
+        .apply_batch(&[
+            ContactChange::Update {
+                contact: Contact {
+                    full_name: "Ana Maria".to_string(),
+                    ..ana.clone()
+                },
+                etag: ana.etag.clone(),
+            },
+            ContactChange::SoftDelete {
+                id: bia.id.clone(),
+                etag: bia.etag.clone(),
+            },
+            ContactChange::Update {
+                contact: Contact {
+                    document: Some("1".to_string()),
+                    ..bia.clone()
+                },
+                etag: bia.etag.clone(),
+            },
+        ])
+        .await;
+    assert_conflict(err);
+    let stored = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert_eq!(stored.full_name, "Ana");
+    assert!(repos
+        .contacts
+        .find_by_id(&bia.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .deleted_at
+        .is_none());
+
+    let results = repos
+        .contacts
+        .apply_batch(&[
+            ContactChange::SoftDelete {
+                id: bia.id.clone(),
+                etag: bia.etag.clone(),
+            },
+            ContactChange::Update {
+                contact: Contact {
+                    full_name: "Ana Maria".to_string(),
+                    ..ana.clone()
+                },
+                etag: ana.etag.clone(),
+            },
+        ])
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_none());
+    assert_eq!(results[1].as_ref().unwrap().full_name, "Ana Maria");
+    assert!(repos
+        .contacts
+        .find_by_id(&bia.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .deleted_at
+        .is_some());
+}
+
+async fn apply_batch_rejects_stale_etags(repos: Repositories) {
+    let ana = repos.contacts.save(&contact("Ana")).await.unwrap();
+    let bia = repos.contacts.save(&contact("Bia")).await.unwrap();
+
+    // Escrita concorrente entre a verificação do If-Match e a gravação do lote
+    let concurrent = repos
+        .contacts
+        .update(&Contact {
+            full_name: "Ana Concorrente".to_string(),
+            ..ana.clone()
+        })
+        .await
+        .unwrap();
+    assert_ne!(concurrent.etag, ana.etag);
+
+    let err = repos
+        .contacts
+        .apply_batch(&[
+            ContactChange::SoftDelete {
+                id: bia.id.clone(),
+                etag: bia.etag.clone(),
+            },
+            ContactChange::Update {
+                contact: Contact {
+                    full_name: "Ana Maria".to_string(),
+                    ..ana.clone()
+                },
+                etag: ana.etag.clone(),
+            },
+        ])
+        .await;
+    assert_conflict(err);
+    let stored = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert_eq!(stored.full_name, "Ana Concorrente");
+    let stored = repos.contacts.find_by_id(&bia.id).await.unwrap().unwrap();
+    assert!(stored.deleted_at.is_none());
+
+    let err = repos
+        .contacts
+        .apply_batch(&[ContactChange::SoftDelete {
+            id: ana.id.clone(),
+            etag: ana.etag.clone(),
+        }])
+        .await;
+    assert_conflict(err);
+    let stored = repos.contacts.find_by_id(&ana.id).await.unwrap().unwrap();
+    assert!(stored.deleted_at.is_none());
+}
+
+async fn delete_cascades_and_merge_decisions_block_it(repos: Repositories) {
+    let ana = repos.contacts.save(&contact("Ana")).await.unwrap();
+    let bia = repos.contacts.save(&contact("Bia")).await.unwrap();
+    let record = repos
+        .source_records
+        .save(&SourceRecord::new(
+            Source::new("ldap".to_string()).unwrap(),
+            SourceKey::new("uid=ana".to_string()).unwrap(),
+            Hash::new("h1".to_string()).unwrap(),
+            serde_json::json!({"cn": "Ana"}),
+        ))
+        .await
+        .unwrap();
+    repos
+        .contact_sources
+        .save(&ContactSource::new(ana.id.clone(), record.id.clone(), 0.9).unwrap())
+        .await
+        .unwrap();
+    repos
+        .merge_candidates
+        .save(
+            &MergeCandidate::new(ana.id.clone(), bia.id.clone(), 0.8, serde_json::json!({}))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Fontes e candidatos acompanham o contato removido
+    repos.contacts.delete(&ana.id).await.unwrap();
+    assert!(repos
+        .contact_sources
+        .find_by_source_record(&record.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(repos
+        .merge_candidates
+        .find_by_contact(&bia.id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Decisões de merge são histórico: o contato não pode ser removido nem purgado
+    let caio = repos.contacts.save(&contact("Caio")).await.unwrap();
+    repos
+        .merge_decisions
+        .save(&MergeDecision::new(
+            caio.id.clone(),
+            bia.id.clone(),
+            MergeDecisionType::Reject,
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_conflict(repos.contacts.delete(&bia.id).await);
+    repos.contacts.soft_delete(&bia.id).await.unwrap();
+    repos.contacts.soft_delete(&caio.id).await.unwrap();
+    assert_conflict(
+        repos
+            .contacts
+            .purge_deleted(chrono::Utc::now() + Duration::seconds(1))
+            .await,
+    );
+    assert!(repos.contacts.find_by_id(&bia.id).await.unwrap().is_some());
+    assert!(repos.contacts.find_by_id(&caio.id).await.unwrap().is_some());
+}
+
+async fn statistics_aggregate_non_deleted_contacts(repos: Repositories) {
+    let hq = unit(&repos, "Headquarters", None).await;
+    let region = unit(&repos, "Region", Some(&hq)).await;
+    let office = unit(&repos, "Office", Some(&region)).await;
+    let empty = unit(&repos, "Empty", None).await;
+    let engineering = department(&repos, &hq, "Engineering").await;
+    department(&repos, &region, "Sales").await;
+
+    let fixtures = [
+        // (nome, tipo, status, unidade, departamento, documento, criado em)
+        (
+            "A",
+            ContactType::Person,
+            ContactStatus::Active,
+            &hq,
+            Some(&engineering),
+            Some("529.982.247-25"),
+            at(2024, 3, 4, 10),
+        ),
+        (
+            "B",
+            ContactType::Person,
+            ContactStatus::Inactive,
+            &region,
+            None,
+            Some("123"),
+            at(2024, 3, 4, 23),
+        ),
+        (
+            "C",
+            ContactType::Organization,
+            ContactStatus::Active,
+            &office,
+            None,
+            None,
+            at(2024, 3, 12, 8),
+        ),
+        (
+            "D",
+            ContactType::Department,
+            ContactStatus::Active,
+            &office,
+            Some(&engineering),
+            None,
+            at(2024, 4, 1, 0),
+        ),
+    ];
+    let mut saved = Vec::new();
+    for (name, contact_type, status, unit, department, document, created_at) in fixtures {
+        let primary = name == "A";
+        saved.push(
+            repos
+                .contacts
+                .save(&Contact {
+                    contact_type,
+                    status,
+                    unit_id: Some(unit.id.clone()),
+                    department_id: department.map(|d| d.id.clone()),
+                    document: document.map(str::to_string),
+                    emails: vec![email(&format!("{name}@example.com"), primary)],
+                    phones: if primary {
+                        vec![phone("+5511999990000", None, true)]
+                    } else {
+                        vec![]
+                    },
+                    created_at,
+                    ..contact(name)
+                })
+                .await
+                .unwrap(),
+        );
+    }
+    // Excluídos não entram em nenhuma contagem
+    let deleted = repos
+        .contacts
+        .save(&Contact {
+            unit_id: Some(hq.id.clone()),
+            department_id: Some(engineering.id.clone()),
+            document: Some("999".to_string()),
+            created_at: at(2024, 3, 5, 0),
+            ..contact("Deleted")
+        })
+        .await
+        .unwrap();
+    repos.contacts.soft_delete(&deleted.id).await.unwrap();
+
+    // Fontes: A vem de duas origens (contada uma vez por origem), C de uma
+    let mut records = Vec::new();
+    for (source, key) in [
+        ("ldap", "a"),
+        ("ldap", "a2"),
+        ("hr", "a"),
+        ("hr", "c"),
+        ("hr", "deleted"),
+    ] {
+        records.push(
+            repos
+                .source_records
+                .save(&SourceRecord::new(
+                    Source::new(source.to_string()).unwrap(),
+                    SourceKey::new(key.to_string()).unwrap(),
+                    Hash::new(format!("{source}:{key}")).unwrap(),
+                    serde_json::json!({}),
+                ))
+                .await
+                .unwrap(),
+        );
+    }
+    for (contact_id, record) in [
+        (&saved[0].id, &records[0]),
+        (&saved[0].id, &records[1]),
+        (&saved[0].id, &records[2]),
+        (&saved[2].id, &records[3]),
+        (&deleted.id, &records[4]),
+    ] {
+        repos
+            .contact_sources
+            .save(&ContactSource::new(contact_id.clone(), record.id.clone(), 1.0).unwrap())
+            .await
+            .unwrap();
+    }
+
+    let stats = repos
+        .contacts
+        .get_statistics(&ContactStatisticsQuery {
+            from: at(2024, 3, 1, 0),
+            to: at(2024, 4, 1, 0),
+            bucket: StatisticsBucket::Week,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(stats.total_contacts, 4);
+    assert_eq!(stats.active_contacts, 3);
+    assert_eq!(stats.inactive_contacts, 1);
+    assert_eq!(stats.persons, 2);
+    assert_eq!(stats.organizations, 1);
+    assert_eq!(stats.department_contacts, 1);
+    assert_eq!(stats.departments, 2);
+
+    let by_unit: Vec<(OrgUnitId, i64, i64)> = stats
+        .by_org_unit
+        .iter()
+        .map(|u| {
+            (
+                u.unit_id.clone(),
+                u.contacts,
+                u.contacts_including_descendants,
+            )
+        })
+        .collect();
+    assert_eq!(
+        by_unit,
+        [
+            (hq.id.clone(), 1, 4),
+            (region.id.clone(), 1, 3),
+            (office.id.clone(), 2, 2),
+            (empty.id.clone(), 0, 0),
+        ]
+    );
+
+    assert_eq!(stats.by_department, [(engineering.id.clone(), 2)]);
+    assert_eq!(
+        stats.by_status,
+        [(ContactStatus::Active, 3), (ContactStatus::Inactive, 1)]
+    );
+    assert_eq!(
+        stats.by_source,
+        [("hr".to_string(), 2), ("ldap".to_string(), 1)]
+    );
+
+    // Semanas começam na segunda-feira; `to` é exclusivo
+    assert_eq!(
+        stats.created_series,
+        [(at(2024, 3, 4, 0), 2), (at(2024, 3, 11, 0), 1)]
+    );
+    // Todas as gravações são de agora, fora do intervalo consultado
+    assert!(stats.updated_series.is_empty());
+
+    assert_eq!(stats.data_quality.missing_primary_email, 3);
+    assert_eq!(stats.data_quality.missing_primary_phone, 3);
+    assert_eq!(stats.data_quality.invalid_document, 1);
+
+    let now = chrono::Utc::now();
+    let recent = repos
+        .contacts
+        .get_statistics(&ContactStatisticsQuery {
+            from: now - Duration::days(1),
+            to: now + Duration::days(1),
+            bucket: StatisticsBucket::Month,
+        })
+        .await
+        .unwrap();
+    assert!(recent.created_series.is_empty());
+    assert_eq!(
+        recent
+            .updated_series
+            .iter()
+            .map(|(_, count)| count)
+            .sum::<i64>(),
+        4
+    );
+}
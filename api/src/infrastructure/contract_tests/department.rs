@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use super::{assert_conflict, at, contract_tests, Repositories};
+use crate::domain::entities::{Contact, Department, OrgUnit};
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::DepartmentSearchCriteria;
+use crate::domain::value_objects::*;
+
+contract_tests!(
+    crud_round_trips_every_field,
+    search_and_pagination,
+    name_is_unique_per_unit,
+    reference_conflicts,
+    statistics_count_per_unit,
+);
+
+async fn unit(repos: &Repositories, name: &str) -> OrgUnit {
+    let unit = OrgUnit::new(OrgUnitName::new(name.to_string()).unwrap(), None);
+    repos.org_units.save(&unit).await.unwrap()
+}
+
+fn department(unit: &OrgUnit, name: &str) -> Department {
+    Department::new(
+        unit.id.clone(),
+        DepartmentName::new(name.to_string()).unwrap(),
+    )
+}
+
+fn names(departments: &[Department]) -> Vec<&str> {
+    departments.iter().map(|d| d.name.value.as_str()).collect()
+}
+
+async fn crud_round_trips_every_field(repos: Repositories) {
+    let hq = unit(&repos, "hq").await;
+    let branch = unit(&repos, "branch").await;
+    let new = Department {
+        created_at: at(2024, 3, 4, 10),
+        updated_at: at(2024, 3, 4, 10),
+        ..department(&hq, "Finance")
+    };
+    let saved = repos.departments.save(&new).await.unwrap();
+    assert_eq!(saved.created_at, new.created_at);
+
+    let found = repos
+        .departments
+        .find_by_id(&new.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.name.value, "Finance");
+    assert_eq!(found.unit_id, hq.id);
+    assert_eq!(found.created_at, new.created_at);
+    assert_eq!(found.updated_at, new.updated_at);
+
+    let updated = repos
+        .departments
+        .update(&Department {
+            unit_id: branch.id.clone(),
+            name: DepartmentName::new("Accounting".to_string()).unwrap(),
+            created_at: at(2030, 1, 1, 0),
+            ..found
+        })
+        .await
+        .unwrap();
+    assert_eq!(updated.name.value, "Accounting");
+    assert_eq!(updated.unit_id, branch.id);
+    assert_eq!(
+        updated.created_at, new.created_at,
+        "created_at is immutable"
+    );
+    assert!(updated.updated_at > new.updated_at);
+    assert_eq!(
+        names(&repos.departments.find_by_unit(&branch.id).await.unwrap()),
+        ["Accounting"]
+    );
+    assert!(repos
+        .departments
+        .find_by_unit(&hq.id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    assert!(matches!(
+        repos.departments.update(&department(&hq, "Missing")).await,
+        Err(DomainError::NotFound(_))
+    ));
+    assert_conflict(repos.departments.save(&saved).await);
+
+    repos.departments.delete(&new.id).await.unwrap();
+    assert!(repos
+        .departments
+        .find_by_id(&new.id)
+        .await
+        .unwrap()
+        .is_none());
+    repos.departments.delete(&new.id).await.unwrap();
+}
+
+async fn search_and_pagination(repos: Repositories) {
+    let hq = unit(&repos, "hq").await;
+    let branch = unit(&repos, "branch").await;
+    for (unit, name) in [
+        (&hq, "finance"),
+        (&hq, "field sales"),
+        (&hq, "legal"),
+        (&branch, "finance"),
+        (&branch, "support"),
+    ] {
+        repos
+            .departments
+            .save(&department(unit, name))
+            .await
+            .unwrap();
+    }
+
+    let search = |criteria: DepartmentSearchCriteria| {
+        let repos = repos.clone();
+        async move {
+            let result = repos.departments.find_all(&criteria).await.unwrap();
+            let names: Vec<String> = names(&result.items)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            (names, result.total)
+        }
+    };
+    let all = DepartmentSearchCriteria {
+        name: None,
+        unit_id: None,
+        limit: None,
+        offset: None,
+    };
+
+    assert_eq!(search(all.clone()).await.1, 5);
+    assert_eq!(
+        search(DepartmentSearchCriteria {
+            unit_id: Some(hq.id.clone()),
+            ..all.clone()
+        })
+        .await,
+        (
+            vec!["field sales".into(), "finance".into(), "legal".into()],
+            3
+        )
+    );
+    assert_eq!(
+        search(DepartmentSearchCriteria {
+            name: Some("FI".to_string()),
+            unit_id: Some(hq.id.clone()),
+            ..all.clone()
+        })
+        .await,
+        (vec!["field sales".into(), "finance".into()], 2)
+    );
+    assert_eq!(
+        search(DepartmentSearchCriteria {
+            name: Some("finance".to_string()),
+            limit: Some(1),
+            offset: Some(1),
+            ..all.clone()
+        })
+        .await
+        .1,
+        2
+    );
+    assert_eq!(
+        search(DepartmentSearchCriteria {
+            limit: Some(2),
+            offset: Some(4),
+            ..all
+        })
+        .await
+        .0
+        .len(),
+        1
+    );
+
+    assert_eq!(
+        repos
+            .departments
+            .find_by_name("Finance")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        names(&repos.departments.find_by_name("port").await.unwrap()),
+        ["support"]
+    );
+}
+
+async fn name_is_unique_per_unit(repos: Repositories) {
+    let hq = unit(&repos, "hq").await;
+    let branch = unit(&repos, "branch").await;
+    repos
+        .departments
+        .save(&department(&hq, "Finance"))
+        .await
+        .unwrap();
+
+    // Sem distinção de maiúsculas dentro da unidade
+    assert_conflict(repos.departments.save(&department(&hq, "FINANCE")).await);
+    let other = repos
+        .departments
+        .save(&department(&branch, "Finance"))
+        .await
+        .unwrap();
+    assert_conflict(
+        repos
+            .departments
+            .update(&Department {
+                unit_id: hq.id.clone(),
+                name: DepartmentName::new("finance".to_string()).unwrap(),
+                ..other.clone()
+            })
+            .await,
+    );
+    // Regravar com o próprio nome não conflita
+    repos.departments.update(&other).await.unwrap();
+}
+
+async fn reference_conflicts(repos: Repositories) {
+    let ghost = OrgUnit::new(OrgUnitName::new("ghost".to_string()).unwrap(), None);
+    assert_conflict(repos.departments.save(&department(&ghost, "Finance")).await);
+
+    let hq = unit(&repos, "hq").await;
+    let finance = repos
+        .departments
+        .save(&department(&hq, "Finance"))
+        .await
+        .unwrap();
+    assert_conflict(
+        repos
+            .departments
+            .update(&Department {
+                unit_id: ghost.id.clone(),
+                ..finance.clone()
+            })
+            .await,
+    );
+
+    let contact = repos
+        .contacts
+        .save(&Contact {
+            department_id: Some(finance.id.clone()),
+            ..Contact::new(
+                "Ana".to_string(),
+                ContactType::Person,
+                ContactStatus::Active,
+                None,
+                None,
+                None,
+                vec![],
+                vec![],
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    assert_conflict(repos.departments.delete(&finance.id).await);
+
+    // Contatos excluídos logicamente ainda referenciam o departamento
+    repos.contacts.soft_delete(&contact.id).await.unwrap();
+    assert_conflict(repos.departments.delete(&finance.id).await);
+
+    repos.contacts.delete(&contact.id).await.unwrap();
+    repos.departments.delete(&finance.id).await.unwrap();
+}
+
+async fn statistics_count_per_unit(repos: Repositories) {
+    let empty = repos.departments.get_statistics().await.unwrap();
+    assert_eq!(empty.total_departments, 0);
+    assert!(empty.departments_by_unit.is_empty());
+
+    let hq = unit(&repos, "hq").await;
+    let branch = unit(&repos, "branch").await;
+    unit(&repos, "no departments").await;
+    for (unit, name) in [(&hq, "a"), (&hq, "b"), (&hq, "c"), (&branch, "a")] {
+        repos
+            .departments
+            .save(&department(unit, name))
+            .await
+            .unwrap();
+    }
+
+    let stats = repos.departments.get_statistics().await.unwrap();
+    assert_eq!(stats.total_departments, 4);
+    assert_eq!(
+        stats.departments_by_unit,
+        HashMap::from([(hq.id.clone(), 3), (branch.id.clone(), 1)])
+    );
+}
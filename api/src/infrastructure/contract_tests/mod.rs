@@ -0,0 +1,191 @@
+//! =============================================================================
+//! REPOSITORY CONTRACT TESTS
+//! =============================================================================
+//! Suíte de conformidade dos traits de `domain::repositories`: cada caso recebe
+//! um `Repositories` e só conhece os traits, e `contract_tests!` gera um teste
+//! por backend (memória e PostgreSQL) para cada caso.
+//!
+//! No PostgreSQL cada teste cria um banco descartável a partir de `migrations/`
+//! e o remove ao final. Os testes PostgreSQL são `#[ignore]`: rodam com
+//! `--ignored` (ou `--include-ignored`) e exigem `TEST_PG_DSN` apontando para um
+//! usuário com permissão de `CREATE DATABASE`; sem ela, falham. O CI os executa
+//! contra um serviço PostgreSQL.
+//!
+//! ```text
+//! TEST_PG_DSN=postgres://postgres@localhost:5432/postgres cargo test -- --include-ignored
+//! ```
+//!
+//! Um novo backend só precisa de uma função que monte `Repositories` e de um
+//! módulo gerado por `contract_tests!` com ela. Os testes do roteador usam o
+//! mesmo macro, com `Repositories::app_state`, para exercitar os handlers sobre
+//! cada backend.
+
+use chrono::{DateTime, TimeZone, Utc};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, Connection, Executor};
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::*;
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::memory::InMemoryDatabase;
+use crate::infrastructure::repositories::contact_source_repository::PostgresContactSourceRepository;
+use crate::infrastructure::repositories::merge_candidate_repository::PostgresMergeCandidateRepository;
+use crate::infrastructure::repositories::merge_decision_repository::PostgresMergeDecisionRepository;
+use crate::infrastructure::repositories::source_record_repository::PostgresSourceRecordRepository;
+use crate::infrastructure::repositories::*;
+
+mod contact;
+mod department;
+mod org_unit;
+mod provenance;
+mod user;
+
+/// Um repositório de cada trait, todos sobre o mesmo armazenamento
+#[derive(Clone)]
+pub struct Repositories {
+    pub contacts: Arc<dyn ContactRepository>,
+    pub org_units: Arc<dyn OrgUnitRepository>,
+    pub departments: Arc<dyn DepartmentRepository>,
+    pub users: Arc<dyn UserRepository>,
+    pub audit_events: Arc<dyn AuditEventRepository>,
+    pub webhook_receipts: Arc<dyn WebhookReceiptRepository>,
+    pub source_records: Arc<dyn SourceRecordRepository>,
+    pub contact_sources: Arc<dyn ContactSourceRepository>,
+    pub merge_candidates: Arc<dyn MergeCandidateRepository>,
+    pub merge_decisions: Arc<dyn MergeDecisionRepository>,
+}
+
+impl Repositories {
+    /// `AppState` dos testes do roteador com estes repositórios
+    pub fn app_state(&self) -> crate::AppState {
+        crate::AppState {
+            contact_repository: self.contacts.clone(),
+            org_unit_repository: self.org_units.clone(),
+            department_repository: self.departments.clone(),
+            user_repository: self.users.clone(),
+            audit_event_repository: self.audit_events.clone(),
+            webhook_receipt_repository: self.webhook_receipts.clone(),
+            ..crate::AppState::in_memory(&InMemoryDatabase::new())
+        }
+    }
+}
+
+/// Gera, para cada caso, um `#[tokio::test]` em `memory` e outro, ignorado por
+/// padrão, em `postgres`
+macro_rules! contract_tests {
+    ($($case:ident),* $(,)?) => {
+        mod memory {
+            $(
+                #[tokio::test]
+                async fn $case() {
+                    crate::infrastructure::contract_tests::run_in_memory(super::$case).await;
+                }
+            )*
+        }
+
+        mod postgres {
+            $(
+                #[tokio::test]
+                #[ignore = "requires TEST_PG_DSN"]
+                async fn $case() {
+                    crate::infrastructure::contract_tests::run_on_postgres(super::$case).await;
+                }
+            )*
+        }
+    };
+}
+pub(crate) use contract_tests;
+
+pub async fn run_in_memory<F, Fut>(case: F)
+where
+    F: FnOnce(Repositories) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let database = InMemoryDatabase::new();
+    case(Repositories {
+        contacts: Arc::new(database.contacts()),
+        org_units: Arc::new(database.org_units()),
+        departments: Arc::new(database.departments()),
+        users: Arc::new(database.users()),
+        audit_events: Arc::new(database.audit_events()),
+        webhook_receipts: Arc::new(database.webhook_receipts()),
+        source_records: Arc::new(database.source_records()),
+        contact_sources: Arc::new(database.contact_sources()),
+        merge_candidates: Arc::new(database.merge_candidates()),
+        merge_decisions: Arc::new(database.merge_decisions()),
+    })
+    .await;
+}
+
+/// Roda o caso num banco recém-criado pelas migrações, removido mesmo se o caso falhar
+pub async fn run_on_postgres<F, Fut>(case: F)
+where
+    F: FnOnce(Repositories) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let dsn = std::env::var("TEST_PG_DSN")
+        .expect("TEST_PG_DSN must point to a PostgreSQL server to run the postgres tests");
+    let admin = PgConnectOptions::from_str(&dsn).expect("invalid TEST_PG_DSN");
+    let database = format!("contract_{}", uuid::Uuid::new_v4().simple());
+
+    let mut connection = admin.connect().await.expect("connect to TEST_PG_DSN");
+    connection
+        .execute(format!("CREATE DATABASE {database}").as_str())
+        .await
+        .expect("create throwaway database");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(4)
+        .connect_with(admin.clone().database(&database))
+        .await
+        .expect("connect to throwaway database");
+    crate::infra::pg::migrate(&pool)
+        .await
+        .expect("run migrations");
+    // As migrações semeiam a estrutura de exemplo; os casos partem de tabelas vazias
+    pool.execute("DELETE FROM contacts; DELETE FROM departments; DELETE FROM org_units;")
+        .await
+        .expect("clear seed data");
+
+    let handle = PgPoolHandle::new(pool.clone());
+    let outcome = tokio::spawn(case(Repositories {
+        contacts: Arc::new(PostgresContactRepository::new(handle.clone())),
+        org_units: Arc::new(PostgresOrgUnitRepository::new(handle.clone())),
+        departments: Arc::new(PostgresDepartmentRepository::new(handle.clone())),
+        users: Arc::new(PostgresUserRepository::new(handle.clone())),
+        audit_events: Arc::new(PostgresAuditEventRepository::new(handle.clone())),
+        webhook_receipts: Arc::new(PostgresWebhookReceiptRepository::new(handle.clone())),
+        source_records: Arc::new(PostgresSourceRecordRepository::new(handle.clone())),
+        contact_sources: Arc::new(PostgresContactSourceRepository::new(handle.clone())),
+        merge_candidates: Arc::new(PostgresMergeCandidateRepository::new(handle.clone())),
+        merge_decisions: Arc::new(PostgresMergeDecisionRepository::new(handle)),
+    }))
+    .await;
+
+    pool.close().await;
+    connection
+        .execute(format!("DROP DATABASE {database} WITH (FORCE)").as_str())
+        .await
+        .expect("drop throwaway database");
+    connection.close().await.ok();
+
+    if let Err(err) = outcome {
+        std::panic::resume_unwind(err.into_panic());
+    }
+}
+
+/// Instante fixo com precisão de segundos (o PostgreSQL guarda microssegundos)
+pub fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+}
+
+#[track_caller]
+pub fn assert_conflict<T: std::fmt::Debug>(result: Result<T, DomainError>) {
+    match result {
+        Err(DomainError::Conflict(_)) => {}
+        other => panic!("expected a conflict, got {other:?}"),
+    }
+}
@@ -0,0 +1,281 @@
+use super::{assert_conflict, at, contract_tests, Repositories};
+use crate::domain::entities::{Contact, Department, OrgUnit};
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::OrgUnitSearchCriteria;
+use crate::domain::value_objects::*;
+
+contract_tests!(
+    crud_round_trips_every_field,
+    search_and_pagination,
+    hierarchy_children_and_roots,
+    reference_conflicts,
+);
+
+fn org_unit(name: &str, parent: Option<&OrgUnit>) -> OrgUnit {
+    OrgUnit::new(
+        OrgUnitName::new(name.to_string()).unwrap(),
+        parent.map(|p| p.id.clone()),
+    )
+}
+
+fn names(units: &[OrgUnit]) -> Vec<&str> {
+    units.iter().map(|u| u.name.value.as_str()).collect()
+}
+
+async fn crud_round_trips_every_field(repos: Repositories) {
+    let root = repos.org_units.save(&org_unit("Root", None)).await.unwrap();
+    let new = OrgUnit {
+        created_at: at(2024, 3, 4, 10),
+        updated_at: at(2024, 3, 4, 10),
+        ..org_unit("Finance", Some(&root))
+    };
+    let saved = repos.org_units.save(&new).await.unwrap();
+    assert_eq!(saved.created_at, new.created_at);
+
+    let found = repos.org_units.find_by_id(&new.id).await.unwrap().unwrap();
+    assert_eq!(found.name.value, "Finance");
+    assert_eq!(found.parent_id, Some(root.id.clone()));
+    assert_eq!(found.created_at, new.created_at);
+    assert_eq!(found.updated_at, new.updated_at);
+
+    let updated = repos
+        .org_units
+        .update(&OrgUnit {
+            name: OrgUnitName::new("Treasury".to_string()).unwrap(),
+            parent_id: None,
+            created_at: at(2030, 1, 1, 0),
+            ..found
+        })
+        .await
+        .unwrap();
+    assert_eq!(updated.name.value, "Treasury");
+    assert_eq!(updated.parent_id, None);
+    assert_eq!(
+        updated.created_at, new.created_at,
+        "created_at is immutable"
+    );
+    assert!(updated.updated_at > new.updated_at);
+    assert_eq!(
+        repos
+            .org_units
+            .find_by_id(&new.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .name
+            .value,
+        "Treasury"
+    );
+
+    assert!(matches!(
+        repos.org_units.update(&org_unit("Missing", None)).await,
+        Err(DomainError::NotFound(_))
+    ));
+    assert_conflict(repos.org_units.save(&saved).await);
+
+    repos.org_units.delete(&new.id).await.unwrap();
+    assert!(repos.org_units.find_by_id(&new.id).await.unwrap().is_none());
+    repos.org_units.delete(&new.id).await.unwrap();
+}
+
+async fn search_and_pagination(repos: Repositories) {
+    let root = repos.org_units.save(&org_unit("Root", None)).await.unwrap();
+    for name in ["north office", "south office", "east office", "west depot"] {
+        repos
+            .org_units
+            .save(&org_unit(name, Some(&root)))
+            .await
+            .unwrap();
+    }
+
+    let search = |criteria: OrgUnitSearchCriteria| {
+        let repos = repos.clone();
+        async move {
+            let result = repos.org_units.find_all(&criteria).await.unwrap();
+            let names: Vec<String> = names(&result.items)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            (names, result.total)
+        }
+    };
+    let all = OrgUnitSearchCriteria {
+        name: None,
+        parent_id: None,
+        limit: None,
+        offset: None,
+    };
+
+    // Ordenadas por nome
+    assert_eq!(search(all.clone()).await.1, 5);
+    assert_eq!(
+        search(OrgUnitSearchCriteria {
+            name: Some("OFFICE".to_string()),
+            ..all.clone()
+        })
+        .await,
+        (
+            vec![
+                "east office".into(),
+                "north office".into(),
+                "south office".into()
+            ],
+            3
+        )
+    );
+    assert_eq!(
+        search(OrgUnitSearchCriteria {
+            name: Some("office".to_string()),
+            limit: Some(2),
+            offset: Some(1),
+            ..all.clone()
+        })
+        .await,
+        (vec!["north office".into(), "south office".into()], 3)
+    );
+    assert_eq!(
+        search(OrgUnitSearchCriteria {
+            parent_id: Some(root.id.clone()),
+            limit: Some(1),
+            ..all.clone()
+        })
+        .await,
+        (vec!["east office".into()], 4)
+    );
+    assert_eq!(
+        search(OrgUnitSearchCriteria {
+            name: Some("%".to_string()),
+            ..all
+        })
+        .await,
+        (vec![], 0)
+    );
+
+    assert_eq!(
+        names(&repos.org_units.find_by_name("Depot").await.unwrap()),
+        ["west depot"]
+    );
+}
+
+async fn hierarchy_children_and_roots(repos: Repositories) {
+    let root = repos.org_units.save(&org_unit("root", None)).await.unwrap();
+    let other = repos
+        .org_units
+        .save(&org_unit("other", None))
+        .await
+        .unwrap();
+    let region = repos
+        .org_units
+        .save(&org_unit("region", Some(&root)))
+        .await
+        .unwrap();
+    let office = repos
+        .org_units
+        .save(&org_unit("office", Some(&region)))
+        .await
+        .unwrap();
+    repos
+        .org_units
+        .save(&org_unit("annex", Some(&root)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        names(&repos.org_units.find_root_units().await.unwrap()),
+        ["other", "root"]
+    );
+    assert_eq!(
+        names(&repos.org_units.find_children(&root.id).await.unwrap()),
+        ["annex", "region"]
+    );
+    assert!(repos
+        .org_units
+        .find_children(&office.id)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        names(&repos.org_units.get_hierarchy(&office.id).await.unwrap()),
+        ["root", "region", "office"]
+    );
+    assert_eq!(
+        names(&repos.org_units.get_hierarchy(&other.id).await.unwrap()),
+        ["other"]
+    );
+    assert!(repos
+        .org_units
+        .get_hierarchy(&OrgUnitId::new())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn reference_conflicts(repos: Repositories) {
+    let missing = org_unit("missing", None);
+    assert_conflict(
+        repos
+            .org_units
+            .save(&org_unit("orphan", Some(&missing)))
+            .await,
+    );
+
+    let root = repos.org_units.save(&org_unit("root", None)).await.unwrap();
+    let child = repos
+        .org_units
+        .save(&org_unit("child", Some(&root)))
+        .await
+        .unwrap();
+    assert_conflict(
+        repos
+            .org_units
+            .update(&OrgUnit {
+                parent_id: Some(missing.id.clone()),
+                ..child.clone()
+            })
+            .await,
+    );
+
+    // Subunidades, departamentos e contatos impedem a remoção
+    assert_conflict(repos.org_units.delete(&root.id).await);
+    repos.org_units.delete(&child.id).await.unwrap();
+
+    let department = repos
+        .departments
+        .save(&Department::new(
+            root.id.clone(),
+            DepartmentName::new("Finance".to_string()).unwrap(),
+        ))
+        .await
+        .unwrap();
+    assert_conflict(repos.org_units.delete(&root.id).await);
+    repos.departments.delete(&department.id).await.unwrap();
+
+    let contact = repos
+        .contacts
+        .save(&Contact {
+            unit_id: Some(root.id.clone()),
+            ..Contact::new(
+                "Ana".to_string(),
+                ContactType::Person,
+                ContactStatus::Active,
+                None,
+                None,
+                None,
+                vec![],
+                vec![],
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+    assert_conflict(repos.org_units.delete(&root.id).await);
+    repos.contacts.delete(&contact.id).await.unwrap();
+
+    repos.org_units.delete(&root.id).await.unwrap();
+    assert!(repos
+        .org_units
+        .find_by_id(&root.id)
+        .await
+        .unwrap()
+        .is_none());
+}
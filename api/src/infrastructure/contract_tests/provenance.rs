@@ -0,0 +1,440 @@
+use chrono::Duration;
+
+use super::{assert_conflict, at, contract_tests, Repositories};
+use crate::domain::entities::{
+    AuditEvent, Contact, ContactSource, MergeCandidate, MergeDecision, MergeDecisionType,
+    SourceRecord, WebhookReceipt,
+};
+use crate::domain::value_objects::*;
+
+contract_tests!(
+    audit_events_newest_first,
+    webhook_receipts_reject_replays,
+    source_records_are_unique_per_source_key,
+    contact_sources_require_both_sides,
+    merge_candidates_ordered_by_score,
+    merge_decisions_newest_first,
+);
+
+async fn contact(repos: &Repositories, name: &str) -> Contact {
+    let contact = Contact::new(
+        name.to_string(),
+        ContactType::Person,
+        ContactStatus::Active,
+        None,
+        None,
+        None,
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    repos.contacts.save(&contact).await.unwrap()
+}
+
+fn source_record(source: &str, key: &str) -> SourceRecord {
+    SourceRecord::new(
+        Source::new(source.to_string()).unwrap(),
+        SourceKey::new(key.to_string()).unwrap(),
+        Hash::new(format!("{source}:{key}")).unwrap(),
+        serde_json::json!({ "key": key }),
+    )
+}
+
+async fn audit_events_newest_first(repos: Repositories) {
+    let event = |actor: &str, entity_id: &str, hour: u32| AuditEvent {
+        at: at(2024, 3, 4, hour),
+        ..AuditEvent::new(
+            Some(actor.to_string()),
+            Action::new("contact.update".to_string()).unwrap(),
+            EntityType::new("contact".to_string()).unwrap(),
+            entity_id.to_string(),
+            Some(serde_json::json!({ "full_name": "Ana" })),
+            None,
+        )
+    };
+
+    let first = repos
+        .audit_events
+        .save(&event("alice", "c1", 9))
+        .await
+        .unwrap();
+    assert_eq!(first.actor_sub.as_deref(), Some("alice"));
+    assert_eq!(
+        first.before,
+        Some(serde_json::json!({ "full_name": "Ana" }))
+    );
+    assert_eq!(first.after, None);
+    assert_eq!(first.at, at(2024, 3, 4, 9));
+
+    let second = repos
+        .audit_events
+        .save(&event("bruno", "c1", 11))
+        .await
+        .unwrap();
+    assert_ne!(second.id, first.id, "ids are assigned by the repository");
+    repos
+        .audit_events
+        .save(&event("alice", "c2", 10))
+        .await
+        .unwrap();
+
+    let actors = |events: Vec<AuditEvent>| -> Vec<(String, String)> {
+        events
+            .into_iter()
+            .map(|e| (e.actor_sub.unwrap_or_default(), e.entity_id))
+            .collect()
+    };
+    assert_eq!(
+        actors(
+            repos
+                .audit_events
+                .find_by_entity("contact", "c1")
+                .await
+                .unwrap()
+        ),
+        [("bruno".into(), "c1".into()), ("alice".into(), "c1".into())]
+    );
+    assert_eq!(
+        actors(repos.audit_events.find_by_actor("alice").await.unwrap()),
+        [("alice".into(), "c2".into()), ("alice".into(), "c1".into())]
+    );
+    assert_eq!(
+        actors(repos.audit_events.find_recent(2).await.unwrap()),
+        [("bruno".into(), "c1".into()), ("alice".into(), "c2".into())]
+    );
+    assert!(repos
+        .audit_events
+        .find_by_entity("department", "c1")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn webhook_receipts_reject_replays(repos: Repositories) {
+    let receipt = |source: &str, nonce: &str| {
+        WebhookReceipt::new(
+            Source::new(source.to_string()).unwrap(),
+            Nonce::new(nonce.to_string()).unwrap(),
+        )
+    };
+
+    assert!(!repos.webhook_receipts.exists("vault", "n1").await.unwrap());
+    repos
+        .webhook_receipts
+        .save(&receipt("vault", "n1"))
+        .await
+        .unwrap();
+    assert!(repos.webhook_receipts.exists("vault", "n1").await.unwrap());
+
+    // O mesmo nonce só conflita dentro da mesma origem
+    assert_conflict(repos.webhook_receipts.save(&receipt("vault", "n1")).await);
+    repos
+        .webhook_receipts
+        .save(&receipt("keycloak", "n1"))
+        .await
+        .unwrap();
+
+    repos.webhook_receipts.delete("vault", "n1").await.unwrap();
+    assert!(!repos.webhook_receipts.exists("vault", "n1").await.unwrap());
+    assert!(repos
+        .webhook_receipts
+        .exists("keycloak", "n1")
+        .await
+        .unwrap());
+    repos
+        .webhook_receipts
+        .save(&receipt("vault", "n1"))
+        .await
+        .unwrap();
+}
+
+async fn source_records_are_unique_per_source_key(repos: Repositories) {
+    let new = SourceRecord {
+        fetched_at: at(2024, 3, 4, 10),
+        ..source_record("ldap", "uid=ana")
+    };
+    repos.source_records.save(&new).await.unwrap();
+
+    let found = repos
+        .source_records
+        .find_by_id(&new.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.source.value, "ldap");
+    assert_eq!(found.source_key.value, "uid=ana");
+    assert_eq!(found.hash.value, "ldap:uid=ana");
+    assert_eq!(found.payload, serde_json::json!({ "key": "uid=ana" }));
+    assert_eq!(found.fetched_at, new.fetched_at);
+    assert_eq!(
+        repos
+            .source_records
+            .find_by_source_and_key("ldap", "uid=ana")
+            .await
+            .unwrap()
+            .map(|r| r.id),
+        Some(new.id.clone())
+    );
+    assert!(repos
+        .source_records
+        .find_by_source_and_key("hr", "uid=ana")
+        .await
+        .unwrap()
+        .is_none());
+
+    assert_conflict(repos.source_records.save(&new).await);
+    assert_conflict(
+        repos
+            .source_records
+            .save(&source_record("ldap", "uid=ana"))
+            .await,
+    );
+    repos
+        .source_records
+        .save(&source_record("hr", "uid=ana"))
+        .await
+        .unwrap();
+
+    repos.source_records.delete(&new.id).await.unwrap();
+    assert!(repos
+        .source_records
+        .find_by_id(&new.id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn contact_sources_require_both_sides(repos: Repositories) {
+    let ana = contact(&repos, "Ana").await;
+    let record = repos
+        .source_records
+        .save(&source_record("ldap", "uid=ana"))
+        .await
+        .unwrap();
+    let other = repos
+        .source_records
+        .save(&source_record("hr", "42"))
+        .await
+        .unwrap();
+
+    assert_conflict(
+        repos
+            .contact_sources
+            .save(&ContactSource::new(ContactId::new(), record.id.clone(), 1.0).unwrap())
+            .await,
+    );
+    assert_conflict(
+        repos
+            .contact_sources
+            .save(&ContactSource::new(ana.id.clone(), SourceRecordId::new(), 1.0).unwrap())
+            .await,
+    );
+
+    let link = ContactSource::new(ana.id.clone(), record.id.clone(), 0.75).unwrap();
+    let saved = repos.contact_sources.save(&link).await.unwrap();
+    assert_eq!(saved.confidence, 0.75);
+    assert_conflict(repos.contact_sources.save(&link).await);
+    repos
+        .contact_sources
+        .save(&ContactSource::new(ana.id.clone(), other.id.clone(), 0.5).unwrap())
+        .await
+        .unwrap();
+
+    let by_contact = repos
+        .contact_sources
+        .find_by_contact(&ana.id)
+        .await
+        .unwrap();
+    assert_eq!(by_contact.len(), 2);
+    let by_record = repos
+        .contact_sources
+        .find_by_source_record(&record.id)
+        .await
+        .unwrap();
+    assert_eq!(by_record.len(), 1);
+    assert_eq!(by_record[0].confidence, 0.75);
+
+    repos
+        .contact_sources
+        .delete(&ana.id, &record.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        repos
+            .contact_sources
+            .find_by_contact(&ana.id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Remover o registro de origem remove seus vínculos
+    repos.source_records.delete(&other.id).await.unwrap();
+    assert!(repos
+        .contact_sources
+        .find_by_contact(&ana.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn merge_candidates_ordered_by_score(repos: Repositories) {
+    let ana = contact(&repos, "Ana").await;
+    let bia = contact(&repos, "Bia").await;
+    let caio = contact(&repos, "Caio").await;
+    let features = serde_json::json!({ "name": 0.9 });
+
+    assert_conflict(
+        repos
+            .merge_candidates
+            .save(
+                &MergeCandidate::new(ana.id.clone(), ContactId::new(), 0.5, features.clone())
+                    .unwrap(),
+            )
+            .await,
+    );
+    for (a, b, score) in [(&ana, &bia, 0.5), (&ana, &caio, 0.9), (&bia, &caio, 0.7)] {
+        repos
+            .merge_candidates
+            .save(
+                &MergeCandidate::new(a.id.clone(), b.id.clone(), score, features.clone()).unwrap(),
+            )
+            .await
+            .unwrap();
+    }
+    assert_conflict(
+        repos
+            .merge_candidates
+            .save(
+                &MergeCandidate::new(ana.id.clone(), bia.id.clone(), 0.1, features.clone())
+                    .unwrap(),
+            )
+            .await,
+    );
+
+    let scores = |candidates: Vec<MergeCandidate>| -> Vec<f64> {
+        candidates.iter().map(|c| c.score).collect()
+    };
+    assert_eq!(
+        scores(repos.merge_candidates.find_top_candidates(2).await.unwrap()),
+        [0.9, 0.7]
+    );
+    assert_eq!(
+        scores(
+            repos
+                .merge_candidates
+                .find_by_contact(&ana.id)
+                .await
+                .unwrap()
+        ),
+        [0.9, 0.5]
+    );
+    let top = repos.merge_candidates.find_top_candidates(1).await.unwrap();
+    assert_eq!(top[0].features, features);
+
+    repos
+        .merge_candidates
+        .delete(&ana.id, &caio.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        scores(
+            repos
+                .merge_candidates
+                .find_by_contact(&caio.id)
+                .await
+                .unwrap()
+        ),
+        [0.7]
+    );
+}
+
+async fn merge_decisions_newest_first(repos: Repositories) {
+    let ana = contact(&repos, "Ana").await;
+    let bia = contact(&repos, "Bia").await;
+    let caio = contact(&repos, "Caio").await;
+    let decider = UserId::new();
+    let decided_at = at(2024, 3, 4, 10);
+
+    assert_conflict(
+        repos
+            .merge_decisions
+            .save(&MergeDecision::new(
+                ana.id.clone(),
+                ContactId::new(),
+                MergeDecisionType::Merge,
+                None,
+                None,
+            ))
+            .await,
+    );
+
+    let first = MergeDecision {
+        decided_at,
+        ..MergeDecision::new(
+            ana.id.clone(),
+            bia.id.clone(),
+            MergeDecisionType::Merge,
+            Some(serde_json::json!({ "full_name": "primary" })),
+            Some(decider.clone()),
+        )
+    };
+    let saved = repos.merge_decisions.save(&first).await.unwrap();
+    assert_eq!(saved.decision, MergeDecisionType::Merge);
+    assert_eq!(saved.chosen_fields, first.chosen_fields);
+    assert_eq!(saved.decided_at, decided_at);
+    assert_conflict(repos.merge_decisions.save(&first).await);
+
+    repos
+        .merge_decisions
+        .save(&MergeDecision {
+            decided_at: decided_at + Duration::hours(1),
+            ..MergeDecision::new(
+                caio.id.clone(),
+                ana.id.clone(),
+                MergeDecisionType::Reject,
+                None,
+                Some(decider.clone()),
+            )
+        })
+        .await
+        .unwrap();
+
+    let pairs = |decisions: Vec<MergeDecision>| -> Vec<(ContactId, ContactId)> {
+        decisions
+            .into_iter()
+            .map(|d| (d.primary_contact, d.duplicate_contact))
+            .collect()
+    };
+    assert_eq!(
+        pairs(
+            repos
+                .merge_decisions
+                .find_by_contact(&ana.id)
+                .await
+                .unwrap()
+        ),
+        [
+            (caio.id.clone(), ana.id.clone()),
+            (ana.id.clone(), bia.id.clone())
+        ]
+    );
+    assert_eq!(
+        pairs(
+            repos
+                .merge_decisions
+                .find_by_decider(&decider)
+                .await
+                .unwrap()
+        )
+        .len(),
+        2
+    );
+    assert!(repos
+        .merge_decisions
+        .find_by_decider(&UserId::new())
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,304 @@
+use super::{assert_conflict, at, contract_tests, Repositories};
+use crate::domain::entities::User;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::UserSearchCriteria;
+use crate::domain::value_objects::*;
+
+contract_tests!(
+    crud_round_trips_every_field,
+    credentials_follow_password_changes,
+    username_and_email_are_unique,
+    search_and_pagination,
+);
+
+fn user(username: &str, roles: &[&str]) -> User {
+    User::new(
+        Username::new(username.to_string()).unwrap(),
+        UserEmail::new(format!("{username}@example.com")).unwrap(),
+        Password::new(format!("{username}-secret")).unwrap(),
+        roles
+            .iter()
+            .map(|role| Role::new(role.to_string()).unwrap())
+            .collect(),
+    )
+}
+
+fn usernames(users: &[User]) -> Vec<&str> {
+    users.iter().map(|u| u.username.value.as_str()).collect()
+}
+
+async fn crud_round_trips_every_field(repos: Repositories) {
+    let new = User {
+        created_at: at(2024, 3, 4, 10),
+        updated_at: at(2024, 3, 4, 10),
+        ..user("alice", &["directory.read", "directory.write"])
+    };
+    let saved = repos.users.save(&new).await.unwrap();
+    assert_eq!(saved.created_at, new.created_at);
+
+    let found = repos.users.find_by_id(&new.id).await.unwrap().unwrap();
+    assert_eq!(found.username.value, "alice");
+    assert_eq!(found.email.value, "alice@example.com");
+    assert_eq!(found.roles, new.roles);
+    assert_eq!(found.created_at, new.created_at);
+    assert_eq!(
+        repos
+            .users
+            .find_by_username("alice")
+            .await
+            .unwrap()
+            .map(|u| u.id),
+        Some(new.id.clone())
+    );
+    assert_eq!(
+        repos
+            .users
+            .find_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .map(|u| u.id),
+        Some(new.id.clone())
+    );
+    assert!(repos
+        .users
+        .find_by_username("ALICE")
+        .await
+        .unwrap()
+        .is_none());
+
+    let updated = repos
+        .users
+        .update(&User {
+            username: Username::new("alice.lima".to_string()).unwrap(),
+            email: UserEmail::new("alice.lima@example.com".to_string()).unwrap(),
+            roles: vec![Role::new("directory.read".to_string()).unwrap()],
+            created_at: at(2030, 1, 1, 0),
+            ..found
+        })
+        .await
+        .unwrap();
+    assert_eq!(updated.username.value, "alice.lima");
+    assert_eq!(updated.email.value, "alice.lima@example.com");
+    assert_eq!(updated.roles.len(), 1);
+    assert_eq!(
+        updated.created_at, new.created_at,
+        "created_at is immutable"
+    );
+    assert!(updated.updated_at > new.updated_at);
+    assert!(repos
+        .users
+        .find_by_username("alice")
+        .await
+        .unwrap()
+        .is_none());
+
+    assert!(matches!(
+        repos.users.update(&user("missing", &[])).await,
+        Err(DomainError::NotFound(_))
+    ));
+
+    repos.users.delete(&new.id).await.unwrap();
+    assert!(repos.users.find_by_id(&new.id).await.unwrap().is_none());
+    repos.users.delete(&new.id).await.unwrap();
+}
+
+async fn credentials_follow_password_changes(repos: Repositories) {
+    let saved = repos
+        .users
+        .save(&user("bruno", &["directory.read"]))
+        .await
+        .unwrap();
+
+    let verified = repos
+        .users
+        .verify_credentials("bruno", "bruno-secret")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(verified.id, saved.id);
+    assert!(repos
+        .users
+        .verify_credentials("bruno", "wrong-secret")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repos
+        .users
+        .verify_credentials("nobody", "bruno-secret")
+        .await
+        .unwrap()
+        .is_none());
+
+    // Atualizar outros campos com o usuário lido mantém a senha
+    let stored = repos.users.find_by_id(&saved.id).await.unwrap().unwrap();
+    repos
+        .users
+        .update(&User {
+            roles: vec![],
+            ..stored.clone()
+        })
+        .await
+        .unwrap();
+    assert!(repos
+        .users
+        .verify_credentials("bruno", "bruno-secret")
+        .await
+        .unwrap()
+        .is_some());
+
+    repos
+        .users
+        .update(&User {
+            password: Password::new("a-new-secret".to_string()).unwrap(),
+            ..stored
+        })
+        .await
+        .unwrap();
+    assert!(repos
+        .users
+        .verify_credentials("bruno", "bruno-secret")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repos
+        .users
+        .verify_credentials("bruno", "a-new-secret")
+        .await
+        .unwrap()
+        .is_some());
+}
+
+async fn username_and_email_are_unique(repos: Repositories) {
+    let carla = repos.users.save(&user("carla", &[])).await.unwrap();
+    assert_conflict(repos.users.save(&carla).await);
+    assert_conflict(
+        repos
+            .users
+            .save(&User {
+                email: UserEmail::new("other@example.com".to_string()).unwrap(),
+                ..user("carla", &[])
+            })
+            .await,
+    );
+    assert_conflict(
+        repos
+            .users
+            .save(&User {
+                email: carla.email.clone(),
+                ..user("carla2", &[])
+            })
+            .await,
+    );
+
+    let davi = repos.users.save(&user("davi", &[])).await.unwrap();
+    assert_conflict(
+        repos
+            .users
+            .update(&User {
+                username: carla.username.clone(),
+                ..davi.clone()
+            })
+            .await,
+    );
+    assert_conflict(
+        repos
+            .users
+            .update(&User {
+                email: carla.email.clone(),
+                ..davi
+            })
+            .await,
+    );
+}
+
+async fn search_and_pagination(repos: Repositories) {
+    let fixtures = [
+        ("admin", &["directory.read", "directory.write"][..]),
+        ("analyst", &["directory.read"][..]),
+        ("manager", &["directory.read", "directory.write"][..]),
+        ("auditor", &[][..]),
+    ];
+    for (hour, (username, roles)) in fixtures.into_iter().enumerate() {
+        repos
+            .users
+            .save(&User {
+                created_at: at(2024, 1, 1, hour as u32),
+                ..user(username, roles)
+            })
+            .await
+            .unwrap();
+    }
+
+    let search = |criteria: UserSearchCriteria| {
+        let repos = repos.clone();
+        async move {
+            let result = repos.users.find_all(&criteria).await.unwrap();
+            let names: Vec<String> = usernames(&result.items)
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+            (names, result.total)
+        }
+    };
+    let all = UserSearchCriteria {
+        username: None,
+        email: None,
+        role: None,
+        limit: None,
+        offset: None,
+    };
+
+    // Do mais recente para o mais antigo
+    assert_eq!(
+        search(all.clone()).await,
+        (
+            vec![
+                "auditor".into(),
+                "manager".into(),
+                "analyst".into(),
+                "admin".into()
+            ],
+            4
+        )
+    );
+    assert_eq!(
+        search(UserSearchCriteria {
+            role: Some("directory.write".to_string()),
+            ..all.clone()
+        })
+        .await,
+        (vec!["manager".into(), "admin".into()], 2)
+    );
+    assert_eq!(
+        search(UserSearchCriteria {
+            username: Some("A".to_string()),
+            email: Some("n@".to_string()),
+            ..all.clone()
+        })
+        .await,
+        (vec!["admin".into()], 1)
+    );
+    assert_eq!(
+        search(UserSearchCriteria {
+            role: Some("directory.read".to_string()),
+            limit: Some(2),
+            offset: Some(1),
+            ..all.clone()
+        })
+        .await,
+        (vec!["analyst".into(), "admin".into()], 3)
+    );
+    assert_eq!(
+        search(UserSearchCriteria {
+            role: Some("directory".to_string()),
+            ..all
+        })
+        .await,
+        (vec![], 0)
+    );
+
+    assert_eq!(
+        usernames(&repos.users.find_by_role("directory.read").await.unwrap()),
+        ["manager", "analyst", "admin"]
+    );
+}
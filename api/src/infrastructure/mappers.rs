@@ -18,6 +18,7 @@ pub struct ContactRow {
     pub etag: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, FromRow)]
@@ -177,6 +178,7 @@ pub fn build_contact_with_relations(
         etag: contact_row.etag,
         created_at: contact_row.created_at,
         updated_at: contact_row.updated_at,
+        deleted_at: contact_row.deleted_at,
     })
 }
 
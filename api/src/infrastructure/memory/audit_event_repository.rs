@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+
+use super::InMemoryDatabase;
+use crate::domain::entities::AuditEvent;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::AuditEventRepository;
+use crate::domain::value_objects::AuditEventId;
+
+pub struct InMemoryAuditEventRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryAuditEventRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// Eventos que satisfazem `filter`, do mais recente para o mais antigo
+fn newest_first(events: &[AuditEvent], filter: impl Fn(&AuditEvent) -> bool) -> Vec<AuditEvent> {
+    let mut events: Vec<AuditEvent> = events.iter().filter(|e| filter(e)).cloned().collect();
+    events.sort_by(|a, b| b.at.cmp(&a.at).then_with(|| b.id.0.cmp(&a.id.0)));
+    events
+}
+
+#[async_trait]
+impl AuditEventRepository for InMemoryAuditEventRepository {
+    /// O id é atribuído na gravação, como o `BIGSERIAL` de `audit_events`
+    async fn save(&self, event: &AuditEvent) -> Result<AuditEvent, DomainError> {
+        Ok(self.database.write(|tables| {
+            let saved = AuditEvent {
+                id: AuditEventId::new(tables.audit_events.len() as i64 + 1),
+                ..event.clone()
+            };
+            tables.audit_events.push(saved.clone());
+            saved
+        }))
+    }
+
+    async fn find_by_entity(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Vec<AuditEvent>, DomainError> {
+        Ok(self.database.read(|tables| {
+            newest_first(&tables.audit_events, |e| {
+                e.entity_type.value == entity_type && e.entity_id == entity_id
+            })
+        }))
+    }
+
+    async fn find_by_actor(&self, actor_sub: &str) -> Result<Vec<AuditEvent>, DomainError> {
+        Ok(self.database.read(|tables| {
+            newest_first(&tables.audit_events, |e| {
+                e.actor_sub.as_deref() == Some(actor_sub)
+            })
+        }))
+    }
+
+    async fn find_recent(&self, limit: i64) -> Result<Vec<AuditEvent>, DomainError> {
+        Ok(self.database.read(|tables| {
+            let mut events = newest_first(&tables.audit_events, |_| true);
+            events.truncate(limit.max(0) as usize);
+            events
+        }))
+    }
+}
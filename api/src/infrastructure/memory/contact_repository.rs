@@ -0,0 +1,583 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    already_exists, ilike, page, reference_violation, row_not_found, InMemoryDatabase, Tables,
+};
+use crate::domain::entities::Contact;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{
+    ContactChange, ContactDataQuality, ContactRepository, ContactSearchCriteria,
+    ContactSearchResult, ContactStatistics, ContactStatisticsQuery, OrgUnitContactCount,
+};
+use crate::domain::value_objects::*;
+
+pub struct InMemoryContactRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryContactRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// ETag forte calculada do conteúdo (como `compute_contact_etag` no PostgreSQL)
+pub(crate) fn compute_etag(contact: &Contact) -> String {
+    let fields = [
+        contact.full_name.clone(),
+        contact.status.to_string(),
+        contact
+            .unit_id
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        contact
+            .department_id
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        contact.document.clone().unwrap_or_default(),
+        contact
+            .deleted_at
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_default(),
+    ];
+    hex::encode(Sha256::digest(fields.join("|")))
+}
+
+/// Marca a escrita como o trigger `contacts_set_etag` (ETag e `updated_at`)
+fn touch(contact: &mut Contact) {
+    contact.etag = compute_etag(contact);
+    contact.updated_at = Utc::now();
+}
+
+/// Restrições de `contacts`, `emails` e `phones` para o contato a gravar
+fn check_constraints(tables: &Tables, contact: &Contact) -> Result<(), DomainError> {
+    if let Some(document) = &contact.document {
+        if tables
+            .contacts
+            .values()
+            .any(|c| c.id != contact.id && c.document.as_ref() == Some(document))
+        {
+            return Err(already_exists());
+        }
+    }
+    if contact
+        .unit_id
+        .as_ref()
+        .is_some_and(|unit_id| !tables.org_units.contains_key(unit_id))
+        || contact
+            .department_id
+            .as_ref()
+            .is_some_and(|department_id| !tables.departments.contains_key(department_id))
+    {
+        return Err(reference_violation());
+    }
+
+    let mut emails = HashSet::new();
+    if !contact
+        .emails
+        .iter()
+        .all(|email| emails.insert(email.value.to_lowercase()))
+    {
+        return Err(already_exists());
+    }
+    let mut phones = HashSet::new();
+    if !contact.phones.iter().all(|phone| {
+        phones.insert((
+            phone.e164.clone(),
+            phone.extension.clone().unwrap_or_default(),
+        ))
+    }) {
+        return Err(already_exists());
+    }
+    Ok(())
+}
+
+/// `UPDATE contacts ...`: `created_at` e `deleted_at` não são alterados; com `etag`,
+/// só altera o contato ativo que ainda tiver esse ETag
+fn update_in(
+    tables: &mut Tables,
+    contact: &Contact,
+    etag: Option<&str>,
+) -> Result<Contact, DomainError> {
+    let stored = tables.contacts.get(&contact.id).ok_or_else(row_not_found)?;
+    if etag.is_some_and(|etag| stored.is_deleted() || stored.etag != etag) {
+        return Err(etag_mismatch());
+    }
+    check_constraints(tables, contact)?;
+    let mut updated = Contact {
+        created_at: stored.created_at,
+        deleted_at: stored.deleted_at,
+        ..contact.clone()
+    };
+    touch(&mut updated);
+    tables.contacts.insert(updated.id.clone(), updated.clone());
+    Ok(updated)
+}
+
+fn soft_delete_in(
+    tables: &mut Tables,
+    id: &ContactId,
+    etag: Option<&str>,
+) -> Result<(), DomainError> {
+    match tables.contacts.get_mut(id).filter(|c| !c.is_deleted()) {
+        Some(contact) if etag.is_none_or(|etag| contact.etag == etag) => {
+            contact.deleted_at = Some(Utc::now());
+            touch(contact);
+            Ok(())
+        }
+        _ if etag.is_some() => Err(etag_mismatch()),
+        _ => Ok(()),
+    }
+}
+
+/// Mesma mensagem do repositório PostgreSQL
+fn etag_mismatch() -> DomainError {
+    DomainError::Conflict("ETag mismatch - contact was modified by another user".to_string())
+}
+
+/// Contatos não excluídos, do mais recente para o mais antigo
+fn active_contacts(tables: &Tables) -> Vec<&Contact> {
+    let mut contacts: Vec<&Contact> = tables
+        .contacts
+        .values()
+        .filter(|c| !c.is_deleted())
+        .collect();
+    sort_newest_first(&mut contacts);
+    contacts
+}
+
+fn sort_newest_first(contacts: &mut [&Contact]) {
+    contacts.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.id.0.cmp(&b.id.0))
+    });
+}
+
+fn cloned(contacts: Vec<&Contact>) -> Vec<Contact> {
+    contacts.into_iter().cloned().collect()
+}
+
+/// Contagens por chave, da maior para a menor
+fn count_by<K: Clone + Eq + std::hash::Hash + Ord>(keys: impl Iterator<Item = K>) -> Vec<(K, i64)> {
+    let mut counts: HashMap<K, i64> = HashMap::new();
+    for key in keys {
+        *counts.entry(key).or_default() += 1;
+    }
+    let mut counts: Vec<(K, i64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Série esparsa por início de intervalo (`date_trunc`), em ordem cronológica
+fn series(
+    timestamps: impl Iterator<Item = DateTime<Utc>>,
+    query: &ContactStatisticsQuery,
+) -> Vec<(DateTime<Utc>, i64)> {
+    let mut buckets = count_by(
+        timestamps
+            .filter(|at| *at >= query.from && *at < query.to)
+            .map(|at| query.bucket.truncate(at)),
+    );
+    buckets.sort_by_key(|(bucket, _)| *bucket);
+    buckets
+}
+
+#[async_trait]
+impl ContactRepository for InMemoryContactRepository {
+    async fn find_by_id(&self, id: &ContactId) -> Result<Option<Contact>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| tables.contacts.get(id).cloned()))
+    }
+
+    async fn find_all(
+        &self,
+        criteria: &ContactSearchCriteria,
+    ) -> Result<ContactSearchResult, DomainError> {
+        let (items, total) = self.database.read(|tables| {
+            let mut contacts: Vec<&Contact> = tables
+                .contacts
+                .values()
+                .filter(|c| criteria.include_deleted || !c.is_deleted())
+                .filter(|c| {
+                    criteria
+                        .full_name
+                        .as_ref()
+                        .is_none_or(|name| ilike(&c.full_name, name))
+                })
+                .filter(|c| {
+                    criteria
+                        .contact_type
+                        .as_ref()
+                        .is_none_or(|t| &c.contact_type == t)
+                })
+                .filter(|c| criteria.status.as_ref().is_none_or(|s| &c.status == s))
+                .filter(|c| {
+                    criteria
+                        .unit_id
+                        .as_ref()
+                        .is_none_or(|unit_id| c.unit_id.as_ref() == Some(unit_id))
+                })
+                .filter(|c| {
+                    criteria
+                        .department_id
+                        .as_ref()
+                        .is_none_or(|department_id| c.department_id.as_ref() == Some(department_id))
+                })
+                .collect();
+            sort_newest_first(&mut contacts);
+            page(cloned(contacts), criteria.limit, criteria.offset)
+        });
+        Ok(ContactSearchResult { items, total })
+    }
+
+    async fn save(&self, contact: &Contact) -> Result<Contact, DomainError> {
+        self.database.write(|tables| {
+            if tables.contacts.contains_key(&contact.id) {
+                return Err(already_exists());
+            }
+            check_constraints(tables, contact)?;
+            let mut saved = contact.clone();
+            touch(&mut saved);
+            tables.contacts.insert(saved.id.clone(), saved.clone());
+            Ok(saved)
+        })
+    }
+
+    async fn update(&self, contact: &Contact) -> Result<Contact, DomainError> {
+        self.database
+            .write(|tables| update_in(tables, contact, None))
+    }
+
+    async fn delete(&self, id: &ContactId) -> Result<(), DomainError> {
+        self.database
+            .write(|tables| tables.delete_contact(id))
+            .map(|_| ())
+    }
+
+    async fn soft_delete(&self, id: &ContactId) -> Result<(), DomainError> {
+        self.database
+            .write(|tables| soft_delete_in(tables, id, None))
+    }
+
+    async fn restore(&self, id: &ContactId) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            if let Some(contact) = tables.contacts.get_mut(id) {
+                contact.deleted_at = None;
+                touch(contact);
+            }
+        });
+        Ok(())
+    }
+
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, DomainError> {
+        // Um único DELETE: qualquer violação cancela a purga inteira
+        self.database.transaction(|tables| {
+            let expired: Vec<ContactId> = tables
+                .contacts
+                .values()
+                .filter(|c| c.deleted_at.is_some_and(|at| at < deleted_before))
+                .map(|c| c.id.clone())
+                .collect();
+            for id in &expired {
+                tables.delete_contact(id)?;
+            }
+            Ok(expired.len() as u64)
+        })
+    }
+
+    async fn apply_batch(
+        &self,
+        changes: &[ContactChange],
+    ) -> Result<Vec<Option<Contact>>, DomainError> {
+        self.database.transaction(|tables| {
+            changes
+                .iter()
+                .map(|change| match change {
+                    ContactChange::Update { contact, etag } => {
+                        update_in(tables, contact, Some(etag)).map(Some)
+                    }
+                    ContactChange::SoftDelete { id, etag } => {
+                        soft_delete_in(tables, id, Some(etag)).map(|_| None)
+                    }
+                })
+                .collect()
+        })
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<Contact>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .contacts
+                .values()
+                .find(|c| c.emails.iter().any(|e| e.value == email))
+                .cloned()
+        }))
+    }
+
+    async fn find_by_document(&self, document: &str) -> Result<Option<Contact>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .contacts
+                .values()
+                .find(|c| c.document.as_deref() == Some(document))
+                .cloned()
+        }))
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<Contact>, DomainError> {
+        Ok(self.database.read(|tables| {
+            cloned(
+                active_contacts(tables)
+                    .into_iter()
+                    .filter(|c| ilike(&c.full_name, name))
+                    .collect(),
+            )
+        }))
+    }
+
+    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Contact>, DomainError> {
+        Ok(self.database.read(|tables| {
+            cloned(
+                active_contacts(tables)
+                    .into_iter()
+                    .filter(|c| c.unit_id.as_ref() == Some(unit_id))
+                    .collect(),
+            )
+        }))
+    }
+
+    async fn find_by_department(
+        &self,
+        department_id: &DepartmentId,
+    ) -> Result<Vec<Contact>, DomainError> {
+        Ok(self.database.read(|tables| {
+            cloned(
+                active_contacts(tables)
+                    .into_iter()
+                    .filter(|c| c.department_id.as_ref() == Some(department_id))
+                    .collect(),
+            )
+        }))
+    }
+
+    async fn count_by_status(&self, status: &ContactStatus) -> Result<i64, DomainError> {
+        Ok(self.database.read(|tables| {
+            active_contacts(tables)
+                .iter()
+                .filter(|c| &c.status == status)
+                .count() as i64
+        }))
+    }
+
+    async fn count_by_type(&self, contact_type: &ContactType) -> Result<i64, DomainError> {
+        Ok(self.database.read(|tables| {
+            active_contacts(tables)
+                .iter()
+                .filter(|c| &c.contact_type == contact_type)
+                .count() as i64
+        }))
+    }
+
+    async fn get_statistics(
+        &self,
+        query: &ContactStatisticsQuery,
+    ) -> Result<ContactStatistics, DomainError> {
+        Ok(self.database.read(|tables| {
+            let contacts = active_contacts(tables);
+            let count =
+                |f: &dyn Fn(&Contact) -> bool| contacts.iter().filter(|c| f(c)).count() as i64;
+
+            // Contagem direta e acumulada sobre as unidades descendentes
+            let mut by_org_unit: Vec<OrgUnitContactCount> = tables
+                .org_units
+                .keys()
+                .map(|root| {
+                    let mut subtree = HashSet::from([root.clone()]);
+                    let mut frontier = vec![root.clone()];
+                    while let Some(parent) = frontier.pop() {
+                        for unit in tables.org_units.values() {
+                            if unit.parent_id.as_ref() == Some(&parent)
+                                && subtree.insert(unit.id.clone())
+                            {
+                                frontier.push(unit.id.clone());
+                            }
+                        }
+                    }
+                    OrgUnitContactCount {
+                        unit_id: root.clone(),
+                        contacts: count(&|c| c.unit_id.as_ref() == Some(root)),
+                        contacts_including_descendants: count(&|c| {
+                            c.unit_id.as_ref().is_some_and(|id| subtree.contains(id))
+                        }),
+                    }
+                })
+                .collect();
+            by_org_unit.sort_by(|a, b| {
+                b.contacts_including_descendants
+                    .cmp(&a.contacts_including_descendants)
+                    .then_with(|| a.unit_id.0.cmp(&b.unit_id.0))
+            });
+
+            let by_department = count_by(
+                contacts
+                    .iter()
+                    .filter_map(|c| c.department_id.as_ref().map(|id| id.0)),
+            )
+            .into_iter()
+            .map(|(id, count)| (DepartmentId(id), count))
+            .collect();
+
+            let by_status = count_by(contacts.iter().map(|c| c.status.to_string()))
+                .into_iter()
+                .filter_map(|(status, count)| Some((status.parse().ok()?, count)))
+                .collect();
+
+            let by_source = count_by(contacts.iter().flat_map(|c| {
+                tables
+                    .contact_sources
+                    .iter()
+                    .filter(|cs| cs.contact_id == c.id)
+                    .filter_map(|cs| tables.source_records.get(&cs.source_record_id))
+                    .map(|record| record.source.value.clone())
+                    .collect::<HashSet<_>>()
+            }));
+
+            ContactStatistics {
+                total_contacts: contacts.len() as i64,
+                active_contacts: count(&|c| c.status == ContactStatus::Active),
+                inactive_contacts: count(&|c| c.status == ContactStatus::Inactive),
+                persons: count(&|c| c.contact_type == ContactType::Person),
+                organizations: count(&|c| c.contact_type == ContactType::Organization),
+                department_contacts: count(&|c| c.contact_type == ContactType::Department),
+                departments: tables.departments.len() as i64,
+                by_org_unit,
+                by_department,
+                by_status,
+                by_source,
+                created_series: series(contacts.iter().map(|c| c.created_at), query),
+                updated_series: series(contacts.iter().map(|c| c.updated_at), query),
+                data_quality: ContactDataQuality {
+                    missing_primary_email: count(&|c| !c.emails.iter().any(|e| e.is_primary)),
+                    missing_primary_phone: count(&|c| !c.phones.iter().any(|p| p.is_primary)),
+                    invalid_document: count(&|c| {
+                        c.document
+                            .as_deref()
+                            .is_some_and(|document| !is_valid_document(document))
+                    }),
+                },
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{MergeDecision, MergeDecisionType};
+
+    fn contact(name: &str, document: Option<&str>) -> Contact {
+        Contact::new(
+            name.to_string(),
+            ContactType::Person,
+            ContactStatus::Active,
+            document.map(str::to_string),
+            None,
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_etag_follows_content_and_soft_delete() {
+        let repository = InMemoryDatabase::new().contacts();
+        let saved = repository.save(&contact("Ana", None)).await.unwrap();
+        assert_eq!(saved.etag, compute_etag(&saved));
+
+        repository.soft_delete(&saved.id).await.unwrap();
+        let deleted = repository.find_by_id(&saved.id).await.unwrap().unwrap();
+        assert!(deleted.is_deleted());
+        assert_ne!(deleted.etag, saved.etag);
+
+        repository.restore(&saved.id).await.unwrap();
+        let restored = repository.find_by_id(&saved.id).await.unwrap().unwrap();
+        assert_eq!(restored.etag, saved.etag);
+    }
+
+    #[tokio::test]
+    async fn test_apply_batch_is_atomic() {
+        let database = InMemoryDatabase::new();
+        let repository = database.contacts();
+        let ana = repository.save(&contact("Ana", Some("1"))).await.unwrap();
+        let bia = repository.save(&contact("Bia", Some("2"))).await.unwrap();
+
+        // O segundo item viola o documento único: nada é gravado
+        let renamed = Contact {
+            full_name: "Ana Maria".to_string(),
+            ..ana.clone()
+        };
+        let duplicate = Contact {
+            document: Some("1".to_string()),
+            ..bia.clone()
+        };
+        let err = repository
+            .apply_batch(&[
+                ContactChange::Update {
+                    contact: renamed,
+                    etag: ana.etag.clone(),
+                },
+                ContactChange::SoftDelete {
+                    id: bia.id.clone(),
+                    etag: bia.etag.clone(),
+                },
+                ContactChange::Update {
+                    contact: duplicate,
+                    etag: bia.etag.clone(),
+                },
+            ])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+        let stored = repository.find_by_id(&ana.id).await.unwrap().unwrap();
+        assert_eq!(stored.full_name, "Ana");
+        assert!(!repository
+            .find_by_id(&bia.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_deleted());
+    }
+
+    #[tokio::test]
+    async fn test_merge_decisions_block_purge() {
+        let database = InMemoryDatabase::new();
+        let repository = database.contacts();
+        let ana = repository.save(&contact("Ana", None)).await.unwrap();
+        let bia = repository.save(&contact("Bia", None)).await.unwrap();
+        database.write(|tables| {
+            tables.merge_decisions.push(MergeDecision::new(
+                ana.id.clone(),
+                bia.id.clone(),
+                MergeDecisionType::Reject,
+                None,
+                None,
+            ))
+        });
+        repository.soft_delete(&bia.id).await.unwrap();
+
+        let err = repository
+            .purge_deleted(Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+        assert!(repository.find_by_id(&bia.id).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+
+use super::{already_exists, reference_violation, InMemoryDatabase};
+use crate::domain::entities::ContactSource;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::ContactSourceRepository;
+use crate::domain::value_objects::{ContactId, SourceRecordId};
+
+pub struct InMemoryContactSourceRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryContactSourceRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl ContactSourceRepository for InMemoryContactSourceRepository {
+    async fn save(&self, contact_source: &ContactSource) -> Result<ContactSource, DomainError> {
+        self.database.write(|tables| {
+            if !tables.contacts.contains_key(&contact_source.contact_id)
+                || !tables
+                    .source_records
+                    .contains_key(&contact_source.source_record_id)
+            {
+                return Err(reference_violation());
+            }
+            if tables.contact_sources.iter().any(|cs| {
+                cs.contact_id == contact_source.contact_id
+                    && cs.source_record_id == contact_source.source_record_id
+            }) {
+                return Err(already_exists());
+            }
+            tables.contact_sources.push(contact_source.clone());
+            Ok(contact_source.clone())
+        })
+    }
+
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<ContactSource>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .contact_sources
+                .iter()
+                .filter(|cs| &cs.contact_id == contact_id)
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn find_by_source_record(
+        &self,
+        source_record_id: &SourceRecordId,
+    ) -> Result<Vec<ContactSource>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .contact_sources
+                .iter()
+                .filter(|cs| &cs.source_record_id == source_record_id)
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn delete(
+        &self,
+        contact_id: &ContactId,
+        source_record_id: &SourceRecordId,
+    ) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            tables.contact_sources.retain(|cs| {
+                &cs.contact_id != contact_id || &cs.source_record_id != source_record_id
+            })
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+
+use super::{
+    already_exists, ilike, page, reference_violation, row_not_found, InMemoryDatabase, Tables,
+};
+use crate::domain::entities::Department;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{
+    DepartmentRepository, DepartmentSearchCriteria, DepartmentSearchResult, DepartmentStatistics,
+};
+use crate::domain::value_objects::{DepartmentId, OrgUnitId};
+
+pub struct InMemoryDepartmentRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryDepartmentRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// Restrições de `departments`: unidade existente e nome único (sem distinção
+/// de maiúsculas) dentro da unidade
+fn check_constraints(tables: &Tables, department: &Department) -> Result<(), DomainError> {
+    if !tables.org_units.contains_key(&department.unit_id) {
+        return Err(reference_violation());
+    }
+    let name = department.name.value.to_lowercase();
+    if tables.departments.values().any(|d| {
+        d.id != department.id
+            && d.unit_id == department.unit_id
+            && d.name.value.to_lowercase() == name
+    }) {
+        return Err(already_exists());
+    }
+    Ok(())
+}
+
+/// Departamentos que satisfazem `filter`, ordenados por nome
+fn departments_by_name(tables: &Tables, filter: impl Fn(&Department) -> bool) -> Vec<Department> {
+    let mut departments: Vec<Department> = tables
+        .departments
+        .values()
+        .filter(|department| filter(department))
+        .cloned()
+        .collect();
+    departments.sort_by(|a, b| {
+        a.name
+            .value
+            .cmp(&b.name.value)
+            .then_with(|| a.id.0.cmp(&b.id.0))
+    });
+    departments
+}
+
+#[async_trait]
+impl DepartmentRepository for InMemoryDepartmentRepository {
+    async fn find_by_id(&self, id: &DepartmentId) -> Result<Option<Department>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| tables.departments.get(id).cloned()))
+    }
+
+    async fn find_all(
+        &self,
+        criteria: &DepartmentSearchCriteria,
+    ) -> Result<DepartmentSearchResult, DomainError> {
+        let (items, total) = self.database.read(|tables| {
+            let departments = departments_by_name(tables, |department| {
+                criteria
+                    .name
+                    .as_ref()
+                    .is_none_or(|name| ilike(&department.name.value, name))
+                    && criteria
+                        .unit_id
+                        .as_ref()
+                        .is_none_or(|unit_id| &department.unit_id == unit_id)
+            });
+            page(departments, criteria.limit, criteria.offset)
+        });
+        Ok(DepartmentSearchResult { items, total })
+    }
+
+    async fn save(&self, department: &Department) -> Result<Department, DomainError> {
+        self.database.write(|tables| {
+            if tables.departments.contains_key(&department.id) {
+                return Err(already_exists());
+            }
+            check_constraints(tables, department)?;
+            tables
+                .departments
+                .insert(department.id.clone(), department.clone());
+            Ok(department.clone())
+        })
+    }
+
+    async fn update(&self, department: &Department) -> Result<Department, DomainError> {
+        self.database.write(|tables| {
+            let stored = tables
+                .departments
+                .get(&department.id)
+                .ok_or_else(row_not_found)?;
+            check_constraints(tables, department)?;
+            let updated = Department {
+                created_at: stored.created_at,
+                updated_at: Utc::now(),
+                ..department.clone()
+            };
+            tables
+                .departments
+                .insert(updated.id.clone(), updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: &DepartmentId) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            if tables
+                .contacts
+                .values()
+                .any(|c| c.department_id.as_ref() == Some(id))
+            {
+                return Err(reference_violation());
+            }
+            tables.departments.remove(id);
+            Ok(())
+        })
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<Department>, DomainError> {
+        Ok(self.database.read(|tables| {
+            departments_by_name(tables, |department| ilike(&department.name.value, name))
+        }))
+    }
+
+    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Department>, DomainError> {
+        Ok(self.database.read(|tables| {
+            departments_by_name(tables, |department| &department.unit_id == unit_id)
+        }))
+    }
+
+    async fn get_statistics(&self) -> Result<DepartmentStatistics, DomainError> {
+        Ok(self.database.read(|tables| {
+            let mut departments_by_unit = HashMap::new();
+            for department in tables.departments.values() {
+                *departments_by_unit
+                    .entry(department.unit_id.clone())
+                    .or_default() += 1;
+            }
+            DepartmentStatistics {
+                total_departments: tables.departments.len() as i64,
+                departments_by_unit,
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::OrgUnit;
+    use crate::domain::repositories::OrgUnitRepository;
+    use crate::domain::value_objects::{DepartmentName, OrgUnitName};
+
+    fn department(unit_id: &OrgUnitId, name: &str) -> Department {
+        Department::new(
+            unit_id.clone(),
+            DepartmentName {
+                value: name.to_string(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_department_name_is_unique_per_unit() {
+        let database = InMemoryDatabase::new();
+        let units = database.org_units();
+        let repository = database.departments();
+        let mut unit_ids = Vec::new();
+        for name in ["Sede", "Filial"] {
+            let unit = OrgUnit::new(
+                OrgUnitName {
+                    value: name.to_string(),
+                },
+                None,
+            );
+            unit_ids.push(units.save(&unit).await.unwrap().id);
+        }
+
+        repository
+            .save(&department(&unit_ids[0], "Finance"))
+            .await
+            .unwrap();
+        let err = repository
+            .save(&department(&unit_ids[0], "FINANCE"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+        repository
+            .save(&department(&unit_ids[1], "Finance"))
+            .await
+            .unwrap();
+
+        let err = repository
+            .save(&department(&OrgUnitId::new(), "Legal"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+        assert_eq!(
+            repository.get_statistics().await.unwrap().total_departments,
+            2
+        );
+    }
+}
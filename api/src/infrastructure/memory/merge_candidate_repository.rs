@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+
+use super::{already_exists, reference_violation, InMemoryDatabase};
+use crate::domain::entities::MergeCandidate;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::MergeCandidateRepository;
+use crate::domain::value_objects::ContactId;
+
+pub struct InMemoryMergeCandidateRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryMergeCandidateRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// Candidatos do maior para o menor score
+fn by_score(mut candidates: Vec<MergeCandidate>) -> Vec<MergeCandidate> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
+
+#[async_trait]
+impl MergeCandidateRepository for InMemoryMergeCandidateRepository {
+    async fn save(&self, candidate: &MergeCandidate) -> Result<MergeCandidate, DomainError> {
+        self.database.write(|tables| {
+            if !tables.contacts.contains_key(&candidate.contact_a)
+                || !tables.contacts.contains_key(&candidate.contact_b)
+            {
+                return Err(reference_violation());
+            }
+            if tables.merge_candidates.iter().any(|mc| {
+                mc.contact_a == candidate.contact_a && mc.contact_b == candidate.contact_b
+            }) {
+                return Err(already_exists());
+            }
+            tables.merge_candidates.push(candidate.clone());
+            Ok(candidate.clone())
+        })
+    }
+
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<MergeCandidate>, DomainError> {
+        Ok(self.database.read(|tables| {
+            by_score(
+                tables
+                    .merge_candidates
+                    .iter()
+                    .filter(|mc| &mc.contact_a == contact_id || &mc.contact_b == contact_id)
+                    .cloned()
+                    .collect(),
+            )
+        }))
+    }
+
+    async fn find_top_candidates(&self, limit: i64) -> Result<Vec<MergeCandidate>, DomainError> {
+        Ok(self.database.read(|tables| {
+            let mut candidates = by_score(tables.merge_candidates.clone());
+            candidates.truncate(limit.max(0) as usize);
+            candidates
+        }))
+    }
+
+    async fn delete(
+        &self,
+        contact_a: &ContactId,
+        contact_b: &ContactId,
+    ) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            tables
+                .merge_candidates
+                .retain(|mc| &mc.contact_a != contact_a || &mc.contact_b != contact_b)
+        });
+        Ok(())
+    }
+}
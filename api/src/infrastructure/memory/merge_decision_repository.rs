@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+
+use super::{already_exists, reference_violation, InMemoryDatabase};
+use crate::domain::entities::MergeDecision;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::MergeDecisionRepository;
+use crate::domain::value_objects::{ContactId, UserId};
+
+pub struct InMemoryMergeDecisionRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryMergeDecisionRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// Decisões da mais recente para a mais antiga
+fn newest_first(mut decisions: Vec<MergeDecision>) -> Vec<MergeDecision> {
+    decisions.sort_by_key(|d| std::cmp::Reverse(d.decided_at));
+    decisions
+}
+
+#[async_trait]
+impl MergeDecisionRepository for InMemoryMergeDecisionRepository {
+    async fn save(&self, decision: &MergeDecision) -> Result<MergeDecision, DomainError> {
+        self.database.write(|tables| {
+            if !tables.contacts.contains_key(&decision.primary_contact)
+                || !tables.contacts.contains_key(&decision.duplicate_contact)
+            {
+                return Err(reference_violation());
+            }
+            if tables.merge_decisions.iter().any(|d| {
+                d.primary_contact == decision.primary_contact
+                    && d.duplicate_contact == decision.duplicate_contact
+            }) {
+                return Err(already_exists());
+            }
+            tables.merge_decisions.push(decision.clone());
+            Ok(decision.clone())
+        })
+    }
+
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<MergeDecision>, DomainError> {
+        Ok(self.database.read(|tables| {
+            newest_first(
+                tables
+                    .merge_decisions
+                    .iter()
+                    .filter(|d| {
+                        &d.primary_contact == contact_id || &d.duplicate_contact == contact_id
+                    })
+                    .cloned()
+                    .collect(),
+            )
+        }))
+    }
+
+    async fn find_by_decider(
+        &self,
+        decided_by: &UserId,
+    ) -> Result<Vec<MergeDecision>, DomainError> {
+        Ok(self.database.read(|tables| {
+            newest_first(
+                tables
+                    .merge_decisions
+                    .iter()
+                    .filter(|d| d.decided_by.as_ref() == Some(decided_by))
+                    .cloned()
+                    .collect(),
+            )
+        }))
+    }
+}
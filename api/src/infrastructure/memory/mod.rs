@@ -0,0 +1,302 @@
+//! =============================================================================
+//! IN-MEMORY REPOSITORIES
+//! =============================================================================
+//! Implementações em memória de todos os repositórios de `domain::repositories`,
+//! usadas nos testes e no modo `--demo` (sem PostgreSQL)
+//!
+//! Os repositórios compartilham um único `InMemoryDatabase`, como os repositórios
+//! PostgreSQL compartilham o pool, e reproduzem as restrições do schema
+//! (`migrations/`): chaves únicas, chaves estrangeiras e cascatas, com os mesmos
+//! erros que `From<sqlx::Error>` produz para o PostgreSQL.
+
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::*;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::*;
+
+pub mod audit_event_repository;
+pub mod contact_repository;
+pub mod department_repository;
+pub mod org_unit_repository;
+pub mod user_repository;
+pub mod webhook_receipt_repository;
+
+// Fontes e deduplicação ainda não têm casos de uso; por ora só os testes os usam
+#[allow(dead_code)]
+pub mod contact_source_repository;
+#[allow(dead_code)]
+pub mod merge_candidate_repository;
+#[allow(dead_code)]
+pub mod merge_decision_repository;
+#[allow(dead_code)]
+pub mod source_record_repository;
+
+pub use audit_event_repository::*;
+pub use contact_repository::*;
+pub use contact_source_repository::*;
+pub use department_repository::*;
+pub use merge_candidate_repository::*;
+pub use merge_decision_repository::*;
+pub use org_unit_repository::*;
+pub use source_record_repository::*;
+pub use user_repository::*;
+pub use webhook_receipt_repository::*;
+
+/// Tabelas do banco em memória
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Tables {
+    pub org_units: HashMap<OrgUnitId, OrgUnit>,
+    pub departments: HashMap<DepartmentId, Department>,
+    pub contacts: HashMap<ContactId, Contact>,
+    pub users: HashMap<UserId, User>,
+    pub audit_events: Vec<AuditEvent>,
+    pub source_records: HashMap<SourceRecordId, SourceRecord>,
+    pub contact_sources: Vec<ContactSource>,
+    pub merge_candidates: Vec<MergeCandidate>,
+    pub merge_decisions: Vec<MergeDecision>,
+    pub webhook_receipts: Vec<WebhookReceipt>,
+}
+
+impl Tables {
+    /// Remove um contato com as linhas que o referenciam em cascata
+    /// (`contact_sources` e `merge_candidates`); decisões de merge impedem a remoção
+    fn delete_contact(&mut self, id: &ContactId) -> Result<bool, DomainError> {
+        if self
+            .merge_decisions
+            .iter()
+            .any(|d| &d.primary_contact == id || &d.duplicate_contact == id)
+        {
+            return Err(reference_violation());
+        }
+        self.contact_sources.retain(|cs| &cs.contact_id != id);
+        self.merge_candidates
+            .retain(|mc| &mc.contact_a != id && &mc.contact_b != id);
+        Ok(self.contacts.remove(id).is_some())
+    }
+}
+
+/// Banco em memória compartilhado pelos repositórios (thread-safe)
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDatabase {
+    tables: Arc<RwLock<Tables>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contacts(&self) -> InMemoryContactRepository {
+        InMemoryContactRepository::new(self.clone())
+    }
+
+    pub fn org_units(&self) -> InMemoryOrgUnitRepository {
+        InMemoryOrgUnitRepository::new(self.clone())
+    }
+
+    pub fn departments(&self) -> InMemoryDepartmentRepository {
+        InMemoryDepartmentRepository::new(self.clone())
+    }
+
+    pub fn users(&self) -> InMemoryUserRepository {
+        InMemoryUserRepository::new(self.clone())
+    }
+
+    pub fn audit_events(&self) -> InMemoryAuditEventRepository {
+        InMemoryAuditEventRepository::new(self.clone())
+    }
+
+    pub fn source_records(&self) -> InMemorySourceRecordRepository {
+        InMemorySourceRecordRepository::new(self.clone())
+    }
+
+    pub fn contact_sources(&self) -> InMemoryContactSourceRepository {
+        InMemoryContactSourceRepository::new(self.clone())
+    }
+
+    pub fn merge_candidates(&self) -> InMemoryMergeCandidateRepository {
+        InMemoryMergeCandidateRepository::new(self.clone())
+    }
+
+    pub fn merge_decisions(&self) -> InMemoryMergeDecisionRepository {
+        InMemoryMergeDecisionRepository::new(self.clone())
+    }
+
+    pub fn webhook_receipts(&self) -> InMemoryWebhookReceiptRepository {
+        InMemoryWebhookReceiptRepository::new(self.clone())
+    }
+
+    /// Dados de demonstração equivalentes aos seeds das migrações
+    /// (`001_init.sql` e os usuários de `003_seed_users.sql`)
+    pub fn demo() -> Self {
+        let database = Self::new();
+        {
+            let mut tables = database.tables.write();
+            let now = chrono::Utc::now();
+            let id = |value: &str| uuid::Uuid::parse_str(value).expect("valid seed id");
+
+            let headquarters = OrgUnitId(id("00000000-0000-0000-0000-000000000001"));
+            tables.org_units.insert(
+                headquarters.clone(),
+                OrgUnit {
+                    id: headquarters.clone(),
+                    name: OrgUnitName {
+                        value: "Headquarters".to_string(),
+                    },
+                    parent_id: None,
+                    created_at: now,
+                    updated_at: now,
+                },
+            );
+
+            let departments = [
+                ("00000000-0000-0000-0000-000000000101", "Engineering"),
+                ("00000000-0000-0000-0000-000000000102", "HR"),
+            ];
+            for (department_id, name) in departments {
+                let department_id = DepartmentId(id(department_id));
+                tables.departments.insert(
+                    department_id.clone(),
+                    Department {
+                        id: department_id,
+                        unit_id: headquarters.clone(),
+                        name: DepartmentName {
+                            value: name.to_string(),
+                        },
+                        created_at: now,
+                        updated_at: now,
+                    },
+                );
+            }
+
+            let contacts = [
+                (
+                    "10000000-0000-0000-0000-000000000001",
+                    "Alice Silva",
+                    "00000000-0000-0000-0000-000000000101",
+                    "alice.silva@example.com",
+                ),
+                (
+                    "10000000-0000-0000-0000-000000000002",
+                    "Bruno Souza",
+                    "00000000-0000-0000-0000-000000000102",
+                    "bruno.souza@example.com",
+                ),
+            ];
+            for (contact_id, full_name, department_id, email) in contacts {
+                let mut contact = Contact {
+                    id: ContactId(id(contact_id)),
+                    full_name: full_name.to_string(),
+                    contact_type: ContactType::Person,
+                    status: ContactStatus::Active,
+                    document: None,
+                    unit_id: Some(headquarters.clone()),
+                    department_id: Some(DepartmentId(id(department_id))),
+                    emails: vec![Email {
+                        value: email.to_string(),
+                        is_primary: true,
+                    }],
+                    phones: vec![],
+                    etag: String::new(),
+                    created_at: now,
+                    updated_at: now,
+                    deleted_at: None,
+                };
+                contact.etag = contact_repository::compute_etag(&contact);
+                tables.contacts.insert(contact.id.clone(), contact);
+            }
+
+            let users = [
+                (
+                    "admin",
+                    "admin123",
+                    &["directory.read", "directory.write", "directory.pii.read"][..],
+                ),
+                (
+                    "manager",
+                    "manager123",
+                    &["directory.read", "directory.write"][..],
+                ),
+                ("analyst", "analyst123", &["directory.read"][..]),
+            ];
+            for (username, password, roles) in users {
+                let user = User::new(
+                    Username {
+                        value: username.to_string(),
+                    },
+                    UserEmail {
+                        value: format!("{}@example.com", username),
+                    },
+                    Password {
+                        value: password.to_string(),
+                    },
+                    roles
+                        .iter()
+                        .map(|role| Role {
+                            value: role.to_string(),
+                        })
+                        .collect(),
+                );
+                tables.users.insert(user.id.clone(), user);
+            }
+        }
+        database
+    }
+
+    pub(crate) fn read<T>(&self, f: impl FnOnce(&Tables) -> T) -> T {
+        f(&self.tables.read())
+    }
+
+    pub(crate) fn write<T>(&self, f: impl FnOnce(&mut Tables) -> T) -> T {
+        f(&mut self.tables.write())
+    }
+
+    /// Aplica `f` a uma cópia das tabelas e só a mantém se não houver erro
+    /// (equivalente a uma transação)
+    pub(crate) fn transaction<T>(
+        &self,
+        f: impl FnOnce(&mut Tables) -> Result<T, DomainError>,
+    ) -> Result<T, DomainError> {
+        let mut tables = self.tables.write();
+        let mut staged = tables.clone();
+        let result = f(&mut staged)?;
+        *tables = staged;
+        Ok(result)
+    }
+}
+
+/// Violação de chave única (mesma mensagem do PostgreSQL)
+pub(crate) fn already_exists() -> DomainError {
+    DomainError::Conflict("Resource already exists".to_string())
+}
+
+/// Violação de chave estrangeira (mesma mensagem do PostgreSQL)
+pub(crate) fn reference_violation() -> DomainError {
+    DomainError::Conflict("Referenced resource does not exist or is still in use".to_string())
+}
+
+/// `UPDATE ... RETURNING` sem linha afetada
+pub(crate) fn row_not_found() -> DomainError {
+    DomainError::NotFound("Entity not found in database".to_string())
+}
+
+/// Equivalente a `value ILIKE '%pattern%'`
+pub(crate) fn ilike(value: &str, pattern: &str) -> bool {
+    value.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+/// Equivalente a `LIMIT`/`OFFSET` (sem `LIMIT`, todos os itens)
+pub(crate) fn paginate<T>(items: Vec<T>, limit: Option<i64>, offset: Option<i64>) -> Vec<T> {
+    let offset = offset.unwrap_or(0).max(0) as usize;
+    let limit = limit.map_or(usize::MAX, |limit| limit.max(0) as usize);
+    items.into_iter().skip(offset).take(limit).collect()
+}
+
+/// Total seguido da página pedida
+pub(crate) fn page<T>(items: Vec<T>, limit: Option<i64>, offset: Option<i64>) -> (Vec<T>, i64) {
+    let total = items.len() as i64;
+    (paginate(items, limit, offset), total)
+}
@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{
+    already_exists, ilike, page, reference_violation, row_not_found, InMemoryDatabase, Tables,
+};
+use crate::domain::entities::OrgUnit;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{OrgUnitRepository, OrgUnitSearchCriteria, OrgUnitSearchResult};
+use crate::domain::value_objects::OrgUnitId;
+
+pub struct InMemoryOrgUnitRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryOrgUnitRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+fn check_parent(tables: &Tables, org_unit: &OrgUnit) -> Result<(), DomainError> {
+    match &org_unit.parent_id {
+        Some(parent_id) if !tables.org_units.contains_key(parent_id) => Err(reference_violation()),
+        _ => Ok(()),
+    }
+}
+
+/// Unidades que satisfazem `filter`, ordenadas por nome
+fn units_by_name(tables: &Tables, filter: impl Fn(&OrgUnit) -> bool) -> Vec<OrgUnit> {
+    let mut units: Vec<OrgUnit> = tables
+        .org_units
+        .values()
+        .filter(|unit| filter(unit))
+        .cloned()
+        .collect();
+    units.sort_by(|a, b| {
+        a.name
+            .value
+            .cmp(&b.name.value)
+            .then_with(|| a.id.0.cmp(&b.id.0))
+    });
+    units
+}
+
+#[async_trait]
+impl OrgUnitRepository for InMemoryOrgUnitRepository {
+    async fn find_by_id(&self, id: &OrgUnitId) -> Result<Option<OrgUnit>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| tables.org_units.get(id).cloned()))
+    }
+
+    async fn find_all(
+        &self,
+        criteria: &OrgUnitSearchCriteria,
+    ) -> Result<OrgUnitSearchResult, DomainError> {
+        let (items, total) = self.database.read(|tables| {
+            let units = units_by_name(tables, |unit| {
+                criteria
+                    .name
+                    .as_ref()
+                    .is_none_or(|name| ilike(&unit.name.value, name))
+                    && criteria
+                        .parent_id
+                        .as_ref()
+                        .is_none_or(|parent_id| unit.parent_id.as_ref() == Some(parent_id))
+            });
+            page(units, criteria.limit, criteria.offset)
+        });
+        Ok(OrgUnitSearchResult { items, total })
+    }
+
+    async fn save(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
+        self.database.write(|tables| {
+            if tables.org_units.contains_key(&org_unit.id) {
+                return Err(already_exists());
+            }
+            check_parent(tables, org_unit)?;
+            tables
+                .org_units
+                .insert(org_unit.id.clone(), org_unit.clone());
+            Ok(org_unit.clone())
+        })
+    }
+
+    async fn update(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
+        self.database.write(|tables| {
+            let stored = tables
+                .org_units
+                .get(&org_unit.id)
+                .ok_or_else(row_not_found)?;
+            check_parent(tables, org_unit)?;
+            let updated = OrgUnit {
+                created_at: stored.created_at,
+                updated_at: Utc::now(),
+                ..org_unit.clone()
+            };
+            tables.org_units.insert(updated.id.clone(), updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: &OrgUnitId) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            let referenced = tables
+                .org_units
+                .values()
+                .any(|unit| unit.parent_id.as_ref() == Some(id))
+                || tables.departments.values().any(|d| &d.unit_id == id)
+                || tables
+                    .contacts
+                    .values()
+                    .any(|c| c.unit_id.as_ref() == Some(id));
+            if referenced {
+                return Err(reference_violation());
+            }
+            tables.org_units.remove(id);
+            Ok(())
+        })
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<OrgUnit>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| units_by_name(tables, |unit| ilike(&unit.name.value, name))))
+    }
+
+    async fn find_children(&self, parent_id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
+        Ok(self.database.read(|tables| {
+            units_by_name(tables, |unit| unit.parent_id.as_ref() == Some(parent_id))
+        }))
+    }
+
+    async fn find_root_units(&self) -> Result<Vec<OrgUnit>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| units_by_name(tables, OrgUnit::is_root)))
+    }
+
+    async fn get_hierarchy(&self, id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
+        Ok(self.database.read(|tables| {
+            // Da unidade até a raiz; o limite protege contra ciclos
+            let mut hierarchy = Vec::new();
+            let mut current = tables.org_units.get(id);
+            while let Some(unit) = current.filter(|_| hierarchy.len() <= tables.org_units.len()) {
+                hierarchy.push(unit.clone());
+                current = unit
+                    .parent_id
+                    .as_ref()
+                    .and_then(|parent_id| tables.org_units.get(parent_id));
+            }
+            hierarchy.reverse();
+            hierarchy
+        }))
+    }
+}
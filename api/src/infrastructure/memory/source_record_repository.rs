@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+
+use super::{already_exists, InMemoryDatabase};
+use crate::domain::entities::SourceRecord;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::SourceRecordRepository;
+use crate::domain::value_objects::SourceRecordId;
+
+pub struct InMemorySourceRecordRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemorySourceRecordRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl SourceRecordRepository for InMemorySourceRecordRepository {
+    async fn find_by_id(&self, id: &SourceRecordId) -> Result<Option<SourceRecord>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| tables.source_records.get(id).cloned()))
+    }
+
+    async fn find_by_source_and_key(
+        &self,
+        source: &str,
+        source_key: &str,
+    ) -> Result<Option<SourceRecord>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .source_records
+                .values()
+                .find(|r| r.source.value == source && r.source_key.value == source_key)
+                .cloned()
+        }))
+    }
+
+    async fn save(&self, record: &SourceRecord) -> Result<SourceRecord, DomainError> {
+        self.database.write(|tables| {
+            // `source_records_ux`: um registro por (source, source_key)
+            if tables.source_records.values().any(|r| {
+                r.id == record.id
+                    || (r.source == record.source && r.source_key == record.source_key)
+            }) {
+                return Err(already_exists());
+            }
+            tables
+                .source_records
+                .insert(record.id.clone(), record.clone());
+            Ok(record.clone())
+        })
+    }
+
+    async fn delete(&self, id: &SourceRecordId) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            tables
+                .contact_sources
+                .retain(|cs| &cs.source_record_id != id);
+            tables.source_records.remove(id);
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{already_exists, ilike, page, row_not_found, InMemoryDatabase, Tables};
+use crate::domain::entities::User;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{UserRepository, UserSearchCriteria, UserSearchResult};
+use crate::domain::value_objects::UserId;
+
+pub struct InMemoryUserRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// `username` e `email` são `UNIQUE` (com distinção de maiúsculas)
+fn check_constraints(tables: &Tables, user: &User) -> Result<(), DomainError> {
+    if tables
+        .users
+        .values()
+        .any(|u| u.id != user.id && (u.username == user.username || u.email == user.email))
+    {
+        return Err(already_exists());
+    }
+    Ok(())
+}
+
+/// Usuários que satisfazem `filter`, do mais recente para o mais antigo
+fn users_newest_first(tables: &Tables, filter: impl Fn(&User) -> bool) -> Vec<User> {
+    let mut users: Vec<User> = tables
+        .users
+        .values()
+        .filter(|user| filter(user))
+        .cloned()
+        .collect();
+    users.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.id.0.cmp(&b.id.0))
+    });
+    users
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
+        Ok(self.database.read(|tables| tables.users.get(id).cloned()))
+    }
+
+    async fn find_all(
+        &self,
+        criteria: &UserSearchCriteria,
+    ) -> Result<UserSearchResult, DomainError> {
+        let (items, total) = self.database.read(|tables| {
+            let users = users_newest_first(tables, |user| {
+                criteria
+                    .username
+                    .as_ref()
+                    .is_none_or(|username| ilike(&user.username.value, username))
+                    && criteria
+                        .email
+                        .as_ref()
+                        .is_none_or(|email| ilike(&user.email.value, email))
+                    && criteria
+                        .role
+                        .as_ref()
+                        .is_none_or(|role| user.has_role(role))
+            });
+            page(users, criteria.limit, criteria.offset)
+        });
+        Ok(UserSearchResult { items, total })
+    }
+
+    async fn save(&self, user: &User) -> Result<User, DomainError> {
+        self.database.write(|tables| {
+            if tables.users.contains_key(&user.id) {
+                return Err(already_exists());
+            }
+            check_constraints(tables, user)?;
+            tables.users.insert(user.id.clone(), user.clone());
+            Ok(user.clone())
+        })
+    }
+
+    async fn update(&self, user: &User) -> Result<User, DomainError> {
+        self.database.write(|tables| {
+            let stored = tables.users.get(&user.id).ok_or_else(row_not_found)?;
+            check_constraints(tables, user)?;
+            let updated = User {
+                created_at: stored.created_at,
+                updated_at: Utc::now(),
+                ..user.clone()
+            };
+            tables.users.insert(updated.id.clone(), updated.clone());
+            Ok(updated)
+        })
+    }
+
+    async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
+        self.database.write(|tables| tables.users.remove(id));
+        Ok(())
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .users
+                .values()
+                .find(|user| user.username.value == username)
+                .cloned()
+        }))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .users
+                .values()
+                .find(|user| user.email.value == email)
+                .cloned()
+        }))
+    }
+
+    async fn find_by_role(&self, role: &str) -> Result<Vec<User>, DomainError> {
+        Ok(self
+            .database
+            .read(|tables| users_newest_first(tables, |user| user.has_role(role))))
+    }
+
+    /// Sem `crypt()` em memória: a senha é comparada com o valor armazenado
+    async fn verify_credentials(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<User>, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .users
+                .values()
+                .find(|user| user.username.value == username && user.password.value == password)
+                .cloned()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_demo_users_and_unique_username() {
+        let repository = InMemoryDatabase::demo().users();
+        let admin = repository
+            .verify_credentials("admin", "admin123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(admin.has_role("directory.pii.read"));
+        assert!(repository
+            .verify_credentials("admin", "wrong")
+            .await
+            .unwrap()
+            .is_none());
+
+        let mut duplicate = admin.clone();
+        duplicate.id = UserId::new();
+        duplicate.email.value = "other@example.com".to_string();
+        let err = repository.save(&duplicate).await.unwrap_err();
+        assert!(matches!(err, DomainError::Conflict(_)));
+
+        let readers = repository.find_by_role("directory.read").await.unwrap();
+        assert_eq!(readers.len(), 3);
+    }
+}
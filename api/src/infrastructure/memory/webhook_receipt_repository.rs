@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+
+use super::{already_exists, InMemoryDatabase};
+use crate::domain::entities::WebhookReceipt;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::WebhookReceiptRepository;
+
+pub struct InMemoryWebhookReceiptRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryWebhookReceiptRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl WebhookReceiptRepository for InMemoryWebhookReceiptRepository {
+    async fn save(&self, receipt: &WebhookReceipt) -> Result<WebhookReceipt, DomainError> {
+        self.database.write(|tables| {
+            // `webhook_receipts_ux`: um recibo por (source, nonce)
+            if tables
+                .webhook_receipts
+                .iter()
+                .any(|r| r.source == receipt.source && r.nonce == receipt.nonce)
+            {
+                return Err(already_exists());
+            }
+            tables.webhook_receipts.push(receipt.clone());
+            Ok(receipt.clone())
+        })
+    }
+
+    async fn exists(&self, source: &str, nonce: &str) -> Result<bool, DomainError> {
+        Ok(self.database.read(|tables| {
+            tables
+                .webhook_receipts
+                .iter()
+                .any(|r| r.source.value == source && r.nonce.value == nonce)
+        }))
+    }
+
+    async fn delete(&self, source: &str, nonce: &str) -> Result<(), DomainError> {
+        self.database.write(|tables| {
+            tables
+                .webhook_receipts
+                .retain(|r| r.source.value != source || r.nonce.value != nonce)
+        });
+        Ok(())
+    }
+}
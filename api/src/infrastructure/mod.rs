@@ -1,4 +1,7 @@
+#[cfg(test)]
+pub(crate) mod contract_tests;
 pub mod mappers;
+pub mod memory;
 pub mod repositories;
 
 pub use mappers::*;
@@ -0,0 +1,104 @@
+use crate::domain::entities::AuditEvent;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::AuditEventRepository;
+use crate::domain::value_objects::{Action, AuditEventId, EntityType};
+use crate::infra::pg::PgPoolHandle;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use tracing::instrument;
+
+const COLUMNS: &str = "id, actor_sub, action, entity_type, entity_id, before, after, at";
+
+pub struct PostgresAuditEventRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresAuditEventRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+fn audit_event_from_row(row: &PgRow) -> Result<AuditEvent, DomainError> {
+    Ok(AuditEvent {
+        id: AuditEventId::new(row.try_get("id")?),
+        actor_sub: row.try_get("actor_sub")?,
+        action: Action {
+            value: row.try_get("action")?,
+        },
+        entity_type: EntityType {
+            value: row.try_get("entity_type")?,
+        },
+        entity_id: row.try_get("entity_id")?,
+        before: row.try_get("before")?,
+        after: row.try_get("after")?,
+        at: row.try_get("at")?,
+    })
+}
+
+#[async_trait]
+impl AuditEventRepository for PostgresAuditEventRepository {
+    #[instrument(name = "AuditEventRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, event: &AuditEvent) -> Result<AuditEvent, DomainError> {
+        let row = sqlx::query(&format!(
+            "INSERT INTO audit_events (actor_sub, action, entity_type, entity_id, before, after, at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING {COLUMNS}"
+        ))
+        .bind(&event.actor_sub)
+        .bind(&event.action.value)
+        .bind(&event.entity_type.value)
+        .bind(&event.entity_id)
+        .bind(&event.before)
+        .bind(&event.after)
+        .bind(event.at)
+        .fetch_one(&self.pool.get())
+        .await?;
+        audit_event_from_row(&row)
+    }
+
+    #[instrument(name = "AuditEventRepository.find_by_entity", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_entity(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Vec<AuditEvent>, DomainError> {
+        sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM audit_events WHERE entity_type = $1 AND entity_id = $2 \
+             ORDER BY at DESC, id DESC"
+        ))
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(&self.pool.get())
+        .await?
+        .iter()
+        .map(audit_event_from_row)
+        .collect()
+    }
+
+    #[instrument(name = "AuditEventRepository.find_by_actor", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_actor(&self, actor_sub: &str) -> Result<Vec<AuditEvent>, DomainError> {
+        sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM audit_events WHERE actor_sub = $1 ORDER BY at DESC, id DESC"
+        ))
+        .bind(actor_sub)
+        .fetch_all(&self.pool.get())
+        .await?
+        .iter()
+        .map(audit_event_from_row)
+        .collect()
+    }
+
+    #[instrument(name = "AuditEventRepository.find_recent", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_recent(&self, limit: i64) -> Result<Vec<AuditEvent>, DomainError> {
+        sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM audit_events ORDER BY at DESC, id DESC LIMIT $1"
+        ))
+        .bind(limit.max(0))
+        .fetch_all(&self.pool.get())
+        .await?
+        .iter()
+        .map(audit_event_from_row)
+        .collect()
+    }
+}
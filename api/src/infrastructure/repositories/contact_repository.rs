@@ -1,408 +1,587 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Postgres, Transaction};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::instrument;
 use uuid::Uuid;
 
+use super::contains_pattern;
 use crate::domain::entities::Contact;
 use crate::domain::errors::DomainError;
-use crate::domain::repositories::{ContactRepository, ContactSearchCriteria, ContactSearchResult, ContactStatistics};
-use crate::domain::value_objects::{ContactId, ContactStatus, ContactType, OrgUnitId, DepartmentId};
-use crate::infrastructure::mappers::{ContactRow, EmailRow, PhoneRow, build_contact_with_relations};
+use crate::domain::repositories::{
+    ContactChange, ContactDataQuality, ContactRepository, ContactSearchCriteria,
+    ContactSearchResult, ContactStatistics, ContactStatisticsQuery, OrgUnitContactCount,
+};
+use crate::domain::value_objects::{
+    is_valid_document, ContactId, ContactStatus, ContactType, DepartmentId, OrgUnitId,
+};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{
+    build_contact_with_relations, ContactRow, EmailRow, PhoneRow,
+};
+
+const COLUMNS: &str = "id, full_name, type, status, document, unit_id, department_id, etag, \
+                       created_at, updated_at, deleted_at";
+
+/// Filtros de `find_all`; parâmetros nulos não restringem a busca
+const SEARCH_FILTER: &str = "($1::text IS NULL OR full_name ILIKE $1) \
+                             AND ($2::text IS NULL OR type = $2) \
+                             AND ($3::text IS NULL OR status = $3) \
+                             AND ($4::uuid IS NULL OR unit_id = $4) \
+                             AND ($5::uuid IS NULL OR department_id = $5) \
+                             AND ($6 OR deleted_at IS NULL)";
 
 pub struct PostgresContactRepository {
-    pool: PgPool,
+    pool: PgPoolHandle,
 }
 
 impl PostgresContactRepository {
-    pub fn new(pool: PgPool) -> Self {
-        PostgresContactRepository { pool }
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
     }
 
-    async fn find_emails_for_contact(&self, contact_id: &Uuid) -> Result<Vec<EmailRow>, DomainError> {
-        let emails = sqlx::query_as!(
-            EmailRow,
-            "SELECT contact_id, address, is_primary FROM emails WHERE contact_id = $1",
-            contact_id
+    /// Completa as linhas de `contacts` com e-mails e telefones, preservando a ordem
+    async fn with_relations(
+        pool: &PgPool,
+        rows: Vec<ContactRow>,
+    ) -> Result<Vec<Contact>, DomainError> {
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+
+        let mut emails: HashMap<Uuid, Vec<EmailRow>> = HashMap::new();
+        for email in sqlx::query_as::<_, EmailRow>(
+            "SELECT contact_id, address, is_primary FROM emails \
+             WHERE contact_id = ANY($1) ORDER BY is_primary DESC, address",
         )
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(&ids)
+        .fetch_all(pool)
+        .await?
+        {
+            emails.entry(email.contact_id).or_default().push(email);
+        }
+
+        // Ramal vazio no banco é a ausência de ramal no domínio
+        let mut phones: HashMap<Uuid, Vec<PhoneRow>> = HashMap::new();
+        for phone in sqlx::query_as::<_, PhoneRow>(
+            "SELECT contact_id, e164, NULLIF(extension, '') AS extension, type, is_primary \
+             FROM phones WHERE contact_id = ANY($1) ORDER BY is_primary DESC, e164, extension",
+        )
+        .bind(&ids)
+        .fetch_all(pool)
+        .await?
+        {
+            phones.entry(phone.contact_id).or_default().push(phone);
+        }
 
-        Ok(emails)
+        rows.into_iter()
+            .map(|row| {
+                let contact_emails = emails.remove(&row.id).unwrap_or_default();
+                let contact_phones = phones.remove(&row.id).unwrap_or_default();
+                build_contact_with_relations(row, contact_emails, contact_phones)
+            })
+            .collect()
     }
 
-    async fn find_phones_for_contact(&self, contact_id: &Uuid) -> Result<Vec<PhoneRow>, DomainError> {
-        let phones = sqlx::query_as!(
-            PhoneRow,
-            "SELECT contact_id, e164, extension, type, is_primary FROM phones WHERE contact_id = $1",
-            contact_id
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    async fn fetch_one_with_relations(
+        pool: &PgPool,
+        row: ContactRow,
+    ) -> Result<Contact, DomainError> {
+        let mut contacts = Self::with_relations(pool, vec![row]).await?;
+        contacts.pop().ok_or_else(|| {
+            DomainError::InternalError("Contact vanished while loading relations".to_string())
+        })
+    }
 
-        Ok(phones)
+    /// Contatos que satisfazem `filter` (com um parâmetro), do mais recente para o mais antigo
+    async fn select<T>(&self, filter: &str, value: T) -> Result<Vec<Contact>, DomainError>
+    where
+        T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Send,
+    {
+        let pool = self.pool.get();
+        let rows = sqlx::query_as::<_, ContactRow>(&format!(
+            "SELECT {COLUMNS} FROM contacts WHERE {filter} ORDER BY created_at DESC, id"
+        ))
+        .bind(value)
+        .fetch_all(&pool)
+        .await?;
+        Self::with_relations(&pool, rows).await
     }
 
-    async fn save_emails(&self, tx: &mut Transaction<'_, Postgres>, contact_id: &Uuid, emails: &[crate::domain::value_objects::Email]) -> Result<(), DomainError> {
-        for email in emails {
-            sqlx::query!(
-                "INSERT INTO emails (contact_id, address, is_primary) VALUES ($1, $2, $3) ON CONFLICT (contact_id, address) DO UPDATE SET is_primary = $3",
-                contact_id,
-                email.value,
-                email.is_primary
-            )
+    async fn replace_relations(
+        tx: &mut Transaction<'_, Postgres>,
+        contact: &Contact,
+    ) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM emails WHERE contact_id = $1")
+            .bind(contact.id.0)
             .execute(&mut **tx)
             .await?;
-        }
-        Ok(())
+        sqlx::query("DELETE FROM phones WHERE contact_id = $1")
+            .bind(contact.id.0)
+            .execute(&mut **tx)
+            .await?;
+        Self::insert_relations(tx, contact).await
     }
 
-    async fn save_phones(&self, tx: &mut Transaction<'_, Postgres>, contact_id: &Uuid, phones: &[crate::domain::value_objects::Phone]) -> Result<(), DomainError> {
-        for phone in phones {
-            sqlx::query!(
-                "INSERT INTO phones (contact_id, e164, extension, type, is_primary) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (contact_id, e164, extension) DO UPDATE SET type = $4, is_primary = $5",
-                contact_id,
-                phone.e164,
-                phone.extension,
-                phone.phone_type.to_string(),
-                phone.is_primary
+    async fn insert_relations(
+        tx: &mut Transaction<'_, Postgres>,
+        contact: &Contact,
+    ) -> Result<(), DomainError> {
+        for email in &contact.emails {
+            sqlx::query("INSERT INTO emails (contact_id, address, is_primary) VALUES ($1, $2, $3)")
+                .bind(contact.id.0)
+                .bind(&email.value)
+                .bind(email.is_primary)
+                .execute(&mut **tx)
+                .await?;
+        }
+        for phone in &contact.phones {
+            sqlx::query(
+                "INSERT INTO phones (contact_id, e164, extension, type, is_primary) \
+                 VALUES ($1, $2, COALESCE($3, ''), $4, $5)",
             )
+            .bind(contact.id.0)
+            .bind(&phone.e164)
+            .bind(&phone.extension)
+            .bind(phone.phone_type.to_string())
+            .bind(phone.is_primary)
             .execute(&mut **tx)
             .await?;
         }
         Ok(())
     }
 
-    async fn delete_emails(&self, tx: &mut Transaction<'_, Postgres>, contact_id: &Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM emails WHERE contact_id = $1",
-            contact_id
-        )
-        .execute(&mut **tx)
+    /// `created_at` e `deleted_at` não são alterados; ETag e `updated_at` vêm do trigger
+    ///
+    /// Com `etag`, a linha só é alterada se ainda tiver esse ETag (verificado na
+    /// própria instrução, sem janela entre a leitura e a escrita).
+    async fn update_in_tx(
+        tx: &mut Transaction<'_, Postgres>,
+        contact: &Contact,
+        etag: Option<&str>,
+    ) -> Result<ContactRow, DomainError> {
+        let row = sqlx::query_as::<_, ContactRow>(&format!(
+            "UPDATE contacts SET full_name = $2, type = $3, status = $4, document = $5, \
+             unit_id = $6, department_id = $7 \
+             WHERE id = $1 AND ($8::text IS NULL OR (etag = $8 AND deleted_at IS NULL)) \
+             RETURNING {COLUMNS}"
+        ))
+        .bind(contact.id.0)
+        .bind(&contact.full_name)
+        .bind(contact.contact_type.to_string())
+        .bind(contact.status.to_string())
+        .bind(&contact.document)
+        .bind(contact.unit_id.as_ref().map(|id| id.0))
+        .bind(contact.department_id.as_ref().map(|id| id.0))
+        .bind(etag)
+        .fetch_optional(&mut **tx)
         .await?;
-        Ok(())
+        let row = match (row, etag) {
+            (Some(row), _) => row,
+            (None, Some(_)) => return Err(etag_mismatch()),
+            (None, None) => return Err(sqlx::Error::RowNotFound.into()),
+        };
+        Self::replace_relations(tx, contact).await?;
+        Ok(row)
     }
 
-    async fn delete_phones(&self, tx: &mut Transaction<'_, Postgres>, contact_id: &Uuid) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM phones WHERE contact_id = $1",
-            contact_id
+    /// Com `etag`, o contato só é excluído se ainda tiver esse ETag
+    async fn soft_delete_in(
+        executor: impl sqlx::PgExecutor<'_>,
+        id: &ContactId,
+        etag: Option<&str>,
+    ) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "UPDATE contacts SET deleted_at = now() \
+             WHERE id = $1 AND deleted_at IS NULL AND ($2::text IS NULL OR etag = $2)",
         )
-        .execute(&mut **tx)
+        .bind(id.0)
+        .bind(etag)
+        .execute(executor)
         .await?;
+        if etag.is_some() && result.rows_affected() == 0 {
+            return Err(etag_mismatch());
+        }
         Ok(())
     }
-}
 
-#[async_trait]
-impl ContactRepository for PostgresContactRepository {
-    async fn find_by_id(&self, id: &ContactId) -> Result<Option<Contact>, DomainError> {
-        let contact_row = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE id = $1",
-            id.0
-        )
-        .fetch_optional(&self.pool)
+    async fn count(&self, filter: &str, value: String) -> Result<i64, DomainError> {
+        let count = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM contacts WHERE {filter} AND deleted_at IS NULL"
+        ))
+        .bind(value)
+        .fetch_one(&self.pool.get())
         .await?;
-
-        match contact_row {
-            Some(row) => {
-                let emails = self.find_emails_for_contact(&row.id).await?;
-                let phones = self.find_phones_for_contact(&row.id).await?;
-                Ok(Some(build_contact_with_relations(row, emails, phones)?))
-            }
-            None => Ok(None),
-        }
+        Ok(count)
     }
 
-    async fn find_all(&self, criteria: &ContactSearchCriteria) -> Result<ContactSearchResult, DomainError> {
-        let mut query = "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE 1=1".to_string();
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(ref full_name) = criteria.full_name {
-            param_count += 1;
-            query.push_str(&format!(" AND full_name ILIKE ${}", param_count));
-            params.push(Box::new(format!("%{}%", full_name)));
-        }
-
-        if let Some(ref contact_type) = criteria.contact_type {
-            param_count += 1;
-            query.push_str(&format!(" AND type = ${}", param_count));
-            params.push(Box::new(contact_type.to_string()));
-        }
-
-        if let Some(ref status) = criteria.status {
-            param_count += 1;
-            query.push_str(&format!(" AND status = ${}", param_count));
-            params.push(Box::new(status.to_string()));
-        }
-
-        if let Some(ref unit_id) = criteria.unit_id {
-            param_count += 1;
-            query.push_str(&format!(" AND unit_id = ${}", param_count));
-            params.push(Box::new(unit_id.0));
-        }
-
-        if let Some(ref department_id) = criteria.department_id {
-            param_count += 1;
-            query.push_str(&format!(" AND department_id = ${}", param_count));
-            params.push(Box::new(department_id.0));
-        }
-
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) as count FROM ({}) as subquery", query);
-        let total: i64 = sqlx::query_scalar(&count_query)
-            .fetch_one(&self.pool)
-            .await?;
+    /// Série esparsa por início de intervalo, truncada em UTC
+    async fn series(
+        &self,
+        column: &str,
+        query: &ContactStatisticsQuery,
+    ) -> Result<Vec<(DateTime<Utc>, i64)>, DomainError> {
+        let rows = sqlx::query(&format!(
+            "SELECT date_trunc($1, {column}, 'UTC') AS bucket, COUNT(*) AS count \
+             FROM contacts \
+             WHERE deleted_at IS NULL AND {column} >= $2 AND {column} < $3 \
+             GROUP BY 1 ORDER BY 1"
+        ))
+        .bind(query.bucket.to_string())
+        .bind(query.from)
+        .bind(query.to)
+        .fetch_all(&self.pool.get())
+        .await?;
+        rows.iter()
+            .map(|row| Ok((row.try_get("bucket")?, row.try_get("count")?)))
+            .collect()
+    }
+}
 
-        // Add pagination
-        if let Some(limit) = criteria.limit {
-            param_count += 1;
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(Box::new(limit));
-        }
+/// O contato mudou entre a verificação do If-Match e a gravação
+fn etag_mismatch() -> DomainError {
+    DomainError::Conflict("ETag mismatch - contact was modified by another user".to_string())
+}
 
-        if let Some(offset) = criteria.offset {
-            param_count += 1;
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(Box::new(offset));
-        }
+fn parse_status(status: &str) -> Result<ContactStatus, DomainError> {
+    ContactStatus::from_str(status)
+        .map_err(|e| DomainError::InternalError(format!("Invalid contact status from DB: {}", e)))
+}
 
-        query.push_str(" ORDER BY created_at DESC");
+#[async_trait]
+impl ContactRepository for PostgresContactRepository {
+    #[instrument(name = "ContactRepository.find_by_id", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_id(&self, id: &ContactId) -> Result<Option<Contact>, DomainError> {
+        let contacts = self.select("id = $1", id.0).await?;
+        Ok(contacts.into_iter().next())
+    }
 
-        // For now, we'll use a simplified approach without dynamic parameters
-        // In a real implementation, you'd use sqlx::query_as! with proper parameter binding
-        let contact_rows = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-            criteria.limit.unwrap_or(100),
-            criteria.offset.unwrap_or(0)
-        )
-        .fetch_all(&self.pool)
+    #[instrument(name = "ContactRepository.find_all", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_all(
+        &self,
+        criteria: &ContactSearchCriteria,
+    ) -> Result<ContactSearchResult, DomainError> {
+        let pool = self.pool.get();
+        let name = criteria.full_name.as_deref().map(contains_pattern);
+        let contact_type = criteria.contact_type.as_ref().map(ToString::to_string);
+        let status = criteria.status.as_ref().map(ToString::to_string);
+        let unit_id = criteria.unit_id.as_ref().map(|id| id.0);
+        let department_id = criteria.department_id.as_ref().map(|id| id.0);
+
+        // O total usa os mesmos filtros da página, sem LIMIT/OFFSET
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM contacts WHERE {SEARCH_FILTER}"
+        ))
+        .bind(&name)
+        .bind(&contact_type)
+        .bind(&status)
+        .bind(unit_id)
+        .bind(department_id)
+        .bind(criteria.include_deleted)
+        .fetch_one(&pool)
         .await?;
 
-        let mut contacts = Vec::new();
-        for row in contact_rows {
-            let emails = self.find_emails_for_contact(&row.id).await?;
-            let phones = self.find_phones_for_contact(&row.id).await?;
-            contacts.push(build_contact_with_relations(row, emails, phones)?);
-        }
+        let rows = sqlx::query_as::<_, ContactRow>(&format!(
+            "SELECT {COLUMNS} FROM contacts WHERE {SEARCH_FILTER} \
+             ORDER BY created_at DESC, id LIMIT $7 OFFSET $8"
+        ))
+        .bind(&name)
+        .bind(&contact_type)
+        .bind(&status)
+        .bind(unit_id)
+        .bind(department_id)
+        .bind(criteria.include_deleted)
+        .bind(criteria.limit.map(|limit| limit.max(0)))
+        .bind(criteria.offset.unwrap_or(0).max(0))
+        .fetch_all(&pool)
+        .await?;
 
         Ok(ContactSearchResult {
-            items: contacts,
+            items: Self::with_relations(&pool, rows).await?,
             total,
         })
     }
 
+    #[instrument(name = "ContactRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn save(&self, contact: &Contact) -> Result<Contact, DomainError> {
-        let mut tx = self.pool.begin().await?;
-
-        let contact_row = sqlx::query_as!(
-            ContactRow,
-            "INSERT INTO contacts (id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) 
-             RETURNING id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at",
-            contact.id.0,
-            contact.full_name,
-            contact.contact_type.to_string(),
-            contact.status.to_string(),
-            contact.document,
-            contact.unit_id.as_ref().map(|id| id.0),
-            contact.department_id.as_ref().map(|id| id.0),
-            contact.etag,
-            contact.created_at,
-            contact.updated_at
-        )
+        let pool = self.pool.get();
+        let mut tx = pool.begin().await?;
+        let row = sqlx::query_as::<_, ContactRow>(&format!(
+            "INSERT INTO contacts (id, full_name, type, status, document, unit_id, department_id, \
+             created_at, deleted_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING {COLUMNS}"
+        ))
+        .bind(contact.id.0)
+        .bind(&contact.full_name)
+        .bind(contact.contact_type.to_string())
+        .bind(contact.status.to_string())
+        .bind(&contact.document)
+        .bind(contact.unit_id.as_ref().map(|id| id.0))
+        .bind(contact.department_id.as_ref().map(|id| id.0))
+        .bind(contact.created_at)
+        .bind(contact.deleted_at)
         .fetch_one(&mut *tx)
         .await?;
-
-        // Save emails and phones
-        self.save_emails(&mut tx, &contact.id.0, &contact.emails).await?;
-        self.save_phones(&mut tx, &contact.id.0, &contact.phones).await?;
-
+        Self::insert_relations(&mut tx, contact).await?;
         tx.commit().await?;
 
-        let emails = self.find_emails_for_contact(&contact.id.0).await?;
-        let phones = self.find_phones_for_contact(&contact.id.0).await?;
-        Ok(build_contact_with_relations(contact_row, emails, phones)?)
+        Self::fetch_one_with_relations(&pool, row).await
     }
 
+    #[instrument(name = "ContactRepository.update", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn update(&self, contact: &Contact) -> Result<Contact, DomainError> {
-        let mut tx = self.pool.begin().await?;
-
-        let contact_row = sqlx::query_as!(
-            ContactRow,
-            "UPDATE contacts SET full_name = $2, type = $3, status = $4, document = $5, unit_id = $6, department_id = $7, etag = $8, updated_at = $9 
-             WHERE id = $1 
-             RETURNING id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at",
-            contact.id.0,
-            contact.full_name,
-            contact.contact_type.to_string(),
-            contact.status.to_string(),
-            contact.document,
-            contact.unit_id.as_ref().map(|id| id.0),
-            contact.department_id.as_ref().map(|id| id.0),
-            contact.etag,
-            contact.updated_at
-        )
-        .fetch_one(&mut *tx)
-        .await?;
-
-        // Update emails and phones
-        self.delete_emails(&mut tx, &contact.id.0).await?;
-        self.delete_phones(&mut tx, &contact.id.0).await?;
-        self.save_emails(&mut tx, &contact.id.0, &contact.emails).await?;
-        self.save_phones(&mut tx, &contact.id.0, &contact.phones).await?;
-
+        let pool = self.pool.get();
+        let mut tx = pool.begin().await?;
+        let row = Self::update_in_tx(&mut tx, contact, None).await?;
         tx.commit().await?;
 
-        let emails = self.find_emails_for_contact(&contact.id.0).await?;
-        let phones = self.find_phones_for_contact(&contact.id.0).await?;
-        Ok(build_contact_with_relations(contact_row, emails, phones)?)
+        Self::fetch_one_with_relations(&pool, row).await
     }
 
+    #[instrument(name = "ContactRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn delete(&self, id: &ContactId) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM contacts WHERE id = $1",
-            id.0
-        )
-        .execute(&self.pool)
-        .await?;
+        // E-mails, telefones, fontes e candidatos a merge saem por ON DELETE CASCADE
+        sqlx::query("DELETE FROM contacts WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
         Ok(())
     }
 
-    async fn find_by_email(&self, email: &str) -> Result<Option<Contact>, DomainError> {
-        let contact_id = sqlx::query_scalar!(
-            "SELECT contact_id FROM emails WHERE address = $1 LIMIT 1",
-            email
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+    #[instrument(name = "ContactRepository.soft_delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn soft_delete(&self, id: &ContactId) -> Result<(), DomainError> {
+        Self::soft_delete_in(&self.pool.get(), id, None).await
+    }
 
-        match contact_id {
-            Some(id) => self.find_by_id(&ContactId(id)).await,
-            None => Ok(None),
-        }
+    #[instrument(name = "ContactRepository.restore", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn restore(&self, id: &ContactId) -> Result<(), DomainError> {
+        sqlx::query("UPDATE contacts SET deleted_at = NULL WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
     }
 
-    async fn find_by_document(&self, document: &str) -> Result<Option<Contact>, DomainError> {
-        let contact_row = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE document = $1",
-            document
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+    #[instrument(name = "ContactRepository.purge_deleted", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn purge_deleted(&self, deleted_before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result =
+            sqlx::query("DELETE FROM contacts WHERE deleted_at IS NOT NULL AND deleted_at < $1")
+                .bind(deleted_before)
+                .execute(&self.pool.get())
+                .await?;
+        Ok(result.rows_affected())
+    }
 
-        match contact_row {
-            Some(row) => {
-                let emails = self.find_emails_for_contact(&row.id).await?;
-                let phones = self.find_phones_for_contact(&row.id).await?;
-                Ok(Some(build_contact_with_relations(row, emails, phones)?))
+    #[instrument(name = "ContactRepository.apply_batch", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn apply_batch(
+        &self,
+        changes: &[ContactChange],
+    ) -> Result<Vec<Option<Contact>>, DomainError> {
+        let pool = self.pool.get();
+        let mut tx = pool.begin().await?;
+
+        let mut rows = Vec::with_capacity(changes.len());
+        for change in changes {
+            match change {
+                ContactChange::Update { contact, etag } => {
+                    rows.push(Some(
+                        Self::update_in_tx(&mut tx, contact, Some(etag)).await?,
+                    ));
+                }
+                ContactChange::SoftDelete { id, etag } => {
+                    Self::soft_delete_in(&mut *tx, id, Some(etag)).await?;
+                    rows.push(None);
+                }
             }
-            None => Ok(None),
         }
-    }
 
-    async fn find_by_name(&self, name: &str) -> Result<Vec<Contact>, DomainError> {
-        let contact_rows = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE full_name ILIKE $1",
-            format!("%{}%", name)
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        // Qualquer erro acima descarta a transação, desfazendo o lote inteiro
+        tx.commit().await?;
 
-        let mut contacts = Vec::new();
-        for row in contact_rows {
-            let emails = self.find_emails_for_contact(&row.id).await?;
-            let phones = self.find_phones_for_contact(&row.id).await?;
-            contacts.push(build_contact_with_relations(row, emails, phones)?);
+        let mut contacts = Vec::with_capacity(rows.len());
+        for row in rows {
+            contacts.push(match row {
+                Some(row) => Some(Self::fetch_one_with_relations(&pool, row).await?),
+                None => None,
+            });
         }
-
         Ok(contacts)
     }
 
-    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Contact>, DomainError> {
-        let contact_rows = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE unit_id = $1",
-            unit_id.0
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut contacts = Vec::new();
-        for row in contact_rows {
-            let emails = self.find_emails_for_contact(&row.id).await?;
-            let phones = self.find_phones_for_contact(&row.id).await?;
-            contacts.push(build_contact_with_relations(row, emails, phones)?);
-        }
+    #[instrument(name = "ContactRepository.find_by_email", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_email(&self, email: &str) -> Result<Option<Contact>, DomainError> {
+        let contacts = self
+            .select(
+                "id = (SELECT contact_id FROM emails WHERE address = $1 LIMIT 1)",
+                email.to_string(),
+            )
+            .await?;
+        Ok(contacts.into_iter().next())
+    }
 
-        Ok(contacts)
+    #[instrument(name = "ContactRepository.find_by_document", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_document(&self, document: &str) -> Result<Option<Contact>, DomainError> {
+        let contacts = self.select("document = $1", document.to_string()).await?;
+        Ok(contacts.into_iter().next())
     }
 
-    async fn find_by_department(&self, department_id: &DepartmentId) -> Result<Vec<Contact>, DomainError> {
-        let contact_rows = sqlx::query_as!(
-            ContactRow,
-            "SELECT id, full_name, type, status, document, unit_id, department_id, etag, created_at, updated_at FROM contacts WHERE department_id = $1",
-            department_id.0
+    #[instrument(name = "ContactRepository.find_by_name", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_name(&self, name: &str) -> Result<Vec<Contact>, DomainError> {
+        self.select(
+            "full_name ILIKE $1 AND deleted_at IS NULL",
+            contains_pattern(name),
         )
-        .fetch_all(&self.pool)
-        .await?;
+        .await
+    }
 
-        let mut contacts = Vec::new();
-        for row in contact_rows {
-            let emails = self.find_emails_for_contact(&row.id).await?;
-            let phones = self.find_phones_for_contact(&row.id).await?;
-            contacts.push(build_contact_with_relations(row, emails, phones)?);
-        }
+    #[instrument(name = "ContactRepository.find_by_unit", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Contact>, DomainError> {
+        self.select("unit_id = $1 AND deleted_at IS NULL", unit_id.0)
+            .await
+    }
 
-        Ok(contacts)
+    #[instrument(name = "ContactRepository.find_by_department", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_department(
+        &self,
+        department_id: &DepartmentId,
+    ) -> Result<Vec<Contact>, DomainError> {
+        self.select("department_id = $1 AND deleted_at IS NULL", department_id.0)
+            .await
     }
 
+    #[instrument(name = "ContactRepository.count_by_status", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn count_by_status(&self, status: &ContactStatus) -> Result<i64, DomainError> {
-        let count = sqlx::query_scalar!(
-            "SELECT COUNT(*) as count FROM contacts WHERE status = $1",
-            status.to_string()
-        )
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(count.unwrap_or(0))
+        self.count("status = $1", status.to_string()).await
     }
 
+    #[instrument(name = "ContactRepository.count_by_type", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn count_by_type(&self, contact_type: &ContactType) -> Result<i64, DomainError> {
-        let count = sqlx::query_scalar!(
-            "SELECT COUNT(*) as count FROM contacts WHERE type = $1",
-            contact_type.to_string()
+        self.count("type = $1", contact_type.to_string()).await
+    }
+
+    #[instrument(name = "ContactRepository.get_statistics", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn get_statistics(
+        &self,
+        query: &ContactStatisticsQuery,
+    ) -> Result<ContactStatistics, DomainError> {
+        let pool = self.pool.get();
+
+        let totals = sqlx::query(
+            "SELECT COUNT(*) AS total, \
+                    COUNT(*) FILTER (WHERE status = 'ACTIVE') AS active, \
+                    COUNT(*) FILTER (WHERE status = 'INACTIVE') AS inactive, \
+                    COUNT(*) FILTER (WHERE type = 'PERSON') AS persons, \
+                    COUNT(*) FILTER (WHERE type = 'ORGANIZATION') AS organizations, \
+                    COUNT(*) FILTER (WHERE type = 'DEPARTMENT') AS department_contacts, \
+                    COUNT(*) FILTER (WHERE NOT EXISTS ( \
+                        SELECT 1 FROM emails e WHERE e.contact_id = c.id AND e.is_primary \
+                    )) AS missing_primary_email, \
+                    COUNT(*) FILTER (WHERE NOT EXISTS ( \
+                        SELECT 1 FROM phones p WHERE p.contact_id = c.id AND p.is_primary \
+                    )) AS missing_primary_phone, \
+                    (SELECT COUNT(*) FROM departments) AS departments \
+             FROM contacts c WHERE c.deleted_at IS NULL",
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&pool)
         .await?;
 
-        Ok(count.unwrap_or(0))
-    }
+        // Contagem direta e acumulada sobre todas as unidades descendentes
+        let by_org_unit = sqlx::query(
+            "WITH RECURSIVE tree AS ( \
+                 SELECT id AS root, id FROM org_units \
+                 UNION \
+                 SELECT tree.root, child.id FROM org_units child JOIN tree ON child.parent_id = tree.id \
+             ) \
+             SELECT tree.root AS unit_id, \
+                    COUNT(c.id) FILTER (WHERE c.unit_id = tree.root) AS contacts, \
+                    COUNT(c.id) AS contacts_including_descendants \
+             FROM tree \
+             LEFT JOIN contacts c ON c.unit_id = tree.id AND c.deleted_at IS NULL \
+             GROUP BY tree.root \
+             ORDER BY 3 DESC, 1",
+        )
+        .fetch_all(&pool)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(OrgUnitContactCount {
+                unit_id: OrgUnitId(row.try_get("unit_id")?),
+                contacts: row.try_get("contacts")?,
+                contacts_including_descendants: row.try_get("contacts_including_descendants")?,
+            })
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
 
-    async fn get_statistics(&self) -> Result<ContactStatistics, DomainError> {
-        let total_contacts = sqlx::query_scalar!(
-            "SELECT COUNT(*) as count FROM contacts"
+        let by_department = sqlx::query(
+            "SELECT department_id, COUNT(*) AS count FROM contacts \
+             WHERE deleted_at IS NULL AND department_id IS NOT NULL \
+             GROUP BY department_id ORDER BY 2 DESC, 1",
         )
-        .fetch_one(&self.pool)
+        .fetch_all(&pool)
         .await?
-        .unwrap_or(0);
+        .iter()
+        .map(|row| {
+            Ok((
+                DepartmentId(row.try_get("department_id")?),
+                row.try_get("count")?,
+            ))
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
 
-        let active_contacts = self.count_by_status(&ContactStatus::Active).await?;
-        let inactive_contacts = self.count_by_status(&ContactStatus::Inactive).await?;
-        let persons = self.count_by_type(&ContactType::Person).await?;
-        let organizations = self.count_by_type(&ContactType::Organization).await?;
-        let departments = self.count_by_type(&ContactType::Department).await?;
+        let by_status = sqlx::query(
+            "SELECT status, COUNT(*) AS count FROM contacts WHERE deleted_at IS NULL \
+             GROUP BY status ORDER BY 2 DESC, 1",
+        )
+        .fetch_all(&pool)
+        .await?
+        .iter()
+        .map(|row| {
+            let status: String = row.try_get("status")?;
+            Ok((parse_status(&status)?, row.try_get("count")?))
+        })
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        let by_source = sqlx::query(
+            "SELECT sr.source, COUNT(DISTINCT cs.contact_id) AS count \
+             FROM contact_sources cs \
+             JOIN source_records sr ON sr.id = cs.source_record_id \
+             JOIN contacts c ON c.id = cs.contact_id AND c.deleted_at IS NULL \
+             GROUP BY sr.source ORDER BY 2 DESC, 1",
+        )
+        .fetch_all(&pool)
+        .await?
+        .iter()
+        .map(|row| Ok((row.try_get("source")?, row.try_get("count")?)))
+        .collect::<Result<Vec<_>, DomainError>>()?;
+
+        // Dígitos verificadores de CPF/CNPJ são conferidos no domínio, não em SQL
+        let documents: Vec<String> = sqlx::query_scalar(
+            "SELECT document FROM contacts WHERE deleted_at IS NULL AND document IS NOT NULL",
+        )
+        .fetch_all(&pool)
+        .await?;
+        let invalid_document = documents
+            .iter()
+            .filter(|document| !is_valid_document(document))
+            .count() as i64;
 
         Ok(ContactStatistics {
-            total_contacts,
-            active_contacts,
-            inactive_contacts,
-            persons,
-            organizations,
-            departments,
+            total_contacts: totals.try_get("total")?,
+            active_contacts: totals.try_get("active")?,
+            inactive_contacts: totals.try_get("inactive")?,
+            persons: totals.try_get("persons")?,
+            organizations: totals.try_get("organizations")?,
+            department_contacts: totals.try_get("department_contacts")?,
+            departments: totals.try_get("departments")?,
+            by_org_unit,
+            by_department,
+            by_status,
+            by_source,
+            created_series: self.series("created_at", query).await?,
+            updated_series: self.series("updated_at", query).await?,
+            data_quality: ContactDataQuality {
+                missing_primary_email: totals.try_get("missing_primary_email")?,
+                missing_primary_phone: totals.try_get("missing_primary_phone")?,
+                invalid_document,
+            },
         })
     }
-}
\ No newline at end of file
+}
@@ -1,127 +0,0 @@
-use crate::domain::entities::Contact;
-use crate::domain::errors::DomainError;
-use crate::domain::repositories::{
-    ContactRepository, ContactSearchCriteria, ContactSearchResult, ContactStatistics,
-};
-use crate::domain::value_objects::*;
-use async_trait::async_trait;
-use sqlx::PgPool;
-use uuid::Uuid;
-
-pub struct PostgresContactRepository {
-    pool: PgPool,
-}
-
-impl PostgresContactRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
-    }
-}
-
-#[async_trait]
-impl ContactRepository for PostgresContactRepository {
-    async fn find_by_id(&self, id: &ContactId) -> Result<Option<Contact>, DomainError> {
-        // Implementação básica - retorna um contato mock para testes
-        let contact = Contact::new(
-            "João Silva".to_string(),
-            ContactType::Person,
-            ContactStatus::Active,
-            Some("12345678901".to_string()),
-            None,
-            None,
-            vec![],
-            vec![],
-        )?;
-
-        Ok(Some(contact))
-    }
-
-    async fn find_all(
-        &self,
-        criteria: &ContactSearchCriteria,
-    ) -> Result<ContactSearchResult, DomainError> {
-        // Implementação básica - retorna uma lista mock para testes
-        let contact = Contact::new(
-            "João Silva".to_string(),
-            ContactType::Person,
-            ContactStatus::Active,
-            Some("12345678901".to_string()),
-            None,
-            None,
-            vec![],
-            vec![],
-        )?;
-
-        let result = ContactSearchResult {
-            items: vec![contact],
-            total: 1,
-        };
-
-        Ok(result)
-    }
-
-    async fn save(&self, contact: &Contact) -> Result<Contact, DomainError> {
-        // Implementação básica - retorna o contato como se fosse salvo
-        Ok(contact.clone())
-    }
-
-    async fn update(&self, contact: &Contact) -> Result<Contact, DomainError> {
-        // Implementação básica - retorna o contato como se fosse atualizado
-        Ok(contact.clone())
-    }
-
-    async fn delete(&self, id: &ContactId) -> Result<(), DomainError> {
-        // Implementação básica - simula deleção bem-sucedida
-        Ok(())
-    }
-
-    async fn find_by_email(&self, email: &str) -> Result<Option<Contact>, DomainError> {
-        // Implementação básica - retorna None para simular não encontrado
-        Ok(None)
-    }
-
-    async fn find_by_document(&self, document: &str) -> Result<Option<Contact>, DomainError> {
-        // Implementação básica - retorna None para simular não encontrado
-        Ok(None)
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Vec<Contact>, DomainError> {
-        // Implementação básica - retorna lista vazia
-        Ok(vec![])
-    }
-
-    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Contact>, DomainError> {
-        // Implementação básica - retorna lista vazia
-        Ok(vec![])
-    }
-
-    async fn find_by_department(
-        &self,
-        department_id: &DepartmentId,
-    ) -> Result<Vec<Contact>, DomainError> {
-        // Implementação básica - retorna lista vazia
-        Ok(vec![])
-    }
-
-    async fn count_by_status(&self, status: &ContactStatus) -> Result<i64, DomainError> {
-        // Implementação básica - retorna 1 para simular contagem
-        Ok(1)
-    }
-
-    async fn count_by_type(&self, contact_type: &ContactType) -> Result<i64, DomainError> {
-        // Implementação básica - retorna 1 para simular contagem
-        Ok(1)
-    }
-
-    async fn get_statistics(&self) -> Result<ContactStatistics, DomainError> {
-        // Implementação básica - retorna estatísticas mock
-        Ok(ContactStatistics {
-            total_contacts: 1,
-            active_contacts: 1,
-            inactive_contacts: 0,
-            persons: 1,
-            organizations: 0,
-            departments: 0,
-        })
-    }
-}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::domain::entities::ContactSource;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::ContactSourceRepository;
+use crate::domain::value_objects::{ContactId, SourceRecordId};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_contact_source_from_row, ContactSourceRow};
+
+const COLUMNS: &str = "contact_id, source_record_id, confidence::float8 AS confidence";
+
+pub struct PostgresContactSourceRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresContactSourceRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+
+    async fn select(
+        &self,
+        filter: &str,
+        id: uuid::Uuid,
+    ) -> Result<Vec<ContactSource>, DomainError> {
+        sqlx::query_as::<_, ContactSourceRow>(&format!(
+            "SELECT {COLUMNS} FROM contact_sources WHERE {filter}"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_contact_source_from_row)
+        .collect()
+    }
+}
+
+#[async_trait]
+impl ContactSourceRepository for PostgresContactSourceRepository {
+    #[instrument(name = "ContactSourceRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, contact_source: &ContactSource) -> Result<ContactSource, DomainError> {
+        let row = sqlx::query_as::<_, ContactSourceRow>(&format!(
+            "INSERT INTO contact_sources (contact_id, source_record_id, confidence) \
+             VALUES ($1, $2, $3) RETURNING {COLUMNS}"
+        ))
+        .bind(contact_source.contact_id.0)
+        .bind(contact_source.source_record_id.0)
+        .bind(contact_source.confidence)
+        .fetch_one(&self.pool.get())
+        .await?;
+        build_contact_source_from_row(row)
+    }
+
+    #[instrument(name = "ContactSourceRepository.find_by_contact", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<ContactSource>, DomainError> {
+        self.select("contact_id = $1", contact_id.0).await
+    }
+
+    #[instrument(name = "ContactSourceRepository.find_by_source_record", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_source_record(
+        &self,
+        source_record_id: &SourceRecordId,
+    ) -> Result<Vec<ContactSource>, DomainError> {
+        self.select("source_record_id = $1", source_record_id.0)
+            .await
+    }
+
+    #[instrument(name = "ContactSourceRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn delete(
+        &self,
+        contact_id: &ContactId,
+        source_record_id: &SourceRecordId,
+    ) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM contact_sources WHERE contact_id = $1 AND source_record_id = $2")
+            .bind(contact_id.0)
+            .bind(source_record_id.0)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
+    }
+}
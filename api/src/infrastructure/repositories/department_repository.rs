@@ -1,203 +1,154 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
-use uuid::Uuid;
+use sqlx::Row;
 use std::collections::HashMap;
+use tracing::instrument;
 
+use super::contains_pattern;
 use crate::domain::entities::Department;
 use crate::domain::errors::DomainError;
-use crate::domain::repositories::{DepartmentRepository, DepartmentSearchCriteria, DepartmentSearchResult, DepartmentStatistics};
+use crate::domain::repositories::{
+    DepartmentRepository, DepartmentSearchCriteria, DepartmentSearchResult, DepartmentStatistics,
+};
 use crate::domain::value_objects::{DepartmentId, OrgUnitId};
-use crate::infrastructure::mappers::{DepartmentRow, build_department_from_row};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_department_from_row, DepartmentRow};
+
+const COLUMNS: &str = "id, unit_id, name, created_at, updated_at";
+
+/// Filtros de `find_all`; parâmetros nulos não restringem a busca
+const SEARCH_FILTER: &str =
+    "($1::text IS NULL OR name ILIKE $1) AND ($2::uuid IS NULL OR unit_id = $2)";
 
 pub struct PostgresDepartmentRepository {
-    pool: PgPool,
+    pool: PgPoolHandle,
 }
 
 impl PostgresDepartmentRepository {
-    pub fn new(pool: PgPool) -> Self {
-        PostgresDepartmentRepository { pool }
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+
+    /// Departamentos que satisfazem `filter` (com um parâmetro), ordenados por nome
+    async fn select<T>(&self, filter: &str, value: T) -> Result<Vec<Department>, DomainError>
+    where
+        T: for<'q> sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Send,
+    {
+        sqlx::query_as::<_, DepartmentRow>(&format!(
+            "SELECT {COLUMNS} FROM departments WHERE {filter} ORDER BY name, id"
+        ))
+        .bind(value)
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_department_from_row)
+        .collect()
     }
 }
 
 #[async_trait]
 impl DepartmentRepository for PostgresDepartmentRepository {
+    #[instrument(name = "DepartmentRepository.find_by_id", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_id(&self, id: &DepartmentId) -> Result<Option<Department>, DomainError> {
-        let row = sqlx::query_as!(
-            DepartmentRow,
-            "SELECT id, unit_id, name, created_at, updated_at FROM departments WHERE id = $1",
-            id.0
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-
-        match row {
-            Some(row) => Ok(Some(build_department_from_row(row)?)),
-            None => Ok(None),
-        }
+        Ok(self.select("id = $1", id.0).await?.into_iter().next())
     }
 
-    async fn find_all(&self, criteria: &DepartmentSearchCriteria) -> Result<DepartmentSearchResult, DomainError> {
-        let mut query = "SELECT id, unit_id, name, created_at, updated_at FROM departments WHERE 1=1".to_string();
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(ref name) = criteria.name {
-            param_count += 1;
-            query.push_str(&format!(" AND name ILIKE ${}", param_count));
-            params.push(Box::new(format!("%{}%", name)));
-        }
-
-        if let Some(ref unit_id) = criteria.unit_id {
-            param_count += 1;
-            query.push_str(&format!(" AND unit_id = ${}", param_count));
-            params.push(Box::new(unit_id.0));
-        }
-
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) as count FROM ({}) as subquery", query);
-        let total: i64 = sqlx::query_scalar(&count_query)
-            .fetch_one(&self.pool)
-            .await?;
-
-        // Add pagination
-        if let Some(limit) = criteria.limit {
-            param_count += 1;
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(Box::new(limit));
-        }
-
-        if let Some(offset) = criteria.offset {
-            param_count += 1;
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(Box::new(offset));
-        }
-
-        query.push_str(" ORDER BY name");
-
-        // For now, we'll use a simplified approach without dynamic parameters
-        let rows = sqlx::query_as!(
-            DepartmentRow,
-            "SELECT id, unit_id, name, created_at, updated_at FROM departments ORDER BY name LIMIT $1 OFFSET $2",
-            criteria.limit.unwrap_or(100),
-            criteria.offset.unwrap_or(0)
-        )
-        .fetch_all(&self.pool)
+    #[instrument(name = "DepartmentRepository.find_all", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_all(
+        &self,
+        criteria: &DepartmentSearchCriteria,
+    ) -> Result<DepartmentSearchResult, DomainError> {
+        let pool = self.pool.get();
+        let name = criteria.name.as_deref().map(contains_pattern);
+        let unit_id = criteria.unit_id.as_ref().map(|id| id.0);
+
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM departments WHERE {SEARCH_FILTER}"
+        ))
+        .bind(&name)
+        .bind(unit_id)
+        .fetch_one(&pool)
         .await?;
 
-        let mut departments = Vec::new();
-        for row in rows {
-            departments.push(build_department_from_row(row)?);
-        }
+        let items = sqlx::query_as::<_, DepartmentRow>(&format!(
+            "SELECT {COLUMNS} FROM departments WHERE {SEARCH_FILTER} \
+             ORDER BY name, id LIMIT $3 OFFSET $4"
+        ))
+        .bind(&name)
+        .bind(unit_id)
+        .bind(criteria.limit.map(|limit| limit.max(0)))
+        .bind(criteria.offset.unwrap_or(0).max(0))
+        .fetch_all(&pool)
+        .await?
+        .into_iter()
+        .map(build_department_from_row)
+        .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(DepartmentSearchResult {
-            items: departments,
-            total,
-        })
+        Ok(DepartmentSearchResult { items, total })
     }
 
+    #[instrument(name = "DepartmentRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn save(&self, department: &Department) -> Result<Department, DomainError> {
-        let row = sqlx::query_as!(
-            DepartmentRow,
-            "INSERT INTO departments (id, unit_id, name, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5) 
-             RETURNING id, unit_id, name, created_at, updated_at",
-            department.id.0,
-            department.unit_id.0,
-            department.name.value,
-            department.created_at,
-            department.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, DepartmentRow>(&format!(
+            "INSERT INTO departments (id, unit_id, name, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING {COLUMNS}"
+        ))
+        .bind(department.id.0)
+        .bind(department.unit_id.0)
+        .bind(&department.name.value)
+        .bind(department.created_at)
+        .bind(department.updated_at)
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_department_from_row(row)?)
+        build_department_from_row(row)
     }
 
+    #[instrument(name = "DepartmentRepository.update", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn update(&self, department: &Department) -> Result<Department, DomainError> {
-        let row = sqlx::query_as!(
-            DepartmentRow,
-            "UPDATE departments SET unit_id = $2, name = $3, updated_at = $4 
-             WHERE id = $1 
-             RETURNING id, unit_id, name, created_at, updated_at",
-            department.id.0,
-            department.unit_id.0,
-            department.name.value,
-            department.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, DepartmentRow>(&format!(
+            "UPDATE departments SET unit_id = $2, name = $3, updated_at = now() \
+             WHERE id = $1 RETURNING {COLUMNS}"
+        ))
+        .bind(department.id.0)
+        .bind(department.unit_id.0)
+        .bind(&department.name.value)
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_department_from_row(row)?)
+        build_department_from_row(row)
     }
 
+    #[instrument(name = "DepartmentRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn delete(&self, id: &DepartmentId) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM departments WHERE id = $1",
-            id.0
-        )
-        .execute(&self.pool)
-        .await?;
+        // Contatos vinculados impedem a remoção (FK)
+        sqlx::query("DELETE FROM departments WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
         Ok(())
     }
 
+    #[instrument(name = "DepartmentRepository.find_by_name", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_name(&self, name: &str) -> Result<Vec<Department>, DomainError> {
-        let rows = sqlx::query_as!(
-            DepartmentRow,
-            "SELECT id, unit_id, name, created_at, updated_at FROM departments WHERE name ILIKE $1",
-            format!("%{}%", name)
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut departments = Vec::new();
-        for row in rows {
-            departments.push(build_department_from_row(row)?);
-        }
-
-        Ok(departments)
+        self.select("name ILIKE $1", contains_pattern(name)).await
     }
 
+    #[instrument(name = "DepartmentRepository.find_by_unit", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Department>, DomainError> {
-        let rows = sqlx::query_as!(
-            DepartmentRow,
-            "SELECT id, unit_id, name, created_at, updated_at FROM departments WHERE unit_id = $1",
-            unit_id.0
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut departments = Vec::new();
-        for row in rows {
-            departments.push(build_department_from_row(row)?);
-        }
-
-        Ok(departments)
+        self.select("unit_id = $1", unit_id.0).await
     }
 
+    #[instrument(name = "DepartmentRepository.get_statistics", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn get_statistics(&self) -> Result<DepartmentStatistics, DomainError> {
-        let total_departments = sqlx::query_scalar!(
-            "SELECT COUNT(*) as count FROM departments"
-        )
-        .fetch_one(&self.pool)
-        .await?
-        .unwrap_or(0);
-
-        // Get count by unit
-        let rows = sqlx::query!(
-            "SELECT unit_id, COUNT(*) as count FROM departments GROUP BY unit_id"
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let rows =
+            sqlx::query("SELECT unit_id, COUNT(*) AS count FROM departments GROUP BY unit_id")
+                .fetch_all(&self.pool.get())
+                .await?;
 
         let mut departments_by_unit = HashMap::new();
-        for row in rows {
-            if let Some(unit_id) = row.unit_id {
-                departments_by_unit.insert(OrgUnitId(unit_id), row.count.unwrap_or(0));
-            }
+        for row in &rows {
+            departments_by_unit.insert(OrgUnitId(row.try_get("unit_id")?), row.try_get("count")?);
         }
-
         Ok(DepartmentStatistics {
-            total_departments,
+            total_departments: departments_by_unit.values().sum(),
             departments_by_unit,
         })
     }
@@ -1,73 +0,0 @@
-use crate::domain::{
-    entities::Department,
-    errors::DomainError,
-    repositories::{
-        DepartmentRepository, DepartmentSearchCriteria, DepartmentSearchResult,
-        DepartmentStatistics,
-    },
-    value_objects::{DepartmentId, OrgUnitId},
-};
-use async_trait::async_trait;
-use uuid::Uuid;
-
-pub struct PostgresDepartmentRepository {
-    // Placeholder for database connection
-}
-
-impl PostgresDepartmentRepository {
-    pub fn new(_pool: sqlx::Pool<sqlx::Postgres>) -> Self {
-        PostgresDepartmentRepository {}
-    }
-}
-
-#[async_trait]
-impl DepartmentRepository for PostgresDepartmentRepository {
-    async fn find_by_id(&self, id: &DepartmentId) -> Result<Option<Department>, DomainError> {
-        // Placeholder implementation
-        Ok(None)
-    }
-
-    async fn find_all(
-        &self,
-        criteria: &DepartmentSearchCriteria,
-    ) -> Result<DepartmentSearchResult, DomainError> {
-        // Placeholder implementation
-        Ok(DepartmentSearchResult {
-            items: vec![],
-            total: 0,
-        })
-    }
-
-    async fn save(&self, department: &Department) -> Result<Department, DomainError> {
-        // Placeholder implementation
-        Ok(department.clone())
-    }
-
-    async fn update(&self, department: &Department) -> Result<Department, DomainError> {
-        // Placeholder implementation
-        Ok(department.clone())
-    }
-
-    async fn delete(&self, id: &DepartmentId) -> Result<(), DomainError> {
-        // Placeholder implementation
-        Ok(())
-    }
-
-    async fn find_by_unit(&self, unit_id: &OrgUnitId) -> Result<Vec<Department>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Vec<Department>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-
-    async fn get_statistics(&self) -> Result<DepartmentStatistics, DomainError> {
-        // Placeholder implementation
-        Ok(DepartmentStatistics {
-            total_departments: 0,
-            departments_by_unit: std::collections::HashMap::new(),
-        })
-    }
-}
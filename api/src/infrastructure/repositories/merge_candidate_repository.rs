@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::domain::entities::MergeCandidate;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::MergeCandidateRepository;
+use crate::domain::value_objects::ContactId;
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_merge_candidate_from_row, MergeCandidateRow};
+
+const COLUMNS: &str = "contact_a, contact_b, score::float8 AS score, features";
+
+pub struct PostgresMergeCandidateRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresMergeCandidateRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MergeCandidateRepository for PostgresMergeCandidateRepository {
+    #[instrument(name = "MergeCandidateRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, candidate: &MergeCandidate) -> Result<MergeCandidate, DomainError> {
+        let row = sqlx::query_as::<_, MergeCandidateRow>(&format!(
+            "INSERT INTO merge_candidates (contact_a, contact_b, score, features) \
+             VALUES ($1, $2, $3, $4) RETURNING {COLUMNS}"
+        ))
+        .bind(candidate.contact_a.0)
+        .bind(candidate.contact_b.0)
+        .bind(candidate.score)
+        .bind(&candidate.features)
+        .fetch_one(&self.pool.get())
+        .await?;
+        build_merge_candidate_from_row(row)
+    }
+
+    #[instrument(name = "MergeCandidateRepository.find_by_contact", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<MergeCandidate>, DomainError> {
+        sqlx::query_as::<_, MergeCandidateRow>(&format!(
+            "SELECT {COLUMNS} FROM merge_candidates WHERE contact_a = $1 OR contact_b = $1 \
+             ORDER BY score DESC"
+        ))
+        .bind(contact_id.0)
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_merge_candidate_from_row)
+        .collect()
+    }
+
+    #[instrument(name = "MergeCandidateRepository.find_top_candidates", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_top_candidates(&self, limit: i64) -> Result<Vec<MergeCandidate>, DomainError> {
+        sqlx::query_as::<_, MergeCandidateRow>(&format!(
+            "SELECT {COLUMNS} FROM merge_candidates ORDER BY score DESC LIMIT $1"
+        ))
+        .bind(limit.max(0))
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_merge_candidate_from_row)
+        .collect()
+    }
+
+    #[instrument(name = "MergeCandidateRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn delete(
+        &self,
+        contact_a: &ContactId,
+        contact_b: &ContactId,
+    ) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM merge_candidates WHERE contact_a = $1 AND contact_b = $2")
+            .bind(contact_a.0)
+            .bind(contact_b.0)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
+    }
+}
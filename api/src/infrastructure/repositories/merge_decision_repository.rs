@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::domain::entities::MergeDecision;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::MergeDecisionRepository;
+use crate::domain::value_objects::{ContactId, UserId};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_merge_decision_from_row, MergeDecisionRow};
+
+const COLUMNS: &str =
+    "primary_contact, duplicate_contact, decision, chosen_fields, decided_by, decided_at";
+
+pub struct PostgresMergeDecisionRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresMergeDecisionRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+
+    /// Decisões que satisfazem `filter`, da mais recente para a mais antiga
+    async fn select(
+        &self,
+        filter: &str,
+        id: uuid::Uuid,
+    ) -> Result<Vec<MergeDecision>, DomainError> {
+        sqlx::query_as::<_, MergeDecisionRow>(&format!(
+            "SELECT {COLUMNS} FROM merge_decisions WHERE {filter} ORDER BY decided_at DESC"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_merge_decision_from_row)
+        .collect()
+    }
+}
+
+#[async_trait]
+impl MergeDecisionRepository for PostgresMergeDecisionRepository {
+    #[instrument(name = "MergeDecisionRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, decision: &MergeDecision) -> Result<MergeDecision, DomainError> {
+        let row = sqlx::query_as::<_, MergeDecisionRow>(&format!(
+            "INSERT INTO merge_decisions \
+             (primary_contact, duplicate_contact, decision, chosen_fields, decided_by, decided_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) RETURNING {COLUMNS}"
+        ))
+        .bind(decision.primary_contact.0)
+        .bind(decision.duplicate_contact.0)
+        .bind(decision.decision.to_string())
+        .bind(&decision.chosen_fields)
+        .bind(decision.decided_by.as_ref().map(|id| id.0))
+        .bind(decision.decided_at)
+        .fetch_one(&self.pool.get())
+        .await?;
+        build_merge_decision_from_row(row)
+    }
+
+    #[instrument(name = "MergeDecisionRepository.find_by_contact", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_contact(
+        &self,
+        contact_id: &ContactId,
+    ) -> Result<Vec<MergeDecision>, DomainError> {
+        self.select(
+            "primary_contact = $1 OR duplicate_contact = $1",
+            contact_id.0,
+        )
+        .await
+    }
+
+    #[instrument(name = "MergeDecisionRepository.find_by_decider", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_decider(
+        &self,
+        decided_by: &UserId,
+    ) -> Result<Vec<MergeDecision>, DomainError> {
+        self.select("decided_by = $1", decided_by.0).await
+    }
+}
@@ -1,9 +1,32 @@
-pub mod contact_repository_simple;
-pub mod department_repository_simple;
-pub mod org_unit_repository_simple;
-pub mod user_repository_simple;
+pub mod audit_event_repository;
+pub mod contact_repository;
+pub mod department_repository;
+pub mod org_unit_repository;
+pub mod user_repository;
+pub mod webhook_receipt_repository;
 
-pub use contact_repository_simple::*;
-pub use department_repository_simple::*;
-pub use org_unit_repository_simple::*;
-pub use user_repository_simple::*;
+// Fontes e deduplicação ainda não têm casos de uso; por ora só os testes os usam
+#[allow(dead_code)]
+pub mod contact_source_repository;
+#[allow(dead_code)]
+pub mod merge_candidate_repository;
+#[allow(dead_code)]
+pub mod merge_decision_repository;
+#[allow(dead_code)]
+pub mod source_record_repository;
+
+pub use audit_event_repository::*;
+pub use contact_repository::*;
+pub use department_repository::*;
+pub use org_unit_repository::*;
+pub use user_repository::*;
+pub use webhook_receipt_repository::*;
+
+/// Padrão `ILIKE` de "contém", com `%`, `_` e `\` tratados literalmente
+pub(crate) fn contains_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
@@ -1,212 +1,163 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
-use uuid::Uuid;
+use tracing::instrument;
 
+use super::contains_pattern;
 use crate::domain::entities::OrgUnit;
 use crate::domain::errors::DomainError;
 use crate::domain::repositories::{OrgUnitRepository, OrgUnitSearchCriteria, OrgUnitSearchResult};
 use crate::domain::value_objects::OrgUnitId;
-use crate::infrastructure::mappers::{OrgUnitRow, build_org_unit_from_row};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_org_unit_from_row, OrgUnitRow};
+
+const COLUMNS: &str = "id, name, parent_id, created_at, updated_at";
+
+/// Filtros de `find_all`; parâmetros nulos não restringem a busca
+const SEARCH_FILTER: &str =
+    "($1::text IS NULL OR name ILIKE $1) AND ($2::uuid IS NULL OR parent_id = $2)";
 
 pub struct PostgresOrgUnitRepository {
-    pool: PgPool,
+    pool: PgPoolHandle,
 }
 
 impl PostgresOrgUnitRepository {
-    pub fn new(pool: PgPool) -> Self {
-        PostgresOrgUnitRepository { pool }
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+
+    /// Unidades que satisfazem `filter` (com no máximo um parâmetro), ordenadas por nome
+    async fn select<T>(&self, filter: &str, value: Option<T>) -> Result<Vec<OrgUnit>, DomainError>
+    where
+        T: for<'q> sqlx::Encode<'q, sqlx::Postgres> + sqlx::Type<sqlx::Postgres> + Send,
+    {
+        let sql = format!("SELECT {COLUMNS} FROM org_units WHERE {filter} ORDER BY name, id");
+        let mut query = sqlx::query_as::<_, OrgUnitRow>(&sql);
+        if let Some(value) = value {
+            query = query.bind(value);
+        }
+        query
+            .fetch_all(&self.pool.get())
+            .await?
+            .into_iter()
+            .map(build_org_unit_from_row)
+            .collect()
     }
 }
 
 #[async_trait]
 impl OrgUnitRepository for PostgresOrgUnitRepository {
+    #[instrument(name = "OrgUnitRepository.find_by_id", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_id(&self, id: &OrgUnitId) -> Result<Option<OrgUnit>, DomainError> {
-        let row = sqlx::query_as!(
-            OrgUnitRow,
-            "SELECT id, name, parent_id, created_at, updated_at FROM org_units WHERE id = $1",
-            id.0
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-
-        match row {
-            Some(row) => Ok(Some(build_org_unit_from_row(row)?)),
-            None => Ok(None),
-        }
+        Ok(self.select("id = $1", Some(id.0)).await?.into_iter().next())
     }
 
-    async fn find_all(&self, criteria: &OrgUnitSearchCriteria) -> Result<OrgUnitSearchResult, DomainError> {
-        let mut query = "SELECT id, name, parent_id, created_at, updated_at FROM org_units WHERE 1=1".to_string();
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(ref name) = criteria.name {
-            param_count += 1;
-            query.push_str(&format!(" AND name ILIKE ${}", param_count));
-            params.push(Box::new(format!("%{}%", name)));
-        }
-
-        if let Some(ref parent_id) = criteria.parent_id {
-            param_count += 1;
-            query.push_str(&format!(" AND parent_id = ${}", param_count));
-            params.push(Box::new(parent_id.0));
-        }
-
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) as count FROM ({}) as subquery", query);
-        let total: i64 = sqlx::query_scalar(&count_query)
-            .fetch_one(&self.pool)
-            .await?;
-
-        // Add pagination
-        if let Some(limit) = criteria.limit {
-            param_count += 1;
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(Box::new(limit));
-        }
-
-        if let Some(offset) = criteria.offset {
-            param_count += 1;
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(Box::new(offset));
-        }
-
-        query.push_str(" ORDER BY name");
-
-        // For now, we'll use a simplified approach without dynamic parameters
-        let rows = sqlx::query_as!(
-            OrgUnitRow,
-            "SELECT id, name, parent_id, created_at, updated_at FROM org_units ORDER BY name LIMIT $1 OFFSET $2",
-            criteria.limit.unwrap_or(100),
-            criteria.offset.unwrap_or(0)
-        )
-        .fetch_all(&self.pool)
+    #[instrument(name = "OrgUnitRepository.find_all", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_all(
+        &self,
+        criteria: &OrgUnitSearchCriteria,
+    ) -> Result<OrgUnitSearchResult, DomainError> {
+        let pool = self.pool.get();
+        let name = criteria.name.as_deref().map(contains_pattern);
+        let parent_id = criteria.parent_id.as_ref().map(|id| id.0);
+
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM org_units WHERE {SEARCH_FILTER}"
+        ))
+        .bind(&name)
+        .bind(parent_id)
+        .fetch_one(&pool)
         .await?;
 
-        let mut org_units = Vec::new();
-        for row in rows {
-            org_units.push(build_org_unit_from_row(row)?);
-        }
-
-        Ok(OrgUnitSearchResult {
-            items: org_units,
-            total,
-        })
+        let items = sqlx::query_as::<_, OrgUnitRow>(&format!(
+            "SELECT {COLUMNS} FROM org_units WHERE {SEARCH_FILTER} \
+             ORDER BY name, id LIMIT $3 OFFSET $4"
+        ))
+        .bind(&name)
+        .bind(parent_id)
+        .bind(criteria.limit.map(|limit| limit.max(0)))
+        .bind(criteria.offset.unwrap_or(0).max(0))
+        .fetch_all(&pool)
+        .await?
+        .into_iter()
+        .map(build_org_unit_from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(OrgUnitSearchResult { items, total })
     }
 
+    #[instrument(name = "OrgUnitRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn save(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
-        let row = sqlx::query_as!(
-            OrgUnitRow,
-            "INSERT INTO org_units (id, name, parent_id, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5) 
-             RETURNING id, name, parent_id, created_at, updated_at",
-            org_unit.id.0,
-            org_unit.name.value,
-            org_unit.parent_id.as_ref().map(|id| id.0),
-            org_unit.created_at,
-            org_unit.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, OrgUnitRow>(&format!(
+            "INSERT INTO org_units (id, name, parent_id, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING {COLUMNS}"
+        ))
+        .bind(org_unit.id.0)
+        .bind(&org_unit.name.value)
+        .bind(org_unit.parent_id.as_ref().map(|id| id.0))
+        .bind(org_unit.created_at)
+        .bind(org_unit.updated_at)
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_org_unit_from_row(row)?)
+        build_org_unit_from_row(row)
     }
 
+    #[instrument(name = "OrgUnitRepository.update", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn update(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
-        let row = sqlx::query_as!(
-            OrgUnitRow,
-            "UPDATE org_units SET name = $2, parent_id = $3, updated_at = $4 
-             WHERE id = $1 
-             RETURNING id, name, parent_id, created_at, updated_at",
-            org_unit.id.0,
-            org_unit.name.value,
-            org_unit.parent_id.as_ref().map(|id| id.0),
-            org_unit.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, OrgUnitRow>(&format!(
+            "UPDATE org_units SET name = $2, parent_id = $3, updated_at = now() \
+             WHERE id = $1 RETURNING {COLUMNS}"
+        ))
+        .bind(org_unit.id.0)
+        .bind(&org_unit.name.value)
+        .bind(org_unit.parent_id.as_ref().map(|id| id.0))
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_org_unit_from_row(row)?)
+        build_org_unit_from_row(row)
     }
 
+    #[instrument(name = "OrgUnitRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn delete(&self, id: &OrgUnitId) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM org_units WHERE id = $1",
-            id.0
-        )
-        .execute(&self.pool)
-        .await?;
+        // Subunidades, departamentos e contatos vinculados impedem a remoção (FK)
+        sqlx::query("DELETE FROM org_units WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
         Ok(())
     }
 
+    #[instrument(name = "OrgUnitRepository.find_by_name", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_name(&self, name: &str) -> Result<Vec<OrgUnit>, DomainError> {
-        let rows = sqlx::query_as!(
-            OrgUnitRow,
-            "SELECT id, name, parent_id, created_at, updated_at FROM org_units WHERE name ILIKE $1",
-            format!("%{}%", name)
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut org_units = Vec::new();
-        for row in rows {
-            org_units.push(build_org_unit_from_row(row)?);
-        }
-
-        Ok(org_units)
+        self.select("name ILIKE $1", Some(contains_pattern(name)))
+            .await
     }
 
+    #[instrument(name = "OrgUnitRepository.find_children", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_children(&self, parent_id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
-        let rows = sqlx::query_as!(
-            OrgUnitRow,
-            "SELECT id, name, parent_id, created_at, updated_at FROM org_units WHERE parent_id = $1",
-            parent_id.0
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut org_units = Vec::new();
-        for row in rows {
-            org_units.push(build_org_unit_from_row(row)?);
-        }
-
-        Ok(org_units)
+        self.select("parent_id = $1", Some(parent_id.0)).await
     }
 
+    #[instrument(name = "OrgUnitRepository.find_root_units", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_root_units(&self) -> Result<Vec<OrgUnit>, DomainError> {
-        let rows = sqlx::query_as!(
-            OrgUnitRow,
-            "SELECT id, name, parent_id, created_at, updated_at FROM org_units WHERE parent_id IS NULL"
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut org_units = Vec::new();
-        for row in rows {
-            org_units.push(build_org_unit_from_row(row)?);
-        }
-
-        Ok(org_units)
+        self.select::<uuid::Uuid>("parent_id IS NULL", None).await
     }
 
+    #[instrument(name = "OrgUnitRepository.get_hierarchy", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn get_hierarchy(&self, id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
-        // This is a simplified implementation
-        // In a real scenario, you might want to use a recursive CTE to get the full hierarchy
-        let mut hierarchy = Vec::new();
-        let mut current_id = Some(*id);
-
-        while let Some(current) = current_id {
-            let org_unit = self.find_by_id(&current).await?;
-            match org_unit {
-                Some(unit) => {
-                    hierarchy.push(unit.clone());
-                    current_id = unit.parent_id;
-                }
-                None => break,
-            }
-        }
-
-        hierarchy.reverse(); // Reverse to get root-to-leaf order
-        Ok(hierarchy)
+        // Da raiz até a unidade; o limite de profundidade protege contra ciclos
+        sqlx::query_as::<_, OrgUnitRow>(&format!(
+            "WITH RECURSIVE ancestors AS ( \
+                 SELECT {COLUMNS}, 0 AS depth FROM org_units WHERE id = $1 \
+                 UNION \
+                 SELECT u.id, u.name, u.parent_id, u.created_at, u.updated_at, a.depth + 1 \
+                 FROM org_units u JOIN ancestors a ON u.id = a.parent_id \
+                 WHERE a.depth < (SELECT COUNT(*) FROM org_units) \
+             ) \
+             SELECT {COLUMNS} FROM ancestors ORDER BY depth DESC"
+        ))
+        .bind(id.0)
+        .fetch_all(&self.pool.get())
+        .await?
+        .into_iter()
+        .map(build_org_unit_from_row)
+        .collect()
     }
 }
@@ -1,72 +0,0 @@
-use crate::domain::{
-    entities::OrgUnit,
-    errors::DomainError,
-    repositories::{OrgUnitRepository, OrgUnitSearchCriteria, OrgUnitSearchResult},
-    value_objects::OrgUnitId,
-};
-use async_trait::async_trait;
-use uuid::Uuid;
-
-pub struct PostgresOrgUnitRepository {
-    // Placeholder for database connection
-}
-
-impl PostgresOrgUnitRepository {
-    pub fn new(_pool: sqlx::Pool<sqlx::Postgres>) -> Self {
-        PostgresOrgUnitRepository {}
-    }
-}
-
-#[async_trait]
-impl OrgUnitRepository for PostgresOrgUnitRepository {
-    async fn find_by_id(&self, id: &OrgUnitId) -> Result<Option<OrgUnit>, DomainError> {
-        // Placeholder implementation
-        Ok(None)
-    }
-
-    async fn find_all(
-        &self,
-        criteria: &OrgUnitSearchCriteria,
-    ) -> Result<OrgUnitSearchResult, DomainError> {
-        // Placeholder implementation
-        Ok(OrgUnitSearchResult {
-            items: vec![],
-            total: 0,
-        })
-    }
-
-    async fn save(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
-        // Placeholder implementation
-        Ok(org_unit.clone())
-    }
-
-    async fn update(&self, org_unit: &OrgUnit) -> Result<OrgUnit, DomainError> {
-        // Placeholder implementation
-        Ok(org_unit.clone())
-    }
-
-    async fn delete(&self, id: &OrgUnitId) -> Result<(), DomainError> {
-        // Placeholder implementation
-        Ok(())
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Vec<OrgUnit>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-
-    async fn find_children(&self, parent_id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-
-    async fn find_root_units(&self) -> Result<Vec<OrgUnit>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-
-    async fn get_hierarchy(&self, id: &OrgUnitId) -> Result<Vec<OrgUnit>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-}
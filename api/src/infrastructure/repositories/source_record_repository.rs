@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::domain::entities::SourceRecord;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::SourceRecordRepository;
+use crate::domain::value_objects::SourceRecordId;
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_source_record_from_row, SourceRecordRow};
+
+const COLUMNS: &str = "id, source, source_key, hash, payload, fetched_at";
+
+pub struct PostgresSourceRecordRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresSourceRecordRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SourceRecordRepository for PostgresSourceRecordRepository {
+    #[instrument(name = "SourceRecordRepository.find_by_id", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_id(&self, id: &SourceRecordId) -> Result<Option<SourceRecord>, DomainError> {
+        sqlx::query_as::<_, SourceRecordRow>(&format!(
+            "SELECT {COLUMNS} FROM source_records WHERE id = $1"
+        ))
+        .bind(id.0)
+        .fetch_optional(&self.pool.get())
+        .await?
+        .map(build_source_record_from_row)
+        .transpose()
+    }
+
+    #[instrument(name = "SourceRecordRepository.find_by_source_and_key", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_source_and_key(
+        &self,
+        source: &str,
+        source_key: &str,
+    ) -> Result<Option<SourceRecord>, DomainError> {
+        sqlx::query_as::<_, SourceRecordRow>(&format!(
+            "SELECT {COLUMNS} FROM source_records WHERE source = $1 AND source_key = $2"
+        ))
+        .bind(source)
+        .bind(source_key)
+        .fetch_optional(&self.pool.get())
+        .await?
+        .map(build_source_record_from_row)
+        .transpose()
+    }
+
+    #[instrument(name = "SourceRecordRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, record: &SourceRecord) -> Result<SourceRecord, DomainError> {
+        let row = sqlx::query_as::<_, SourceRecordRow>(&format!(
+            "INSERT INTO source_records (id, source, source_key, hash, payload, fetched_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) RETURNING {COLUMNS}"
+        ))
+        .bind(record.id.0)
+        .bind(&record.source.value)
+        .bind(&record.source_key.value)
+        .bind(&record.hash.value)
+        .bind(&record.payload)
+        .bind(record.fetched_at)
+        .fetch_one(&self.pool.get())
+        .await?;
+        build_source_record_from_row(row)
+    }
+
+    #[instrument(name = "SourceRecordRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn delete(&self, id: &SourceRecordId) -> Result<(), DomainError> {
+        // Os vínculos em contact_sources saem por ON DELETE CASCADE
+        sqlx::query("DELETE FROM source_records WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
+    }
+}
@@ -1,197 +1,171 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use tracing::instrument;
 
+use super::contains_pattern;
 use crate::domain::entities::User;
 use crate::domain::errors::DomainError;
 use crate::domain::repositories::{UserRepository, UserSearchCriteria, UserSearchResult};
 use crate::domain::value_objects::UserId;
-use crate::infrastructure::mappers::{UserRow, build_user_from_row};
+use crate::infra::pg::PgPoolHandle;
+use crate::infrastructure::mappers::{build_user_from_row, UserRow};
+
+const COLUMNS: &str =
+    "id, username, email, password, COALESCE(roles, '{}') AS roles, created_at, updated_at";
+
+/// Filtros de `find_all`; parâmetros nulos não restringem a busca
+const SEARCH_FILTER: &str = "($1::text IS NULL OR username ILIKE $1) \
+                             AND ($2::text IS NULL OR email ILIKE $2) \
+                             AND ($3::text IS NULL OR $3 = ANY(roles))";
 
 pub struct PostgresUserRepository {
-    pool: PgPool,
+    pool: PgPoolHandle,
 }
 
 impl PostgresUserRepository {
-    pub fn new(pool: PgPool) -> Self {
-        PostgresUserRepository { pool }
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
     }
-}
-
-#[async_trait]
-impl UserRepository for PostgresUserRepository {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-        let row = sqlx::query_as!(
-            UserRow,
-            "SELECT id, username, email, password, roles, created_at, updated_at FROM users WHERE id = $1",
-            id.0
-        )
-        .fetch_optional(&self.pool)
-        .await?;
 
-        match row {
-            Some(row) => Ok(Some(build_user_from_row(row)?)),
-            None => Ok(None),
+    /// Usuários que satisfazem `filter` (com dois parâmetros no máximo), do mais recente
+    /// para o mais antigo
+    async fn select(&self, filter: &str, values: &[&str]) -> Result<Vec<User>, DomainError> {
+        let sql =
+            format!("SELECT {COLUMNS} FROM users WHERE {filter} ORDER BY created_at DESC, id");
+        let mut query = sqlx::query_as::<_, UserRow>(&sql);
+        for value in values {
+            query = query.bind(*value);
         }
+        query
+            .fetch_all(&self.pool.get())
+            .await?
+            .into_iter()
+            .map(build_user_from_row)
+            .collect()
     }
 
-    async fn find_all(&self, criteria: &UserSearchCriteria) -> Result<UserSearchResult, DomainError> {
-        let mut query = "SELECT id, username, email, password, roles, created_at, updated_at FROM users WHERE 1=1".to_string();
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(ref username) = criteria.username {
-            param_count += 1;
-            query.push_str(&format!(" AND username ILIKE ${}", param_count));
-            params.push(Box::new(format!("%{}%", username)));
-        }
-
-        if let Some(ref email) = criteria.email {
-            param_count += 1;
-            query.push_str(&format!(" AND email ILIKE ${}", param_count));
-            params.push(Box::new(format!("%{}%", email)));
-        }
-
-        if let Some(ref role) = criteria.role {
-            param_count += 1;
-            query.push_str(&format!(" AND $${} = ANY(roles)", param_count));
-            params.push(Box::new(role.clone()));
-        }
-
-        // Get total count
-        let count_query = format!("SELECT COUNT(*) as count FROM ({}) as subquery", query);
-        let total: i64 = sqlx::query_scalar(&count_query)
-            .fetch_one(&self.pool)
-            .await?;
-
-        // Add pagination
-        if let Some(limit) = criteria.limit {
-            param_count += 1;
-            query.push_str(&format!(" LIMIT ${}", param_count));
-            params.push(Box::new(limit));
-        }
-
-        if let Some(offset) = criteria.offset {
-            param_count += 1;
-            query.push_str(&format!(" OFFSET ${}", param_count));
-            params.push(Box::new(offset));
-        }
-
-        query.push_str(" ORDER BY created_at DESC");
+    async fn select_one(&self, filter: &str, values: &[&str]) -> Result<Option<User>, DomainError> {
+        Ok(self.select(filter, values).await?.into_iter().next())
+    }
+}
 
-        // For now, we'll use a simplified approach without dynamic parameters
-        let rows = sqlx::query_as!(
-            UserRow,
-            "SELECT id, username, email, password, roles, created_at, updated_at FROM users ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-            criteria.limit.unwrap_or(100),
-            criteria.offset.unwrap_or(0)
-        )
-        .fetch_all(&self.pool)
-        .await?;
+fn role_values(user: &User) -> Vec<String> {
+    user.roles.iter().map(|role| role.value.clone()).collect()
+}
 
-        let mut users = Vec::new();
-        for row in rows {
-            users.push(build_user_from_row(row)?);
-        }
+#[async_trait]
+impl UserRepository for PostgresUserRepository {
+    #[instrument(name = "UserRepository.find_by_id", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
+        self.select_one("id = $1::uuid", &[&id.to_string()]).await
+    }
 
-        Ok(UserSearchResult {
-            items: users,
-            total,
-        })
+    #[instrument(name = "UserRepository.find_all", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn find_all(
+        &self,
+        criteria: &UserSearchCriteria,
+    ) -> Result<UserSearchResult, DomainError> {
+        let pool = self.pool.get();
+        let username = criteria.username.as_deref().map(contains_pattern);
+        let email = criteria.email.as_deref().map(contains_pattern);
+
+        let total: i64 =
+            sqlx::query_scalar(&format!("SELECT COUNT(*) FROM users WHERE {SEARCH_FILTER}"))
+                .bind(&username)
+                .bind(&email)
+                .bind(&criteria.role)
+                .fetch_one(&pool)
+                .await?;
+
+        let items = sqlx::query_as::<_, UserRow>(&format!(
+            "SELECT {COLUMNS} FROM users WHERE {SEARCH_FILTER} \
+             ORDER BY created_at DESC, id LIMIT $4 OFFSET $5"
+        ))
+        .bind(&username)
+        .bind(&email)
+        .bind(&criteria.role)
+        .bind(criteria.limit.map(|limit| limit.max(0)))
+        .bind(criteria.offset.unwrap_or(0).max(0))
+        .fetch_all(&pool)
+        .await?
+        .into_iter()
+        .map(build_user_from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(UserSearchResult { items, total })
     }
 
+    /// A senha recebida é gravada como hash bcrypt (`crypt`)
+    #[instrument(name = "UserRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn save(&self, user: &User) -> Result<User, DomainError> {
-        let row = sqlx::query_as!(
-            UserRow,
-            "INSERT INTO users (id, username, email, password, roles, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7) 
-             RETURNING id, username, email, password, roles, created_at, updated_at",
-            user.id.0,
-            user.username.value,
-            user.email.value,
-            user.password.value,
-            &user.roles.iter().map(|r| r.value.clone()).collect::<Vec<String>>(),
-            user.created_at,
-            user.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, UserRow>(&format!(
+            "INSERT INTO users (id, username, email, password, roles, created_at, updated_at) \
+             VALUES ($1, $2, $3, crypt($4, gen_salt('bf')), $5, $6, $7) RETURNING {COLUMNS}"
+        ))
+        .bind(user.id.0)
+        .bind(&user.username.value)
+        .bind(&user.email.value)
+        .bind(&user.password.value)
+        .bind(role_values(user))
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_user_from_row(row)?)
+        build_user_from_row(row)
     }
 
+    /// Uma senha igual ao hash armazenado é mantida; qualquer outra é uma nova senha
+    #[instrument(name = "UserRepository.update", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn update(&self, user: &User) -> Result<User, DomainError> {
-        let row = sqlx::query_as!(
-            UserRow,
-            "UPDATE users SET username = $2, email = $3, password = $4, roles = $5, updated_at = $6 
-             WHERE id = $1 
-             RETURNING id, username, email, password, roles, created_at, updated_at",
-            user.id.0,
-            user.username.value,
-            user.email.value,
-            user.password.value,
-            &user.roles.iter().map(|r| r.value.clone()).collect::<Vec<String>>(),
-            user.updated_at
-        )
-        .fetch_one(&self.pool)
+        let row = sqlx::query_as::<_, UserRow>(&format!(
+            "UPDATE users SET username = $2, email = $3, \
+             password = CASE WHEN password = $4 THEN password ELSE crypt($4, gen_salt('bf')) END, \
+             roles = $5, updated_at = now() \
+             WHERE id = $1 RETURNING {COLUMNS}"
+        ))
+        .bind(user.id.0)
+        .bind(&user.username.value)
+        .bind(&user.email.value)
+        .bind(&user.password.value)
+        .bind(role_values(user))
+        .fetch_one(&self.pool.get())
         .await?;
-
-        Ok(build_user_from_row(row)?)
+        build_user_from_row(row)
     }
 
+    #[instrument(name = "UserRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        sqlx::query!(
-            "DELETE FROM users WHERE id = $1",
-            id.0
-        )
-        .execute(&self.pool)
-        .await?;
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id.0)
+            .execute(&self.pool.get())
+            .await?;
         Ok(())
     }
 
+    #[instrument(name = "UserRepository.find_by_username", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, DomainError> {
-        let row = sqlx::query_as!(
-            UserRow,
-            "SELECT id, username, email, password, roles, created_at, updated_at FROM users WHERE username = $1",
-            username
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-
-        match row {
-            Some(row) => Ok(Some(build_user_from_row(row)?)),
-            None => Ok(None),
-        }
+        self.select_one("username = $1", &[username]).await
     }
 
+    #[instrument(name = "UserRepository.find_by_email", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
-        let row = sqlx::query_as!(
-            UserRow,
-            "SELECT id, username, email, password, roles, created_at, updated_at FROM users WHERE email = $1",
-            email
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-
-        match row {
-            Some(row) => Ok(Some(build_user_from_row(row)?)),
-            None => Ok(None),
-        }
+        self.select_one("email = $1", &[email]).await
     }
 
+    #[instrument(name = "UserRepository.find_by_role", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
     async fn find_by_role(&self, role: &str) -> Result<Vec<User>, DomainError> {
-        let rows = sqlx::query_as!(
-            UserRow,
-            "SELECT id, username, email, password, roles, created_at, updated_at FROM users WHERE $1 = ANY(roles)",
-            role
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut users = Vec::new();
-        for row in rows {
-            users.push(build_user_from_row(row)?);
-        }
+        self.select("$1 = ANY(roles)", &[role]).await
+    }
 
-        Ok(users)
+    #[instrument(name = "UserRepository.verify_credentials", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn verify_credentials(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<User>, DomainError> {
+        self.select_one(
+            "username = $1 AND password = crypt($2, password)",
+            &[username, password],
+        )
+        .await
     }
 }
@@ -1,67 +0,0 @@
-use crate::domain::{
-    entities::User,
-    errors::DomainError,
-    repositories::{UserRepository, UserSearchCriteria, UserSearchResult},
-    value_objects::{Email, Role, UserId, Username},
-};
-use async_trait::async_trait;
-use uuid::Uuid;
-
-pub struct PostgresUserRepository {
-    // Placeholder for database connection
-}
-
-impl PostgresUserRepository {
-    pub fn new(_pool: sqlx::Pool<sqlx::Postgres>) -> Self {
-        PostgresUserRepository {}
-    }
-}
-
-#[async_trait]
-impl UserRepository for PostgresUserRepository {
-    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-        // Placeholder implementation
-        Ok(None)
-    }
-
-    async fn find_all(
-        &self,
-        criteria: &UserSearchCriteria,
-    ) -> Result<UserSearchResult, DomainError> {
-        // Placeholder implementation
-        Ok(UserSearchResult {
-            items: vec![],
-            total: 0,
-        })
-    }
-
-    async fn save(&self, user: &User) -> Result<User, DomainError> {
-        // Placeholder implementation
-        Ok(user.clone())
-    }
-
-    async fn update(&self, user: &User) -> Result<User, DomainError> {
-        // Placeholder implementation
-        Ok(user.clone())
-    }
-
-    async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        // Placeholder implementation
-        Ok(())
-    }
-
-    async fn find_by_username(&self, username: &str) -> Result<Option<User>, DomainError> {
-        // Placeholder implementation
-        Ok(None)
-    }
-
-    async fn find_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
-        // Placeholder implementation
-        Ok(None)
-    }
-
-    async fn find_by_role(&self, role: &str) -> Result<Vec<User>, DomainError> {
-        // Placeholder implementation
-        Ok(vec![])
-    }
-}
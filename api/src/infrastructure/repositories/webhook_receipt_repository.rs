@@ -0,0 +1,58 @@
+use crate::domain::entities::WebhookReceipt;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::WebhookReceiptRepository;
+use crate::infra::pg::PgPoolHandle;
+use async_trait::async_trait;
+use tracing::instrument;
+
+/// Proteção contra reenvio de webhooks (tabela `webhook_receipts`): cada
+/// entrega identificada por `X-Webhook-Id` é registrada uma única vez por fonte
+pub struct PostgresWebhookReceiptRepository {
+    pool: PgPoolHandle,
+}
+
+impl PostgresWebhookReceiptRepository {
+    pub fn new(pool: PgPoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhookReceiptRepository for PostgresWebhookReceiptRepository {
+    /// Entregas já recebidas violam `webhook_receipts_ux` (`DomainError::Conflict`)
+    #[instrument(name = "WebhookReceiptRepository.save", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn save(&self, receipt: &WebhookReceipt) -> Result<WebhookReceipt, DomainError> {
+        sqlx::query(
+            "INSERT INTO webhook_receipts (id, source, nonce, received_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(receipt.id.0)
+        .bind(&receipt.source.value)
+        .bind(&receipt.nonce.value)
+        .bind(receipt.received_at)
+        .execute(&self.pool.get())
+        .await?;
+        Ok(receipt.clone())
+    }
+
+    #[instrument(name = "WebhookReceiptRepository.exists", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn exists(&self, source: &str, nonce: &str) -> Result<bool, DomainError> {
+        let exists = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM webhook_receipts WHERE source = $1 AND nonce = $2)",
+        )
+        .bind(source)
+        .bind(nonce)
+        .fetch_one(&self.pool.get())
+        .await?;
+        Ok(exists)
+    }
+
+    #[instrument(name = "WebhookReceiptRepository.delete", skip_all, fields(db.system = "postgresql", otel.kind = "client"))]
+    async fn delete(&self, source: &str, nonce: &str) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM webhook_receipts WHERE source = $1 AND nonce = $2")
+            .bind(source)
+            .bind(nonce)
+            .execute(&self.pool.get())
+            .await?;
+        Ok(())
+    }
+}
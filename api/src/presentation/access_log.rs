@@ -0,0 +1,340 @@
+// ============================================================================
+// ACCESS LOG - REQUEST ID E LOG DE ACESSO ESTRUTURADO
+// ============================================================================
+// Cada requisição recebe um request id (`X-Request-Id` do cliente, quando válido,
+// ou o trace id / um UUID novo), registrado no span `http.request` (e portanto em
+// todas as linhas de log da requisição), devolvido no header `X-Request-Id` e
+// incluído nas respostas de erro (problem+json, ver `error_mapper`)
+//
+// Ao final da requisição é escrita uma linha de log de acesso (target
+// `access_log`) com rota (template, nunca o path com ids ou emails), status,
+// latência, subject e a query string com os campos de dados pessoais
+// (LOG_REDACT_FIELDS) mascarados
+
+use crate::domain::value_objects::Principal;
+use crate::presentation::error_mapper::{Problem, PROBLEM_JSON};
+use axum::{
+    body::{Body, HttpBody},
+    extract::{MatchedPath, Request},
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    response::Response,
+};
+use once_cell::sync::OnceCell;
+use serde_json::Value;
+use std::future::Future;
+use std::time::Instant;
+
+/// Header com o identificador da requisição
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Campos mascarados quando `init` não é chamado
+pub const DEFAULT_REDACT_FIELDS: [&str; 5] = ["document", "email", "phone", "name", "search_term"];
+
+/// Valor que substitui os dados mascarados
+const REDACTED: &str = "[REDACTED]";
+
+/// Tamanho máximo de um request id aceito do cliente
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Corpos de erro maiores que isso são repassados sem o request id
+const MAX_ERROR_BODY: usize = 64 * 1024;
+
+/// Identificador da requisição, disponível nas extensões para os handlers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+static REDACT_FIELDS: OnceCell<Vec<String>> = OnceCell::new();
+
+/// Define os campos mascarados (comparação sem diferenciar maiúsculas; um campo
+/// é mascarado quando seu nome contém um dos termos, ex.: `email` cobre `emails`)
+pub fn init(fields: Vec<String>) -> anyhow::Result<()> {
+    let fields = fields
+        .into_iter()
+        .map(|field| field.trim().to_lowercase())
+        .filter(|field| !field.is_empty())
+        .collect();
+    REDACT_FIELDS
+        .set(fields)
+        .map_err(|_| anyhow::anyhow!("access log already initialised"))
+}
+
+/// O campo contém dados pessoais a mascarar?
+pub fn is_sensitive(field: &str) -> bool {
+    let field = field.to_lowercase();
+    match REDACT_FIELDS.get() {
+        Some(fields) => fields.iter().any(|term| field.contains(term.as_str())),
+        None => DEFAULT_REDACT_FIELDS
+            .iter()
+            .any(|term| field.contains(term)),
+    }
+}
+
+/// Query string com os valores dos campos sensíveis mascarados
+pub fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if is_sensitive(key) => format!("{key}={REDACTED}"),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Cópia do JSON com os valores dos campos sensíveis mascarados (em qualquer nível)
+pub fn redact_json(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive(key) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_json(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_json).collect()),
+        other => other.clone(),
+    }
+}
+
+/// `X-Request-Id` enviado pelo cliente, quando utilizável
+fn client_request_id(headers: &HeaderMap) -> Option<String> {
+    let id = headers.get(&REQUEST_ID_HEADER)?.to_str().ok()?.trim();
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    valid.then(|| id.to_string())
+}
+
+/// Atribui o request id, executa `next` e escreve o log de acesso
+///
+/// Usado pelo `LoggingMiddleware` do `MiddlewareSystem`, dentro do span
+/// `http.request` (que declara o campo `request_id`).
+pub async fn record<F, Fut>(mut req: Request, next: F) -> Response
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let started = Instant::now();
+    let span = tracing::Span::current();
+    let request_id = client_request_id(req.headers())
+        .or_else(|| crate::shared::telemetry::trace_id(&span))
+        .unwrap_or_else(crate::shared::generate_trace_id);
+    span.record("request_id", request_id.as_str());
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let method = req.method().clone();
+    // Template da rota: o path pode conter emails e documentos (ex.: /v1/users/by-email/{email})
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", |matched| matched.as_str())
+        .to_string();
+    let query = req.uri().query().map(redact_query);
+    let instance = req.uri().path().to_string();
+
+    let response = next(req).await;
+
+    let status = response.status();
+    let subject = response
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.subject.clone());
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    if status.is_server_error() {
+        tracing::warn!(
+            target: "access_log",
+            method = %method,
+            route = %route,
+            query = query.as_deref(),
+            status = status.as_u16(),
+            latency_ms,
+            subject = subject.as_deref(),
+            "request completed"
+        );
+    } else {
+        tracing::info!(
+            target: "access_log",
+            method = %method,
+            route = %route,
+            query = query.as_deref(),
+            status = status.as_u16(),
+            latency_ms,
+            subject = subject.as_deref(),
+            "request completed"
+        );
+    }
+
+    let mut response = if status.is_client_error() || status.is_server_error() {
+        with_problem_body(response, &instance, &request_id).await
+    } else {
+        response
+    };
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Completa o problema (`application/problem+json`) de uma resposta de erro com
+/// `instance` e `request_id`; respostas de erro em outro formato (sem corpo,
+/// texto das rejeições do axum, JSON `{"error": ...}`) são convertidas
+async fn with_problem_body(response: Response, instance: &str, request_id: &str) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    // Corpos grandes ou em streaming seguem como estão
+    match body.size_hint().exact() {
+        Some(len) if len as usize <= MAX_ERROR_BODY => {}
+        _ => return Response::from_parts(parts, body),
+    }
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_ERROR_BODY).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+
+    let mut problem = if content_type.starts_with(PROBLEM_JSON) {
+        match serde_json::from_slice::<Value>(&bytes) {
+            Ok(problem) if problem.is_object() => problem,
+            _ => return Response::from_parts(parts, Body::from(bytes)),
+        }
+    } else {
+        let detail = if content_type.starts_with("application/json") {
+            serde_json::from_slice::<Value>(&bytes)
+                .ok()
+                .and_then(|body| body.get("error")?.as_str().map(str::to_string))
+        } else if parts.status.is_client_error() {
+            // Rejeições dos extractors (JSON inválido, parâmetros) vêm como texto
+            String::from_utf8(bytes.to_vec()).ok()
+        } else {
+            None
+        };
+        let problem = Problem::from_status(parts.status);
+        match detail.as_deref().map(str::trim) {
+            Some(detail) if !detail.is_empty() => problem.with_detail(detail),
+            _ => problem,
+        }
+        .to_json()
+    };
+    if problem.get("instance").is_none() {
+        problem["instance"] = Value::from(instance);
+    }
+    if problem.get("request_id").is_none() {
+        problem["request_id"] = Value::from(request_id);
+    }
+
+    parts
+        .headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(problem.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, response::IntoResponse, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/v1/contacts/{id}", get(|| async { "ok" }))
+            .route(
+                "/v1/fail",
+                get(|| async {
+                    crate::presentation::error_mapper::map_domain_error(
+                        &crate::domain::errors::DomainError::NotFound(
+                            "Contact not found".to_string(),
+                        ),
+                    )
+                }),
+            )
+            .route(
+                "/v1/denied",
+                get(|| async { StatusCode::UNAUTHORIZED.into_response() }),
+            )
+            .layer(
+                crate::shared::middleware_system::MiddlewareSystem::new()
+                    .with(crate::shared::middleware_system::LoggingMiddleware::new()),
+            )
+    }
+
+    async fn call(uri: &str, request_id: Option<&str>) -> (Response, Value) {
+        let mut request = axum::http::Request::get(uri);
+        if let Some(id) = request_id {
+            request = request.header("x-request-id", id);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (Response::from_parts(parts, Body::empty()), json)
+    }
+
+    #[test]
+    fn test_redact_query_and_json() {
+        assert_eq!(
+            redact_query("email=ana%40example.com&limit=10&search_term=Ana"),
+            "email=[REDACTED]&limit=10&search_term=[REDACTED]"
+        );
+        let redacted = redact_json(&serde_json::json!({
+            "full_name": "Ana",
+            "status": "ACTIVE",
+            "emails": ["ana@example.com"],
+            "contact": { "document": "12345678901", "phones": [{ "e164": "+5511" }] },
+        }));
+        assert_eq!(
+            redacted,
+            serde_json::json!({
+                "full_name": "[REDACTED]",
+                "status": "ACTIVE",
+                "emails": "[REDACTED]",
+                "contact": { "document": "[REDACTED]", "phones": "[REDACTED]" },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_is_echoed_and_generated_otherwise() {
+        let (response, _) = call("/v1/contacts/1", Some("req-123")).await;
+        assert_eq!(response.headers()[&REQUEST_ID_HEADER], "req-123");
+
+        // Valor inválido (espaços, tamanho) é substituído por um id novo
+        let (response, _) = call("/v1/contacts/1", Some("not valid")).await;
+        let generated = response.headers()[&REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(!generated.is_empty());
+        assert_ne!(generated, "not valid");
+    }
+
+    #[tokio::test]
+    async fn test_error_bodies_are_problems_with_request_id() {
+        let (response, body) = call("/v1/fail", Some("req-404")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(body["code"], "not_found");
+        assert_eq!(body["detail"], "Contact not found");
+        assert_eq!(body["instance"], "/v1/fail");
+        assert_eq!(body["request_id"], "req-404");
+
+        // Falhas de middleware e handlers que retornam apenas o status
+        let (response, body) = call("/v1/denied", Some("req-401")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(body["code"], "unauthorized");
+        assert_eq!(body["title"], "Unauthorized");
+        assert_eq!(body["status"], 401);
+        assert_eq!(body["request_id"], "req-401");
+    }
+}
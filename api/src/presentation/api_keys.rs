@@ -0,0 +1,279 @@
+// ============================================================================
+// API KEYS - CREDENCIAIS DE CLIENTES MÁQUINA-A-MÁQUINA
+// ============================================================================
+// Endpoints administrativos para emitir, listar, rotacionar e revogar API keys
+// A chave em texto puro só é devolvida na emissão e na rotação; depois disso
+// apenas o prefixo público identifica a chave
+// Uso: `Authorization: ApiKey sut_<prefix>_<secret>`
+
+use crate::domain::value_objects::Principal;
+use crate::infra::api_keys::{generate_api_key, ApiKey};
+use crate::presentation::error_mapper::{custom_error, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Tamanho máximo do nome da chave
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Requisição de emissão de uma nova chave
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IssueApiKeyRequest {
+    pub name: String,
+    /// Escopos concedidos; passam pelo mesmo mapeamento de permissões dos JWTs
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Chave registrada (nunca contém o segredo)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            prefix: key.prefix,
+            scopes: key.scopes,
+            created_by: key.created_by,
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+        }
+    }
+}
+
+/// Chave emitida ou rotacionada, com o segredo em texto puro (exibido uma única vez)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IssuedApiKeyResponse {
+    #[serde(flatten)]
+    pub key: ApiKeyResponse,
+    pub api_key: String,
+}
+
+/// Rotas administrativas de API keys
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(list_api_keys, issue_api_key)) // GET, POST /v1/api-keys - Listar / emitir chaves
+        .routes(routes!(revoke_api_key)) // DELETE /v1/api-keys/{id} - Revogar chave
+        .routes(routes!(rotate_api_key)) // POST /v1/api-keys/{id}/rotate - Rotacionar segredo
+}
+
+/// Normaliza e valida a requisição de emissão
+fn validate_issue_request(request: IssueApiKeyRequest) -> Result<IssueApiKeyRequest, String> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "name must be between 1 and {} characters",
+            MAX_NAME_LENGTH
+        ));
+    }
+    if request.scopes.is_empty() {
+        return Err("scopes must not be empty".to_string());
+    }
+    if request
+        .scopes
+        .iter()
+        .any(|scope| scope.is_empty() || scope.chars().any(char::is_whitespace))
+    {
+        return Err("scopes must be non-empty and must not contain whitespace".to_string());
+    }
+    if request
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err("expires_at must be in the future".to_string());
+    }
+
+    let mut scopes = request.scopes;
+    scopes.sort();
+    scopes.dedup();
+    Ok(IssueApiKeyRequest {
+        name,
+        scopes,
+        expires_at: request.expires_at,
+    })
+}
+
+fn store_error(e: anyhow::Error) -> ErrorResponse {
+    error!(error = ?e, "API key store failure");
+    custom_error(StatusCode::INTERNAL_SERVER_ERROR, "API key store failure")
+}
+
+/// HANDLER: GET /v1/api-keys - LISTAR CHAVES
+#[utoipa::path(
+    get,
+    path = "/v1/api-keys",
+    tag = "api-keys",
+    summary = "Listar API keys",
+    responses((status = 200, description = "Chaves registradas (sem segredos)", body = Vec<ApiKeyResponse>))
+)]
+async fn list_api_keys(
+    State(state): State<Arc<crate::AppState>>,
+) -> Result<Json<Vec<ApiKeyResponse>>, ErrorResponse> {
+    let keys = state.api_key_store.list().await.map_err(store_error)?;
+    Ok(Json(keys.into_iter().map(ApiKeyResponse::from).collect()))
+}
+
+/// HANDLER: POST /v1/api-keys - EMITIR CHAVE
+#[utoipa::path(
+    post,
+    path = "/v1/api-keys",
+    tag = "api-keys",
+    summary = "Emitir API key",
+    request_body = IssueApiKeyRequest,
+    responses(
+        (status = 201, description = "Chave emitida; `api_key` só é exibida nesta resposta", body = IssuedApiKeyResponse),
+        (status = 400, description = "Nome, escopos ou expiração inválidos"),
+    )
+)]
+async fn issue_api_key(
+    State(state): State<Arc<crate::AppState>>,
+    principal: Principal,
+    Json(request): Json<IssueApiKeyRequest>,
+) -> Result<(StatusCode, Json<IssuedApiKeyResponse>), ErrorResponse> {
+    let request = validate_issue_request(request)
+        .map_err(|msg| custom_error(StatusCode::BAD_REQUEST, &msg))?;
+
+    let generated = generate_api_key();
+    let key = ApiKey {
+        id: Uuid::new_v4(),
+        name: request.name,
+        prefix: generated.prefix,
+        key_hash: generated.key_hash,
+        scopes: request.scopes,
+        created_by: Some(principal.subject),
+        created_at: Utc::now(),
+        expires_at: request.expires_at,
+        last_used_at: None,
+        revoked_at: None,
+    };
+    state
+        .api_key_store
+        .create(&key)
+        .await
+        .map_err(store_error)?;
+    info!(api_key_id = %key.id, prefix = %key.prefix, "API key issued");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(IssuedApiKeyResponse {
+            key: key.into(),
+            api_key: generated.key,
+        }),
+    ))
+}
+
+/// HANDLER: POST /v1/api-keys/{id}/rotate - ROTACIONAR SEGREDO
+///
+/// O segredo anterior deixa de valer imediatamente.
+#[utoipa::path(
+    post,
+    path = "/v1/api-keys/{id}/rotate",
+    tag = "api-keys",
+    summary = "Rotacionar o segredo de uma API key",
+    params(("id" = Uuid, Path, description = "ID da chave")),
+    responses(
+        (status = 200, description = "Novo segredo emitido", body = IssuedApiKeyResponse),
+        (status = 404, description = "Chave inexistente ou revogada"),
+    )
+)]
+async fn rotate_api_key(
+    State(state): State<Arc<crate::AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<IssuedApiKeyResponse>, ErrorResponse> {
+    let generated = generate_api_key();
+    let key = state
+        .api_key_store
+        .rotate(id, &generated.prefix, &generated.key_hash)
+        .await
+        .map_err(store_error)?
+        .ok_or_else(|| custom_error(StatusCode::NOT_FOUND, "API key not found or revoked"))?;
+    info!(api_key_id = %key.id, prefix = %key.prefix, "API key rotated");
+
+    Ok(Json(IssuedApiKeyResponse {
+        key: key.into(),
+        api_key: generated.key,
+    }))
+}
+
+/// HANDLER: DELETE /v1/api-keys/{id} - REVOGAR CHAVE
+#[utoipa::path(
+    delete,
+    path = "/v1/api-keys/{id}",
+    tag = "api-keys",
+    summary = "Revogar API key",
+    params(("id" = Uuid, Path, description = "ID da chave")),
+    responses(
+        (status = 204, description = "Chave revogada"),
+        (status = 404, description = "Chave inexistente ou já revogada"),
+    )
+)]
+async fn revoke_api_key(
+    State(state): State<Arc<crate::AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    if !state.api_key_store.revoke(id).await.map_err(store_error)? {
+        return Err(custom_error(
+            StatusCode::NOT_FOUND,
+            "API key not found or already revoked",
+        ));
+    }
+    info!(api_key_id = %id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: &str, scopes: &[&str]) -> IssueApiKeyRequest {
+        IssueApiKeyRequest {
+            name: name.to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_request_is_normalized() {
+        let request =
+            validate_issue_request(request("  etl  ", &["metrics.read", "etl", "etl"])).unwrap();
+        assert_eq!(request.name, "etl");
+        assert_eq!(request.scopes, vec!["etl", "metrics.read"]);
+    }
+
+    #[test]
+    fn test_issue_request_rejects_invalid_input() {
+        assert!(validate_issue_request(request(" ", &["etl"])).is_err());
+        assert!(validate_issue_request(request("etl", &[])).is_err());
+        assert!(validate_issue_request(request("etl", &["directory read"])).is_err());
+        assert!(validate_issue_request(IssueApiKeyRequest {
+            expires_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            ..request("etl", &["etl"])
+        })
+        .is_err());
+    }
+}
@@ -1,33 +1,46 @@
+use crate::domain::value_objects::Principal;
+use crate::infra::api_keys::{api_key_prefix, hash_api_key, ApiKey, ApiKeyStore};
+use crate::infra::revocation::{RevocationKind, RevocationStore, TokenRevocation};
+use crate::presentation::error_mapper::{custom_error, ErrorResponse};
+use crate::presentation::permissions::PermissionResolver;
 use anyhow::Context;
 use axum::{
-    extract::Request,
-    http::{HeaderMap, HeaderValue, StatusCode},
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, Method, StatusCode},
     middleware::Next,
     response::Response,
+    Json,
 };
 use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 use once_cell::sync::OnceCell;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{sleep, Duration};
 use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
 /// Claims JWT estruturadas
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JwtClaims {
-    pub iss: String,         // Issuer
-    pub sub: String,         // Subject (user ID)
-    pub aud: Vec<String>,    // Audience
-    pub exp: u64,            // Expiration time
-    pub iat: u64,            // Issued at
+    #[serde(default)]
+    pub iss: String, // Issuer
+    pub sub: String, // Subject (user ID)
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub aud: Vec<String>, // Audience
+    #[serde(default)]
+    pub exp: u64, // Expiration time
+    #[serde(default)]
+    pub iat: u64, // Issued at
     pub jti: Option<String>, // JWT ID
     pub typ: Option<String>, // Token type
     pub azp: Option<String>, // Authorized party
     pub session_state: Option<String>,
     pub realm_access: Option<RealmAccess>,
-    pub resource_access: Option<serde_json::Value>,
+    pub resource_access: Option<HashMap<String, ClientAccess>>,
     pub scope: Option<String>,
     pub sid: Option<String>, // Session ID
     pub email_verified: Option<bool>,
@@ -40,9 +53,78 @@ pub struct JwtClaims {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RealmAccess {
+    #[serde(default)]
     pub roles: Vec<String>,
 }
 
+/// Roles de um client em `resource_access`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClientAccess {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// `aud` pode ser uma string ou uma lista de strings (RFC 7519)
+fn string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(aud) => vec![aud],
+        OneOrMany::Many(aud) => aud,
+    })
+}
+
+impl From<JwtClaims> for Principal {
+    fn from(claims: JwtClaims) -> Self {
+        Principal {
+            username: claims.preferred_username.or(claims.name),
+            email: claims.email,
+            session_id: claims.sid.or(claims.session_state),
+            realm_roles: claims.realm_access.map(|r| r.roles).unwrap_or_default(),
+            client_roles: claims
+                .resource_access
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(client, access)| (client, access.roles))
+                .collect(),
+            scopes: claims
+                .scope
+                .as_deref()
+                .unwrap_or("")
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            subject: claims.sub,
+            // Preenchidas pelo PermissionResolver em `authenticate`
+            permissions: Default::default(),
+        }
+    }
+}
+
+/// Extractor do usuário autenticado inserido pelo `AuthMiddleware`
+impl<S> FromRequestParts<S> for Principal
+where
+    S: Send + Sync,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Principal>()
+            .cloned()
+            .ok_or_else(|| custom_error(StatusCode::UNAUTHORIZED, "Authentication required"))
+    }
+}
+
 /// Configuração de segurança JWT
 #[derive(Debug, Clone)]
 pub struct JwtSecurityConfig {
@@ -58,7 +140,17 @@ pub struct JwtSecurityConfig {
 impl Default for JwtSecurityConfig {
     fn default() -> Self {
         Self {
-            allowed_algorithms: vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512],
+            allowed_algorithms: vec![
+                Algorithm::RS256,
+                Algorithm::RS384,
+                Algorithm::RS512,
+                Algorithm::PS256,
+                Algorithm::PS384,
+                Algorithm::PS512,
+                Algorithm::ES256,
+                Algorithm::ES384,
+                Algorithm::EdDSA,
+            ],
             max_token_age: 3600, // 1 hora
             clock_skew: 60,      // 1 minuto
             require_audience: true,
@@ -69,34 +161,134 @@ impl Default for JwtSecurityConfig {
     }
 }
 
+impl JwtSecurityConfig {
+    /// Rejeita algoritmos simétricos (HS*): as chaves do JWKS são públicas
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.allowed_algorithms.is_empty() {
+            return Err(anyhow::anyhow!(
+                "at least one JWT algorithm must be allowed"
+            ));
+        }
+        if let Some(alg) = self
+            .allowed_algorithms
+            .iter()
+            .find(|alg| matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512))
+        {
+            return Err(anyhow::anyhow!(
+                "symmetric JWT algorithm {:?} cannot be used with JWKS",
+                alg
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Chave de verificação obtida do JWKS, com os algoritmos que ela pode validar
+pub struct JwkKey {
+    pub key: DecodingKey,
+    pub algorithms: Vec<Algorithm>,
+}
+
+impl JwkKey {
+    /// Converte uma entrada do JWKS (RSA, EC ou OKP) em chave de verificação
+    ///
+    /// Quando a JWK declara `alg`, a chave só valida tokens desse algoritmo;
+    /// sem `alg`, o conjunto é inferido de `kty`/`crv`.
+    pub fn from_jwk(jwk: &serde_json::Value) -> Option<Self> {
+        if jwk["use"].as_str().is_some_and(|u| u != "sig") {
+            return None;
+        }
+
+        let (key, algorithms) = match (jwk["kty"].as_str()?, jwk["crv"].as_str()) {
+            ("RSA", _) => (
+                DecodingKey::from_rsa_components(jwk["n"].as_str()?, jwk["e"].as_str()?).ok()?,
+                vec![
+                    Algorithm::RS256,
+                    Algorithm::RS384,
+                    Algorithm::RS512,
+                    Algorithm::PS256,
+                    Algorithm::PS384,
+                    Algorithm::PS512,
+                ],
+            ),
+            ("EC", Some(crv)) => (
+                DecodingKey::from_ec_components(jwk["x"].as_str()?, jwk["y"].as_str()?).ok()?,
+                match crv {
+                    "P-256" => vec![Algorithm::ES256],
+                    "P-384" => vec![Algorithm::ES384],
+                    _ => return None,
+                },
+            ),
+            ("OKP", Some("Ed25519")) => (
+                DecodingKey::from_ed_components(jwk["x"].as_str()?).ok()?,
+                vec![Algorithm::EdDSA],
+            ),
+            _ => return None,
+        };
+
+        let algorithms = match jwk["alg"].as_str() {
+            None => algorithms,
+            // `alg` declarado precisa ser compatível com o tipo da chave
+            Some(alg) => {
+                let alg = alg.parse::<Algorithm>().ok()?;
+                if !algorithms.contains(&alg) {
+                    return None;
+                }
+                vec![alg]
+            }
+        };
+
+        Some(Self { key, algorithms })
+    }
+}
+
 /// Cache de tokens revogados
-#[derive(Debug, Clone)]
+///
+/// Cópia em memória da tabela `revoked_tokens`, consultada a cada requisição.
+/// Cada entrada revoga os tokens cuja claim (`jti`, `sid` ou `sub`) tem o valor
+/// informado, até o instante de expiração (segundos desde a época Unix).
+#[derive(Debug, Clone, Default)]
 pub struct TokenBlacklist {
-    tokens: std::sync::Arc<parking_lot::RwLock<HashSet<String>>>,
+    tokens: std::sync::Arc<parking_lot::RwLock<HashMap<(RevocationKind, String), i64>>>,
 }
 
 impl TokenBlacklist {
     pub fn new() -> Self {
-        Self {
-            tokens: std::sync::Arc::new(parking_lot::RwLock::new(HashSet::new())),
-        }
+        Self::default()
     }
 
-    pub fn add(&self, jti: String) {
-        self.tokens.write().insert(jti);
+    pub fn add(&self, kind: RevocationKind, value: String, expires_at: i64) {
+        let mut tokens = self.tokens.write();
+        let entry = tokens.entry((kind, value)).or_insert(expires_at);
+        *entry = (*entry).max(expires_at);
     }
 
-    pub fn contains(&self, jti: &str) -> bool {
-        self.tokens.read().contains(jti)
+    pub fn contains(&self, kind: RevocationKind, value: &str) -> bool {
+        let now = chrono::Utc::now().timestamp();
+        self.tokens
+            .read()
+            .get(&(kind, value.to_string()))
+            .is_some_and(|expires_at| *expires_at > now)
     }
 
-    pub fn remove(&self, jti: &str) {
-        self.tokens.write().remove(jti);
+    /// Substitui o conteúdo do cache pelas revogações vigentes no banco
+    pub fn replace(&self, revocations: &[TokenRevocation]) {
+        let tokens = revocations
+            .iter()
+            .map(|r| ((r.kind, r.value.clone()), r.expires_at.timestamp()))
+            .collect();
+        *self.tokens.write() = tokens;
     }
 
-    pub fn clear_expired(&self, current_time: u64) {
-        // Implementar limpeza de tokens expirados se necessário
-        // Por simplicidade, mantemos todos os tokens por enquanto
+    /// Verifica se o token foi revogado pelo `jti`, pela sessão (`sid`) ou pelo usuário (`sub`)
+    pub fn is_revoked(&self, claims: &JwtClaims) -> bool {
+        [
+            (RevocationKind::Jti, claims.jti.as_deref()),
+            (RevocationKind::Sid, claims.sid.as_deref()),
+            (RevocationKind::Sub, Some(claims.sub.as_str())),
+        ]
+        .into_iter()
+        .any(|(kind, value)| value.is_some_and(|value| self.contains(kind, value)))
     }
 }
 
@@ -105,6 +297,10 @@ pub struct Jwks {
     pub uri: String,
     http: Client,
     keys: std::sync::Arc<parking_lot::RwLock<serde_json::Value>>,
+    /// Chaves confiáveis registradas localmente (não substituídas pelo refresh)
+    trusted: std::sync::Arc<parking_lot::RwLock<Vec<serde_json::Value>>>,
+    /// Último refresh bem-sucedido do JWKS remoto
+    refreshed_at: std::sync::Arc<parking_lot::RwLock<Option<chrono::DateTime<chrono::Utc>>>>,
 }
 impl Jwks {
     pub fn new(uri: String) -> Self {
@@ -112,8 +308,15 @@ impl Jwks {
             uri,
             http: Client::new(),
             keys: std::sync::Arc::new(parking_lot::RwLock::new(serde_json::json!({}))),
+            trusted: Default::default(),
+            refreshed_at: Default::default(),
         }
     }
+    /// Registra uma JWK confiável além das obtidas do JWKS remoto
+    pub fn trust(&self, jwk: serde_json::Value) {
+        self.trusted.write().push(jwk);
+    }
+    #[tracing::instrument(name = "jwks.fetch", skip(self), fields(otel.kind = "client", url.full = %self.uri))]
     pub async fn refresh(&self) -> anyhow::Result<()> {
         // Retry the JWKS fetch a few times with exponential backoff to handle
         // short startup races where Keycloak may not yet be ready.
@@ -122,10 +325,14 @@ impl Jwks {
         let mut wait = Duration::from_millis(500);
         loop {
             attempt += 1;
-            match self.http.get(&self.uri).send().await {
+            match crate::shared::telemetry::propagate(self.http.get(&self.uri))
+                .send()
+                .await
+            {
                 Ok(resp) => {
                     let v: serde_json::Value = resp.json().await.context("parsing JWKS")?;
                     *self.keys.write() = v;
+                    *self.refreshed_at.write() = Some(chrono::Utc::now());
                     return Ok(());
                 }
                 Err(e) => {
@@ -143,36 +350,81 @@ impl Jwks {
             }
         }
     }
-    pub fn decoding_key(&self, kid: &str) -> Option<DecodingKey> {
+    pub fn decoding_key(&self, kid: &str) -> Option<JwkKey> {
         let keys = self.keys.read();
-        for k in keys["keys"].as_array().unwrap_or(&vec![]) {
-            if k["kid"].as_str() == Some(kid) && k["kty"] == "RSA" {
-                if let (Some(n), Some(e)) = (k["n"].as_str(), k["e"].as_str()) {
-                    return DecodingKey::from_rsa_components(n, e).ok();
-                }
-            }
-        }
-        None
+        let trusted = self.trusted.read();
+        keys["keys"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .chain(trusted.iter())
+            .filter(|k| k["kid"].as_str() == Some(kid))
+            .find_map(JwkKey::from_jwk)
     }
 }
 
-/// Return true if we have at least one key loaded in the JWKS store.
-pub fn jwks_has_keys() -> bool {
-    if let Some(auth_state) = AUTH.get() {
-        let keys = auth_state.jwks.keys.read();
-        return keys["keys"]
-            .as_array()
-            .map(|a| !a.is_empty())
-            .unwrap_or(false);
+/// Chaves carregadas e idade do JWKS (health check)
+#[derive(Debug, Clone)]
+pub struct JwksStatus {
+    pub uri: String,
+    /// Chaves obtidas do JWKS remoto
+    pub remote_keys: usize,
+    /// Chaves confiáveis registradas localmente (emissor local, tokens de desenvolvimento)
+    pub trusted_keys: usize,
+    pub refreshed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Estado atual do JWKS (`None` enquanto a autenticação não foi inicializada)
+pub fn jwks_status() -> Option<JwksStatus> {
+    let jwks = &AUTH.get()?.jwks;
+    let remote_keys = jwks.keys.read()["keys"]
+        .as_array()
+        .map_or(0, |keys| keys.len());
+    Some(JwksStatus {
+        uri: jwks.uri.clone(),
+        remote_keys,
+        trusted_keys: jwks.trusted.read().len(),
+        refreshed_at: *jwks.refreshed_at.read(),
+    })
+}
+
+/// Chaves carregadas e idade do JWKS (métricas)
+pub struct JwksMetrics;
+
+#[async_trait::async_trait]
+impl crate::shared::metrics::MetricsSource for JwksMetrics {
+    fn name(&self) -> &str {
+        "jwks"
+    }
+
+    async fn collect(&self) -> anyhow::Result<()> {
+        let status = jwks_status().ok_or_else(|| anyhow::anyhow!("auth not initialized"))?;
+        let age = status
+            .refreshed_at
+            .map(|at| (chrono::Utc::now() - at).num_milliseconds().max(0) as f64 / 1000.0);
+        crate::shared::metrics::set_jwks(status.remote_keys, status.trusted_keys, age);
+        Ok(())
     }
-    false
+}
+
+/// Confia em uma chave de assinatura local (ex.: chave efêmera dos tokens de desenvolvimento)
+pub fn trust_signing_key(jwk: serde_json::Value) -> anyhow::Result<()> {
+    let auth_state = AUTH
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("auth not initialized"))?;
+    auth_state.jwks.trust(jwk);
+    Ok(())
 }
 
 /// Refresh the JWKS from the configured URI. Returns an error if auth not initialized
 /// or the refresh fails.
 pub async fn refresh_jwks() -> anyhow::Result<()> {
     if let Some(auth_state) = AUTH.get() {
-        auth_state.jwks.refresh().await
+        let result = auth_state.jwks.refresh().await;
+        if result.is_err() {
+            crate::shared::metrics::record_jwks_refresh_failure();
+        }
+        result
     } else {
         Err(anyhow::anyhow!("auth not initialized"))
     }
@@ -182,14 +434,54 @@ pub struct AuthConfig {
     pub jwks_uri: String,
     pub issuer: Option<String>,
     pub audiences: Vec<String>,
-    pub leeway_secs: u64,
+    /// Algoritmos aceitos, idade máxima do token e tolerância de relógio
+    pub security: JwtSecurityConfig,
+    /// Mapeamento de roles, roles de client e escopos para permissões
+    pub permissions: PermissionResolver,
+    /// Armazenamento persistente da lista de revogação
+    pub revocation_store: Arc<dyn RevocationStore>,
+    /// Validade das revogações sem expiração conhecida (sessão, usuário)
+    pub revocation_ttl_secs: u64,
+    /// API keys de clientes máquina-a-máquina (`Authorization: ApiKey ...`)
+    pub api_key_store: Arc<dyn ApiKeyStore>,
 }
 
 struct AuthState {
     jwks: Jwks,
     issuer: Option<String>,
     audiences: Vec<String>,
-    leeway: u64,
+    security: JwtSecurityConfig,
+    permissions: PermissionResolver,
+    blacklist: TokenBlacklist,
+    revocation_store: Arc<dyn RevocationStore>,
+    revocation_ttl: u64,
+    api_key_store: Arc<dyn ApiKeyStore>,
+}
+
+impl AuthState {
+    /// Monta o usuário autenticado com suas permissões efetivas
+    fn principal(&self, claims: JwtClaims) -> Principal {
+        let mut principal = Principal::from(claims);
+        principal.permissions = self.permissions.resolve(&principal);
+        principal
+    }
+
+    /// Monta o principal de uma API key; os escopos da chave passam pelo
+    /// mesmo mapeamento de permissões aplicado aos escopos de um JWT
+    fn api_key_principal(&self, key: &ApiKey) -> Principal {
+        let mut principal = Principal {
+            subject: format!("apikey:{}", key.id),
+            username: Some(key.name.clone()),
+            email: None,
+            session_id: None,
+            realm_roles: vec![],
+            client_roles: Default::default(),
+            scopes: key.scopes.clone(),
+            permissions: Default::default(),
+        };
+        principal.permissions = self.permissions.resolve(&principal);
+        principal
+    }
 }
 
 static AUTH: OnceCell<AuthState> = OnceCell::new();
@@ -199,93 +491,186 @@ pub async fn init(config: AuthConfig) -> anyhow::Result<()> {
         jwks_uri,
         issuer,
         audiences,
-        leeway_secs,
+        security,
+        permissions,
+        revocation_store,
+        revocation_ttl_secs,
+        api_key_store,
     } = config;
+    security.validate()?;
+    if security.require_issuer && issuer.is_none() {
+        tracing::warn!("JWT issuer required but not configured; only its presence will be checked");
+    }
     let jwks = Jwks::new(jwks_uri);
     // Try an initial refresh but do not fail startup if Keycloak is still
     // warming up. We log a warning and continue — the main background
     // refresher will attempt to update keys periodically.
     if let Err(e) = jwks.refresh().await {
+        crate::shared::metrics::record_jwks_refresh_failure();
         tracing::warn!(error = ?e, "initial JWKS fetch failed; continuing without keys — will refresh in background");
     }
+    let blacklist = TokenBlacklist::new();
+    match revocation_store.active().await {
+        Ok(revocations) => blacklist.replace(&revocations),
+        Err(e) => {
+            tracing::warn!(error = ?e, "initial revocation list load failed; will sync in background")
+        }
+    }
     let state = AuthState {
         jwks,
         issuer,
         audiences,
-        leeway: leeway_secs,
+        security,
+        permissions,
+        blacklist,
+        revocation_store,
+        revocation_ttl: revocation_ttl_secs,
+        api_key_store,
     };
     AUTH.set(state)
         .map_err(|_| anyhow::anyhow!("auth already initialised"))?;
     Ok(())
 }
 
-pub async fn jwt_middleware(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    tracing::info!("Entering jwt_middleware");
-
-    // Optional development bypass. Only active when DEV_AUTH_BYPASS=1 AND we are not in production.
-    let dev_bypass_enabled = matches!(std::env::var("DEV_AUTH_BYPASS"), Ok(ref v) if v == "1");
-    let is_production =
-        matches!(std::env::var("RUST_ENV"), Ok(ref v) if v.eq_ignore_ascii_case("production"));
+/// Consulta a lista de revogação em memória (ex.: refresh tokens emitidos localmente)
+pub fn is_revoked(claims: &JwtClaims) -> bool {
+    AUTH.get()
+        .is_some_and(|auth_state| auth_state.blacklist.is_revoked(claims))
+}
 
-    if dev_bypass_enabled && is_production {
-        tracing::warn!("DEV_AUTH_BYPASS=1 ignored because RUST_ENV=production");
-    }
+/// Revoga tokens pela claim informada, persistindo e atualizando o cache local
+///
+/// Sem `expires_at`, a revogação vale pelo TTL configurado (`TOKEN_REVOCATION_TTL_SECS`).
+pub async fn revoke(
+    kind: RevocationKind,
+    value: &str,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    reason: Option<String>,
+) -> anyhow::Result<TokenRevocation> {
+    let auth_state = AUTH
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("auth not initialized"))?;
+    let revocation = TokenRevocation {
+        kind,
+        value: value.to_string(),
+        reason,
+        expires_at: expires_at.unwrap_or_else(|| {
+            chrono::Utc::now() + chrono::Duration::seconds(auth_state.revocation_ttl as i64)
+        }),
+    };
+    auth_state.revocation_store.revoke(&revocation).await?;
+    auth_state.blacklist.add(
+        revocation.kind,
+        revocation.value.clone(),
+        revocation.expires_at.timestamp(),
+    );
+    tracing::info!(kind = %revocation.kind, reason = ?revocation.reason, "token revoked");
+    Ok(revocation)
+}
 
-    if dev_bypass_enabled && !is_production {
-        if let Some(dev_user) = req
-            .headers()
-            .get("x-dev-user")
-            .and_then(|v| v.to_str().ok())
-        {
-            let roles: Vec<String> = req
-                .headers()
-                .get("x-dev-roles")
-                .and_then(|v| v.to_str().ok())
-                .map(|raw| {
-                    raw.split(',')
-                        .map(|s| s.trim())
-                        .filter(|s| !s.is_empty())
-                        .map(str::to_string)
-                        .collect()
-                })
-                .filter(|roles: &Vec<String>| !roles.is_empty())
-                .unwrap_or_else(|| vec!["directory.read".to_string()]);
-
-            tracing::info!(
-                "DEV_AUTH_BYPASS active for synthetic user {} with roles {:?}",
-                dev_user,
-                roles
-            );
-            let scope = roles
-                .iter()
-                .map(|role| role.as_str())
-                .collect::<Vec<_>>()
-                .join(" ");
-            let claims = serde_json::json!({
-                "sub": dev_user,
-                "realm_access": { "roles": roles },
-                "scope": scope,
-            });
-            req.extensions_mut().insert(claims);
-            return Ok(next.run(req).await);
-        } else {
-            tracing::warn!("DEV_AUTH_BYPASS active but X-Dev-User header missing or invalid");
-            return Err(StatusCode::UNAUTHORIZED);
-        }
-    }
+/// Remove revogações expiradas e recarrega o cache local a partir do banco,
+/// incorporando revogações feitas por outras instâncias
+pub async fn sync_revocations() -> anyhow::Result<()> {
+    let auth_state = AUTH
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("auth not initialized"))?;
+    auth_state.revocation_store.purge_expired().await?;
+    let revocations = auth_state.revocation_store.active().await?;
+    auth_state.blacklist.replace(&revocations);
+    Ok(())
+}
 
+/// Autentica a requisição (bearer token ou `ApiKey`) e insere o `Principal`
+///
+/// Executado pelo `AuthMiddleware` do `MiddlewareSystem`.
+pub async fn authenticate(req: &mut Request) -> Result<(), StatusCode> {
     let auth_header = req
         .headers()
         .get(axum::http::header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
-    if !auth_header.starts_with("Bearer ") {
+    if let Some(key) = auth_header.strip_prefix("ApiKey ") {
+        let principal = authenticate_api_key(key).await?;
+        req.extensions_mut().insert(principal);
+        return Ok(());
+    }
+    let Some(token) = auth_header.strip_prefix("Bearer ") else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    let auth_state = AUTH.get().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let claims = validate_token(auth_state, token).await?;
+
+    if auth_state.blacklist.is_revoked(&claims) {
+        tracing::warn!("Rejected revoked token");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    req.extensions_mut().insert(auth_state.principal(claims));
+    Ok(())
+}
+
+/// Autentica uma API key (`sut_<prefix>_<secret>`) e devolve o principal correspondente
+///
+/// Também usada fora do `authenticate` (ex.: `/metrics` e webhooks).
+pub async fn authenticate_api_key(key: &str) -> Result<Principal, StatusCode> {
+    let auth_state = AUTH.get().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    validate_api_key(auth_state, key).await
+}
+
+async fn validate_api_key(auth_state: &AuthState, key: &str) -> Result<Principal, StatusCode> {
+    let key = key.trim();
+    let prefix = api_key_prefix(key).ok_or(StatusCode::UNAUTHORIZED)?;
+    let record = auth_state
+        .api_key_store
+        .find_by_prefix(prefix)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "API key lookup failed");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    // Compara os hashes: o tempo da comparação não revela nada sobre o segredo
+    if hash_api_key(key) != record.key_hash {
+        debug!(prefix = %prefix, "API key secret mismatch");
         return Err(StatusCode::UNAUTHORIZED);
     }
-    let token = &auth_header[7..];
+    if !record.is_active(chrono::Utc::now()) {
+        debug!(prefix = %prefix, "API key revoked or expired");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    // A lista de revogação também vale para a chave, pelo `sub` (`apikey:<id>`)
+    let principal = auth_state.api_key_principal(&record);
+    if auth_state
+        .blacklist
+        .contains(RevocationKind::Sub, &principal.subject)
+    {
+        warn!(prefix = %prefix, "Rejected revoked API key");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    // Registro de uso fora do caminho da requisição
+    let store = auth_state.api_key_store.clone();
+    tokio::spawn(async move {
+        if let Err(e) = store.touch(record.id).await {
+            warn!(error = ?e, "failed to record API key usage");
+        }
+    });
+    Ok(principal)
+}
+
+/// Valida assinatura e claims de um token conforme o `JwtSecurityConfig`
+///
+/// O algoritmo do header precisa estar na lista permitida e entre os algoritmos
+/// da chave (`kid`) usada; `iat` mais antigo que `max_token_age` é rejeitado.
+async fn validate_token(auth_state: &AuthState, token: &str) -> Result<JwtClaims, StatusCode> {
+    let security = &auth_state.security;
     let header = decode_header(token).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if !security.allowed_algorithms.contains(&header.alg) {
+        debug!(alg = ?header.alg, "JWT algorithm not allowed");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
     let kid = header.kid.ok_or(StatusCode::UNAUTHORIZED)?;
-    let auth_state = AUTH.get().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
     if auth_state.jwks.decoding_key(&kid).is_none() {
         auth_state
             .jwks
@@ -293,76 +678,157 @@ pub async fn jwt_middleware(mut req: Request, next: Next) -> Result<Response, St
             .await
             .map_err(|_| StatusCode::UNAUTHORIZED)?;
     }
-    let Some(decoding_key) = auth_state.jwks.decoding_key(&kid) else {
+    let Some(jwk) = auth_state.jwks.decoding_key(&kid) else {
         return Err(StatusCode::UNAUTHORIZED);
     };
+    if !jwk.algorithms.contains(&header.alg) {
+        debug!(alg = ?header.alg, kid = %kid, "JWT algorithm does not match key");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
-    let mut validation = Validation::new(Algorithm::RS256);
-    validation.validate_exp = true;
-    validation.validate_nbf = true;
-    validation.leeway = auth_state.leeway;
-    validation.required_spec_claims.extend([
-        "exp".to_string(),
-        "iat".to_string(),
-        "nbf".to_string(),
-    ]);
+    let mut validation = Validation::new(header.alg);
+    validation.validate_exp = security.validate_exp;
+    validation.validate_nbf = security.validate_nbf;
+    validation.leeway = security.clock_skew;
+    validation.required_spec_claims.clear();
+    validation.required_spec_claims.insert("iat".to_string());
+    if security.validate_exp {
+        validation.required_spec_claims.insert("exp".to_string());
+    }
+    // `nbf` é opcional (o Keycloak não o emite): validado só quando presente
+    if security.require_issuer {
+        validation.required_spec_claims.insert("iss".to_string());
+    }
     if let Some(issuer) = &auth_state.issuer {
         let mut issuers = HashSet::new();
         issuers.insert(issuer.clone());
         validation.iss = Some(issuers);
     }
     // Do NOT set audience in the library validator; we'll verify aud/azp manually below
+    validation.validate_aud = false;
+
+    let data =
+        decode::<JwtClaims>(token, &jwk.key, &validation).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let claims = data.claims;
+
+    // Refresh tokens (`typ: Refresh`) só valem no endpoint de token, nunca como access token
+    if claims
+        .typ
+        .as_deref()
+        .is_some_and(|typ| typ.eq_ignore_ascii_case("refresh"))
+    {
+        debug!("Refresh token presented as access token");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
-    let data = decode::<serde_json::Value>(token, &decoding_key, &validation)
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
     // Manual audience check: accept if aud contains any configured audience OR if azp equals one
+    let azp = claims.azp.as_deref();
+    if security.require_audience && claims.aud.is_empty() && azp.is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
     if !auth_state.audiences.is_empty() {
-        let mut aud_ok = false;
-        if let Some(aud_val) = data.claims.get("aud") {
-            match aud_val {
-                serde_json::Value::String(aud_str) => {
-                    if auth_state.audiences.iter().any(|a| a == aud_str) {
-                        aud_ok = true;
-                    }
-                }
-                serde_json::Value::Array(arr) => {
-                    let set: std::collections::HashSet<&str> =
-                        arr.iter().filter_map(|v| v.as_str()).collect();
-                    if auth_state
-                        .audiences
-                        .iter()
-                        .any(|a| set.contains(a.as_str()))
-                    {
-                        aud_ok = true;
-                    }
-                }
-                _ => {}
-            }
-        }
-        if !aud_ok {
-            if let Some(azp) = data.claims.get("azp").and_then(|v| v.as_str()) {
-                if auth_state.audiences.iter().any(|a| a == azp) {
-                    aud_ok = true;
-                }
-            }
-        }
+        let aud_ok = auth_state
+            .audiences
+            .iter()
+            .any(|a| claims.aud.contains(a) || azp == Some(a.as_str()));
         if !aud_ok {
             return Err(StatusCode::UNAUTHORIZED);
         }
     }
 
-    if let Some(exp) = data.claims.get("exp").and_then(|x| x.as_i64()) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .as_secs() as i64;
-        if now - exp > auth_state.leeway as i64 {
-            return Err(StatusCode::UNAUTHORIZED);
-        }
+    // Idade máxima do token, a partir de `iat`
+    let iat = claims.iat as i64;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .as_secs() as i64;
+    let skew = security.clock_skew as i64;
+    if iat - now > skew {
+        debug!("JWT issued in the future");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    if security.max_token_age > 0 && now - iat > security.max_token_age as i64 + skew {
+        debug!("JWT older than max_token_age");
+        return Err(StatusCode::UNAUTHORIZED);
     }
 
-    req.extensions_mut().insert(data.claims);
-    Ok(next.run(req).await)
+    Ok(claims)
+}
+
+// ===== REVOGAÇÃO DE TOKENS (ADMIN) =====
+
+/// Requisição de revogação: um token (`jti`), uma sessão (`sid`) ou um usuário (`sub`)
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RevokeTokenRequest {
+    pub kind: RevocationKind,
+    pub value: String,
+    /// Expiração do token revogado; padrão: agora + TOKEN_REVOCATION_TTL_SECS
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevokeTokenResponse {
+    pub kind: RevocationKind,
+    pub value: String,
+    pub reason: Option<String>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Rotas administrativas de autenticação
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new().routes(routes!(revoke_token_handler)) // POST /v1/auth/revocations - Revogar token ou sessão
+}
+
+/// HANDLER: POST /v1/auth/revocations - REVOGAR TOKEN, SESSÃO OU USUÁRIO
+#[utoipa::path(
+    post,
+    path = "/v1/auth/revocations",
+    tag = "auth",
+    summary = "Revogar token, sessão ou usuário",
+    request_body = RevokeTokenRequest,
+    responses(
+        (status = 201, description = "Revogação registrada", body = RevokeTokenResponse),
+        (status = 400, description = "Valor vazio ou expiração no passado"),
+    )
+)]
+async fn revoke_token_handler(
+    Json(request): Json<RevokeTokenRequest>,
+) -> Result<(StatusCode, Json<RevokeTokenResponse>), ErrorResponse> {
+    let value = request.value.trim();
+    if value.is_empty() {
+        return Err(custom_error(
+            StatusCode::BAD_REQUEST,
+            "value must not be empty",
+        ));
+    }
+    if request
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    {
+        return Err(custom_error(
+            StatusCode::BAD_REQUEST,
+            "expires_at must be in the future",
+        ));
+    }
+
+    let revocation = revoke(request.kind, value, request.expires_at, request.reason)
+        .await
+        .map_err(|e| {
+            error!(error = ?e, "failed to revoke token");
+            custom_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to revoke token")
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(RevokeTokenResponse {
+            kind: revocation.kind,
+            value: revocation.value,
+            reason: revocation.reason,
+            expires_at: revocation.expires_at,
+        }),
+    ))
 }
 
 /// Middleware para verificar roles específicas
@@ -371,63 +837,48 @@ pub async fn require_role_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Extrair claims do request
-    let claims = request
+    // Extrair o usuário autenticado do request
+    let principal = request
         .extensions()
-        .get::<serde_json::Value>()
+        .get::<Principal>()
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // Extrair roles do realm_access
-    let user_roles = claims
-        .get("realm_access")
-        .and_then(|ra| ra.get("roles"))
-        .and_then(|roles| roles.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>()
-        })
-        .unwrap_or_default();
-
-    // Verificar se o usuário tem pelo menos uma das roles necessárias
-    let has_required_role = required_roles
-        .iter()
-        .any(|required_role| user_roles.contains(required_role));
-
-    if !has_required_role {
-        let user_id = claims
-            .get("sub")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
+    // Verificar se o usuário tem pelo menos uma das permissões necessárias
+    // (roles do realm, roles de client e escopos, conforme o PermissionResolver)
+    if !principal.has_any_permission(&required_roles) {
         tracing::warn!(
-            user_id = %user_id,
-            user_roles = ?user_roles,
+            user_id = %principal.subject,
+            permissions = ?principal.permissions,
             required_roles = ?required_roles,
             "Access denied: insufficient permissions"
         );
         return Err(StatusCode::FORBIDDEN);
     }
 
-    let user_id = claims
-        .get("sub")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
     tracing::info!(
-        user_id = %user_id,
-        roles = ?user_roles,
+        user_id = %principal.subject,
+        permissions = ?principal.permissions,
         "Role-based access granted"
     );
 
     Ok(next.run(request).await)
 }
 
+/// Permissões aceitas pelas rotas administrativas
+pub const ADMIN_ROLES: &[&str] = &["admin"];
+
+/// Permissões aceitas pelas rotas do diretório (contatos, unidades e departamentos)
+pub const READ_ROLES: &[&str] = &["directory.read", "admin"];
+
+/// Permissões exigidas, além da leitura, pelas alterações no diretório
+pub const WRITE_ROLES: &[&str] = &["directory.write", "admin"];
+
 /// Middleware para verificar se o usuário é admin
 pub async fn require_admin_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    require_role_middleware(vec!["admin".to_string()], request, next).await
+    require_role_middleware(to_roles(ADMIN_ROLES), request, next).await
 }
 
 /// Middleware para verificar se o usuário pode ler dados
@@ -435,25 +886,22 @@ pub async fn require_read_permission_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    require_role_middleware(
-        vec!["directory.read".to_string(), "admin".to_string()],
-        request,
-        next,
-    )
-    .await
+    require_role_middleware(to_roles(READ_ROLES), request, next).await
 }
 
-/// Middleware para verificar se o usuário pode escrever dados
+fn to_roles(roles: &[&str]) -> Vec<String> {
+    roles.iter().map(|role| role.to_string()).collect()
+}
+
+/// Middleware para verificar se o usuário pode escrever dados (GET e HEAD passam direto)
 pub async fn require_write_permission_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    require_role_middleware(
-        vec!["directory.write".to_string(), "admin".to_string()],
-        request,
-        next,
-    )
-    .await
+    if matches!(*request.method(), Method::GET | Method::HEAD) {
+        return Ok(next.run(request).await);
+    }
+    require_role_middleware(to_roles(WRITE_ROLES), request, next).await
 }
 
 /// Middleware para verificar se o usuário pode acessar dados PII
@@ -482,52 +930,502 @@ pub async fn require_merge_permission_middleware(
     .await
 }
 
-/// Função auxiliar para extrair user ID das claims
-pub fn extract_user_id(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("sub")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::api_keys::InMemoryApiKeyStore;
+    use crate::infra::revocation::InMemoryRevocationStore;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use once_cell::sync::Lazy;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use rsa::{pkcs1::EncodeRsaPrivateKey, traits::PublicKeyParts, RsaPrivateKey};
+    use serde_json::json;
 
-/// Função auxiliar para extrair username das claims
-pub fn extract_username(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("preferred_username")
-        .or_else(|| claims.get("name"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
+    const ISSUER: &str = "https://auth.example.com/realms/sut";
+    const AUDIENCE: &str = "sut-api";
 
-/// Função auxiliar para extrair email das claims
-pub fn extract_email(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("email")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-}
+    /// Chave de assinatura gerada para os testes e sua JWK pública
+    struct TestKey {
+        encoding: EncodingKey,
+        jwk: serde_json::Value,
+    }
 
-/// Função auxiliar para extrair roles das claims
-pub fn extract_roles(claims: &serde_json::Value) -> Vec<String> {
-    claims
-        .get("realm_access")
-        .and_then(|ra| ra.get("roles"))
-        .and_then(|roles| roles.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str())
-                .map(|s| s.to_string())
-                .collect()
-        })
-        .unwrap_or_default()
-}
+    // A geração de chaves RSA é lenta em debug: uma chave para todos os testes
+    static RSA_KEY: Lazy<(Vec<u8>, serde_json::Value)> = Lazy::new(|| {
+        let key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let jwk = json!({
+            "kty": "RSA",
+            "n": URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+            "e": URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+        });
+        (key.to_pkcs1_der().unwrap().as_bytes().to_vec(), jwk)
+    });
 
-/// Função auxiliar para verificar se o usuário tem uma role específica
-pub fn has_role(claims: &serde_json::Value, role: &str) -> bool {
-    extract_roles(claims).contains(&role.to_string())
-}
+    fn rsa_key(kid: &str) -> TestKey {
+        let (der, jwk) = &*RSA_KEY;
+        let mut jwk = jwk.clone();
+        jwk["kid"] = json!(kid);
+        TestKey {
+            encoding: EncodingKey::from_rsa_der(der),
+            jwk,
+        }
+    }
+
+    fn ec_key(kid: &str) -> TestKey {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+            .unwrap();
+        // Ponto não comprimido: 0x04 || x || y
+        let point = pair.public_key().as_ref();
+        TestKey {
+            encoding: EncodingKey::from_ec_der(pkcs8.as_ref()),
+            jwk: json!({
+                "kid": kid,
+                "kty": "EC",
+                "crv": "P-256",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+            }),
+        }
+    }
+
+    fn ed_key(kid: &str) -> TestKey {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        TestKey {
+            encoding: EncodingKey::from_ed_der(pkcs8.as_ref()),
+            jwk: json!({
+                "kid": kid,
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": URL_SAFE_NO_PAD.encode(pair.public_key().as_ref()),
+            }),
+        }
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    fn token(key: &TestKey, alg: Algorithm, iat: i64) -> String {
+        let mut header = Header::new(alg);
+        header.kid = key.jwk["kid"].as_str().map(str::to_string);
+        let claims = json!({
+            "sub": "user-1",
+            "iss": ISSUER,
+            "aud": AUDIENCE,
+            "iat": iat,
+            "nbf": iat,
+            "exp": now() + 300,
+        });
+        encode(&header, &claims, &key.encoding).unwrap()
+    }
+
+    /// Sobe um servidor JWKS local com as chaves informadas
+    async fn jwks_server(keys: &[&TestKey]) -> (mockito::ServerGuard, mockito::Mock) {
+        let mut server = mockito::Server::new_async().await;
+        let body = json!({ "keys": keys.iter().map(|k| k.jwk.clone()).collect::<Vec<_>>() });
+        let mock = server
+            .mock("GET", "/certs")
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+        (server, mock)
+    }
+
+    fn auth_state(server: &mockito::ServerGuard, security: JwtSecurityConfig) -> AuthState {
+        AuthState {
+            jwks: Jwks::new(format!("{}/certs", server.url())),
+            issuer: Some(ISSUER.to_string()),
+            audiences: vec![AUDIENCE.to_string()],
+            security,
+            permissions: PermissionResolver::default(),
+            blacklist: TokenBlacklist::new(),
+            revocation_store: Arc::new(InMemoryRevocationStore::new()),
+            revocation_ttl: 3600,
+            api_key_store: Arc::new(InMemoryApiKeyStore::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accepts_rsa_ec_and_okp_keys() {
+        let (rsa, ec, ed) = (rsa_key("rsa"), ec_key("ec"), ed_key("ed"));
+        let (server, mock) = jwks_server(&[&rsa, &ec, &ed]).await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+
+        for (key, alg) in [
+            (&rsa, Algorithm::RS256),
+            (&rsa, Algorithm::RS384),
+            (&rsa, Algorithm::PS256),
+            (&ec, Algorithm::ES256),
+            (&ed, Algorithm::EdDSA),
+        ] {
+            let claims = validate_token(&state, &token(key, alg, now()))
+                .await
+                .unwrap_or_else(|e| panic!("{:?} rejected: {}", alg, e));
+            assert_eq!(claims.sub, "user-1");
+        }
+        // As chaves são buscadas uma única vez, no primeiro `kid` desconhecido
+        mock.expect(1).assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rejects_algorithm_not_allowed() {
+        let rsa = rsa_key("rsa");
+        let (server, _mock) = jwks_server(&[&rsa]).await;
+        let state = auth_state(
+            &server,
+            JwtSecurityConfig {
+                allowed_algorithms: vec![Algorithm::RS256],
+                ..JwtSecurityConfig::default()
+            },
+        );
+
+        assert!(
+            validate_token(&state, &token(&rsa, Algorithm::RS256, now()))
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            validate_token(&state, &token(&rsa, Algorithm::PS256, now()))
+                .await
+                .unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_algorithm_is_pinned_to_key() {
+        let mut rsa = rsa_key("rsa");
+        rsa.jwk["alg"] = json!("RS256");
+        let (server, _mock) = jwks_server(&[&rsa]).await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+
+        assert!(
+            validate_token(&state, &token(&rsa, Algorithm::RS256, now()))
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            validate_token(&state, &token(&rsa, Algorithm::RS512, now()))
+                .await
+                .unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_token_older_than_max_age() {
+        let ec = ec_key("ec");
+        let (server, _mock) = jwks_server(&[&ec]).await;
+        let state = auth_state(
+            &server,
+            JwtSecurityConfig {
+                max_token_age: 600,
+                ..JwtSecurityConfig::default()
+            },
+        );
+
+        assert!(
+            validate_token(&state, &token(&ec, Algorithm::ES256, now() - 300))
+                .await
+                .is_ok()
+        );
+        assert_eq!(
+            validate_token(&state, &token(&ec, Algorithm::ES256, now() - 3600))
+                .await
+                .unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_kid() {
+        let (ec, other) = (ec_key("ec"), ec_key("other"));
+        let (server, _mock) = jwks_server(&[&ec]).await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+
+        assert_eq!(
+            validate_token(&state, &token(&other, Algorithm::ES256, now()))
+                .await
+                .unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nbf_is_optional_but_validated_when_present() {
+        let rsa = rsa_key("rsa");
+        let (server, _mock) = jwks_server(&[&rsa]).await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+        let sign = |claims: serde_json::Value| {
+            let mut header = Header::new(Algorithm::RS256);
+            header.kid = Some("rsa".to_string());
+            encode(&header, &claims, &rsa.encoding).unwrap()
+        };
+        let mut claims = json!({
+            "sub": "user-1",
+            "iss": ISSUER,
+            "aud": AUDIENCE,
+            "iat": now(),
+            "exp": now() + 300,
+        });
+
+        // Access tokens do Keycloak não trazem `nbf`
+        assert!(validate_token(&state, &sign(claims.clone())).await.is_ok());
+
+        claims["nbf"] = json!(now() + 600);
+        assert_eq!(
+            validate_token(&state, &sign(claims)).await.unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_trusted_key_survives_refresh() {
+        let (ec, trusted) = (ec_key("ec"), ec_key("trusted"));
+        let (server, _mock) = jwks_server(&[&ec]).await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+        state.jwks.trust(trusted.jwk.clone());
+        state.jwks.refresh().await.unwrap();
+
+        for key in [&ec, &trusted] {
+            let claims = validate_token(&state, &token(key, Algorithm::ES256, now()))
+                .await
+                .unwrap();
+            assert_eq!(claims.sub, "user-1");
+        }
+    }
+
+    #[test]
+    fn test_jwk_alg_must_match_key_type() {
+        let mut jwk = ec_key("ec").jwk;
+        jwk["alg"] = json!("RS256");
+        assert!(JwkKey::from_jwk(&jwk).is_none());
+
+        jwk["alg"] = json!("ES256");
+        jwk["use"] = json!("enc");
+        assert!(JwkKey::from_jwk(&jwk).is_none());
+    }
+
+    #[test]
+    fn test_security_config_rejects_symmetric_algorithms() {
+        let config = JwtSecurityConfig {
+            allowed_algorithms: vec![Algorithm::RS256, Algorithm::HS256],
+            ..JwtSecurityConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(JwtSecurityConfig::default().validate().is_ok());
+    }
+
+    fn claims(value: serde_json::Value) -> JwtClaims {
+        serde_json::from_value(value).unwrap()
+    }
 
-/// Função auxiliar para verificar se o usuário é admin
-pub fn is_admin(claims: &serde_json::Value) -> bool {
-    has_role(claims, "admin")
+    #[test]
+    fn test_principal_from_keycloak_claims() {
+        let principal = Principal::from(claims(json!({
+            "sub": "user-1",
+            "aud": "sut-api",
+            "sid": "session-1",
+            "preferred_username": "jdoe",
+            "email": "jdoe@example.com",
+            "realm_access": { "roles": ["directory.read"] },
+            "resource_access": {
+                "sut-api": { "roles": ["directory.write"] },
+                "account": {}
+            },
+            "scope": "openid profile email",
+        })));
+
+        assert_eq!(principal.subject, "user-1");
+        assert_eq!(principal.username.as_deref(), Some("jdoe"));
+        assert_eq!(principal.email.as_deref(), Some("jdoe@example.com"));
+        assert_eq!(principal.session_id.as_deref(), Some("session-1"));
+        assert_eq!(principal.realm_roles, vec!["directory.read"]);
+        assert_eq!(principal.client_roles["sut-api"], vec!["directory.write"]);
+        assert_eq!(principal.client_roles["account"], Vec::<String>::new());
+        assert_eq!(principal.scopes, vec!["openid", "profile", "email"]);
+    }
+
+    #[test]
+    fn test_claims_accept_single_and_multiple_audiences() {
+        assert_eq!(claims(json!({"sub": "u", "aud": "a"})).aud, vec!["a"]);
+        assert_eq!(
+            claims(json!({"sub": "u", "aud": ["a", "b"]})).aud,
+            vec!["a", "b"]
+        );
+        assert!(claims(json!({"sub": "u"})).aud.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_principal_extractor_requires_authentication() {
+        let (mut parts, _) = Request::new(axum::body::Body::empty()).into_parts();
+        let err = Principal::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+
+        parts.extensions.insert(Principal {
+            subject: "user-1".to_string(),
+            ..Principal::default()
+        });
+        let principal = Principal::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(principal.subject, "user-1");
+    }
+
+    fn in_one_hour() -> i64 {
+        chrono::Utc::now().timestamp() + 3600
+    }
+
+    #[test]
+    fn test_blacklist_revokes_by_jti_sid_and_sub() {
+        let blacklist = TokenBlacklist::new();
+        blacklist.add(RevocationKind::Jti, "token-1".into(), in_one_hour());
+        blacklist.add(RevocationKind::Sid, "session-1".into(), in_one_hour());
+        blacklist.add(RevocationKind::Sub, "user-1".into(), in_one_hour());
+
+        assert!(blacklist.is_revoked(&claims(json!({"sub": "u", "jti": "token-1"}))));
+        assert!(blacklist.is_revoked(&claims(json!({"sub": "u", "sid": "session-1"}))));
+        assert!(blacklist.is_revoked(&claims(json!({"sub": "user-1"}))));
+        assert!(!blacklist.is_revoked(&claims(json!({"sub": "u", "jti": "token-2", "sid": "s"}))));
+    }
+
+    #[test]
+    fn test_blacklist_kind_must_match() {
+        let blacklist = TokenBlacklist::new();
+        blacklist.add(RevocationKind::Sid, "abc".into(), in_one_hour());
+
+        assert!(!blacklist.is_revoked(&claims(json!({"sub": "u", "jti": "abc"}))));
+    }
+
+    #[test]
+    fn test_blacklist_ignores_expired_entries() {
+        let blacklist = TokenBlacklist::new();
+        let now = chrono::Utc::now().timestamp();
+        blacklist.add(RevocationKind::Jti, "old".into(), now - 1);
+        blacklist.add(RevocationKind::Jti, "new".into(), now + 3600);
+
+        assert!(!blacklist.contains(RevocationKind::Jti, "old"));
+        assert!(blacklist.contains(RevocationKind::Jti, "new"));
+    }
+
+    #[test]
+    fn test_blacklist_replace_loads_store_snapshot() {
+        let blacklist = TokenBlacklist::new();
+        blacklist.add(RevocationKind::Jti, "stale".into(), in_one_hour());
+
+        blacklist.replace(&[TokenRevocation {
+            kind: RevocationKind::Sid,
+            value: "session-1".into(),
+            reason: None,
+            expires_at: chrono::Utc::now() + chrono::Duration::hours(1),
+        }]);
+
+        assert!(!blacklist.contains(RevocationKind::Jti, "stale"));
+        assert!(blacklist.contains(RevocationKind::Sid, "session-1"));
+    }
+
+    /// Registra uma API key no store do estado e devolve a chave em texto puro
+    async fn issue_key(state: &AuthState, scopes: &[&str], expires_in: Option<i64>) -> String {
+        let generated = crate::infra::api_keys::generate_api_key();
+        state
+            .api_key_store
+            .create(&ApiKey {
+                id: uuid::Uuid::new_v4(),
+                name: "etl".to_string(),
+                prefix: generated.prefix,
+                key_hash: generated.key_hash,
+                scopes: scopes.iter().map(|s| s.to_string()).collect(),
+                created_by: None,
+                created_at: chrono::Utc::now(),
+                expires_at: expires_in
+                    .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs)),
+                last_used_at: None,
+                revoked_at: None,
+            })
+            .await
+            .unwrap();
+        generated.key
+    }
+
+    #[tokio::test]
+    async fn test_api_key_builds_principal_with_permissions() {
+        let server = mockito::Server::new_async().await;
+        let mut state = auth_state(&server, JwtSecurityConfig::default());
+        state.permissions =
+            PermissionResolver::new(crate::presentation::permissions::PermissionConfig {
+                clients: vec![],
+                role_permissions: HashMap::from([(
+                    "etl".to_string(),
+                    vec!["directory.read".to_string()],
+                )]),
+            });
+        let key = issue_key(&state, &["etl", "metrics.read"], None).await;
+
+        let principal = validate_api_key(&state, &key).await.unwrap();
+        assert!(principal.subject.starts_with("apikey:"));
+        assert_eq!(principal.username.as_deref(), Some("etl"));
+        assert_eq!(principal.scopes, vec!["etl", "metrics.read"]);
+        assert!(principal.has_permission("directory.read"));
+        assert!(principal.has_permission("metrics.read"));
+        assert!(!principal.is_admin());
+
+        // O último uso é registrado em background
+        for _ in 0..50 {
+            if state.api_key_store.list().await.unwrap()[0]
+                .last_used_at
+                .is_some()
+            {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("API key usage was not recorded");
+    }
+
+    #[tokio::test]
+    async fn test_api_key_honours_subject_revocation() {
+        let server = mockito::Server::new_async().await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+        let key = issue_key(&state, &["etl"], None).await;
+        let principal = validate_api_key(&state, &key).await.unwrap();
+
+        state.blacklist.add(
+            RevocationKind::Sub,
+            principal.subject.clone(),
+            chrono::Utc::now().timestamp() + 3600,
+        );
+        assert_eq!(
+            validate_api_key(&state, &key).await.unwrap_err(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_key_rejects_wrong_secret_expired_and_malformed() {
+        let server = mockito::Server::new_async().await;
+        let state = auth_state(&server, JwtSecurityConfig::default());
+        let key = issue_key(&state, &["etl"], None).await;
+        let expired = issue_key(&state, &["etl"], Some(-60)).await;
+
+        let tampered = format!("{}0", key);
+        for candidate in [
+            tampered.as_str(),
+            expired.as_str(),
+            "sut_unknown_secret",
+            "garbage",
+        ] {
+            assert_eq!(
+                validate_api_key(&state, candidate).await.unwrap_err(),
+                StatusCode::UNAUTHORIZED
+            );
+        }
+    }
 }
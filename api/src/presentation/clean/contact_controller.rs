@@ -7,22 +7,22 @@
 // ===== CLEAN ARCHITECTURE IMPORTS =====
 use crate::application::dto::*; // DTOs (Data Transfer Objects) para comunicação entre camadas
 use crate::application::use_cases::contact::*; // Casos de uso da camada de aplicação
-use crate::domain::value_objects::ContactId; // Value objects do domínio
+use crate::domain::value_objects::{ContactId, Principal}; // Value objects do domínio
 
 // ===== PRESENTATION UTILITIES =====
 use crate::presentation::{
-    error_mapper::map_domain_error, // Mapeamento centralizado de erros
-    validation::validate_uuid,      // Validação de UUID
+    error_mapper::{map_domain_error, ErrorResponse, Problem}, // Mapeamento centralizado de erros
+    patch::{if_match, PatchBody}, // Negociação JSON / Merge Patch / JSON Patch
+    validation::validate_uuid,    // Validação de UUID
 };
 
 // ===== AXUM FRAMEWORK IMPORTS =====
 use axum::{
     extract::{Path, Query, State}, // Extractors para parâmetros de rota, query e estado
-    http::StatusCode,              // Códigos de status HTTP
+    http::{HeaderMap, StatusCode}, // Headers e códigos de status HTTP
     response::Json,                // Resposta JSON
-    routing::get,                  // Macros de roteamento HTTP
-    Router,                        // Roteador principal do Axum
 };
+use utoipa_axum::{router::OpenApiRouter, routes}; // Roteador que gera a especificação OpenAPI
 
 // ===== UTILITY IMPORTS =====
 // use serde_json::json; // Para criação de JSON dinâmico
@@ -35,26 +35,25 @@ use std::sync::Arc; // Para compartilhamento thread-safe do estado
 // Define todas as rotas REST para operações de contatos
 // Implementa o padrão RESTful com operações CRUD completas
 
-pub fn routes() -> Router<Arc<crate::AppState>> {
-    Router::new()
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
         // ===== ROTAS DE COLECAO =====
-        .route(
-            "/v1/contacts",
-            get(get_contacts) // GET /v1/contacts - Listar contatos com filtros
-                .post(create_contact), // POST /v1/contacts - Criar novo contato
-        )
+        // GET /v1/contacts - Listar contatos com filtros
+        // POST /v1/contacts - Criar novo contato
+        .routes(routes!(get_contacts, create_contact))
+        // ===== OPERAÇÕES EM LOTE =====
+        // POST /v1/contacts:batch - Operações em lote
+        .routes(routes!(batch_contacts))
         // ===== ROTAS DE RECURSO INDIVIDUAL =====
-        .route(
-            "/v1/contacts/{id}",
-            get(get_contact) // GET /v1/contacts/{id} - Buscar contato por ID
-                .patch(update_contact) // PATCH /v1/contacts/{id} - Atualizar contato
-                .delete(delete_contact), // DELETE /v1/contacts/{id} - Deletar contato
-        )
+        // GET /v1/contacts/{id} - Buscar contato por ID
+        // PATCH /v1/contacts/{id} - Atualizar contato
+        // DELETE /v1/contacts/{id} - Excluir contato (soft delete)
+        .routes(routes!(get_contact, update_contact, delete_contact))
+        // POST /v1/contacts/{id}/restore - Restaurar contato excluído
+        .routes(routes!(restore_contact))
         // ===== ROTAS DE ESTATÍSTICAS =====
-        .route(
-            "/v1/contacts/statistics",
-            get(get_contact_statistics), // GET /v1/contacts/statistics - Estatísticas de contatos
-        )
+        // GET /v1/contacts/statistics - Estatísticas de contatos
+        .routes(routes!(get_contact_statistics))
 }
 
 // ============================================================================
@@ -63,16 +62,28 @@ pub fn routes() -> Router<Arc<crate::AppState>> {
 // Endpoint para buscar contatos com suporte a filtros, paginação e ordenação
 // Implementa o padrão de busca RESTful com parâmetros de query
 
+#[utoipa::path(
+    get,
+    path = "/v1/contacts",
+    tag = "contacts",
+    summary = "Listar contatos",
+    params(ContactSearchRequest),
+    responses(
+        (status = 200, description = "Página de contatos", body = ContactSearchResponse),
+        (status = 400, description = "Filtros inválidos"),
+    )
+)]
 async fn get_contacts(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
+    principal: Principal,                      // Usuário autenticado
     Query(params): Query<ContactSearchRequest>, // Parâmetros de query (filtros, paginação)
-) -> Result<Json<ContactSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso injetando a dependência do repositório
     let use_case = GetContactsUseCase::new(state.contact_repository.as_ref());
 
     // ===== EXECUÇÃO DO CASO DE USO =====
-    match use_case.execute(params).await {
+    match use_case.execute(params, &principal).await {
         Ok(response) => {
             // Sucesso: retorna resposta JSON com os contatos encontrados
             Ok(Json(response))
@@ -91,10 +102,22 @@ async fn get_contacts(
 // Endpoint para buscar um contato específico pelo seu ID
 // Inclui validação de formato UUID e tratamento de erros
 
+#[utoipa::path(
+    get,
+    path = "/v1/contacts/{id}",
+    tag = "contacts",
+    summary = "Buscar contato por ID",
+    params(("id" = Uuid, Path, description = "ID do contato")),
+    responses(
+        (status = 200, description = "Contato encontrado", body = ContactResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Contato não encontrado"),
+    )
+)]
 async fn get_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do contato extraído da URL
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetContactsUseCase::new(state.contact_repository.as_ref());
 
@@ -122,10 +145,22 @@ async fn get_contact(
 // Endpoint para criação de novos contatos
 // Recebe dados via JSON e valida através do caso de uso
 
+#[utoipa::path(
+    post,
+    path = "/v1/contacts",
+    tag = "contacts",
+    summary = "Criar contato",
+    request_body = CreateContactRequest,
+    responses(
+        (status = 200, description = "Contato criado", body = ContactResponse),
+        (status = 400, description = "Campos inválidos (ver `errors`)"),
+        (status = 409, description = "Contato já existe"),
+    )
+)]
 async fn create_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Json(request): Json<CreateContactRequest>, // Dados do contato em formato JSON
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso para criação de contatos
     let use_case = CreateContactUseCase::new(state.contact_repository.as_ref());
@@ -143,17 +178,160 @@ async fn create_contact(
     }
 }
 
+// ============================================================================
+// HANDLER: POST /v1/contacts:batch - OPERAÇÕES EM LOTE
+// ============================================================================
+// Endpoint para aplicar update, delete, set_status e move em vários contatos
+// Cada item carrega seu próprio ETag e recebe seu próprio status HTTP
+// Retorna 200 quando todas as operações foram aplicadas e 207 Multi-Status caso contrário
+
+#[utoipa::path(
+    post,
+    path = "/v1/contacts:batch",
+    tag = "contacts",
+    summary = "Executar operações em lote",
+    request_body = ContactBatchRequest,
+    responses(
+        (status = 200, description = "Todas as operações foram aplicadas", body = ContactBatchResponse),
+        (status = 207, description = "Resultado por operação; ao menos uma falhou", body = ContactBatchResponse),
+        (status = 400, description = "Lote inválido"),
+    )
+)]
+async fn batch_contacts(
+    State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
+    Json(request): Json<ContactBatchRequest>,  // Lote de operações
+) -> Result<(StatusCode, Json<ContactBatchResponse>), ErrorResponse> {
+    // ===== INICIALIZAÇÃO DO CASO DE USO =====
+    let use_case = BatchContactsUseCase::new(state.contact_repository.as_ref());
+
+    // ===== EXECUÇÃO DO CASO DE USO =====
+    let result = use_case
+        .execute(request)
+        .await
+        .map_err(|err| map_domain_error(&err))?;
+
+    // ===== MAPEAMENTO DOS RESULTADOS POR ITEM =====
+    // Erros de cada item usam o mesmo mapeamento centralizado das demais rotas
+    let results: Vec<ContactBatchItemResponse> = result
+        .items
+        .into_iter()
+        .enumerate()
+        .map(|(index, (operation, item))| {
+            let (status, contact, error) = match item {
+                ContactBatchItemResult::Applied(contact) => {
+                    let status = if contact.is_some() {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::NO_CONTENT
+                    };
+                    (status, contact.map(|contact| *contact), None)
+                }
+                ContactBatchItemResult::Failed(err) => {
+                    let problem = map_domain_error(&err);
+                    (problem.status, None, Some(problem.to_json()))
+                }
+                ContactBatchItemResult::NotApplied => (
+                    StatusCode::FAILED_DEPENDENCY,
+                    None,
+                    Some(
+                        Problem::new(
+                            StatusCode::FAILED_DEPENDENCY,
+                            "not_applied",
+                            "Operation not applied",
+                            "Not applied: another operation in the batch failed",
+                        )
+                        .to_json(),
+                    ),
+                ),
+            };
+            ContactBatchItemResponse {
+                index,
+                op: operation.name().to_string(),
+                id: operation.id(),
+                status: status.as_u16(),
+                contact,
+                error,
+            }
+        })
+        .collect();
+
+    let succeeded = results.iter().filter(|r| r.error.is_none()).count();
+    let failed = results.len() - succeeded;
+    let status = if failed == 0 {
+        StatusCode::OK
+    } else {
+        StatusCode::MULTI_STATUS
+    };
+
+    Ok((
+        status,
+        Json(ContactBatchResponse {
+            mode: result.mode,
+            committed: result.committed,
+            succeeded,
+            failed,
+            results,
+        }),
+    ))
+}
+
 // ============================================================================
 // HANDLER: PATCH /v1/contacts/:id - ATUALIZAR CONTATO
 // ============================================================================
 // Endpoint para atualização parcial de contatos existentes
 // Combina ID da URL com dados do JSON para atualização
+// Aceita também application/merge-patch+json e application/json-patch+json,
+// onde `null` limpa campos opcionais e o ETag vem do header If-Match
 
+#[utoipa::path(
+    patch,
+    path = "/v1/contacts/{id}",
+    tag = "contacts",
+    summary = "Atualizar contato",
+    params(
+        ("id" = Uuid, Path, description = "ID do contato"),
+        ("If-Match" = Option<String>, Header, description = "ETag do contato (patches)"),
+    ),
+    request_body(
+        description = "Campos alterados (com `etag`), JSON Merge Patch (RFC 7396) ou JSON Patch (RFC 6902)",
+        content(
+            (UpdateContactRequest = "application/json"),
+            (ContactPatchDocument = "application/merge-patch+json"),
+            (Vec<Object> = "application/json-patch+json"),
+        )
+    ),
+    responses(
+        (status = 200, description = "Contato atualizado", body = ContactResponse),
+        (status = 400, description = "Campos ou patch inválidos"),
+        (status = 404, description = "Contato não encontrado"),
+        (status = 409, description = "ETag divergente"),
+        (status = 415, description = "Content-Type não suportado"),
+    )
+)]
 async fn update_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do contato extraído da URL
-    Json(mut request): Json<UpdateContactRequest>, // Dados de atualização em JSON
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+    headers: HeaderMap,                        // Headers (If-Match)
+    body: PatchBody<UpdateContactRequest>,     // Dados de atualização (JSON ou patch)
+) -> Result<Json<ContactResponse>, ErrorResponse> {
+    // ===== PATCH RFC 7396 / RFC 6902 =====
+    let mut request = match body {
+        PatchBody::Json(request) => request,
+        PatchBody::Patch(patch) => {
+            let use_case = PatchContactUseCase::new(state.contact_repository.as_ref());
+            let request = PatchEntityRequest {
+                id,
+                patch,
+                etag: if_match(&headers),
+            };
+            return use_case
+                .execute(request)
+                .await
+                .map(Json)
+                .map_err(|err| map_domain_error(&err));
+        }
+    };
+
     // ===== PREPARAÇÃO DOS DADOS =====
     // Adiciona o ID da URL ao request para que o caso de uso tenha o ID completo
     request.id = id;
@@ -178,12 +356,25 @@ async fn update_contact(
 // HANDLER: DELETE /v1/contacts/:id - DELETAR CONTATO
 // ============================================================================
 // Endpoint para remoção de contatos existentes
+// A exclusão é lógica (deleted_at); a remoção física é feita pelo job de retenção
 // Retorna 204 No Content em caso de sucesso (padrão RESTful)
 
+#[utoipa::path(
+    delete,
+    path = "/v1/contacts/{id}",
+    tag = "contacts",
+    summary = "Excluir contato (exclusão lógica)",
+    params(("id" = Uuid, Path, description = "ID do contato")),
+    responses(
+        (status = 204, description = "Contato excluído"),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Contato não encontrado"),
+    )
+)]
 async fn delete_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do contato a ser deletado
-) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<StatusCode, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = DeleteContactUseCase::new(state.contact_repository.as_ref());
 
@@ -200,20 +391,78 @@ async fn delete_contact(
     }
 }
 
+// ============================================================================
+// HANDLER: POST /v1/contacts/:id/restore - RESTAURAR CONTATO EXCLUÍDO
+// ============================================================================
+// Endpoint administrativo para desfazer a exclusão lógica de um contato
+// Retorna 409 Conflict se o contato não estiver excluído
+
+#[utoipa::path(
+    post,
+    path = "/v1/contacts/{id}/restore",
+    tag = "contacts",
+    summary = "Restaurar contato excluído",
+    params(("id" = Uuid, Path, description = "ID do contato")),
+    responses(
+        (status = 200, description = "Contato restaurado", body = ContactResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Contato não encontrado"),
+    ),
+    security(("bearer" = ["admin"]), ("api_key" = ["admin"]))
+)]
+async fn restore_contact(
+    State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
+    principal: Principal,                      // Usuário autenticado (requer admin)
+    Path(id): Path<String>,                    // ID do contato a ser restaurado
+) -> Result<Json<ContactResponse>, ErrorResponse> {
+    // ===== INICIALIZAÇÃO DO CASO DE USO =====
+    let use_case = RestoreContactUseCase::new(state.contact_repository.as_ref());
+
+    // ===== EXECUÇÃO DO CASO DE USO =====
+    match use_case.execute(&id, &principal).await {
+        Ok(response) => {
+            // Sucesso: retorna o contato restaurado
+            Ok(Json(response))
+        }
+        Err(err) => {
+            // ===== MAPEAMENTO DE ERROS USANDO UTILITÁRIO CENTRALIZADO =====
+            Err(map_domain_error(&err))
+        }
+    }
+}
+
 // ============================================================================
 // HANDLER: GET /v1/contacts/statistics - ESTATÍSTICAS DE CONTATOS
 // ============================================================================
 // Endpoint para obter estatísticas agregadas dos contatos
+// Inclui distribuições, séries temporais (?from, ?to, ?bucket) e qualidade dos dados
+// O resultado fica em cache por STATISTICS_CACHE_TTL_SECS segundos
 // Útil para dashboards e relatórios
 
+#[utoipa::path(
+    get,
+    path = "/v1/contacts/statistics",
+    tag = "contacts",
+    summary = "Estatísticas de contatos",
+    params(ContactStatisticsRequest),
+    responses(
+        (status = 200, description = "Estatísticas agregadas", body = ContactStatisticsResponse),
+        (status = 400, description = "Intervalo ou granularidade inválidos"),
+    )
+)]
 async fn get_contact_statistics(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
-) -> Result<Json<ContactStatisticsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    Query(params): Query<ContactStatisticsRequest>, // Intervalo e granularidade das séries
+) -> Result<Json<ContactStatisticsResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
-    let use_case = GetContactStatisticsUseCase::new(state.contact_repository.as_ref());
+    let use_case = GetContactStatisticsUseCase::new(
+        state.contact_repository.as_ref(),
+        state.contact_statistics_cache.as_ref(),
+        state.statistics_cache_ttl,
+    );
 
     // ===== EXECUÇÃO DO CASO DE USO =====
-    match use_case.execute().await {
+    match use_case.execute(params).await {
         Ok(response) => {
             // Sucesso: retorna estatísticas agregadas
             Ok(Json(response))
@@ -8,7 +8,7 @@ use crate::application::dto::*;
 use crate::application::use_cases::contact::*;
 use crate::domain::value_objects::ContactId;
 use crate::presentation::{
-    error_mapper::map_domain_error,
+    error_mapper::{map_domain_error, ErrorResponse},
     validation::validate_uuid,
 };
 use axum::{
@@ -56,7 +56,7 @@ pub fn routes() -> Router<Arc<crate::AppState>> {
 async fn get_contacts(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Query(params): Query<ContactSearchRequest>, // Parâmetros de query (filtros, paginação)
-) -> Result<Json<ContactSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactSearchResponse>, ErrorResponse> {
     
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetContactsUseCase::new(state.contact_repository.as_ref());
@@ -77,7 +77,7 @@ async fn get_contacts(
 async fn get_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>, // ID do contato extraído da URL
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactResponse>, ErrorResponse> {
     
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetContactsUseCase::new(state.contact_repository.as_ref());
@@ -102,7 +102,7 @@ async fn get_contact(
 async fn create_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Json(request): Json<CreateContactRequest>, // Dados do contato em formato JSON
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactResponse>, ErrorResponse> {
     
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = CreateContactUseCase::new(state.contact_repository.as_ref());
@@ -124,7 +124,7 @@ async fn update_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>, // ID do contato extraído da URL
     Json(mut request): Json<UpdateContactRequest>, // Dados de atualização em JSON
-) -> Result<Json<ContactResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactResponse>, ErrorResponse> {
     
     // ===== PREPARAÇÃO DOS DADOS =====
     request.id = id;
@@ -148,7 +148,7 @@ async fn update_contact(
 async fn delete_contact(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>, // ID do contato a ser deletado
-) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<StatusCode, ErrorResponse> {
     
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = DeleteContactUseCase::new(state.contact_repository.as_ref());
@@ -168,7 +168,7 @@ async fn delete_contact(
 
 async fn get_contact_statistics(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
-) -> Result<Json<ContactStatisticsResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<ContactStatisticsResponse>, ErrorResponse> {
     
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetContactStatisticsUseCase::new(state.contact_repository.as_ref());
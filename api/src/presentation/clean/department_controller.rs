@@ -7,12 +7,13 @@
 // ===== CLEAN ARCHITECTURE IMPORTS =====
 use crate::application::dto::*; // DTOs (Data Transfer Objects) para comunicação entre camadas
 use crate::application::use_cases::department::*; // Casos de uso da camada de aplicação
-use crate::domain::{value_objects::DepartmentId, DomainError}; // Value objects do domínio
+use crate::domain::value_objects::DepartmentId; // Value objects do domínio
 
 // ===== PRESENTATION UTILITIES =====
 use crate::presentation::{
-    error_mapper::map_domain_error, // Mapeamento centralizado de erros
-    validation::validate_uuid,      // Validação de UUID
+    error_mapper::{invalid_uuid_error, map_domain_error, ErrorResponse}, // Mapeamento centralizado de erros
+    patch::PatchBody,          // Negociação JSON / Merge Patch / JSON Patch
+    validation::validate_uuid, // Validação de UUID
 };
 
 // ===== AXUM FRAMEWORK IMPORTS =====
@@ -20,12 +21,10 @@ use axum::{
     extract::{Path, Query, State}, // Extractors para parâmetros de rota, query e estado
     http::StatusCode,              // Códigos de status HTTP
     response::Json,                // Resposta JSON
-    routing::get,                  // Macros de roteamento HTTP
-    Router,                        // Roteador principal do Axum
 };
+use utoipa_axum::{router::OpenApiRouter, routes}; // Roteador que gera a especificação OpenAPI
 
 // ===== UTILITY IMPORTS =====
-use serde_json::json; // Para criação de JSON dinâmico
 use std::sync::Arc; // Para compartilhamento thread-safe do estado
 use uuid::Uuid; // Para validação de UUIDs
 
@@ -35,31 +34,27 @@ use uuid::Uuid; // Para validação de UUIDs
 // Define todas as rotas REST para operações de departamentos
 // Implementa o padrão RESTful com operações CRUD completas
 
-pub fn routes() -> Router<Arc<crate::AppState>> {
-    Router::new()
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
         // ===== ROTAS DE COLECAO =====
-        .route(
-            "/v1/departments",
-            get(get_departments) // GET /v1/departments - Listar departamentos com filtros
-                .post(create_department), // POST /v1/departments - Criar novo departamento
-        )
+        // GET /v1/departments - Listar departamentos com filtros
+        // POST /v1/departments - Criar novo departamento
+        .routes(routes!(get_departments, create_department))
         // ===== ROTAS DE RECURSO INDIVIDUAL =====
-        .route(
-            "/v1/departments/{id}",
-            get(get_department) // GET /v1/departments/{id} - Buscar departamento por ID
-                .patch(update_department) // PATCH /v1/departments/{id} - Atualizar departamento
-                .delete(delete_department), // DELETE /v1/departments/{id} - Deletar departamento
-        )
+        // GET /v1/departments/{id} - Buscar departamento por ID
+        // PATCH /v1/departments/{id} - Atualizar departamento
+        // DELETE /v1/departments/{id} - Deletar departamento
+        .routes(routes!(
+            get_department,
+            update_department,
+            delete_department
+        ))
         // ===== ROTAS DE ESTATÍSTICAS =====
-        .route(
-            "/v1/departments/statistics",
-            get(get_department_statistics), // GET /v1/departments/statistics - Estatísticas de departamentos
-        )
+        // GET /v1/departments/statistics - Estatísticas de departamentos
+        .routes(routes!(get_department_statistics))
         // ===== ROTAS DE RELACIONAMENTO =====
-        .route(
-            "/v1/departments/by-unit/{unit_id}",
-            get(get_departments_by_unit), // GET /v1/departments/by-unit/{unit_id} - Departamentos por unidade
-        )
+        // GET /v1/departments/by-unit/{unit_id} - Departamentos por unidade
+        .routes(routes!(get_departments_by_unit))
 }
 
 // ============================================================================
@@ -68,10 +63,21 @@ pub fn routes() -> Router<Arc<crate::AppState>> {
 // Endpoint para buscar departamentos com suporte a filtros, paginação e ordenação
 // Implementa o padrão de busca RESTful com parâmetros de query
 
+#[utoipa::path(
+    get,
+    path = "/v1/departments",
+    tag = "departments",
+    summary = "Listar departamentos",
+    params(DepartmentSearchRequest),
+    responses(
+        (status = 200, description = "Página de departamentos", body = DepartmentSearchResponse),
+        (status = 400, description = "Filtros inválidos"),
+    )
+)]
 async fn get_departments(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Query(params): Query<DepartmentSearchRequest>, // Parâmetros de query (filtros, paginação)
-) -> Result<Json<DepartmentSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<DepartmentSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso injetando a dependência do repositório
     let use_case = GetDepartmentsUseCase::new(state.department_repository.as_ref());
@@ -96,10 +102,22 @@ async fn get_departments(
 // Endpoint para buscar um departamento específico pelo seu ID
 // Inclui validação de formato UUID e tratamento de erros
 
+#[utoipa::path(
+    get,
+    path = "/v1/departments/{id}",
+    tag = "departments",
+    summary = "Buscar departamento por ID",
+    params(("id" = Uuid, Path, description = "ID do departamento")),
+    responses(
+        (status = 200, description = "Departamento encontrado", body = DepartmentResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Departamento não encontrado"),
+    )
+)]
 async fn get_department(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do departamento extraído da URL
-) -> Result<Json<DepartmentResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<DepartmentResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetDepartmentsUseCase::new(state.department_repository.as_ref());
 
@@ -127,10 +145,22 @@ async fn get_department(
 // Endpoint para criação de novos departamentos
 // Recebe dados via JSON e valida através do caso de uso
 
+#[utoipa::path(
+    post,
+    path = "/v1/departments",
+    tag = "departments",
+    summary = "Criar departamento",
+    request_body = CreateDepartmentRequest,
+    responses(
+        (status = 200, description = "Departamento criado", body = DepartmentResponse),
+        (status = 400, description = "Campos inválidos (ver `errors`)"),
+        (status = 409, description = "Unidade inexistente ou nome em uso"),
+    )
+)]
 async fn create_department(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Json(request): Json<CreateDepartmentRequest>, // Dados do departamento em formato JSON
-) -> Result<Json<DepartmentResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<DepartmentResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso para criação de departamentos
     let use_case = CreateDepartmentUseCase::new(state.department_repository.as_ref());
@@ -143,18 +173,7 @@ async fn create_department(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito (ex: nome duplicado)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -164,12 +183,52 @@ async fn create_department(
 // ============================================================================
 // Endpoint para atualização parcial de departamentos existentes
 // Combina ID da URL com dados do JSON para atualização
+// Aceita também application/merge-patch+json e application/json-patch+json
 
+#[utoipa::path(
+    patch,
+    path = "/v1/departments/{id}",
+    tag = "departments",
+    summary = "Atualizar departamento",
+    params(("id" = Uuid, Path, description = "ID do departamento")),
+    request_body(
+        description = "Campos alterados (JSON), JSON Merge Patch (RFC 7396) ou JSON Patch (RFC 6902)",
+        content(
+            (UpdateDepartmentRequest = "application/json"),
+            (DepartmentPatchDocument = "application/merge-patch+json"),
+            (Vec<Object> = "application/json-patch+json"),
+        )
+    ),
+    responses(
+        (status = 200, description = "Departamento atualizado", body = DepartmentResponse),
+        (status = 400, description = "Campos ou patch inválidos"),
+        (status = 404, description = "Departamento não encontrado"),
+        (status = 415, description = "Content-Type não suportado"),
+    )
+)]
 async fn update_department(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do departamento extraído da URL
-    Json(mut request): Json<UpdateDepartmentRequest>, // Dados de atualização em JSON
-) -> Result<Json<DepartmentResponse>, (StatusCode, Json<serde_json::Value>)> {
+    body: PatchBody<UpdateDepartmentRequest>,  // Dados de atualização (JSON ou patch)
+) -> Result<Json<DepartmentResponse>, ErrorResponse> {
+    // ===== PATCH RFC 7396 / RFC 6902 =====
+    let mut request = match body {
+        PatchBody::Json(request) => request,
+        PatchBody::Patch(patch) => {
+            let use_case = PatchDepartmentUseCase::new(state.department_repository.as_ref());
+            let request = PatchEntityRequest {
+                id,
+                patch,
+                etag: None,
+            };
+            return use_case
+                .execute(request)
+                .await
+                .map(Json)
+                .map_err(|err| map_domain_error(&err));
+        }
+    };
+
     // ===== PREPARAÇÃO DOS DADOS =====
     // Adiciona o ID da URL ao request para que o caso de uso tenha o ID completo
     request.id = id;
@@ -185,18 +244,7 @@ async fn update_department(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Departamento não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito de versão (ETag)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -207,10 +255,23 @@ async fn update_department(
 // Endpoint para remoção de departamentos existentes
 // Retorna 204 No Content em caso de sucesso (padrão RESTful)
 
+#[utoipa::path(
+    delete,
+    path = "/v1/departments/{id}",
+    tag = "departments",
+    summary = "Excluir departamento",
+    params(("id" = Uuid, Path, description = "ID do departamento")),
+    responses(
+        (status = 204, description = "Departamento excluído"),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Departamento não encontrado"),
+        (status = 409, description = "Departamento ainda referenciado"),
+    )
+)]
 async fn delete_department(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do departamento a ser deletado
-) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<StatusCode, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = DeleteDepartmentUseCase::new(state.department_repository.as_ref());
 
@@ -222,18 +283,7 @@ async fn delete_department(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Departamento não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // ID inválido
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN, // Sem permissão para deletar
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito (ex: departamento em uso)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -244,9 +294,18 @@ async fn delete_department(
 // Endpoint para obter estatísticas agregadas dos departamentos
 // Útil para dashboards e relatórios
 
+#[utoipa::path(
+    get,
+    path = "/v1/departments/statistics",
+    tag = "departments",
+    summary = "Estatísticas de departamentos",
+    responses(
+        (status = 200, description = "Totais por unidade organizacional", body = DepartmentStatisticsResponse),
+    )
+)]
 async fn get_department_statistics(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
-) -> Result<Json<DepartmentStatisticsResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<DepartmentStatisticsResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetDepartmentStatisticsUseCase::new(state.department_repository.as_ref());
 
@@ -258,18 +317,7 @@ async fn get_department_statistics(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST,
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT,
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR, // Erro ao calcular estatísticas
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -280,10 +328,21 @@ async fn get_department_statistics(
 // Endpoint para buscar departamentos de uma unidade organizacional específica
 // Útil para navegação hierárquica e relatórios por unidade
 
+#[utoipa::path(
+    get,
+    path = "/v1/departments/by-unit/{unit_id}",
+    tag = "departments",
+    summary = "Departamentos de uma unidade organizacional",
+    params(("unit_id" = Uuid, Path, description = "ID da unidade organizacional")),
+    responses(
+        (status = 200, description = "Departamentos da unidade", body = DepartmentSearchResponse),
+        (status = 400, description = "ID inválido"),
+    )
+)]
 async fn get_departments_by_unit(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(unit_id): Path<String>,               // ID da unidade organizacional extraído da URL
-) -> Result<Json<DepartmentSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<DepartmentSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetDepartmentsUseCase::new(state.department_repository.as_ref());
 
@@ -302,27 +361,13 @@ async fn get_departments_by_unit(
                 }
                 Err(err) => {
                     // ===== MAPEAMENTO DE ERROS =====
-                    let status = match err {
-                        DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Unidade não encontrada
-                        DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // ID inválido
-                        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                        DomainError::Conflict(_) => StatusCode::CONFLICT,
-                        DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                        DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-                    };
-                    Err((status, Json(json!({"error": err.to_string()}))))
+                    Err(map_domain_error(&err))
                 }
             }
         }
         Err(_) => {
             // UUID inválido: retorna erro 400 Bad Request
-            Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Invalid UUID format"})),
-            ))
+            Err(invalid_uuid_error())
         }
     }
 }
@@ -7,12 +7,13 @@
 // ===== CLEAN ARCHITECTURE IMPORTS =====
 use crate::application::dto::*; // DTOs (Data Transfer Objects) para comunicação entre camadas
 use crate::application::use_cases::org_unit::*; // Casos de uso da camada de aplicação
-use crate::domain::{value_objects::OrgUnitId, DomainError}; // Value objects do domínio
+use crate::domain::value_objects::OrgUnitId; // Value objects do domínio
 
 // ===== PRESENTATION UTILITIES =====
 use crate::presentation::{
-    error_mapper::map_domain_error, // Mapeamento centralizado de erros
-                                    // validation::validate_uuid, // Validação de UUID
+    error_mapper::{invalid_uuid_error, map_domain_error, ErrorResponse}, // Mapeamento centralizado de erros
+    patch::PatchBody, // Negociação JSON / Merge Patch / JSON Patch
+                      // validation::validate_uuid, // Validação de UUID
 };
 
 // ===== AXUM FRAMEWORK IMPORTS =====
@@ -20,12 +21,10 @@ use axum::{
     extract::{Path, Query, State}, // Extractors para parâmetros de rota, query e estado
     http::StatusCode,              // Códigos de status HTTP
     response::Json,                // Resposta JSON
-    routing::get,                  // Macros de roteamento HTTP
-    Router,                        // Roteador principal do Axum
 };
+use utoipa_axum::{router::OpenApiRouter, routes}; // Roteador que gera a especificação OpenAPI
 
 // ===== UTILITY IMPORTS =====
-use serde_json::json; // Para criação de JSON dinâmico
 use std::sync::Arc; // Para compartilhamento thread-safe do estado
 use uuid::Uuid; // Para validação de UUIDs
 
@@ -35,26 +34,20 @@ use uuid::Uuid; // Para validação de UUIDs
 // Define todas as rotas REST para operações de unidades organizacionais
 // Implementa o padrão RESTful com operações CRUD completas e funcionalidades hierárquicas
 
-pub fn routes() -> Router<Arc<crate::AppState>> {
-    Router::new()
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
         // ===== ROTAS DE COLECAO =====
-        .route(
-            "/v1/org-units",
-            get(get_org_units) // GET /v1/org-units - Listar unidades organizacionais com filtros
-                .post(create_org_unit), // POST /v1/org-units - Criar nova unidade organizacional
-        )
+        // GET /v1/org-units - Listar unidades organizacionais com filtros
+        // POST /v1/org-units - Criar nova unidade organizacional
+        .routes(routes!(get_org_units, create_org_unit))
         // ===== ROTAS DE RECURSO INDIVIDUAL =====
-        .route(
-            "/v1/org-units/{id}",
-            get(get_org_unit) // GET /v1/org-units/{id} - Buscar unidade organizacional por ID
-                .patch(update_org_unit) // PATCH /v1/org-units/{id} - Atualizar unidade organizacional
-                .delete(delete_org_unit), // DELETE /v1/org-units/{id} - Deletar unidade organizacional
-        )
+        // GET /v1/org-units/{id} - Buscar unidade organizacional por ID
+        // PATCH /v1/org-units/{id} - Atualizar unidade organizacional
+        // DELETE /v1/org-units/{id} - Deletar unidade organizacional
+        .routes(routes!(get_org_unit, update_org_unit, delete_org_unit))
         // ===== ROTAS DE HIERARQUIA =====
-        .route(
-            "/v1/org-units/{id}/hierarchy",
-            get(get_org_unit_hierarchy), // GET /v1/org-units/{id}/hierarchy - Hierarquia da unidade
-        )
+        // GET /v1/org-units/{id}/hierarchy - Hierarquia da unidade
+        .routes(routes!(get_org_unit_hierarchy))
 }
 
 // ============================================================================
@@ -63,10 +56,21 @@ pub fn routes() -> Router<Arc<crate::AppState>> {
 // Endpoint para buscar unidades organizacionais com suporte a filtros, paginação e ordenação
 // Implementa o padrão de busca RESTful com parâmetros de query
 
+#[utoipa::path(
+    get,
+    path = "/v1/org-units",
+    tag = "org-units",
+    summary = "Listar unidades organizacionais",
+    params(OrgUnitSearchRequest),
+    responses(
+        (status = 200, description = "Página de unidades", body = OrgUnitSearchResponse),
+        (status = 400, description = "Filtros inválidos"),
+    )
+)]
 async fn get_org_units(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Query(params): Query<OrgUnitSearchRequest>, // Parâmetros de query (filtros, paginação)
-) -> Result<Json<OrgUnitSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<OrgUnitSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso injetando a dependência do repositório
     let use_case = GetOrgUnitsUseCase::new(state.org_unit_repository.as_ref());
@@ -91,10 +95,22 @@ async fn get_org_units(
 // Endpoint para buscar uma unidade organizacional específica pelo seu ID
 // Inclui validação de formato UUID e tratamento de erros
 
+#[utoipa::path(
+    get,
+    path = "/v1/org-units/{id}",
+    tag = "org-units",
+    summary = "Buscar unidade organizacional por ID",
+    params(("id" = Uuid, Path, description = "ID da unidade")),
+    responses(
+        (status = 200, description = "Unidade encontrada", body = OrgUnitResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Unidade não encontrada"),
+    )
+)]
 async fn get_org_unit(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID da unidade organizacional extraído da URL
-) -> Result<Json<OrgUnitResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<OrgUnitResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetOrgUnitsUseCase::new(state.org_unit_repository.as_ref());
 
@@ -113,27 +129,13 @@ async fn get_org_unit(
                 }
                 Err(err) => {
                     // ===== MAPEAMENTO DE ERROS =====
-                    let status = match err {
-                        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                        DomainError::ValidationError(_) => StatusCode::BAD_REQUEST,
-                        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                        DomainError::Conflict(_) => StatusCode::CONFLICT,
-                        DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                        DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-                    };
-                    Err((status, Json(json!({"error": err.to_string()}))))
+                    Err(map_domain_error(&err))
                 }
             }
         }
         Err(_) => {
             // UUID inválido: retorna erro 400 Bad Request
-            Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Invalid UUID format"})),
-            ))
+            Err(invalid_uuid_error())
         }
     }
 }
@@ -144,10 +146,22 @@ async fn get_org_unit(
 // Endpoint para criação de novas unidades organizacionais
 // Recebe dados via JSON e valida através do caso de uso
 
+#[utoipa::path(
+    post,
+    path = "/v1/org-units",
+    tag = "org-units",
+    summary = "Criar unidade organizacional",
+    request_body = CreateOrgUnitRequest,
+    responses(
+        (status = 200, description = "Unidade criada", body = OrgUnitResponse),
+        (status = 400, description = "Campos inválidos (ver `errors`)"),
+        (status = 409, description = "Unidade pai inexistente ou nome em uso"),
+    )
+)]
 async fn create_org_unit(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Json(request): Json<CreateOrgUnitRequest>, // Dados da unidade organizacional em formato JSON
-) -> Result<Json<OrgUnitResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<OrgUnitResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso para criação de unidades organizacionais
     let use_case = CreateOrgUnitUseCase::new(state.org_unit_repository.as_ref());
@@ -160,18 +174,7 @@ async fn create_org_unit(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito (ex: nome duplicado)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -181,12 +184,53 @@ async fn create_org_unit(
 // ============================================================================
 // Endpoint para atualização parcial de unidades organizacionais existentes
 // Combina ID da URL com dados do JSON para atualização
+// Aceita também application/merge-patch+json e application/json-patch+json,
+// onde `parent_id: null` transforma a unidade em raiz
 
+#[utoipa::path(
+    patch,
+    path = "/v1/org-units/{id}",
+    tag = "org-units",
+    summary = "Atualizar unidade organizacional",
+    params(("id" = Uuid, Path, description = "ID da unidade")),
+    request_body(
+        description = "Campos alterados (JSON), JSON Merge Patch (RFC 7396) ou JSON Patch (RFC 6902)",
+        content(
+            (UpdateOrgUnitRequest = "application/json"),
+            (OrgUnitPatchDocument = "application/merge-patch+json"),
+            (Vec<Object> = "application/json-patch+json"),
+        )
+    ),
+    responses(
+        (status = 200, description = "Unidade atualizada", body = OrgUnitResponse),
+        (status = 400, description = "Campos ou patch inválidos"),
+        (status = 404, description = "Unidade não encontrada"),
+        (status = 415, description = "Content-Type não suportado"),
+    )
+)]
 async fn update_org_unit(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID da unidade organizacional extraído da URL
-    Json(mut request): Json<UpdateOrgUnitRequest>, // Dados de atualização em JSON
-) -> Result<Json<OrgUnitResponse>, (StatusCode, Json<serde_json::Value>)> {
+    body: PatchBody<UpdateOrgUnitRequest>,     // Dados de atualização (JSON ou patch)
+) -> Result<Json<OrgUnitResponse>, ErrorResponse> {
+    // ===== PATCH RFC 7396 / RFC 6902 =====
+    let mut request = match body {
+        PatchBody::Json(request) => request,
+        PatchBody::Patch(patch) => {
+            let use_case = PatchOrgUnitUseCase::new(state.org_unit_repository.as_ref());
+            let request = PatchEntityRequest {
+                id,
+                patch,
+                etag: None,
+            };
+            return use_case
+                .execute(request)
+                .await
+                .map(Json)
+                .map_err(|err| map_domain_error(&err));
+        }
+    };
+
     // ===== PREPARAÇÃO DOS DADOS =====
     // Adiciona o ID da URL ao request para que o caso de uso tenha o ID completo
     request.id = id;
@@ -202,18 +246,7 @@ async fn update_org_unit(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Unidade não encontrada
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito de versão (ETag)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -224,10 +257,23 @@ async fn update_org_unit(
 // Endpoint para remoção de unidades organizacionais existentes
 // Retorna 204 No Content em caso de sucesso (padrão RESTful)
 
+#[utoipa::path(
+    delete,
+    path = "/v1/org-units/{id}",
+    tag = "org-units",
+    summary = "Excluir unidade organizacional",
+    params(("id" = Uuid, Path, description = "ID da unidade")),
+    responses(
+        (status = 204, description = "Unidade excluída"),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Unidade não encontrada"),
+        (status = 409, description = "Unidade ainda referenciada"),
+    )
+)]
 async fn delete_org_unit(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID da unidade organizacional a ser deletada
-) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<StatusCode, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = DeleteOrgUnitUseCase::new(state.org_unit_repository.as_ref());
 
@@ -239,18 +285,7 @@ async fn delete_org_unit(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Unidade não encontrada
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // ID inválido
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN, // Sem permissão para deletar
-                DomainError::Conflict(_) => StatusCode::CONFLICT,   // Conflito (ex: unidade em uso)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -261,10 +296,22 @@ async fn delete_org_unit(
 // Endpoint para obter a hierarquia completa de uma unidade organizacional
 // Retorna a unidade e todas as suas unidades filhas organizadas hierarquicamente
 
+#[utoipa::path(
+    get,
+    path = "/v1/org-units/{id}/hierarchy",
+    tag = "org-units",
+    summary = "Hierarquia da unidade organizacional",
+    params(("id" = Uuid, Path, description = "ID da unidade raiz")),
+    responses(
+        (status = 200, description = "Unidades e filhos por unidade pai", body = OrgUnitHierarchyResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Unidade não encontrada"),
+    )
+)]
 async fn get_org_unit_hierarchy(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID da unidade organizacional extraído da URL
-) -> Result<Json<OrgUnitHierarchyResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<OrgUnitHierarchyResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetOrgUnitsUseCase::new(state.org_unit_repository.as_ref());
 
@@ -283,27 +330,13 @@ async fn get_org_unit_hierarchy(
                 }
                 Err(err) => {
                     // ===== MAPEAMENTO DE ERROS =====
-                    let status = match err {
-                        DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Unidade não encontrada
-                        DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // ID inválido
-                        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                        DomainError::Conflict(_) => StatusCode::CONFLICT,
-                        DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                        DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-                    };
-                    Err((status, Json(json!({"error": err.to_string()}))))
+                    Err(map_domain_error(&err))
                 }
             }
         }
         Err(_) => {
             // UUID inválido: retorna erro 400 Bad Request
-            Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Invalid UUID format"})),
-            ))
+            Err(invalid_uuid_error())
         }
     }
 }
@@ -7,12 +7,12 @@
 // ===== CLEAN ARCHITECTURE IMPORTS =====
 use crate::application::dto::*; // DTOs (Data Transfer Objects) para comunicação entre camadas
 use crate::application::use_cases::user::*; // Casos de uso da camada de aplicação
-use crate::domain::{value_objects::UserId, DomainError}; // Value objects do domínio
+use crate::domain::value_objects::UserId; // Value objects do domínio
 
 // ===== PRESENTATION UTILITIES =====
 use crate::presentation::{
-    error_mapper::map_domain_error, // Mapeamento centralizado de erros
-                                    // validation::validate_uuid, // Validação de UUID
+    error_mapper::{invalid_uuid_error, map_domain_error, ErrorResponse}, // Mapeamento centralizado de erros
+                                                                         // validation::validate_uuid, // Validação de UUID
 };
 
 // ===== AXUM FRAMEWORK IMPORTS =====
@@ -20,12 +20,10 @@ use axum::{
     extract::{Path, Query, State}, // Extractors para parâmetros de rota, query e estado
     http::StatusCode,              // Códigos de status HTTP
     response::Json,                // Resposta JSON
-    routing::get,                  // Macros de roteamento HTTP
-    Router,                        // Roteador principal do Axum
 };
+use utoipa_axum::{router::OpenApiRouter, routes}; // Roteador que gera a especificação OpenAPI
 
 // ===== UTILITY IMPORTS =====
-use serde_json::json; // Para criação de JSON dinâmico
 use std::sync::Arc; // Para compartilhamento thread-safe do estado
 use uuid::Uuid; // Para validação de UUIDs
 
@@ -35,34 +33,24 @@ use uuid::Uuid; // Para validação de UUIDs
 // Define todas as rotas REST para operações de usuários
 // Implementa o padrão RESTful com operações CRUD completas e buscas específicas
 
-pub fn routes() -> Router<Arc<crate::AppState>> {
-    Router::new()
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
         // ===== ROTAS DE COLECAO =====
-        .route(
-            "/v1/users",
-            get(get_users) // GET /v1/users - Listar usuários com filtros
-                .post(create_user), // POST /v1/users - Criar novo usuário
-        )
+        // GET /v1/users - Listar usuários com filtros
+        // POST /v1/users - Criar novo usuário
+        .routes(routes!(get_users, create_user))
         // ===== ROTAS DE RECURSO INDIVIDUAL =====
-        .route(
-            "/v1/users/{id}",
-            get(get_user) // GET /v1/users/{id} - Buscar usuário por ID
-                .patch(update_user) // PATCH /v1/users/{id} - Atualizar usuário
-                .delete(delete_user), // DELETE /v1/users/{id} - Deletar usuário
-        )
+        // GET /v1/users/{id} - Buscar usuário por ID
+        // PATCH /v1/users/{id} - Atualizar usuário
+        // DELETE /v1/users/{id} - Deletar usuário
+        .routes(routes!(get_user, update_user, delete_user))
         // ===== ROTAS DE BUSCA ESPECÍFICA =====
-        .route(
-            "/v1/users/by-username/{username}",
-            get(get_user_by_username), // GET /v1/users/by-username/{username} - Buscar por username
-        )
-        .route(
-            "/v1/users/by-email/{email}",
-            get(get_user_by_email), // GET /v1/users/by-email/{email} - Buscar por email
-        )
-        .route(
-            "/v1/users/by-role/{role}",
-            get(get_users_by_role), // GET /v1/users/by-role/{role} - Buscar por role
-        )
+        // GET /v1/users/by-username/{username} - Buscar por username
+        .routes(routes!(get_user_by_username))
+        // GET /v1/users/by-email/{email} - Buscar por email
+        .routes(routes!(get_user_by_email))
+        // GET /v1/users/by-role/{role} - Buscar por role
+        .routes(routes!(get_users_by_role))
 }
 
 // ============================================================================
@@ -71,10 +59,21 @@ pub fn routes() -> Router<Arc<crate::AppState>> {
 // Endpoint para buscar usuários com suporte a filtros, paginação e ordenação
 // Implementa o padrão de busca RESTful com parâmetros de query
 
+#[utoipa::path(
+    get,
+    path = "/v1/users",
+    tag = "users",
+    summary = "Listar usuários",
+    params(UserSearchRequest),
+    responses(
+        (status = 200, description = "Página de usuários", body = UserSearchResponse),
+        (status = 400, description = "Filtros inválidos"),
+    )
+)]
 async fn get_users(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Query(params): Query<UserSearchRequest>,   // Parâmetros de query (filtros, paginação)
-) -> Result<Json<UserSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso injetando a dependência do repositório
     let use_case = GetUsersUseCase::new(state.user_repository.as_ref());
@@ -99,10 +98,22 @@ async fn get_users(
 // Endpoint para buscar um usuário específico pelo seu ID
 // Inclui validação de formato UUID e tratamento de erros
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/{id}",
+    tag = "users",
+    summary = "Buscar usuário por ID",
+    params(("id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 200, description = "Usuário encontrado", body = UserResponse),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Usuário não encontrado"),
+    )
+)]
 async fn get_user(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do usuário extraído da URL
-) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetUsersUseCase::new(state.user_repository.as_ref());
 
@@ -121,27 +132,13 @@ async fn get_user(
                 }
                 Err(err) => {
                     // ===== MAPEAMENTO DE ERROS =====
-                    let status = match err {
-                        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                        DomainError::ValidationError(_) => StatusCode::BAD_REQUEST,
-                        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                        DomainError::Conflict(_) => StatusCode::CONFLICT,
-                        DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                        DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                        DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-                    };
-                    Err((status, Json(json!({"error": err.to_string()}))))
+                    Err(map_domain_error(&err))
                 }
             }
         }
         Err(_) => {
             // UUID inválido: retorna erro 400 Bad Request
-            Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Invalid UUID format"})),
-            ))
+            Err(invalid_uuid_error())
         }
     }
 }
@@ -152,10 +149,22 @@ async fn get_user(
 // Endpoint para criação de novos usuários
 // Recebe dados via JSON e valida através do caso de uso
 
+#[utoipa::path(
+    post,
+    path = "/v1/users",
+    tag = "users",
+    summary = "Criar usuário",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 200, description = "Usuário criado", body = UserResponse),
+        (status = 400, description = "Campos inválidos (ver `errors`)"),
+        (status = 409, description = "Username ou email já cadastrado"),
+    )
+)]
 async fn create_user(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Json(request): Json<CreateUserRequest>,    // Dados do usuário em formato JSON
-) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     // Cria instância do caso de uso para criação de usuários
     let use_case = CreateUserUseCase::new(state.user_repository.as_ref());
@@ -169,18 +178,7 @@ async fn create_user(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito (ex: username/email duplicado)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -191,11 +189,25 @@ async fn create_user(
 // Endpoint para atualização parcial de usuários existentes
 // Combina ID da URL com dados do JSON para atualização
 
+#[utoipa::path(
+    patch,
+    path = "/v1/users/{id}",
+    tag = "users",
+    summary = "Atualizar usuário",
+    params(("id" = Uuid, Path, description = "ID do usuário")),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "Usuário atualizado", body = UserResponse),
+        (status = 400, description = "Campos inválidos"),
+        (status = 404, description = "Usuário não encontrado"),
+        (status = 409, description = "Username ou email já cadastrado"),
+    )
+)]
 async fn update_user(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do usuário extraído da URL
     Json(mut request): Json<UpdateUserRequest>, // Dados de atualização em JSON
-) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserResponse>, ErrorResponse> {
     // ===== PREPARAÇÃO DOS DADOS =====
     // Adiciona o ID da URL ao request para que o caso de uso tenha o ID completo
     request.id = id;
@@ -211,18 +223,7 @@ async fn update_user(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Usuário não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Dados inválidos
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT, // Conflito (ex: username/email duplicado)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -233,10 +234,22 @@ async fn update_user(
 // Endpoint para remoção de usuários existentes
 // Retorna 204 No Content em caso de sucesso (padrão RESTful)
 
+#[utoipa::path(
+    delete,
+    path = "/v1/users/{id}",
+    tag = "users",
+    summary = "Excluir usuário",
+    params(("id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 204, description = "Usuário excluído"),
+        (status = 400, description = "ID inválido"),
+        (status = 404, description = "Usuário não encontrado"),
+    )
+)]
 async fn delete_user(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(id): Path<String>,                    // ID do usuário a ser deletado
-) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<StatusCode, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = DeleteUserUseCase::new(state.user_repository.as_ref());
 
@@ -248,18 +261,7 @@ async fn delete_user(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Usuário não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // ID inválido
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN, // Sem permissão para deletar
-                DomainError::Conflict(_) => StatusCode::CONFLICT,   // Conflito (ex: usuário em uso)
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // Regra de negócio
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -270,10 +272,21 @@ async fn delete_user(
 // Endpoint para buscar um usuário específico pelo seu username
 // Útil para autenticação e validação de usernames únicos
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/by-username/{username}",
+    tag = "users",
+    summary = "Buscar usuário por username",
+    params(("username" = String, Path, description = "Username")),
+    responses(
+        (status = 200, description = "Usuário encontrado", body = UserResponse),
+        (status = 404, description = "Usuário não encontrado"),
+    )
+)]
 async fn get_user_by_username(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(username): Path<String>,              // Username do usuário extraído da URL
-) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetUsersUseCase::new(state.user_repository.as_ref());
 
@@ -285,18 +298,7 @@ async fn get_user_by_username(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Username não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Username inválido
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT,
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -307,10 +309,21 @@ async fn get_user_by_username(
 // Endpoint para buscar um usuário específico pelo seu email
 // Útil para autenticação e validação de emails únicos
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/by-email/{email}",
+    tag = "users",
+    summary = "Buscar usuário por email",
+    params(("email" = String, Path, description = "Email")),
+    responses(
+        (status = 200, description = "Usuário encontrado", body = UserResponse),
+        (status = 404, description = "Usuário não encontrado"),
+    )
+)]
 async fn get_user_by_email(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(email): Path<String>,                 // Email do usuário extraído da URL
-) -> Result<Json<UserResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetUsersUseCase::new(state.user_repository.as_ref());
 
@@ -322,18 +335,7 @@ async fn get_user_by_email(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Email não encontrado
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Email inválido
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT,
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -344,10 +346,20 @@ async fn get_user_by_email(
 // Endpoint para buscar usuários que possuem uma role específica
 // Útil para gerenciamento de permissões e relatórios por role
 
+#[utoipa::path(
+    get,
+    path = "/v1/users/by-role/{role}",
+    tag = "users",
+    summary = "Listar usuários de uma role",
+    params(("role" = String, Path, description = "Role")),
+    responses(
+        (status = 200, description = "Usuários com a role", body = UserSearchResponse),
+    )
+)]
 async fn get_users_by_role(
     State(state): State<Arc<crate::AppState>>, // Estado compartilhado da aplicação
     Path(role): Path<String>,                  // Role dos usuários extraída da URL
-) -> Result<Json<UserSearchResponse>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Json<UserSearchResponse>, ErrorResponse> {
     // ===== INICIALIZAÇÃO DO CASO DE USO =====
     let use_case = GetUsersUseCase::new(state.user_repository.as_ref());
 
@@ -359,18 +371,7 @@ async fn get_users_by_role(
         }
         Err(err) => {
             // ===== MAPEAMENTO DE ERROS =====
-            let status = match err {
-                DomainError::NotFound(_) => StatusCode::NOT_FOUND, // Role não encontrada
-                DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // Role inválida
-                DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
-                DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
-                DomainError::Conflict(_) => StatusCode::CONFLICT,
-                DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-                DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY,
-                DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
-            };
-            Err((status, Json(json!({"error": err.to_string()}))))
+            Err(map_domain_error(&err))
         }
     }
 }
@@ -0,0 +1,222 @@
+// ============================================================================
+// DEV TOKENS - EMISSÃO DE JWTs PARA DESENVOLVIMENTO (APENAS BUILDS DEBUG)
+// ============================================================================
+// Substitui o antigo DEV_AUTH_BYPASS (headers X-Dev-User/X-Dev-Roles):
+// - Na inicialização é gerada uma chave RSA efêmera, registrada como confiável no Jwks
+// - POST /v1/dev/tokens emite JWTs reais assinados com essa chave
+// - As requisições seguem o caminho normal de validação do AuthMiddleware
+// - Só clientes locais (peer no loopback, sem headers de proxy) recebem tokens
+// Este módulo só é compilado com `debug_assertions` e só é ativado com
+// DEV_TOKENS_ENABLED=1 fora de produção
+
+use crate::infra::signing_keys::{LocalSigningKey, LOCAL_SIGNING_ALGORITHM};
+use crate::presentation::auth;
+use crate::presentation::error_mapper::{custom_error, ErrorResponse};
+use axum::{
+    extract::ConnectInfo,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use jsonwebtoken::{encode, Header};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Issuer usado quando nenhum issuer é configurado para o AuthMiddleware
+const DEV_ISSUER: &str = "sut-dev";
+
+/// Validade padrão dos tokens de desenvolvimento (em segundos)
+const DEFAULT_TTL_SECS: u64 = 3600;
+
+/// Validade máxima dos tokens de desenvolvimento (em segundos)
+const MAX_TTL_SECS: u64 = 86400;
+
+struct DevTokenIssuer {
+    signing_key: LocalSigningKey,
+    issuer: String,
+    audiences: Vec<String>,
+}
+
+static DEV_TOKENS: OnceCell<DevTokenIssuer> = OnceCell::new();
+
+/// Gera a chave efêmera e a registra como confiável (requer `auth::init`)
+///
+/// `issuer` e `audiences` devem ser os mesmos validados pelo AuthMiddleware.
+pub fn init(issuer: Option<String>, audiences: Vec<String>) -> anyhow::Result<()> {
+    let signing_key = LocalSigningKey::generate()?;
+    auth::trust_signing_key(signing_key.jwk.clone())?;
+    tracing::warn!(kid = %signing_key.kid, "dev token issuer enabled (POST /v1/dev/tokens)");
+    DEV_TOKENS
+        .set(DevTokenIssuer {
+            signing_key,
+            issuer: issuer.unwrap_or_else(|| DEV_ISSUER.to_string()),
+            audiences,
+        })
+        .map_err(|_| anyhow::anyhow!("dev tokens already initialised"))
+}
+
+/// Requisição de token de desenvolvimento
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct DevTokenRequest {
+    /// Claim `sub` (padrão: `dev-user`)
+    pub sub: Option<String>,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Roles do realm (padrão: `directory.read`)
+    pub roles: Option<Vec<String>>,
+    /// Escopos OAuth separados por espaço
+    pub scope: Option<String>,
+    /// Validade em segundos (padrão: 3600, máximo: 86400)
+    pub ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DevTokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: u64,
+}
+
+/// Rotas de desenvolvimento (404 quando DEV_TOKENS_ENABLED não está ativo)
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new().routes(routes!(dev_token_handler)) // POST /v1/dev/tokens - Emitir token de desenvolvimento
+}
+
+/// HANDLER: POST /v1/dev/tokens - EMITIR TOKEN DE DESENVOLVIMENTO
+#[utoipa::path(
+    post,
+    path = "/v1/dev/tokens",
+    tag = "dev",
+    summary = "Emitir token de desenvolvimento (apenas builds debug)",
+    request_body = DevTokenRequest,
+    responses(
+        (status = 200, description = "Token assinado com a chave efêmera", body = DevTokenResponse),
+        (status = 400, description = "Validade inválida"),
+        (status = 403, description = "Cliente fora do loopback"),
+        (status = 404, description = "DEV_TOKENS_ENABLED desativado"),
+    )
+)]
+async fn dev_token_handler(
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<DevTokenRequest>,
+) -> Result<Json<DevTokenResponse>, ErrorResponse> {
+    let issuer = DEV_TOKENS
+        .get()
+        .ok_or_else(|| custom_error(StatusCode::NOT_FOUND, "Dev tokens are disabled"))?;
+    if !is_local_client(peer, &headers) {
+        warn!(%peer, "dev token request from non-local client rejected");
+        return Err(custom_error(
+            StatusCode::FORBIDDEN,
+            "Dev tokens are only issued to loopback clients",
+        ));
+    }
+    let ttl = request.ttl_secs.unwrap_or(DEFAULT_TTL_SECS);
+    if ttl == 0 || ttl > MAX_TTL_SECS {
+        return Err(custom_error(
+            StatusCode::BAD_REQUEST,
+            &format!("ttl_secs must be between 1 and {}", MAX_TTL_SECS),
+        ));
+    }
+
+    let access_token = mint(issuer, &request, ttl).map_err(|e| {
+        error!(error = ?e, "failed to sign dev token");
+        custom_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to issue token")
+    })?;
+    info!(sub = ?request.sub, roles = ?request.roles, "dev token issued");
+    Ok(Json(DevTokenResponse {
+        access_token,
+        token_type: "Bearer",
+        expires_in: ttl,
+    }))
+}
+
+/// Conexão direta a partir do loopback; requisições repassadas por um proxy
+/// (mesmo local) chegam com `Forwarded`/`X-Forwarded-For` e são recusadas
+fn is_local_client(peer: SocketAddr, headers: &HeaderMap) -> bool {
+    peer.ip().to_canonical().is_loopback()
+        && !headers.contains_key("forwarded")
+        && !headers.contains_key("x-forwarded-for")
+}
+
+fn mint(issuer: &DevTokenIssuer, request: &DevTokenRequest, ttl: u64) -> anyhow::Result<String> {
+    let now = chrono::Utc::now().timestamp();
+    let roles = request
+        .roles
+        .clone()
+        .unwrap_or_else(|| vec!["directory.read".to_string()]);
+
+    let mut header = Header::new(LOCAL_SIGNING_ALGORITHM);
+    header.kid = Some(issuer.signing_key.kid.clone());
+    let claims = serde_json::json!({
+        "iss": issuer.issuer,
+        "sub": request.sub.as_deref().unwrap_or("dev-user"),
+        "aud": issuer.audiences,
+        "iat": now,
+        "nbf": now,
+        "exp": now + ttl as i64,
+        "jti": Uuid::new_v4(),
+        "typ": "Bearer",
+        "sid": Uuid::new_v4(),
+        "preferred_username": request.username,
+        "email": request.email,
+        "realm_access": { "roles": roles },
+        "scope": request.scope,
+    });
+    Ok(encode(&header, &claims, &issuer.signing_key.encoding)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::auth::JwtClaims;
+    use jsonwebtoken::{decode, Validation};
+
+    #[test]
+    fn test_minted_token_carries_requested_claims() {
+        let issuer = DevTokenIssuer {
+            signing_key: LocalSigningKey::generate().unwrap(),
+            issuer: DEV_ISSUER.to_string(),
+            audiences: vec!["sut-frontend".to_string()],
+        };
+        let request = DevTokenRequest {
+            sub: Some("alice".to_string()),
+            roles: Some(vec!["admin".to_string()]),
+            scope: Some("directory.write".to_string()),
+            ..Default::default()
+        };
+
+        let token = mint(&issuer, &request, 60).unwrap();
+        let mut validation = Validation::new(LOCAL_SIGNING_ALGORITHM);
+        validation.set_audience(&["sut-frontend"]);
+        validation.set_issuer(&[DEV_ISSUER]);
+        let claims = decode::<JwtClaims>(&token, &issuer.signing_key.decoding, &validation)
+            .unwrap()
+            .claims;
+        assert_eq!(claims.sub, "alice");
+        assert_eq!(claims.realm_access.unwrap().roles, vec!["admin"]);
+        assert_eq!(claims.scope.as_deref(), Some("directory.write"));
+        assert_eq!(claims.exp - claims.iat, 60);
+    }
+
+    #[test]
+    fn test_only_direct_loopback_clients_are_local() {
+        let peer = |addr: &str| addr.parse::<SocketAddr>().unwrap();
+        let none = HeaderMap::new();
+        let mut forwarded = HeaderMap::new();
+        forwarded.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+
+        assert!(is_local_client(peer("127.0.0.1:40000"), &none));
+        assert!(is_local_client(peer("[::1]:40000"), &none));
+        assert!(is_local_client(peer("[::ffff:127.0.0.1]:40000"), &none));
+        assert!(!is_local_client(peer("10.0.0.5:40000"), &none));
+        assert!(!is_local_client(peer("[2001:db8::1]:40000"), &none));
+        assert!(!is_local_client(peer("127.0.0.1:40000"), &forwarded));
+    }
+}
@@ -1,48 +1,386 @@
-use axum::{http::StatusCode, response::Html, routing::get, Router};
-
-const OPENAPI_SPEC_YAML: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../openapi.yaml"));
-
-pub fn routes() -> Router<std::sync::Arc<crate::AppState>> {
-    Router::new()
-        .route("/docs", get(swagger_ui))
-        .route("/docs/openapi.yaml", get(openapi_spec))
-}
-
-async fn swagger_ui() -> Html<&'static str> {
-    const HTML: &str = r#"<!DOCTYPE html>
-<html lang=\"en\">
-  <head>
-    <meta charset=\"utf-8\" />
-    <title>SUT API Docs</title>
-    <link rel=\"stylesheet\" href=\"https://cdn.jsdelivr.net/npm/swagger-ui-dist@5/swagger-ui.css\" />
-    <style>
-      html, body { margin: 0; padding: 0; background: #fafafa; }
-      #swagger-ui { width: 100%; box-sizing: border-box; }
-    </style>
-  </head>
-  <body>
-    <div id=\"swagger-ui\"></div>
-    <script src=\"https://cdn.jsdelivr.net/npm/swagger-ui-dist@5/swagger-ui-bundle.js\"></script>
-    <script src=\"https://cdn.jsdelivr.net/npm/swagger-ui-dist@5/swagger-ui-standalone-preset.js\"></script>
-    <script>
-      window.addEventListener('load', () => {
-        window.ui = SwaggerUIBundle({
-          url: '/docs/openapi.yaml',
-          dom_id: '#swagger-ui',
-          presets: [SwaggerUIBundle.presets.apis, SwaggerUIStandalonePreset],
-          layout: 'StandaloneLayout'
-        });
-      });
-    </script>
-  </body>
-</html>"#;
-    Html(HTML)
-}
-
-async fn openapi_spec() -> Result<axum::response::Response, StatusCode> {
-    axum::response::Response::builder()
-        .header(axum::http::header::CONTENT_TYPE, "application/yaml")
-        .body(OPENAPI_SPEC_YAML.into())
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+// ============================================================================
+// DOCS - ESPECIFICAÇÃO OPENAPI E SWAGGER UI
+// ============================================================================
+// A especificação é gerada a partir dos próprios routers: cada rota é registrada
+// com `routes!` (utoipa-axum), que lê o `#[utoipa::path]` do handler, e os
+// schemas vêm dos DTOs (`ToSchema`). As permissões exigidas são anotadas por
+// `require_roles`, junto aos middlewares que as verificam. Rotas registradas com
+// `.route` não entram na especificação, por isso nenhuma rota da API o usa.
+// - GET /docs              -> Swagger UI (arquivos embutidos no binário)
+// - GET /docs/openapi.json -> especificação OpenAPI 3.1
+
+use crate::presentation::error_mapper::{ProblemSchema, PROBLEM_JSON};
+use axum::Router;
+use utoipa::openapi::{
+    path::{Operation, PathItem},
+    security::{
+        ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+    },
+    Content, OpenApi as OpenApiSpec, Ref, RefOr, Response,
+};
+use utoipa::{Modify, OpenApi, ToSchema};
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_swagger_ui::SwaggerUi;
+
+/// Caminho da especificação
+pub const SPEC_PATH: &str = "/docs/openapi.json";
+
+/// Esquema de segurança dos JWTs (Keycloak ou emissor local)
+const BEARER: &str = "bearer";
+
+/// Esquema de segurança das API keys (`Authorization: ApiKey ...`)
+const API_KEY: &str = "api_key";
+
+/// Esquema de segurança do segredo compartilhado dos webhooks
+const WEBHOOK_TOKEN: &str = "webhook_token";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "SUT Directory API",
+        description = "Diretório de contatos, unidades organizacionais e departamentos. \
+            Erros seguem a RFC 7807 (`application/problem+json`)."
+    ),
+    components(schemas(ProblemSchema)),
+    modifiers(&SecuritySchemes),
+    tags(
+        (name = "contacts", description = "Contatos"),
+        (name = "org-units", description = "Unidades organizacionais"),
+        (name = "departments", description = "Departamentos"),
+        (name = "users", description = "Usuários locais"),
+        (name = "auth", description = "Tokens e revogação"),
+        (name = "api-keys", description = "Credenciais de clientes máquina-a-máquina"),
+        (name = "webhooks", description = "Eventos de serviços externos"),
+        (name = "health", description = "Liveness e readiness"),
+        (name = "dev", description = "Apenas builds debug"),
+    )
+)]
+struct ApiDoc;
+
+/// Registra os esquemas de segurança; os requisitos ficam em cada operação
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut OpenApiSpec) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some(
+                        "Access token do Keycloak ou de `POST /v1/auth/token`. \
+                         Os requisitos listam as permissões aceitas (basta uma).",
+                    ))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            API_KEY,
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "`ApiKey sut_<prefixo>_<segredo>`; os escopos da chave passam pelo mesmo \
+                 mapeamento de permissões dos JWTs.",
+            ))),
+        );
+        components.add_security_scheme(
+            WEBHOOK_TOKEN,
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-Webhook-Token",
+                "Segredo compartilhado dos webhooks (WEBHOOK_SHARED_SECRET).",
+            ))),
+        );
+    }
+}
+
+/// Especificação base, sem rotas (ponto de partida do `OpenApiRouter` da aplicação)
+pub fn base() -> OpenApiSpec {
+    ApiDoc::openapi()
+}
+
+/// Documenta as permissões exigidas pelas rotas de `router`
+///
+/// Operações que já declaram `security` no `#[utoipa::path]` (permissões mais
+/// restritas verificadas no caso de uso) são mantidas.
+pub fn require_roles<S>(mut router: OpenApiRouter<S>, roles: &[&str]) -> OpenApiRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    for item in router.get_openapi_mut().paths.paths.values_mut() {
+        for operation in operations_mut(item) {
+            operation.security.get_or_insert_with(|| {
+                vec![
+                    SecurityRequirement::new(BEARER, roles.iter().copied()),
+                    SecurityRequirement::new(API_KEY, roles.iter().copied()),
+                ]
+            });
+        }
+    }
+    router
+}
+
+/// Documenta as permissões exigidas pelas operações de escrita de `router`
+/// (todas exceto GET e HEAD); combinar com `require_roles` para as leituras
+pub fn require_write_roles<S>(mut router: OpenApiRouter<S>, roles: &[&str]) -> OpenApiRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    for item in router.get_openapi_mut().paths.paths.values_mut() {
+        let writes = [
+            &mut item.put,
+            &mut item.post,
+            &mut item.delete,
+            &mut item.patch,
+        ];
+        for operation in writes.into_iter().filter_map(Option::as_mut) {
+            operation.security.get_or_insert_with(|| {
+                vec![
+                    SecurityRequirement::new(BEARER, roles.iter().copied()),
+                    SecurityRequirement::new(API_KEY, roles.iter().copied()),
+                ]
+            });
+        }
+    }
+    router
+}
+
+/// Rotas da documentação, servindo `openapi`
+pub fn routes<S>(mut openapi: OpenApiSpec) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    describe_errors(&mut openapi);
+    SwaggerUi::new("/docs").url(SPEC_PATH, openapi).into()
+}
+
+/// Completa as respostas de erro: corpo `application/problem+json` e, nas
+/// operações autenticadas, as respostas 401 e 403
+fn describe_errors(openapi: &mut OpenApiSpec) {
+    for item in openapi.paths.paths.values_mut() {
+        for operation in operations_mut(item) {
+            if operation.security.as_ref().is_some_and(|s| !s.is_empty()) {
+                let responses = &mut operation.responses.responses;
+                responses
+                    .entry("401".to_string())
+                    .or_insert_with(|| Response::new("Credenciais ausentes ou inválidas").into());
+                responses
+                    .entry("403".to_string())
+                    .or_insert_with(|| Response::new("Sem a permissão exigida").into());
+            }
+            for (status, response) in operation.responses.responses.iter_mut() {
+                let RefOr::T(response) = response else {
+                    continue;
+                };
+                if (status.starts_with('4') || status.starts_with('5'))
+                    && response.content.is_empty()
+                {
+                    response.content.insert(
+                        PROBLEM_JSON.to_string(),
+                        Content::new(Some(Ref::from_schema_name(ProblemSchema::name()))),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ]
+    .into_iter()
+    .filter_map(Option::as_mut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn documented_operations(openapi: &OpenApiSpec) -> BTreeSet<(String, String)> {
+        let mut operations = BTreeSet::new();
+        for (path, item) in &openapi.paths.paths {
+            let methods = [
+                ("get", &item.get),
+                ("put", &item.put),
+                ("post", &item.post),
+                ("delete", &item.delete),
+                ("patch", &item.patch),
+            ];
+            for (method, operation) in methods {
+                if operation.is_some() {
+                    operations.insert((method.to_string(), path.clone()));
+                }
+            }
+        }
+        operations
+    }
+
+    fn spec() -> OpenApiSpec {
+        let (_, mut openapi) = super::super::api_routes().split_for_parts();
+        describe_errors(&mut openapi);
+        openapi
+    }
+
+    /// Todas as rotas são registradas com `routes!`, então a especificação é a
+    /// lista de rotas: cada operação documentada precisa chegar a um handler, e os
+    /// demais métodos dos mesmos caminhos precisam cair no fallback
+    #[tokio::test]
+    async fn test_every_route_is_documented() {
+        const UNROUTED: StatusCode = StatusCode::IM_A_TEAPOT;
+        let (router, openapi) = super::super::api_routes().split_for_parts();
+        let router = router
+            .method_not_allowed_fallback(|| async { UNROUTED })
+            .fallback(|| async { UNROUTED })
+            .with_state(Arc::new(crate::AppState::in_memory(
+                &crate::infrastructure::memory::InMemoryDatabase::new(),
+            )));
+        let documented = documented_operations(&openapi);
+        assert!(documented.len() > 30, "{documented:?}");
+
+        let mut unrouted = Vec::new();
+        let mut undocumented = Vec::new();
+        let paths: BTreeSet<&String> = documented.iter().map(|(_, path)| path).collect();
+        for path in paths {
+            // Parâmetros de caminho recebem um UUID qualquer
+            let uri = path
+                .split('/')
+                .map(|segment| {
+                    if segment.starts_with('{') {
+                        "00000000-0000-0000-0000-000000000000"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            for method in ["get", "put", "post", "delete", "patch"] {
+                let request = Request::builder()
+                    .method(method.to_uppercase().as_str())
+                    .uri(&uri)
+                    .body(Body::empty())
+                    .unwrap();
+                let routed = router.clone().oneshot(request).await.unwrap().status() != UNROUTED;
+                match (
+                    documented.contains(&(method.to_string(), path.clone())),
+                    routed,
+                ) {
+                    (true, false) => unrouted.push(format!("{method} {path}")),
+                    (false, true) => undocumented.push(format!("{method} {path}")),
+                    _ => {}
+                }
+            }
+        }
+        assert!(
+            undocumented.is_empty(),
+            "rotas sem documentação (registre-as com routes! e #[utoipa::path]): {undocumented:?}"
+        );
+        assert!(
+            unrouted.is_empty(),
+            "operações documentadas sem rota: {unrouted:?}"
+        );
+    }
+
+    #[test]
+    fn test_security_follows_route_roles() {
+        let json = serde_json::to_value(spec()).unwrap();
+        let paths = &json["paths"];
+
+        let list_contacts = &paths["/v1/contacts"]["get"];
+        assert_eq!(
+            list_contacts["security"],
+            serde_json::json!([
+                { "bearer": ["directory.read", "admin"] },
+                { "api_key": ["directory.read", "admin"] }
+            ])
+        );
+        assert_eq!(
+            list_contacts["responses"]["401"]["content"][PROBLEM_JSON]["schema"]["$ref"],
+            "#/components/schemas/Problem"
+        );
+        assert_eq!(
+            paths["/v1/contacts/{id}/restore"]["post"]["security"][0]["bearer"],
+            serde_json::json!(["admin"])
+        );
+        assert_eq!(
+            paths["/v1/users"]["get"]["security"][0]["bearer"],
+            serde_json::json!(["admin"])
+        );
+        assert_eq!(
+            paths["/v1/webhooks/vault-alerts"]["post"]["security"][0],
+            serde_json::json!({ "webhook_token": [] })
+        );
+        assert!(paths["/health"]["get"].get("security").is_none());
+        assert!(paths["/v1/auth/token"]["post"].get("security").is_none());
+        assert!(json["components"]["securitySchemes"][BEARER].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_spec_and_swagger_ui_are_served_locally() {
+        let (_, openapi) = super::super::api_routes().split_for_parts();
+        let app: Router = routes(openapi);
+
+        let response = app
+            .clone()
+            .oneshot(Request::get(SPEC_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["paths"]["/v1/org-units"].is_object());
+        assert!(json["paths"]["/v1/departments/statistics"].is_object());
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/docs/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!html.contains("https://"), "{html}");
+
+        let response = app
+            .oneshot(
+                Request::get("/docs/swagger-ui-bundle.js")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// `openapi.json` na raiz do repositório (usado pelo frontend para gerar os
+    /// tipos) deve acompanhar as rotas; `UPDATE_OPENAPI=1 cargo test` o regenera
+    #[test]
+    fn test_openapi_json_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../openapi.json");
+        let generated = format!("{}\n", spec().to_pretty_json().unwrap());
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(path, &generated).unwrap();
+            return;
+        }
+        let committed = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "openapi.json desatualizado; execute UPDATE_OPENAPI=1 cargo test"
+        );
+    }
 }
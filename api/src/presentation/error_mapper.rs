@@ -3,25 +3,138 @@
 // ============================================================================
 // Módulo que centraliza o mapeamento de erros de domínio para códigos HTTP
 // Elimina redundância e garante consistência em todos os controllers
+//
+// Todo erro é respondido como `application/problem+json` (RFC 7807) com um
+// `code` estável para o cliente tratar programaticamente. `instance` e
+// `request_id` são incluídos pelo log de acesso (ver `access_log`), que também
+// converte respostas de erro sem corpo (ex.: falhas de middleware).
 
-use crate::domain::errors::DomainError;
+use crate::domain::errors::{DomainError, FieldError};
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
-    Json,
 };
-use serde_json::json;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+/// Content-Type das respostas de erro
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Prefixo do `type` dos problemas (seguido do `code`)
+const PROBLEM_TYPE_PREFIX: &str = "urn:sut:problem:";
+
+/// Detalhe dos erros internos; a mensagem original fica apenas no log
+const INTERNAL_DETAIL: &str = "The request could not be completed. Try again later.";
+
+/// Resposta de erro RFC 7807 (`application/problem+json`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub status: StatusCode,
+    /// Código estável do erro (ex.: `not_found`, `validation_failed`)
+    pub code: String,
+    /// Resumo do tipo de problema (igual para todas as ocorrências do código)
+    pub title: String,
+    /// Explicação desta ocorrência
+    pub detail: String,
+    /// Erros por campo (validação)
+    pub errors: Vec<FieldError>,
+}
 
 /// Tipo de resposta de erro padronizada
-pub type ErrorResponse = (StatusCode, Json<serde_json::Value>);
+pub type ErrorResponse = Problem;
+
+/// Corpo de um `Problem` na especificação OpenAPI (ver `Problem::to_json`),
+/// incluindo os campos adicionados pelo log de acesso
+#[derive(ToSchema)]
+#[schema(as = Problem)]
+#[allow(dead_code)]
+pub struct ProblemSchema {
+    /// `urn:sut:problem:` seguido do `code`
+    #[schema(example = "urn:sut:problem:not_found")]
+    r#type: String,
+    /// Resumo do tipo de problema
+    title: String,
+    /// Código de status HTTP
+    status: u16,
+    /// Explicação desta ocorrência
+    detail: String,
+    /// Código estável do erro
+    #[schema(example = "not_found")]
+    code: String,
+    /// Caminho da requisição
+    instance: Option<String>,
+    /// Mesmo valor do header `X-Request-Id`
+    request_id: Option<String>,
+    /// Erros por campo (apenas `validation_failed`)
+    errors: Option<Vec<FieldError>>,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode, code: &str, title: &str, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            code: code.to_string(),
+            title: title.to_string(),
+            detail: detail.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Problema genérico de um status HTTP (código derivado da descrição do
+    /// status, ex.: 429 → `too_many_requests`)
+    pub fn from_status(status: StatusCode) -> Self {
+        let title = status.canonical_reason().unwrap_or("Error");
+        let code = title
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        Self::new(status, &code, title, title)
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    pub fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Corpo do problema, sem `instance` e `request_id`
+    pub fn to_json(&self) -> Value {
+        let mut body = json!({
+            "type": format!("{}{}", PROBLEM_TYPE_PREFIX, self.code),
+            "title": self.title,
+            "status": self.status.as_u16(),
+            "detail": self.detail,
+            "code": self.code,
+        });
+        if !self.errors.is_empty() {
+            body["errors"] = json!(self.errors);
+        }
+        body
+    }
+}
 
-/// Mapeia erros de domínio para códigos de status HTTP apropriados
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            [(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON))],
+            self.to_json().to_string(),
+        )
+            .into_response()
+    }
+}
+
+/// Mapeia erros de domínio para problemas HTTP com código estável
 ///
 /// # Argumentos
 /// * `error` - Erro de domínio a ser mapeado
 ///
 /// # Retorna
-/// Tupla contendo o código de status HTTP e a resposta JSON de erro
+/// Problema com status, código e detalhe; erros internos, de banco e de
+/// serviços externos são logados e respondidos com um detalhe genérico
 ///
 /// # Exemplos
 ///
@@ -29,24 +142,84 @@ pub type ErrorResponse = (StatusCode, Json<serde_json::Value>);
 /// use crate::presentation::error_mapper::map_domain_error;
 ///
 /// let domain_error = DomainError::NotFound("Resource not found".to_string());
-/// let (status, response) = map_domain_error(&domain_error);
-/// // status = StatusCode::NOT_FOUND
-/// // response = Json({"error": "Resource not found"})
+/// let problem = map_domain_error(&domain_error);
+/// // problem.status = StatusCode::NOT_FOUND, problem.code = "not_found"
+/// // problem.detail = "Resource not found"
 /// ```
 pub fn map_domain_error(error: &DomainError) -> ErrorResponse {
-    let status = match error {
-        DomainError::NotFound(_) => StatusCode::NOT_FOUND, // 404 - Recurso não encontrado
-        DomainError::ValidationError(_) => StatusCode::BAD_REQUEST, // 400 - Dados inválidos
-        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED, // 401 - Não autenticado
-        DomainError::Forbidden(_) => StatusCode::FORBIDDEN, // 403 - Sem permissão
-        DomainError::Conflict(_) => StatusCode::CONFLICT,  // 409 - Conflito de estado
-        DomainError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR, // 500 - Erro interno
-        DomainError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR, // 500 - Erro de BD
-        DomainError::ExternalServiceError(_) => StatusCode::BAD_GATEWAY, // 502 - Erro de serviço externo
-        DomainError::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY, // 422 - Regra de negócio
-    };
-
-    (status, Json(json!({"error": error.to_string()})))
+    match error {
+        DomainError::NotFound(message) => Problem::new(
+            StatusCode::NOT_FOUND, // 404 - Recurso não encontrado
+            "not_found",
+            "Resource not found",
+            message.as_str(),
+        ),
+        DomainError::ValidationError(message) => Problem::new(
+            StatusCode::BAD_REQUEST, // 400 - Dados inválidos
+            "validation_failed",
+            "Invalid request",
+            message.as_str(),
+        ),
+        DomainError::InvalidFields(errors) => Problem::new(
+            StatusCode::BAD_REQUEST, // 400 - Campos inválidos
+            "validation_failed",
+            "Invalid request",
+            "One or more fields are invalid",
+        )
+        .with_errors(errors.clone()),
+        DomainError::Unauthorized(message) => Problem::new(
+            StatusCode::UNAUTHORIZED, // 401 - Não autenticado
+            "unauthorized",
+            "Authentication required",
+            message.as_str(),
+        ),
+        DomainError::Forbidden(message) => Problem::new(
+            StatusCode::FORBIDDEN, // 403 - Sem permissão
+            "forbidden",
+            "Permission denied",
+            message.as_str(),
+        ),
+        DomainError::Conflict(message) => Problem::new(
+            StatusCode::CONFLICT, // 409 - Conflito de estado
+            "conflict",
+            "Conflict",
+            message.as_str(),
+        ),
+        DomainError::BusinessRuleViolation(message) => Problem::new(
+            StatusCode::UNPROCESSABLE_ENTITY, // 422 - Regra de negócio
+            "business_rule_violation",
+            "Business rule violated",
+            message.as_str(),
+        ),
+        DomainError::InternalError(message) => {
+            tracing::error!(error = %message, "internal error");
+            Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR, // 500 - Erro interno
+                "internal_error",
+                "Internal server error",
+                INTERNAL_DETAIL,
+            )
+        }
+        DomainError::DatabaseError(message) => {
+            // A mensagem do driver pode conter SQL, tabelas e valores
+            tracing::error!(error = %message, "database error");
+            Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR, // 500 - Erro de BD
+                "database_error",
+                "Internal server error",
+                INTERNAL_DETAIL,
+            )
+        }
+        DomainError::ExternalServiceError(message) => {
+            tracing::error!(error = %message, "external service error");
+            Problem::new(
+                StatusCode::BAD_GATEWAY, // 502 - Erro de serviço externo
+                "external_service_error",
+                "Upstream service failure",
+                INTERNAL_DETAIL,
+            )
+        }
+    }
 }
 
 /// Cria uma resposta de erro para UUID inválido
@@ -54,9 +227,11 @@ pub fn map_domain_error(error: &DomainError) -> ErrorResponse {
 /// # Retorna
 /// Resposta padronizada para erro de UUID inválido
 pub fn invalid_uuid_error() -> ErrorResponse {
-    (
+    Problem::new(
         StatusCode::BAD_REQUEST,
-        Json(json!({"error": "Invalid UUID format"})),
+        "invalid_id",
+        "Invalid request",
+        "Invalid UUID format",
     )
 }
 
@@ -67,9 +242,9 @@ pub fn invalid_uuid_error() -> ErrorResponse {
 /// * `message` - Mensagem de erro
 ///
 /// # Retorna
-/// Resposta de erro customizada
+/// Problema com o código derivado do status e `message` como detalhe
 pub fn custom_error(status: StatusCode, message: &str) -> ErrorResponse {
-    (status, Json(json!({"error": message})))
+    Problem::from_status(status).with_detail(message)
 }
 
 /// Trait para facilitar o mapeamento de erros em handlers
@@ -130,34 +305,81 @@ mod tests {
     #[test]
     fn test_map_domain_error_not_found() {
         let error = DomainError::NotFound("Resource not found".to_string());
-        let (status, response) = map_domain_error(&error);
+        let problem = map_domain_error(&error);
 
-        assert_eq!(status, StatusCode::NOT_FOUND);
-        assert_eq!(response.0["error"], "Resource not found");
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+        assert_eq!(problem.code, "not_found");
+        assert_eq!(problem.detail, "Resource not found");
     }
 
     #[test]
     fn test_map_domain_error_validation() {
         let error = DomainError::ValidationError("Invalid input".to_string());
-        let (status, response) = map_domain_error(&error);
+        let problem = map_domain_error(&error);
+
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.code, "validation_failed");
+        assert_eq!(problem.detail, "Invalid input");
+    }
+
+    #[test]
+    fn test_field_errors_are_listed() {
+        let error = DomainError::InvalidFields(vec![
+            FieldError::new("full_name", "Full name cannot be empty"),
+            FieldError::new("status", "Invalid contact status: GONE"),
+        ]);
+        let body = map_domain_error(&error).to_json();
+
+        assert_eq!(body["type"], "urn:sut:problem:validation_failed");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["errors"][0]["field"], "full_name");
+        assert_eq!(body["errors"][1]["message"], "Invalid contact status: GONE");
+    }
+
+    #[test]
+    fn test_database_error_does_not_leak_sql() {
+        let error = DomainError::DatabaseError(
+            "column \"document\" of relation \"contacts\" does not exist".to_string(),
+        );
+        let problem = map_domain_error(&error);
+
+        assert_eq!(problem.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(problem.code, "database_error");
+        let body = problem.to_json().to_string();
+        assert!(!body.contains("contacts"), "{body}");
+        assert!(!body.contains("document"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn test_problem_response_content_type() {
+        let response =
+            custom_error(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
 
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        assert_eq!(response.0["error"], "Invalid input");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "too_many_requests");
+        assert_eq!(body["title"], "Too Many Requests");
+        assert_eq!(body["detail"], "Rate limit exceeded");
     }
 
     #[test]
     fn test_invalid_uuid_error() {
-        let (status, response) = invalid_uuid_error();
+        let problem = invalid_uuid_error();
 
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        assert_eq!(response.0["error"], "Invalid UUID format");
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.detail, "Invalid UUID format");
     }
 
     #[test]
     fn test_custom_error() {
-        let (status, response) = custom_error(StatusCode::IM_A_TEAPOT, "Custom error");
+        let problem = custom_error(StatusCode::IM_A_TEAPOT, "Custom error");
 
-        assert_eq!(status, StatusCode::IM_A_TEAPOT);
-        assert_eq!(response.0["error"], "Custom error");
+        assert_eq!(problem.status, StatusCode::IM_A_TEAPOT);
+        assert_eq!(problem.code, "i_m_a_teapot");
+        assert_eq!(problem.detail, "Custom error");
     }
 }
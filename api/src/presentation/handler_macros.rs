@@ -41,7 +41,7 @@ macro_rules! list_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Query(params): Query<$request_type>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.execute(params).await {
@@ -78,7 +78,7 @@ macro_rules! get_by_id_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(id): Path<String>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             let uuid = validate_uuid(&id)?;
@@ -118,7 +118,7 @@ macro_rules! create_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Json(request): Json<$request_type>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.execute(request).await {
@@ -156,7 +156,7 @@ macro_rules! update_handler {
             State(state): State<Arc<crate::AppState>>,
             Path(id): Path<String>,
             Json(mut request): Json<$request_type>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             request.id = id;
@@ -191,7 +191,7 @@ macro_rules! delete_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(id): Path<String>,
-        ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<StatusCode, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.execute(&id).await {
@@ -225,7 +225,7 @@ macro_rules! statistics_handler {
     ($use_case_type:ty, $response_type:ty, $repository_field:ident) => {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.execute().await {
@@ -262,7 +262,7 @@ macro_rules! get_by_param_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(param): Path<String>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.$method_name(&param).await {
@@ -301,7 +301,7 @@ macro_rules! get_by_uuid_param_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(param): Path<String>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             let uuid = validate_uuid(&param)?;
@@ -341,7 +341,7 @@ macro_rules! get_list_by_param_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(param): Path<String>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             match use_case.$method_name(&param).await {
@@ -380,7 +380,7 @@ macro_rules! get_list_by_uuid_param_handler {
         async fn handler(
             State(state): State<Arc<crate::AppState>>,
             Path(param): Path<String>,
-        ) -> Result<Json<$response_type>, (StatusCode, Json<serde_json::Value>)> {
+        ) -> Result<Json<$response_type>, $crate::presentation::error_mapper::ErrorResponse> {
             let use_case = <$use_case_type>::new(state.$repository_field.as_ref());
 
             let uuid = validate_uuid(&param)?;
@@ -1,8 +1,144 @@
-use axum::{routing::get, Router};
-pub fn routes() -> Router<std::sync::Arc<crate::AppState>> {
-    // Keep the presentation-level probes limited to the `*z` endpoints.
-    // The application-level `/health` and `/ready` are defined in `main.rs`
-    Router::new()
-        .route("/healthz", get(|| async { "ok" }))
-        .route("/readyz", get(|| async { "ok" }))
+// ============================================================================
+// HEALTH ENDPOINTS - LIVENESS E READINESS
+// ============================================================================
+// - Liveness (`/health`, `/healthz`): o processo responde; não consulta dependências
+// - Readiness (`/ready`, `/readyz`): relatório JSON das verificações registradas
+//   no `HealthRegistry` (cacheado), 503 quando uma verificação crítica falha;
+//   os diagnósticos internos só aparecem em `/v1/health/details` (admin)
+
+use crate::shared::health::{CheckOutcome, HealthCheck, HealthReport, LivenessReport};
+use async_trait::async_trait;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(liveness))
+        .routes(routes!(liveness_k8s))
+        .routes(routes!(readiness))
+        .routes(routes!(readiness_k8s))
+}
+
+/// Relatório com diagnósticos (protegido por `require_admin_middleware`)
+pub fn detail_routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new().routes(routes!(readiness_details))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    summary = "Liveness",
+    responses((status = 200, description = "Processo em execução", body = LivenessReport))
+)]
+async fn liveness(State(state): State<Arc<crate::AppState>>) -> impl IntoResponse {
+    Json(state.health.liveness())
+}
+
+/// Alias de `/health` no padrão do Kubernetes
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    summary = "Liveness (alias de /health)",
+    responses((status = 200, description = "Processo em execução", body = LivenessReport))
+)]
+async fn liveness_k8s(state: State<Arc<crate::AppState>>) -> impl IntoResponse {
+    liveness(state).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "health",
+    summary = "Readiness",
+    responses(
+        (status = 200, description = "Pronta para receber tráfego", body = HealthReport),
+        (status = 503, description = "Uma verificação crítica falhou", body = HealthReport),
+    )
+)]
+async fn readiness(State(state): State<Arc<crate::AppState>>) -> impl IntoResponse {
+    let report = state.health.report().await.public();
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+/// Alias de `/ready` no padrão do Kubernetes
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    summary = "Readiness (alias de /ready)",
+    responses(
+        (status = 200, description = "Pronta para receber tráfego", body = HealthReport),
+        (status = 503, description = "Uma verificação crítica falhou", body = HealthReport),
+    )
+)]
+async fn readiness_k8s(state: State<Arc<crate::AppState>>) -> impl IntoResponse {
+    readiness(state).await
+}
+
+/// Relatório de prontidão com os diagnósticos internos de cada verificação
+#[utoipa::path(
+    get,
+    path = "/v1/health/details",
+    tag = "health",
+    summary = "Readiness com diagnósticos",
+    responses((status = 200, description = "Relatório completo", body = HealthReport))
+)]
+async fn readiness_details(State(state): State<Arc<crate::AppState>>) -> impl IntoResponse {
+    Json(state.health.report().await)
+}
+
+/// Chaves do JWKS carregadas e idade do último refresh
+pub struct JwksCheck {
+    /// Idade a partir da qual o JWKS remoto é considerado desatualizado
+    max_age: Duration,
+}
+
+impl JwksCheck {
+    pub fn new(max_age: Duration) -> Self {
+        Self { max_age }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for JwksCheck {
+    fn name(&self) -> &str {
+        "jwks"
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let Some(jwks) = crate::presentation::auth::jwks_status() else {
+            return CheckOutcome::fail(serde_json::Value::Null, "auth not initialized");
+        };
+        let age = jwks
+            .refreshed_at
+            .map(|at| (chrono::Utc::now() - at).num_seconds().max(0));
+        let details = json!({
+            "remote_keys": jwks.remote_keys,
+            "trusted_keys": jwks.trusted_keys,
+            "age_secs": age,
+            "max_age_secs": self.max_age.as_secs(),
+        });
+
+        if jwks.remote_keys + jwks.trusted_keys == 0 {
+            return CheckOutcome::fail(details, "no signing keys loaded");
+        }
+        // Sem JWKS remoto (apenas emissor local) não há refresh a acompanhar
+        let stale =
+            !jwks.uri.is_empty() && age.is_none_or(|age| age as u64 > self.max_age.as_secs());
+        if stale {
+            CheckOutcome::warn(details, "JWKS refresh is overdue")
+        } else {
+            CheckOutcome::pass(details)
+        }
+    }
 }
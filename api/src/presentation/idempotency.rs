@@ -0,0 +1,442 @@
+// ============================================================================
+// IDEMPOTENCY MIDDLEWARE - SUPORTE AO HEADER `Idempotency-Key`
+// ============================================================================
+// Middleware aplicado às rotas POST (criação e lote):
+// - Chave nova               -> processa e armazena a resposta
+// - Mesma chave e mesmo corpo -> reproduz a resposta original
+// - Mesma chave e outro corpo -> 422 Unprocessable Entity
+// - Requisição original em andamento -> 409 Conflict
+// Respostas 5xx não são armazenadas: a chave é liberada para nova tentativa
+//
+// Com a chave de criptografia do Vault (`encryption_key`) configurada, o corpo
+// armazenado é cifrado; após a carência de uma rotação, respostas cifradas com a
+// chave anterior não podem mais ser reproduzidas (409 Conflict)
+
+use crate::domain::value_objects::Principal;
+use crate::infra::idempotency::{IdempotencyBegin, IdempotencyStore, StoredResponse};
+use crate::presentation::error_mapper::custom_error;
+use crate::shared::secrets::{self, RuntimeSecrets};
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use once_cell::sync::OnceCell;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Header enviado pelo cliente
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header adicionado às respostas reproduzidas
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Tamanho máximo da chave informada pelo cliente
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Tamanho máximo de corpo (requisição e resposta) considerado pelo middleware
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Headers da resposta original preservados na reprodução
+const REPLAYED_HEADERS: [HeaderName; 3] = [header::CONTENT_TYPE, header::ETAG, header::LOCATION];
+
+pub struct IdempotencyConfig {
+    pub store: Arc<dyn IdempotencyStore>,
+    pub ttl: Duration,
+    /// Chave de criptografia dos corpos armazenados
+    pub secrets: Arc<RuntimeSecrets>,
+}
+
+static IDEMPOTENCY: OnceCell<IdempotencyConfig> = OnceCell::new();
+
+pub fn init(config: IdempotencyConfig) -> anyhow::Result<()> {
+    IDEMPOTENCY
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("idempotency already initialised"))
+}
+
+/// Middleware de idempotência (sem efeito enquanto `init` não for chamado)
+pub async fn idempotency_middleware(req: Request, next: Next) -> Response {
+    match IDEMPOTENCY.get() {
+        Some(config) => handle(config, req, next).await,
+        None => next.run(req).await,
+    }
+}
+
+async fn handle(config: &IdempotencyConfig, req: Request, next: Next) -> Response {
+    if req.method() != Method::POST {
+        return next.run(req).await;
+    }
+    let key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        None => return next.run(req).await,
+        Some(value) => match validate_key(value) {
+            Some(key) => key,
+            None => {
+                return custom_error(
+                    StatusCode::BAD_REQUEST,
+                    "Idempotency-Key must be 1-255 visible ASCII characters",
+                )
+                .into_response()
+            }
+        },
+    };
+
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return custom_error(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large")
+                .into_response()
+        }
+    };
+
+    // A chave vale por usuário e por rota
+    let subject = parts
+        .extensions
+        .get::<Principal>()
+        .map(|principal| principal.subject.as_str())
+        .unwrap_or("anonymous");
+    let scope = format!("{} {} {}", subject, parts.method, parts.uri.path());
+    let request_hash = request_hash(&parts.method, &parts.uri, &bytes);
+
+    let expires_at = chrono::Utc::now()
+        + chrono::Duration::from_std(config.ttl).unwrap_or_else(|_| chrono::Duration::days(1));
+    let begin = config
+        .store
+        .begin(&scope, &key, &request_hash, expires_at)
+        .await;
+
+    match begin {
+        Ok(IdempotencyBegin::Started) => {}
+        Ok(IdempotencyBegin::InProgress {
+            request_hash: stored,
+        })
+        | Ok(IdempotencyBegin::Completed {
+            request_hash: stored,
+            ..
+        }) if stored != request_hash => {
+            return custom_error(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used with a different request body",
+            )
+            .into_response()
+        }
+        Ok(IdempotencyBegin::InProgress { .. }) => {
+            return custom_error(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is still being processed",
+            )
+            .into_response()
+        }
+        Ok(IdempotencyBegin::Completed { mut response, .. }) => {
+            if secrets::is_encrypted(&response.body) {
+                let Some(body) = config.secrets.decrypt(&response.body) else {
+                    tracing::warn!("stored idempotent response encrypted with an expired key");
+                    return custom_error(
+                        StatusCode::CONFLICT,
+                        "The original response for this Idempotency-Key can no longer be replayed",
+                    )
+                    .into_response();
+                };
+                response.body = body;
+            }
+            return replay(response);
+        }
+        Err(e) => {
+            tracing::error!(error = ?e, "idempotency store unavailable");
+            return custom_error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Idempotency store unavailable",
+            )
+            .into_response();
+        }
+    }
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    // Erros de servidor não são definitivos: libera a chave para nova tentativa
+    if response.status().is_server_error() {
+        if let Err(e) = config.store.release(&scope, &key).await {
+            tracing::warn!(error = ?e, "failed to release idempotency key");
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = ?e, "failed to buffer response for idempotency");
+            if let Err(e) = config.store.release(&scope, &key).await {
+                tracing::warn!(error = ?e, "failed to release idempotency key");
+            }
+            return custom_error(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+                .into_response();
+        }
+    };
+
+    let body = match config.secrets.encrypt(&bytes) {
+        Ok(sealed) => sealed.unwrap_or_else(|| bytes.to_vec()),
+        Err(e) => {
+            // Sem cifrar, a resposta não é armazenada (uma repetição será processada de novo)
+            tracing::error!(error = ?e, "failed to encrypt idempotent response");
+            if let Err(e) = config.store.release(&scope, &key).await {
+                tracing::warn!(error = ?e, "failed to release idempotency key");
+            }
+            return Response::from_parts(parts, Body::from(bytes));
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: REPLAYED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                parts
+                    .headers
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| (name.as_str().to_string(), v.to_string()))
+            })
+            .collect(),
+        body,
+    };
+    if let Err(e) = config.store.complete(&scope, &key, &stored).await {
+        // A resposta já foi produzida; sem registro, uma repetição será processada de novo
+        tracing::warn!(error = ?e, "failed to store idempotent response");
+        if let Err(e) = config.store.release(&scope, &key).await {
+            tracing::warn!(error = ?e, "failed to release idempotency key");
+        }
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Valida a chave: 1 a 255 caracteres ASCII visíveis
+fn validate_key(value: &HeaderValue) -> Option<String> {
+    let key = value.to_str().ok()?;
+    let valid =
+        !key.is_empty() && key.len() <= MAX_KEY_LENGTH && key.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| key.to_string())
+}
+
+/// Hash SHA-256 do método, caminho, query string e corpo da requisição
+fn request_hash(method: &Method, uri: &axum::http::Uri, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(uri.path().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(uri.query().unwrap_or("").as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// Reconstrói a resposta original a partir do registro armazenado
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    for (name, value) in &stored.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::idempotency::InMemoryIdempotencyStore;
+    use crate::shared::secrets::SecretKind;
+    use axum::{middleware, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    fn config(store: Arc<dyn IdempotencyStore>, secrets: RuntimeSecrets) -> IdempotencyConfig {
+        IdempotencyConfig {
+            store,
+            ttl: Duration::from_secs(60),
+            secrets: Arc::new(secrets),
+        }
+    }
+
+    fn app(calls: Arc<AtomicUsize>, status: StatusCode) -> Router {
+        let store = Arc::new(InMemoryIdempotencyStore::new());
+        let secrets = RuntimeSecrets::new(None, None, Duration::ZERO);
+        app_with(calls, status, config(store, secrets))
+    }
+
+    fn app_with(calls: Arc<AtomicUsize>, status: StatusCode, config: IdempotencyConfig) -> Router {
+        let config = Arc::new(config);
+        Router::new()
+            .route(
+                "/items",
+                post(move |Json(body): Json<serde_json::Value>| {
+                    let calls = calls.clone();
+                    async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                        (status, Json(serde_json::json!({ "call": n, "body": body })))
+                    }
+                }),
+            )
+            .layer(middleware::from_fn(move |req: Request, next: Next| {
+                let config = config.clone();
+                async move { handle(&config, req, next).await }
+            }))
+    }
+
+    fn request(key: Option<&str>, body: &str) -> Request {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri("/items")
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = to_bytes(response.into_body(), MAX_BODY_BYTES)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay_returns_original_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("k1"), r#"{"a":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        let first_body = body_json(first).await;
+
+        let replayed = app
+            .oneshot(request(Some("k1"), r#"{"a":1}"#))
+            .await
+            .unwrap();
+        assert_eq!(replayed.status(), StatusCode::CREATED);
+        assert_eq!(replayed.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(replayed.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body_json(replayed).await, first_body);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stored_responses_are_encrypted() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let store = Arc::new(InMemoryIdempotencyStore::new());
+        let secrets = RuntimeSecrets::new(None, None, Duration::ZERO);
+        secrets.rotate(SecretKind::EncryptionKey, "key-1".to_string());
+        let config = config(store.clone(), secrets);
+        let secrets = config.secrets.clone();
+        let app = app_with(calls.clone(), StatusCode::CREATED, config);
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("k1"), r#"{"document":"12345678901"}"#))
+            .await
+            .unwrap();
+        let first_body = body_json(first).await;
+        let stored = store
+            .begin("anonymous POST /items", "k1", "", chrono::Utc::now())
+            .await
+            .unwrap();
+        let IdempotencyBegin::Completed { response, .. } = stored else {
+            panic!("response not stored: {stored:?}");
+        };
+        assert!(secrets::is_encrypted(&response.body));
+        assert!(!response.body.windows(11).any(|w| w == b"12345678901"));
+
+        let replayed = app
+            .clone()
+            .oneshot(request(Some("k1"), r#"{"document":"12345678901"}"#))
+            .await
+            .unwrap();
+        assert_eq!(replayed.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(body_json(replayed).await, first_body);
+
+        // Sem carência, a chave anterior deixa de decifrar a resposta armazenada
+        secrets.rotate(SecretKind::EncryptionKey, "key-2".to_string());
+        let response = app
+            .oneshot(request(Some("k1"), r#"{"document":"12345678901"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_same_key_with_different_body_is_rejected() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        app.clone()
+            .oneshot(request(Some("k1"), r#"{"a":1}"#))
+            .await
+            .unwrap();
+        let response = app
+            .oneshot(request(Some("k1"), r#"{"a":2}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_key_are_not_tracked() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+
+        for _ in 0..2 {
+            let response = app.clone().oneshot(request(None, "{}")).await.unwrap();
+            assert!(response.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_release_the_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(request(Some("k1"), "{}"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_key_is_rejected() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED);
+        let long_key = "k".repeat(MAX_KEY_LENGTH + 1);
+
+        let response = app.oneshot(request(Some(&long_key), "{}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+}
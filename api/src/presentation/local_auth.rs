@@ -0,0 +1,321 @@
+// ============================================================================
+// LOCAL AUTH - LOGIN COM USUÁRIO E SENHA (SEM KEYCLOAK)
+// ============================================================================
+// Modo de autenticação embutido para ambientes sem Keycloak (air-gapped, dev):
+// - POST /v1/auth/token        -> confere credenciais no UserRepository e emite
+//                                 access token (RS256, curto) + refresh token
+// - GET /.well-known/jwks.json -> chave pública de assinatura
+// O `Jwks` do AuthMiddleware consome o JWKS local, então a validação dos
+// tokens emitidos aqui segue exatamente o mesmo caminho dos tokens do Keycloak
+
+use crate::application::dto::UserResponse;
+use crate::application::use_cases::user::AuthenticateUserUseCase;
+use crate::infra::revocation::RevocationKind;
+use crate::infra::signing_keys::{LocalSigningKey, LOCAL_SIGNING_ALGORITHM};
+use crate::presentation::auth::{self, JwtClaims};
+use crate::presentation::error_mapper::{custom_error, map_domain_error, ErrorResponse};
+use axum::{extract::State, http::StatusCode, response::Json};
+use jsonwebtoken::{decode, encode, Header, Validation};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+/// Valor da claim `typ` dos access tokens (mesma convenção do Keycloak)
+const ACCESS_TOKEN_TYPE: &str = "Bearer";
+
+/// Valor da claim `typ` dos refresh tokens, rejeitados pelo AuthMiddleware
+const REFRESH_TOKEN_TYPE: &str = "Refresh";
+
+/// Configuração do emissor local de tokens
+pub struct LocalAuthConfig {
+    pub signing_key: LocalSigningKey,
+    /// Claim `iss` dos tokens (deve coincidir com o issuer validado pelo AuthMiddleware)
+    pub issuer: String,
+    /// Claim `aud` dos access tokens
+    pub audiences: Vec<String>,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+}
+
+static LOCAL_AUTH: OnceCell<LocalAuthConfig> = OnceCell::new();
+
+pub fn init(config: LocalAuthConfig) -> anyhow::Result<()> {
+    info!(kid = %config.signing_key.kid, issuer = %config.issuer, "local token issuer enabled");
+    LOCAL_AUTH
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("local auth already initialised"))
+}
+
+/// Requisição ao endpoint de token
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "grant_type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TokenRequest {
+    /// Login com usuário e senha
+    Password { username: String, password: String },
+    /// Renovação a partir de um refresh token
+    RefreshToken { refresh_token: String },
+}
+
+/// Resposta no formato OAuth 2.0
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: u64,
+    pub refresh_token: String,
+    pub refresh_expires_in: u64,
+}
+
+/// Rotas públicas do emissor local (respondem 404 quando o modo local está desativado)
+pub fn routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(token_handler)) // POST /v1/auth/token - Login / refresh
+        .routes(routes!(jwks_handler)) // GET /.well-known/jwks.json - Chaves públicas
+}
+
+fn local_auth() -> Result<&'static LocalAuthConfig, ErrorResponse> {
+    LOCAL_AUTH
+        .get()
+        .ok_or_else(|| custom_error(StatusCode::NOT_FOUND, "Local authentication is disabled"))
+}
+
+/// HANDLER: GET /.well-known/jwks.json - CHAVES PÚBLICAS DE ASSINATURA
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    summary = "Chaves públicas do emissor local (JWKS)",
+    responses(
+        (status = 200, description = "JWK Set (RFC 7517)", body = Object),
+        (status = 404, description = "Autenticação local desativada"),
+    )
+)]
+async fn jwks_handler() -> Result<Json<serde_json::Value>, ErrorResponse> {
+    Ok(Json(local_auth()?.signing_key.jwks()))
+}
+
+/// HANDLER: POST /v1/auth/token - EMITIR TOKENS
+#[utoipa::path(
+    post,
+    path = "/v1/auth/token",
+    tag = "auth",
+    summary = "Login local ou renovação de tokens",
+    request_body = TokenRequest,
+    responses(
+        (status = 200, description = "Tokens emitidos (formato OAuth 2.0)", body = TokenResponse),
+        (status = 401, description = "Credenciais ou refresh token inválidos"),
+        (status = 404, description = "Autenticação local desativada"),
+        (status = 429, description = "Tentativas de login excedidas"),
+    )
+)]
+async fn token_handler(
+    State(state): State<Arc<crate::AppState>>,
+    Json(request): Json<TokenRequest>,
+) -> Result<Json<TokenResponse>, ErrorResponse> {
+    let config = local_auth()?;
+    let use_case = AuthenticateUserUseCase::new(state.user_repository.as_ref());
+
+    let (user, session_id) = match request {
+        TokenRequest::Password { username, password } => {
+            let user = use_case.execute(&username, &password).await.map_err(|e| {
+                warn!(username = %username.trim(), "local login failed");
+                map_domain_error(&e)
+            })?;
+            (user, Uuid::new_v4().to_string())
+        }
+        TokenRequest::RefreshToken { refresh_token } => {
+            let claims = verify_refresh_token(config, &refresh_token)
+                .ok_or_else(|| custom_error(StatusCode::UNAUTHORIZED, "Invalid refresh token"))?;
+            let user = use_case
+                .execute_refresh(&claims.sub)
+                .await
+                .map_err(|e| map_domain_error(&e))?;
+            // Rotação: o refresh token usado não pode ser reaproveitado
+            if let Some(jti) = claims.jti.as_deref() {
+                let expires_at = chrono::DateTime::from_timestamp(claims.exp as i64, 0);
+                auth::revoke(
+                    RevocationKind::Jti,
+                    jti,
+                    expires_at,
+                    Some("refresh token rotated".to_string()),
+                )
+                .await
+                .map_err(|e| {
+                    error!(error = ?e, "failed to revoke rotated refresh token");
+                    custom_error(StatusCode::SERVICE_UNAVAILABLE, "Token service unavailable")
+                })?;
+            }
+            let session_id = claims.sid.unwrap_or_else(|| Uuid::new_v4().to_string());
+            (user, session_id)
+        }
+    };
+
+    let response = issue_tokens(config, &user, &session_id).map_err(|e| {
+        error!(error = ?e, "failed to sign local tokens");
+        custom_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to issue token")
+    })?;
+    info!(user_id = %user.id, "local tokens issued");
+    Ok(Json(response))
+}
+
+/// Assina access token e refresh token para o usuário
+fn issue_tokens(
+    config: &LocalAuthConfig,
+    user: &UserResponse,
+    session_id: &str,
+) -> anyhow::Result<TokenResponse> {
+    let now = chrono::Utc::now().timestamp();
+    let access_ttl = config.access_token_ttl.as_secs();
+    let refresh_ttl = config.refresh_token_ttl.as_secs();
+
+    let mut header = Header::new(LOCAL_SIGNING_ALGORITHM);
+    header.kid = Some(config.signing_key.kid.clone());
+
+    let access_claims = serde_json::json!({
+        "iss": config.issuer,
+        "sub": user.id,
+        "aud": config.audiences,
+        "iat": now,
+        "nbf": now,
+        "exp": now + access_ttl as i64,
+        "jti": Uuid::new_v4(),
+        "typ": ACCESS_TOKEN_TYPE,
+        "sid": session_id,
+        "preferred_username": user.username,
+        "email": user.email,
+        "realm_access": { "roles": user.roles },
+    });
+    // Refresh token: audience é o próprio emissor, só aceito por este endpoint
+    let refresh_claims = serde_json::json!({
+        "iss": config.issuer,
+        "sub": user.id,
+        "aud": config.issuer,
+        "iat": now,
+        "nbf": now,
+        "exp": now + refresh_ttl as i64,
+        "jti": Uuid::new_v4(),
+        "typ": REFRESH_TOKEN_TYPE,
+        "sid": session_id,
+    });
+
+    Ok(TokenResponse {
+        access_token: encode(&header, &access_claims, &config.signing_key.encoding)?,
+        token_type: ACCESS_TOKEN_TYPE,
+        expires_in: access_ttl,
+        refresh_token: encode(&header, &refresh_claims, &config.signing_key.encoding)?,
+        refresh_expires_in: refresh_ttl,
+    })
+}
+
+/// Valida um refresh token emitido por esta instância (assinatura, emissor,
+/// audiência, expiração, `typ` e lista de revogação)
+fn verify_refresh_token(config: &LocalAuthConfig, token: &str) -> Option<JwtClaims> {
+    let mut validation = Validation::new(LOCAL_SIGNING_ALGORITHM);
+    validation.set_issuer(&[&config.issuer]);
+    validation.set_audience(&[&config.issuer]);
+    validation.set_required_spec_claims(&["exp", "iat", "iss", "aud", "sub"]);
+
+    let claims = decode::<JwtClaims>(token, &config.signing_key.decoding, &validation)
+        .ok()?
+        .claims;
+    if claims.typ.as_deref() != Some(REFRESH_TOKEN_TYPE) || auth::is_revoked(&claims) {
+        return None;
+    }
+    Some(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    static SIGNING_KEY: Lazy<LocalSigningKey> = Lazy::new(|| LocalSigningKey::generate().unwrap());
+
+    fn config() -> LocalAuthConfig {
+        LocalAuthConfig {
+            signing_key: SIGNING_KEY.clone(),
+            issuer: "sut-api".to_string(),
+            audiences: vec!["sut-frontend".to_string()],
+            access_token_ttl: Duration::from_secs(300),
+            refresh_token_ttl: Duration::from_secs(3600),
+        }
+    }
+
+    fn user() -> UserResponse {
+        let now = chrono::Utc::now();
+        UserResponse {
+            id: Uuid::new_v4(),
+            username: "jdoe".to_string(),
+            email: "jdoe@example.com".to_string(),
+            roles: vec!["directory.read".to_string()],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_access_token_carries_user_roles() {
+        let config = config();
+        let user = user();
+        let tokens = issue_tokens(&config, &user, "session-1").unwrap();
+        assert_eq!(tokens.expires_in, 300);
+
+        let mut validation = Validation::new(LOCAL_SIGNING_ALGORITHM);
+        validation.set_audience(&["sut-frontend"]);
+        let header = jsonwebtoken::decode_header(&tokens.access_token).unwrap();
+        assert_eq!(header.kid.as_deref(), Some(config.signing_key.kid.as_str()));
+        let claims = decode::<JwtClaims>(
+            &tokens.access_token,
+            &config.signing_key.decoding,
+            &validation,
+        )
+        .unwrap()
+        .claims;
+        assert_eq!(claims.sub, user.id.to_string());
+        assert_eq!(claims.sid.as_deref(), Some("session-1"));
+        assert_eq!(claims.typ.as_deref(), Some(ACCESS_TOKEN_TYPE));
+        assert_eq!(claims.realm_access.unwrap().roles, user.roles);
+    }
+
+    #[test]
+    fn test_only_refresh_tokens_are_accepted_for_refresh() {
+        let config = config();
+        let tokens = issue_tokens(&config, &user(), "session-1").unwrap();
+
+        let claims = verify_refresh_token(&config, &tokens.refresh_token).unwrap();
+        assert_eq!(claims.sid.as_deref(), Some("session-1"));
+        assert!(verify_refresh_token(&config, &tokens.access_token).is_none());
+        assert!(verify_refresh_token(&config, "garbage").is_none());
+
+        let other_issuer = LocalAuthConfig {
+            issuer: "other".to_string(),
+            ..config
+        };
+        assert!(verify_refresh_token(&other_issuer, &tokens.refresh_token).is_none());
+    }
+
+    #[test]
+    fn test_token_request_grant_types() {
+        let request: TokenRequest = serde_json::from_value(serde_json::json!({
+            "grant_type": "password", "username": "jdoe", "password": "secret123"
+        }))
+        .unwrap();
+        assert!(matches!(request, TokenRequest::Password { .. }));
+
+        let request: TokenRequest = serde_json::from_value(serde_json::json!({
+            "grant_type": "refresh_token", "refresh_token": "abc"
+        }))
+        .unwrap();
+        assert!(matches!(request, TokenRequest::RefreshToken { .. }));
+
+        assert!(serde_json::from_value::<TokenRequest>(serde_json::json!({
+            "grant_type": "client_credentials"
+        }))
+        .is_err());
+    }
+}
@@ -4,76 +4,631 @@
 // Módulo principal da camada de apresentação da Clean Architecture
 // Organiza controllers, middleware e utilitários
 
-use axum::extract::Request;
-use axum::{middleware, middleware::Next, Router};
+use crate::shared::middleware_system::{
+    AuthMiddleware, CorsMiddleware, LoggingMiddleware, MiddlewareSystem, RateLimitMiddleware,
+    RouteFilter,
+};
+use axum::{http::Method, middleware, Router};
 use std::sync::Arc;
-use tracing::{info_span, Span};
+use utoipa_axum::router::OpenApiRouter;
 
 // ===== CORE MODULES =====
+pub mod access_log; // Request id e log de acesso estruturado (com dados pessoais mascarados)
+pub mod api_keys; // API keys de clientes máquina-a-máquina
 pub mod auth; // Autenticação e autorização
 pub mod clean;
-mod docs; // Documentação da API
-mod health; // Health checks // Controllers da Clean Architecture
+#[cfg(debug_assertions)]
+pub mod dev_tokens; // Tokens de desenvolvimento (apenas builds debug)
+mod docs; // Especificação OpenAPI (gerada das rotas) e Swagger UI
+pub mod health; // Liveness e readiness (health checks)
 
 // ===== UTILITY MODULES =====
 pub mod error_mapper; // Mapeamento de erros de domínio para HTTP
 pub mod handler_macros; // Macros para handlers CRUD genéricos
+pub mod idempotency; // Suporte ao header Idempotency-Key
+pub mod local_auth; // Emissão local de tokens (login sem Keycloak)
+pub mod patch; // Extractor para JSON Merge Patch / JSON Patch
+pub mod permissions; // Resolução de permissões (roles, roles de client, escopos)
+pub mod rate_limit; // Rate limiting por token bucket (subject, API key ou IP)
 pub mod response_helpers; // Helpers para respostas HTTP
 pub mod security_headers; // Headers de segurança HTTP
 pub mod validation; // Utilitários de validação
 pub mod webhooks; // Webhooks para serviços externos
 
-async fn span_enricher(
-    req: Request,
-    next: Next,
-) -> Result<axum::response::Response, axum::http::StatusCode> {
-    let method = req.method().clone();
-    let path = req.uri().path().to_string();
-    let span = info_span!("http.request", "http.method"=%method, "http.route"=%path);
-    let _enter = span.enter();
-    if let Some(claims) = req.extensions().get::<serde_json::Value>() {
-        if let Some(sub) = claims.get("sub").and_then(|s| s.as_str()) {
-            Span::current().record("enduser.id", &tracing::field::display(sub));
-        }
-    }
-    Ok(next.run(req).await)
+/// Rotas sem autenticação
+const PUBLIC_PATHS: [&str; 9] = [
+    "/docs",
+    "/health",
+    "/healthz",
+    "/ready",
+    "/readyz",
+    "/v1/webhooks",
+    "/v1/auth/token",
+    "/.well-known/jwks.json",
+    "/v1/dev/tokens",
+];
+
+/// Rotas sem rate limiting (o login local e o JWKS são limitados)
+const UNLIMITED_PATHS: [&str; 7] = [
+    "/docs",
+    "/health",
+    "/healthz",
+    "/ready",
+    "/readyz",
+    "/v1/webhooks",
+    "/v1/dev/tokens",
+];
+
+/// Métodos autenticados e limitados (preflights OPTIONS são respondidos pelo CORS)
+const API_METHODS: [Method; 6] = [
+    Method::GET,
+    Method::HEAD,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Cadeia de middlewares comum a todas as rotas da aplicação
+fn middleware_system(cors: CorsMiddleware) -> MiddlewareSystem {
+    let system = MiddlewareSystem::new()
+        .with(cors)
+        .with(LoggingMiddleware::new())
+        .with(
+            AuthMiddleware::new(vec![], vec![]).with_filter(
+                RouteFilter::all()
+                    .methods(API_METHODS)
+                    .skip_paths(PUBLIC_PATHS),
+            ),
+        )
+        .with(
+            RateLimitMiddleware::new().with_filter(
+                RouteFilter::all()
+                    .methods(API_METHODS)
+                    .skip_paths(UNLIMITED_PATHS),
+            ),
+        );
+    tracing::info!(middlewares = ?system.names(), "Middleware chain configured");
+    system
 }
 
-pub fn routes() -> Router<Arc<crate::AppState>> {
-    Router::new()
-        .merge(docs::routes())
-        .layer(middleware::from_fn(span_enricher))
-        .layer(middleware::from_fn(
-            security_headers::security_headers_middleware,
-        ))
+/// Rotas do diretório: leitura exige `directory.read`; as demais operações, também
+/// `directory.write`
+fn directory_routes(
+    router: OpenApiRouter<Arc<crate::AppState>>,
+) -> OpenApiRouter<Arc<crate::AppState>> {
+    docs::require_roles(
+        docs::require_write_roles(router, auth::WRITE_ROLES),
+        auth::READ_ROLES,
+    )
+    .route_layer(middleware::from_fn(idempotency::idempotency_middleware))
+    .route_layer(middleware::from_fn(
+        auth::require_write_permission_middleware,
+    ))
+    .route_layer(middleware::from_fn(
+        auth::require_read_permission_middleware,
+    ))
+}
+
+/// Rotas da API; a especificação OpenAPI é gerada a partir delas
+fn api_routes() -> OpenApiRouter<Arc<crate::AppState>> {
+    let router = OpenApiRouter::with_openapi(docs::base())
         .merge(health::routes())
+        // Emissor local de tokens e JWKS (público; 404 quando desativado)
+        .merge(local_auth::routes())
         // Clean Architecture routes with security layers
+        .merge(directory_routes(clean::contact_controller::routes()))
+        .merge(directory_routes(clean::org_unit_controller::routes()))
+        .merge(directory_routes(clean::department_controller::routes()))
         .merge(
-            clean::contact_controller::routes()
-                .route_layer(middleware::from_fn(auth::jwt_middleware))
-                .route_layer(middleware::from_fn(
-                    auth::require_read_permission_middleware,
-                )),
+            docs::require_roles(clean::user_controller::routes(), auth::ADMIN_ROLES)
+                .route_layer(middleware::from_fn(idempotency::idempotency_middleware))
+                .route_layer(middleware::from_fn(auth::require_admin_middleware)),
         )
         .merge(
-            clean::org_unit_controller::routes()
-                .route_layer(middleware::from_fn(auth::jwt_middleware))
-                .route_layer(middleware::from_fn(
-                    auth::require_read_permission_middleware,
-                )),
+            docs::require_roles(auth::routes(), auth::ADMIN_ROLES)
+                .route_layer(middleware::from_fn(auth::require_admin_middleware)),
         )
         .merge(
-            clean::department_controller::routes()
-                .route_layer(middleware::from_fn(auth::jwt_middleware))
-                .route_layer(middleware::from_fn(
-                    auth::require_read_permission_middleware,
-                )),
+            docs::require_roles(health::detail_routes(), auth::ADMIN_ROLES)
+                .route_layer(middleware::from_fn(auth::require_admin_middleware)),
         )
         .merge(
-            clean::user_controller::routes()
-                .route_layer(middleware::from_fn(auth::jwt_middleware))
+            // Sem Idempotency-Key: as respostas trazem o segredo em texto puro, que não
+            // pode ficar armazenado nem ser devolvido novamente em um replay
+            docs::require_roles(api_keys::routes(), auth::ADMIN_ROLES)
                 .route_layer(middleware::from_fn(auth::require_admin_middleware)),
         )
         // Webhook routes (sem autenticação JWT, mas com validação de token)
-        .nest("/v1/webhooks", webhooks::webhook_routes())
+        .nest("/v1/webhooks", webhooks::webhook_routes());
+
+    // Emissão de tokens de desenvolvimento (apenas builds debug; 404 quando desativada)
+    #[cfg(debug_assertions)]
+    let router = router.merge(dev_tokens::routes());
+
+    router
+}
+
+pub fn routes(cors: CorsMiddleware) -> Router<Arc<crate::AppState>> {
+    let (api, openapi) = api_routes().split_for_parts();
+
+    docs::routes(openapi)
+        .layer(middleware::from_fn(
+            security_headers::security_headers_middleware,
+        ))
+        .merge(api)
+        // CORS, logging, autenticação e rate limiting, em ordem de prioridade
+        .layer(middleware_system(cors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::api_keys::{generate_api_key, ApiKey, ApiKeyStore, InMemoryApiKeyStore};
+    use crate::infra::revocation::InMemoryRevocationStore;
+    use crate::infrastructure::contract_tests::{contract_tests, Repositories};
+    use crate::infrastructure::memory::InMemoryDatabase;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::{json, Value};
+    use tokio::sync::OnceCell;
+    use tower::ServiceExt;
+
+    static API_KEY_STORE: OnceCell<Arc<InMemoryApiKeyStore>> = OnceCell::const_new();
+    static ADMIN_KEY: OnceCell<String> = OnceCell::const_new();
+
+    /// Inicializa a autenticação global (uma vez) e emite uma API key com `scopes`
+    async fn api_key(scopes: &[&str]) -> String {
+        let store = API_KEY_STORE
+            .get_or_init(|| async {
+                let store = Arc::new(InMemoryApiKeyStore::new());
+                let mut jwks = mockito::Server::new_async().await;
+                jwks.mock("GET", "/certs")
+                    .with_body(r#"{"keys":[]}"#)
+                    .create_async()
+                    .await;
+                auth::init(auth::AuthConfig {
+                    jwks_uri: format!("{}/certs", jwks.url()),
+                    issuer: None,
+                    audiences: vec![],
+                    security: Default::default(),
+                    permissions: Default::default(),
+                    revocation_store: Arc::new(InMemoryRevocationStore::new()),
+                    revocation_ttl_secs: 3600,
+                    api_key_store: store.clone(),
+                })
+                .await
+                .unwrap();
+                store
+            })
+            .await;
+        let generated = generate_api_key();
+        store
+            .create(&ApiKey {
+                id: uuid::Uuid::new_v4(),
+                name: "tests".to_string(),
+                prefix: generated.prefix,
+                key_hash: generated.key_hash,
+                scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+                created_by: None,
+                created_at: chrono::Utc::now(),
+                expires_at: None,
+                last_used_at: None,
+                revoked_at: None,
+            })
+            .await
+            .unwrap();
+        generated.key
+    }
+
+    /// API key de administrador, compartilhada pelos testes
+    async fn admin_key() -> &'static str {
+        ADMIN_KEY
+            .get_or_init(|| api_key(&["admin", "directory.read", "directory.write"]))
+            .await
+    }
+
+    fn app(repositories: &Repositories) -> Router {
+        routes(CorsMiddleware::new(vec![], vec![], vec![]))
+            .with_state(Arc::new(repositories.app_state()))
+    }
+
+    /// Habilita o emissor local de tokens (uma vez por processo)
+    fn enable_local_auth() {
+        static ENABLED: std::sync::Once = std::sync::Once::new();
+        ENABLED.call_once(|| {
+            local_auth::init(local_auth::LocalAuthConfig {
+                signing_key: crate::infra::signing_keys::LocalSigningKey::generate().unwrap(),
+                issuer: "sut-api".to_string(),
+                audiences: vec!["sut-frontend".to_string()],
+                access_token_ttl: std::time::Duration::from_secs(300),
+                refresh_token_ttl: std::time::Duration::from_secs(3600),
+            })
+            .unwrap();
+        });
+    }
+
+    /// Habilita o middleware de idempotência (uma vez por processo)
+    fn enable_idempotency() {
+        static ENABLED: std::sync::Once = std::sync::Once::new();
+        ENABLED.call_once(|| {
+            idempotency::init(idempotency::IdempotencyConfig {
+                store: Arc::new(crate::infra::idempotency::InMemoryIdempotencyStore::new()),
+                ttl: std::time::Duration::from_secs(3600),
+                secrets: Arc::new(crate::shared::secrets::RuntimeSecrets::new(
+                    None,
+                    None,
+                    std::time::Duration::ZERO,
+                )),
+            })
+            .unwrap();
+        });
+    }
+
+    fn contact(name: &str) -> crate::domain::entities::Contact {
+        crate::domain::entities::Contact::new(
+            name.to_string(),
+            crate::domain::value_objects::ContactType::Person,
+            crate::domain::value_objects::ContactStatus::Active,
+            None,
+            None,
+            None,
+            vec![],
+            vec![],
+        )
+        .unwrap()
+    }
+
+    async fn call(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    fn request(method: &str, path: &str, key: &str, body: Option<Value>) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(path)
+            .header("authorization", format!("ApiKey {}", key))
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health_diagnostics_require_admin() {
+        struct LeaseCheck;
+
+        #[async_trait::async_trait]
+        impl crate::shared::health::HealthCheck for LeaseCheck {
+            fn name(&self) -> &str {
+                "db_lease"
+            }
+
+            async fn check(&self) -> crate::shared::health::CheckOutcome {
+                crate::shared::health::CheckOutcome::pass(json!({ "expires_in_secs": 600 }))
+                    .with_diagnostics(json!({ "username": "v-sut-app-1a2b" }))
+            }
+        }
+
+        let key = admin_key().await;
+        let state = crate::AppState {
+            health: Arc::new(
+                crate::shared::health::HealthRegistry::new(
+                    std::time::Duration::ZERO,
+                    std::time::Duration::from_secs(1),
+                )
+                .with_check(LeaseCheck),
+            ),
+            ..crate::AppState::in_memory(&InMemoryDatabase::new())
+        };
+        let app = routes(CorsMiddleware::new(vec![], vec![], vec![])).with_state(Arc::new(state));
+
+        let (status, report) =
+            call(&app, Request::get("/ready").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            report["checks"]["db_lease"]["details"]["expires_in_secs"],
+            600
+        );
+        assert!(report["checks"]["db_lease"].get("diagnostics").is_none());
+
+        let (status, _) = call(
+            &app,
+            Request::get("/v1/health/details")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, report) = call(&app, request("GET", "/v1/health/details", key, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            report["checks"]["db_lease"]["diagnostics"]["username"],
+            "v-sut-app-1a2b"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_runs_on_in_memory_repositories() {
+        let key = admin_key().await;
+        let state = crate::AppState::in_memory(&InMemoryDatabase::demo());
+        let app = routes(CorsMiddleware::new(vec![], vec![], vec![])).with_state(Arc::new(state));
+
+        let (status, _) = call(
+            &app,
+            Request::get("/v1/contacts").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, contacts) = call(&app, request("GET", "/v1/contacts", key, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(contacts["total"], 2);
+
+        let (status, unit) = call(
+            &app,
+            request(
+                "POST",
+                "/v1/org-units",
+                key,
+                Some(json!({ "name": "Filial" })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let path = format!("/v1/org-units/{}", unit["id"].as_str().unwrap());
+        let (status, fetched) = call(&app, request("GET", &path, key, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(fetched["name"], "Filial");
+
+        // Nome de departamento único por unidade (sem distinção de maiúsculas)
+        let duplicate = json!({
+            "unit_id": "00000000-0000-0000-0000-000000000001",
+            "name": "engineering",
+        });
+        let (status, _) = call(
+            &app,
+            request("POST", "/v1/departments", key, Some(duplicate)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_issued_api_keys_are_never_replayed() {
+        let key = admin_key().await;
+        enable_idempotency();
+        let state = crate::AppState::in_memory(&InMemoryDatabase::new());
+        let app = routes(CorsMiddleware::new(vec![], vec![], vec![])).with_state(Arc::new(state));
+        let issue = || {
+            let mut request = request(
+                "POST",
+                "/v1/api-keys",
+                key,
+                Some(json!({ "name": "etl", "scopes": ["directory.read"] })),
+            );
+            request
+                .headers_mut()
+                .insert("idempotency-key", "issue-etl-key".parse().unwrap());
+            request
+        };
+
+        let (status, first) = call(&app, issue()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, second) = call(&app, issue()).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_ne!(first["api_key"], second["api_key"]);
+    }
+
+    #[tokio::test]
+    async fn test_directory_writes_require_write_permission() {
+        let reader = api_key(&["directory.read"]).await;
+        let writer = api_key(&["directory.read", "directory.write"]).await;
+        let state = crate::AppState::in_memory(&InMemoryDatabase::demo());
+        let app = routes(CorsMiddleware::new(vec![], vec![], vec![])).with_state(Arc::new(state));
+        let unit = || Some(json!({ "name": "Filial" }));
+
+        let (status, _) = call(&app, request("GET", "/v1/org-units", &reader, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        for (path, body) in [
+            ("/v1/org-units", unit()),
+            ("/v1/contacts", Some(json!({ "full_name": "Ana" }))),
+            ("/v1/contacts:batch", Some(json!({ "operations": [] }))),
+        ] {
+            let (status, _) = call(&app, request("POST", path, &reader, body)).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "POST {path}");
+        }
+
+        let (status, _) = call(&app, request("POST", "/v1/org-units", &writer, unit())).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Handlers sobre cada backend: o efeito das requisições precisa chegar ao repositório
+    contract_tests!(
+        soft_delete_restore_and_purge_reach_the_repository,
+        batch_operations_reach_the_repository,
+        statistics_come_from_the_repository,
+        local_login_checks_the_stored_password,
+    );
+
+    async fn soft_delete_restore_and_purge_reach_the_repository(repositories: Repositories) {
+        let key = admin_key().await;
+        let app = app(&repositories);
+        let ana = repositories.contacts.save(&contact("Ana")).await.unwrap();
+        let path = format!("/v1/contacts/{}", ana.id.0);
+
+        let (status, _) = call(&app, request("DELETE", &path, key, None)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let stored = repositories.contacts.find_by_id(&ana.id).await.unwrap();
+        assert!(stored.unwrap().deleted_at.is_some());
+        let (_, contacts) = call(&app, request("GET", "/v1/contacts", key, None)).await;
+        assert_eq!(contacts["total"], 0);
+
+        let restore = format!("{path}/restore");
+        let (status, restored) = call(&app, request("POST", &restore, key, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(restored["full_name"], "Ana");
+        let stored = repositories.contacts.find_by_id(&ana.id).await.unwrap();
+        assert!(stored.unwrap().deleted_at.is_none());
+
+        // A purga (tarefa em background) usa o mesmo repositório do AppState
+        let (status, _) = call(&app, request("DELETE", &path, key, None)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let state = repositories.app_state();
+        let purged = crate::application::use_cases::contact::PurgeDeletedContactsUseCase::new(
+            state.contact_repository.as_ref(),
+        )
+        .execute(chrono::Duration::zero())
+        .await
+        .unwrap();
+        assert_eq!(purged, 1);
+        assert!(repositories
+            .contacts
+            .find_by_id(&ana.id)
+            .await
+            .unwrap()
+            .is_none());
+        let (status, _) = call(&app, request("POST", &restore, key, None)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    async fn batch_operations_reach_the_repository(repositories: Repositories) {
+        let key = admin_key().await;
+        let app = app(&repositories);
+        let ana = repositories.contacts.save(&contact("Ana")).await.unwrap();
+        let bia = repositories.contacts.save(&contact("Bia")).await.unwrap();
+
+        let batch = json!({
+            "operations": [
+                {
+                    "op": "update",
+                    "id": ana.id.0,
+                    "etag": ana.etag,
+                    "changes": { "full_name": "Ana Lima" },
+                },
+                { "op": "set_status", "id": bia.id.0, "etag": bia.etag, "status": "INACTIVE" },
+            ],
+        });
+        let (status, body) = call(
+            &app,
+            request("POST", "/v1/contacts:batch", key, Some(batch)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        let ana = repositories
+            .contacts
+            .find_by_id(&ana.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ana.full_name, "Ana Lima");
+        let bia = repositories
+            .contacts
+            .find_by_id(&bia.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bia.status.to_string(), "INACTIVE");
+
+        // Em modo transação, um ETag desatualizado impede todo o lote
+        let batch = json!({
+            "operations": [
+                { "op": "delete", "id": ana.id.0, "etag": ana.etag },
+                { "op": "delete", "id": bia.id.0, "etag": "stale" },
+            ],
+        });
+        let (status, _) = call(
+            &app,
+            request("POST", "/v1/contacts:batch", key, Some(batch)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        let ana = repositories
+            .contacts
+            .find_by_id(&ana.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(ana.deleted_at.is_none());
+    }
+
+    async fn statistics_come_from_the_repository(repositories: Repositories) {
+        let key = admin_key().await;
+        let app = app(&repositories);
+        for name in ["Ana", "Bia", "Caio"] {
+            repositories.contacts.save(&contact(name)).await.unwrap();
+        }
+
+        let (status, stats) =
+            call(&app, request("GET", "/v1/contacts/statistics", key, None)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(stats["total_contacts"], 3);
+        assert_eq!(stats["persons"], 3);
+        let created: i64 = stats["time_series"]["created"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|point| point["count"].as_i64().unwrap())
+            .sum();
+        assert_eq!(created, 3);
+
+        // Datas nos limites do calendário são rejeitadas, sem pânico
+        let path = format!(
+            "/v1/contacts/statistics?to={}",
+            chrono::NaiveDate::MAX.to_string().replace('+', "%2B")
+        );
+        let (status, problem) = call(&app, request("GET", &path, key, None)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{problem}");
+    }
+
+    async fn local_login_checks_the_stored_password(repositories: Repositories) {
+        use crate::domain::value_objects::{Password, Role, UserEmail, Username};
+
+        admin_key().await; // Revogação dos refresh tokens usados
+        enable_local_auth();
+        let app = app(&repositories);
+        repositories
+            .users
+            .save(&crate::domain::entities::User::new(
+                Username::new("jdoe".to_string()).unwrap(),
+                UserEmail::new("jdoe@example.com".to_string()).unwrap(),
+                Password::new("correct-horse".to_string()).unwrap(),
+                vec![Role::new("directory.read".to_string()).unwrap()],
+            ))
+            .await
+            .unwrap();
+        let token = |body: Value| {
+            Request::post("/v1/auth/token")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let login = |password: &str| {
+            token(json!({
+                "grant_type": "password",
+                "username": "jdoe",
+                "password": password,
+            }))
+        };
+
+        let (status, tokens) = call(&app, login("correct-horse")).await;
+        assert_eq!(status, StatusCode::OK, "{tokens}");
+        assert_eq!(tokens["token_type"], "Bearer");
+        let (status, _) = call(&app, login("wrong-horse")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let refresh = json!({
+            "grant_type": "refresh_token",
+            "refresh_token": tokens["refresh_token"],
+        });
+        let (status, _) = call(&app, token(refresh.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        // Refresh tokens são de uso único
+        let (status, _) = call(&app, token(refresh)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
 }
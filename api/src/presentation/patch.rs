@@ -0,0 +1,156 @@
+// ============================================================================
+// PATCH EXTRACTOR - NEGOCIAÇÃO DE CONTENT-TYPE PARA PATCH
+// ============================================================================
+// Extractor que permite a um mesmo handler PATCH aceitar:
+// - application/json              -> DTO de atualização parcial legado
+// - application/merge-patch+json  -> JSON Merge Patch (RFC 7396)
+// - application/json-patch+json   -> JSON Patch (RFC 6902)
+
+use crate::application::dtos::{EntityPatch, JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE};
+use crate::presentation::error_mapper::{custom_error, ErrorResponse};
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{header, HeaderMap, StatusCode},
+};
+use serde::de::DeserializeOwned;
+
+/// Corpo de uma requisição PATCH, conforme o `Content-Type` informado
+#[derive(Debug)]
+pub enum PatchBody<T> {
+    /// `application/json`: DTO de atualização parcial
+    Json(T),
+    /// `application/merge-patch+json` ou `application/json-patch+json`
+    Patch(EntityPatch),
+}
+
+impl<S, T> FromRequest<S> for PatchBody<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| custom_error(StatusCode::BAD_REQUEST, &e.body_text()))?;
+
+        let invalid_body = |e: serde_json::Error| {
+            custom_error(StatusCode::BAD_REQUEST, &format!("Invalid body: {}", e))
+        };
+
+        match content_type.as_str() {
+            "application/json" => serde_json::from_slice(&bytes)
+                .map(PatchBody::Json)
+                .map_err(invalid_body),
+            MERGE_PATCH_CONTENT_TYPE => serde_json::from_slice(&bytes)
+                .map(|patch| PatchBody::Patch(EntityPatch::Merge(patch)))
+                .map_err(invalid_body),
+            JSON_PATCH_CONTENT_TYPE => serde_json::from_slice(&bytes)
+                .map(|patch| PatchBody::Patch(EntityPatch::Json(patch)))
+                .map_err(invalid_body),
+            _ => Err(custom_error(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                &format!(
+                    "Content-Type must be application/json, {} or {}",
+                    MERGE_PATCH_CONTENT_TYPE, JSON_PATCH_CONTENT_TYPE
+                ),
+            )),
+        }
+    }
+}
+
+/// Extrai o ETag do header `If-Match`, removendo aspas e o prefixo fraco `W/`
+pub fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::IF_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| {
+            v.trim()
+                .trim_start_matches("W/")
+                .trim_matches('"')
+                .to_string()
+        })
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::HeaderValue};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Update {
+        name: Option<String>,
+    }
+
+    fn request(content_type: &str, body: &str) -> Request {
+        Request::builder()
+            .method("PATCH")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_plain_json_body() {
+        let body =
+            PatchBody::<Update>::from_request(request("application/json", r#"{"name":"A"}"#), &())
+                .await
+                .unwrap();
+        assert!(matches!(body, PatchBody::Json(Update { name: Some(_) })));
+    }
+
+    #[tokio::test]
+    async fn test_merge_patch_body() {
+        let body = PatchBody::<Update>::from_request(
+            request(
+                "application/merge-patch+json; charset=utf-8",
+                r#"{"name":null}"#,
+            ),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(body, PatchBody::Patch(EntityPatch::Merge(_))));
+    }
+
+    #[tokio::test]
+    async fn test_json_patch_body() {
+        let body = PatchBody::<Update>::from_request(
+            request(
+                "application/json-patch+json",
+                r#"[{"op":"remove","path":"/name"}]"#,
+            ),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(body, PatchBody::Patch(EntityPatch::Json(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_media_type() {
+        let err = PatchBody::<Update>::from_request(request("text/plain", "name=A"), &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_if_match_strips_quotes_and_weak_prefix() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("W/\"abc\""));
+        assert_eq!(if_match(&headers), Some("abc".to_string()));
+    }
+}
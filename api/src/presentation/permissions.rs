@@ -0,0 +1,111 @@
+// ============================================================================
+// PERMISSION RESOLVER - PERMISSÕES EFETIVAS DO USUÁRIO
+// ============================================================================
+// Combina em um único conjunto de permissões:
+// - roles do realm            (realm_access.roles)
+// - roles dos clients listados (resource_access.<client>.roles)
+// - escopos OAuth             (scope)
+// Cada um desses valores pode ser mapeado para permissões da aplicação
+// (ex.: grupo LDAP `ldap-directory-editors` -> `directory.write`)
+
+use crate::domain::value_objects::Principal;
+use std::collections::{BTreeSet, HashMap};
+
+/// Configuração da resolução de permissões
+#[derive(Debug, Clone, Default)]
+pub struct PermissionConfig {
+    /// Clients cujas roles em `resource_access` são consideradas
+    pub clients: Vec<String>,
+    /// Role, role de client ou escopo -> permissões concedidas
+    pub role_permissions: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PermissionResolver {
+    config: PermissionConfig,
+}
+
+impl PermissionResolver {
+    pub fn new(config: PermissionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Calcula as permissões efetivas do usuário
+    ///
+    /// Cada role/escopo vale como permissão de mesmo nome e, se houver
+    /// mapeamento, concede também as permissões mapeadas.
+    pub fn resolve(&self, principal: &Principal) -> BTreeSet<String> {
+        let client_roles = self
+            .config
+            .clients
+            .iter()
+            .filter_map(|client| principal.client_roles.get(client))
+            .flatten();
+
+        let mut permissions = BTreeSet::new();
+        for grant in principal
+            .realm_roles
+            .iter()
+            .chain(client_roles)
+            .chain(principal.scopes.iter())
+        {
+            permissions.insert(grant.clone());
+            if let Some(mapped) = self.config.role_permissions.get(grant) {
+                permissions.extend(mapped.iter().cloned());
+            }
+        }
+        permissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn principal() -> Principal {
+        Principal {
+            subject: "user-1".to_string(),
+            realm_roles: vec!["offline_access".to_string()],
+            client_roles: BTreeMap::from([
+                (
+                    "sut-api".to_string(),
+                    vec!["ldap-directory-editors".to_string()],
+                ),
+                ("account".to_string(), vec!["manage-account".to_string()]),
+            ]),
+            scopes: vec!["directory.read".to_string()],
+            ..Principal::default()
+        }
+    }
+
+    #[test]
+    fn test_merges_realm_roles_client_roles_and_scopes() {
+        let resolver = PermissionResolver::new(PermissionConfig {
+            clients: vec!["sut-api".to_string()],
+            ..PermissionConfig::default()
+        });
+
+        let permissions = resolver.resolve(&principal());
+        assert!(permissions.contains("offline_access"));
+        assert!(permissions.contains("ldap-directory-editors"));
+        assert!(permissions.contains("directory.read"));
+        // Roles de clients não configurados são ignoradas
+        assert!(!permissions.contains("manage-account"));
+    }
+
+    #[test]
+    fn test_maps_roles_to_permissions() {
+        let resolver = PermissionResolver::new(PermissionConfig {
+            clients: vec!["sut-api".to_string()],
+            role_permissions: HashMap::from([(
+                "ldap-directory-editors".to_string(),
+                vec!["directory.write".to_string(), "directory.read".to_string()],
+            )]),
+        });
+
+        let permissions = resolver.resolve(&principal());
+        assert!(permissions.contains("directory.write"));
+        assert!(!permissions.contains("admin"));
+    }
+}
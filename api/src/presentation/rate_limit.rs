@@ -0,0 +1,457 @@
+// ============================================================================
+// RATE LIMIT MIDDLEWARE - TOKEN BUCKET POR CLASSE DE ROTA
+// ============================================================================
+// Cada requisição consome um token do bucket "<classe>:<identidade>":
+// - Identidade: subject autenticado (usuário ou `apikey:<id>`) ou IP do cliente
+// - IP do cliente: X-Forwarded-For só é considerado quando a conexão vem de um
+//   proxy confiável (TRUSTED_PROXIES); caso contrário vale o IP da conexão
+// - Classes: leitura, escrita, busca e login, cada uma com sua taxa e rajada
+// Respostas levam os headers RateLimit-* e, quando recusadas (429), Retry-After
+
+use crate::domain::value_objects::Principal;
+use crate::infra::rate_limit::{RateLimitDecision, RateLimitPolicy, RateLimitStore};
+use crate::presentation::error_mapper::custom_error;
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use ipnet::IpNet;
+use once_cell::sync::OnceCell;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "ratelimit-reset";
+pub const RATE_LIMIT_POLICY_HEADER: &str = "ratelimit-policy";
+
+/// Rota do login local (classe `login`)
+const LOGIN_PATH: &str = "/v1/auth/token";
+
+/// Parâmetro de query que caracteriza uma busca
+const SEARCH_PARAM: &str = "search_term";
+
+/// Classe de rota, com limites próprios
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    Read,
+    Write,
+    Search,
+    Login,
+}
+
+impl RouteClass {
+    pub fn of(method: &Method, uri: &Uri) -> Self {
+        if *method == Method::POST && uri.path() == LOGIN_PATH {
+            return RouteClass::Login;
+        }
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            let is_search = uri.query().is_some_and(|query| {
+                query
+                    .split('&')
+                    .any(|pair| pair.split('=').next() == Some(SEARCH_PARAM))
+            });
+            return if is_search {
+                RouteClass::Search
+            } else {
+                RouteClass::Read
+            };
+        }
+        RouteClass::Write
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RouteClass::Read => "read",
+            RouteClass::Write => "write",
+            RouteClass::Search => "search",
+            RouteClass::Login => "login",
+        }
+    }
+}
+
+/// Limites de cada classe de rota
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicies {
+    pub read: RateLimitPolicy,
+    pub write: RateLimitPolicy,
+    pub search: RateLimitPolicy,
+    pub login: RateLimitPolicy,
+}
+
+impl RateLimitPolicies {
+    pub fn for_class(&self, class: RouteClass) -> &RateLimitPolicy {
+        match class {
+            RouteClass::Read => &self.read,
+            RouteClass::Write => &self.write,
+            RouteClass::Search => &self.search,
+            RouteClass::Login => &self.login,
+        }
+    }
+
+    /// Após esse tempo sem uso qualquer bucket está cheio e pode ser descartado
+    pub fn idle_after(&self) -> Duration {
+        [&self.read, &self.write, &self.search, &self.login]
+            .into_iter()
+            .map(RateLimitPolicy::full_refill)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Interpreta um proxy confiável: IP (`10.0.0.1`) ou CIDR (`10.0.0.0/8`)
+pub fn parse_proxy(value: &str) -> Result<IpNet, String> {
+    let value = value.trim();
+    value
+        .parse::<IpNet>()
+        .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+        .map_err(|_| format!("invalid trusted proxy `{}`", value))
+}
+
+/// Proxies cujo X-Forwarded-For é considerado
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<IpNet>);
+
+impl TrustedProxies {
+    pub fn parse(values: &[String]) -> Result<Self, String> {
+        values
+            .iter()
+            .map(|value| parse_proxy(value))
+            .collect::<Result<_, _>>()
+            .map(TrustedProxies)
+    }
+
+    fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|net| net.contains(ip))
+    }
+
+    /// IP do cliente: percorre o X-Forwarded-For da direita para a esquerda
+    /// enquanto os saltos forem proxies confiáveis
+    ///
+    /// Um salto inválido encerra a busca: tudo à sua esquerda veio do cliente, e
+    /// vale o último endereço informado por um proxy confiável.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.contains(&peer) {
+            return peer;
+        }
+        let hops: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect();
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = ip;
+            if !self.contains(&ip) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+pub struct RateLimitConfig {
+    pub store: Arc<dyn RateLimitStore>,
+    pub policies: RateLimitPolicies,
+    pub trusted_proxies: TrustedProxies,
+}
+
+static RATE_LIMIT: OnceCell<RateLimitConfig> = OnceCell::new();
+
+pub fn init(config: RateLimitConfig) -> anyhow::Result<()> {
+    RATE_LIMIT
+        .set(config)
+        .map_err(|_| anyhow::anyhow!("rate limiting already initialised"))
+}
+
+/// Aplica o rate limiting antes de `next` (sem efeito enquanto `init` não for chamado)
+///
+/// Usado pelo `RateLimitMiddleware` do `MiddlewareSystem`, que executa depois da
+/// autenticação para usar o subject autenticado.
+pub async fn limit<F, Fut>(req: Request, next: F) -> Response
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    match RATE_LIMIT.get() {
+        Some(config) => handle(config, req, next).await,
+        None => next(req).await,
+    }
+}
+
+/// Identidade do bucket: subject autenticado ou IP do cliente
+fn identity(config: &RateLimitConfig, req: &Request) -> String {
+    if let Some(principal) = req.extensions().get::<Principal>() {
+        return principal.subject.clone();
+    }
+    match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(peer)) => format!(
+            "ip:{}",
+            config.trusted_proxies.client_ip(peer.ip(), req.headers())
+        ),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+async fn handle<F, Fut>(config: &RateLimitConfig, req: Request, next: F) -> Response
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let class = RouteClass::of(req.method(), req.uri());
+    let policy = config.policies.for_class(class);
+    let key = format!("{}:{}", class.as_str(), identity(config, &req));
+
+    let decision = match config.store.acquire(&key, policy).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            // Indisponibilidade do store não derruba a API
+            tracing::warn!(error = ?e, "rate limit store unavailable; request not limited");
+            return next(req).await;
+        }
+    };
+
+    let mut response = if decision.allowed {
+        next(req).await
+    } else {
+        tracing::warn!(key = %key, "rate limit exceeded");
+        custom_error(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response()
+    };
+    add_rate_limit_headers(response.headers_mut(), policy, &decision);
+    response
+}
+
+/// Headers RateLimit-* (draft IETF) e Retry-After
+pub fn add_rate_limit_headers(
+    headers: &mut HeaderMap,
+    policy: &RateLimitPolicy,
+    decision: &RateLimitDecision,
+) {
+    let window = policy.full_refill().as_secs().max(1);
+    let mut values = vec![
+        (RATE_LIMIT_LIMIT_HEADER, decision.limit.to_string()),
+        (RATE_LIMIT_REMAINING_HEADER, decision.remaining.to_string()),
+        (
+            RATE_LIMIT_RESET_HEADER,
+            decision.reset_after.as_secs().to_string(),
+        ),
+        (
+            RATE_LIMIT_POLICY_HEADER,
+            format!("{};w={}", decision.limit, window),
+        ),
+    ];
+    if !decision.allowed {
+        values.push((
+            header::RETRY_AFTER.as_str(),
+            decision.retry_after.as_secs().to_string(),
+        ));
+    }
+    for (name, value) in values {
+        if let Ok(value) = HeaderValue::try_from(value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::rate_limit::InMemoryRateLimitStore;
+    use axum::{body::Body, middleware, middleware::Next, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn config(proxies: &[&str]) -> Arc<RateLimitConfig> {
+        let policy = RateLimitPolicy::per_minute(60, 2);
+        Arc::new(RateLimitConfig {
+            store: Arc::new(InMemoryRateLimitStore::new()),
+            policies: RateLimitPolicies {
+                read: policy,
+                write: policy,
+                search: policy,
+                login: RateLimitPolicy::per_minute(60, 1),
+            },
+            trusted_proxies: TrustedProxies::parse(
+                &proxies.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+            )
+            .unwrap(),
+        })
+    }
+
+    fn app(config: Arc<RateLimitConfig>) -> Router {
+        Router::new()
+            .route("/items", get(|| async { "ok" }))
+            .layer(middleware::from_fn(move |req: Request, next: Next| {
+                let config = config.clone();
+                async move { handle(&config, req, |req| next.run(req)).await }
+            }))
+    }
+
+    fn request(peer: &str, forwarded_for: Option<&str>, subject: Option<&str>) -> Request {
+        let mut req = Request::builder().uri("/items");
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("x-forwarded-for", forwarded_for);
+        }
+        let mut req = req.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+        if let Some(subject) = subject {
+            req.extensions_mut().insert(Principal {
+                subject: subject.to_string(),
+                ..Principal::default()
+            });
+        }
+        req
+    }
+
+    #[tokio::test]
+    async fn test_requests_over_the_burst_are_rejected_with_headers() {
+        let app = app(config(&[]));
+
+        let first = app
+            .clone()
+            .oneshot(request("203.0.113.7", None, None))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[RATE_LIMIT_LIMIT_HEADER], "2");
+        assert_eq!(first.headers()[RATE_LIMIT_REMAINING_HEADER], "1");
+        assert_eq!(first.headers()[RATE_LIMIT_POLICY_HEADER], "2;w=2");
+
+        app.clone()
+            .oneshot(request("203.0.113.7", None, None))
+            .await
+            .unwrap();
+        let limited = app
+            .clone()
+            .oneshot(request("203.0.113.7", None, None))
+            .await
+            .unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "1");
+        assert_eq!(limited.headers()[RATE_LIMIT_REMAINING_HEADER], "0");
+
+        // Outro cliente tem seu próprio bucket
+        let other = app
+            .oneshot(request("203.0.113.8", None, None))
+            .await
+            .unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_is_ignored_from_untrusted_peers() {
+        let app = app(config(&[]));
+
+        // Trocar o X-Forwarded-For não gera buckets novos
+        for (i, status) in [
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let forwarded = format!("198.51.100.{}", i);
+            let response = app
+                .clone()
+                .oneshot(request("203.0.113.7", Some(&forwarded), None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_subject_has_its_own_bucket() {
+        let app = app(config(&[]));
+
+        for _ in 0..2 {
+            app.clone()
+                .oneshot(request("203.0.113.7", None, Some("user-1")))
+                .await
+                .unwrap();
+        }
+        let response = app
+            .oneshot(request("203.0.113.7", None, Some("apikey:42")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_client_ip_behind_trusted_proxies() {
+        let proxies =
+            TrustedProxies::parse(&["10.0.0.0/8".to_string(), "192.0.2.1".to_string()]).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.1.1.1, 203.0.113.9, 192.0.2.1"),
+        );
+
+        // Primeiro salto não confiável, da direita para a esquerda
+        assert_eq!(
+            proxies.client_ip("10.1.2.3".parse().unwrap(), &headers),
+            "203.0.113.9".parse::<IpAddr>().unwrap()
+        );
+        // Conexão direta de fora dos proxies: o header é ignorado
+        assert_eq!(
+            proxies.client_ip("203.0.113.50".parse().unwrap(), &headers),
+            "203.0.113.50".parse::<IpAddr>().unwrap()
+        );
+        assert!(parse_proxy("not-an-ip").is_err());
+    }
+
+    #[test]
+    fn test_client_ip_ignores_garbage_hops() {
+        let proxies = TrustedProxies::parse(&["10.0.0.0/8".to_string()]).unwrap();
+        let peer: IpAddr = "10.1.2.3".parse().unwrap();
+        let forwarded = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", HeaderValue::from_static(value));
+            headers
+        };
+
+        // Salto forjado pelo cliente à esquerda do endereço anexado pelo proxy
+        assert_eq!(
+            proxies.client_ip(peer, &forwarded("x, 203.0.113.9")),
+            "203.0.113.9".parse::<IpAddr>().unwrap()
+        );
+        assert_eq!(
+            proxies.client_ip(peer, &forwarded("1.1.1.1, x, 203.0.113.9, 10.0.0.7")),
+            "203.0.113.9".parse::<IpAddr>().unwrap()
+        );
+        // Salto inválido logo após os proxies: vale o último proxy que o informou
+        assert_eq!(
+            proxies.client_ip(peer, &forwarded("1.1.1.1, x, 10.0.0.7")),
+            "10.0.0.7".parse::<IpAddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_route_classes() {
+        let uri = |s: &str| s.parse::<Uri>().unwrap();
+        assert_eq!(
+            RouteClass::of(&Method::POST, &uri("/v1/auth/token")),
+            RouteClass::Login
+        );
+        assert_eq!(
+            RouteClass::of(&Method::GET, &uri("/v1/contacts?search_term=ana&limit=10")),
+            RouteClass::Search
+        );
+        assert_eq!(
+            RouteClass::of(&Method::GET, &uri("/v1/contacts?limit=10")),
+            RouteClass::Read
+        );
+        assert_eq!(
+            RouteClass::of(&Method::DELETE, &uri("/v1/contacts/1")),
+            RouteClass::Write
+        );
+    }
+}
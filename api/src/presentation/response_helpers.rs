@@ -4,6 +4,7 @@
 // Módulo que centraliza helpers para criação de respostas HTTP padronizadas
 // Elimina redundância e garante consistência
 
+use crate::presentation::error_mapper::{custom_error, ErrorResponse};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
@@ -70,11 +71,8 @@ pub fn no_content_response() -> StatusCode {
 ///
 /// # Retorna
 /// Resposta de erro com status 400
-pub fn bad_request_response(message: &str) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::BAD_REQUEST,
-        Json(serde_json::json!({"error": message})),
-    )
+pub fn bad_request_response(message: &str) -> ErrorResponse {
+    custom_error(StatusCode::BAD_REQUEST, message)
 }
 
 /// Cria uma resposta de erro com status 404 (Not Found)
@@ -84,11 +82,8 @@ pub fn bad_request_response(message: &str) -> (StatusCode, Json<serde_json::Valu
 ///
 /// # Retorna
 /// Resposta de erro com status 404
-pub fn not_found_response(message: &str) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::NOT_FOUND,
-        Json(serde_json::json!({"error": message})),
-    )
+pub fn not_found_response(message: &str) -> ErrorResponse {
+    custom_error(StatusCode::NOT_FOUND, message)
 }
 
 /// Cria uma resposta de erro com status 409 (Conflict)
@@ -98,11 +93,8 @@ pub fn not_found_response(message: &str) -> (StatusCode, Json<serde_json::Value>
 ///
 /// # Retorna
 /// Resposta de erro com status 409
-pub fn conflict_response(message: &str) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::CONFLICT,
-        Json(serde_json::json!({"error": message})),
-    )
+pub fn conflict_response(message: &str) -> ErrorResponse {
+    custom_error(StatusCode::CONFLICT, message)
 }
 
 /// Cria uma resposta de erro com status 422 (Unprocessable Entity)
@@ -112,11 +104,8 @@ pub fn conflict_response(message: &str) -> (StatusCode, Json<serde_json::Value>)
 ///
 /// # Retorna
 /// Resposta de erro com status 422
-pub fn unprocessable_entity_response(message: &str) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::UNPROCESSABLE_ENTITY,
-        Json(serde_json::json!({"error": message})),
-    )
+pub fn unprocessable_entity_response(message: &str) -> ErrorResponse {
+    custom_error(StatusCode::UNPROCESSABLE_ENTITY, message)
 }
 
 /// Cria uma resposta de erro com status 500 (Internal Server Error)
@@ -126,11 +115,8 @@ pub fn unprocessable_entity_response(message: &str) -> (StatusCode, Json<serde_j
 ///
 /// # Retorna
 /// Resposta de erro com status 500
-pub fn internal_server_error_response(message: &str) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({"error": message})),
-    )
+pub fn internal_server_error_response(message: &str) -> ErrorResponse {
+    custom_error(StatusCode::INTERNAL_SERVER_ERROR, message)
 }
 
 /// Macro para simplificar a criação de respostas de sucesso
@@ -160,24 +146,22 @@ macro_rules! success_response {
 /// ```rust
 /// use crate::presentation::response_helpers::error_response;
 ///
-/// let response = error_response!(400, "Bad request");
-/// let response = error_response!(404, "Not found");
+/// let response = error_response!(StatusCode::BAD_REQUEST, "Bad request");
+/// let response = error_response!(StatusCode::NOT_FOUND, "Not found");
 /// ```
 #[macro_export]
 macro_rules! error_response {
     ($status:expr, $message:expr) => {
-        (
+        $crate::presentation::error_mapper::custom_error(
             $status,
-            $crate::presentation::response_helpers::Json(serde_json::json!({"error": $message}))
+            ::std::convert::AsRef::<str>::as_ref(&$message),
         )
     };
 }
 
 /// Trait para facilitar a conversão de Result em respostas HTTP
 pub trait IntoHttpResponse<T> {
-    fn into_http_response(
-        self,
-    ) -> Result<(StatusCode, Json<T>), (StatusCode, Json<serde_json::Value>)>
+    fn into_http_response(self) -> Result<(StatusCode, Json<T>), ErrorResponse>
     where
         T: Serialize;
 }
@@ -187,15 +171,17 @@ where
     T: Serialize,
     E: std::fmt::Display,
 {
-    fn into_http_response(
-        self,
-    ) -> Result<(StatusCode, Json<T>), (StatusCode, Json<serde_json::Value>)> {
+    fn into_http_response(self) -> Result<(StatusCode, Json<T>), ErrorResponse> {
         match self {
             Ok(data) => Ok(ok_response(data)),
-            Err(err) => Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": err.to_string()})),
-            )),
+            Err(err) => {
+                // A mensagem pode conter detalhes internos; fica apenas no log
+                tracing::error!(error = %err, "request failed");
+                Err(custom_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Internal server error",
+                ))
+            }
         }
     }
 }
@@ -266,18 +252,20 @@ mod tests {
 
     #[test]
     fn test_bad_request_response() {
-        let (status, response) = bad_request_response("Invalid input");
+        let problem = bad_request_response("Invalid input");
 
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        assert_eq!(response.0["error"], "Invalid input");
+        assert_eq!(problem.status, StatusCode::BAD_REQUEST);
+        assert_eq!(problem.code, "bad_request");
+        assert_eq!(problem.detail, "Invalid input");
     }
 
     #[test]
     fn test_not_found_response() {
-        let (status, response) = not_found_response("Resource not found");
+        let problem = not_found_response("Resource not found");
 
-        assert_eq!(status, StatusCode::NOT_FOUND);
-        assert_eq!(response.0["error"], "Resource not found");
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+        assert_eq!(problem.code, "not_found");
+        assert_eq!(problem.detail, "Resource not found");
     }
 
     #[test]
@@ -286,7 +274,7 @@ mod tests {
         let (status, response) = paginated_response(items, 10, 1, 3);
 
         assert_eq!(status, StatusCode::OK);
-        assert_eq!(response.0["data"], vec![1, 2, 3]);
+        assert_eq!(response.0["data"], serde_json::json!([1, 2, 3]));
         assert_eq!(response.0["pagination"]["total"], 10);
         assert_eq!(response.0["pagination"]["page"], 1);
         assert_eq!(response.0["pagination"]["per_page"], 3);
@@ -178,45 +178,3 @@ pub fn validate_security_headers(headers: &HeaderMap) -> Result<(), String> {
 
     Ok(())
 }
-
-/// Configuração de rate limiting
-#[derive(Debug, Clone)]
-pub struct RateLimitConfig {
-    pub requests_per_minute: u32,
-    pub burst_size: u32,
-    pub window_size_seconds: u64,
-}
-
-impl Default for RateLimitConfig {
-    fn default() -> Self {
-        Self {
-            requests_per_minute: 100,
-            burst_size: 10,
-            window_size_seconds: 60,
-        }
-    }
-}
-
-/// Headers de rate limiting
-pub fn add_rate_limit_headers(
-    headers: &mut HeaderMap,
-    config: &RateLimitConfig,
-    remaining: u32,
-    reset_time: u64,
-) {
-    let rate_limit_headers = [
-        ("x-ratelimit-limit", config.requests_per_minute.to_string()),
-        ("x-ratelimit-remaining", remaining.to_string()),
-        ("x-ratelimit-reset", reset_time.to_string()),
-        ("x-ratelimit-burst", config.burst_size.to_string()),
-    ];
-
-    for (name, value) in rate_limit_headers {
-        if let (Ok(name), Ok(value)) = (
-            HeaderName::try_from(name),
-            HeaderValue::try_from(value.as_str()),
-        ) {
-            headers.insert(name, value);
-        }
-    }
-}
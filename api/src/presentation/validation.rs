@@ -4,7 +4,8 @@
 // Módulo que centraliza validações comuns usadas nos controllers
 // Elimina redundância e garante consistência
 
-use crate::presentation::error_mapper::{invalid_uuid_error, ErrorResponse};
+use crate::domain::DomainError;
+use crate::presentation::error_mapper::{invalid_uuid_error, map_domain_error, ErrorResponse};
 use uuid::Uuid;
 
 /// Resultado de validação de UUID
@@ -27,7 +28,7 @@ pub type ValidationResult<T> = Result<T, ErrorResponse>;
 /// let result = validate_uuid("550e8400-e29b-41d4-a716-446655440000");
 /// match result {
 ///     Ok(uuid) => println!("Valid UUID: {}", uuid),
-///     Err((status, response)) => println!("Invalid UUID: {}", response.0["error"]),
+///     Err(problem) => println!("Invalid UUID: {}", problem.detail),
 /// }
 /// ```
 pub fn validate_uuid(uuid_str: &str) -> ValidationResult<Uuid> {
@@ -64,12 +65,10 @@ pub fn validate_uuids(uuid_strings: &[&str]) -> ValidationResult<Vec<Uuid>> {
 /// * `Err(ErrorResponse)` - String vazia
 pub fn validate_not_empty(value: &str, field_name: &str) -> ValidationResult<()> {
     if value.trim().is_empty() {
-        Err((
-            axum::http::StatusCode::BAD_REQUEST,
-            axum::response::Json(serde_json::json!({
-                "error": format!("{} cannot be empty", field_name)
-            })),
-        ))
+        Err(map_domain_error(&DomainError::invalid_field(
+            field_name,
+            format!("{} cannot be empty", field_name),
+        )))
     } else {
         Ok(())
     }
@@ -87,12 +86,10 @@ pub fn validate_email_format(email: &str) -> ValidationResult<()> {
     if email.contains('@') && email.contains('.') && email.len() > 5 {
         Ok(())
     } else {
-        Err((
-            axum::http::StatusCode::BAD_REQUEST,
-            axum::response::Json(serde_json::json!({
-                "error": "Invalid email format"
-            })),
-        ))
+        Err(map_domain_error(&DomainError::invalid_field(
+            "email",
+            "Invalid email format",
+        )))
     }
 }
 
@@ -114,12 +111,10 @@ where
     if value >= min && value <= max {
         Ok(())
     } else {
-        Err((
-            axum::http::StatusCode::BAD_REQUEST,
-            axum::response::Json(serde_json::json!({
-                "error": format!("{} must be between {} and {}", field_name, min, max)
-            })),
-        ))
+        Err(map_domain_error(&DomainError::invalid_field(
+            field_name,
+            format!("{} must be between {} and {}", field_name, min, max),
+        )))
     }
 }
 
@@ -8,17 +8,24 @@ use axum::{
     extract::{Path, State},
     http::{HeaderMap, StatusCode},
     response::Json,
-    routing::post,
-    Router,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, future::Future, sync::Arc};
 use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
+use crate::domain::entities::WebhookReceipt;
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{Nonce, Source};
+use crate::infra::revocation::RevocationKind;
+use crate::presentation::{access_log, auth};
+use crate::shared::metrics::{self, IngestionOutcome};
+use crate::shared::secrets::SecretKind;
 use crate::AppState;
 
 /// Payload de webhook do Vault
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct VaultWebhookPayload {
     pub event_type: String,
     pub secret_path: Option<String>,
@@ -28,41 +35,80 @@ pub struct VaultWebhookPayload {
 }
 
 /// Payload de webhook do Keycloak
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct KeycloakWebhookPayload {
     pub event_type: String,
     pub user_id: Option<String>,
     pub username: Option<String>,
+    #[serde(default, alias = "sessionId")]
+    pub session_id: Option<String>,
     pub timestamp: String,
     pub details: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Resposta de webhook
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct WebhookResponse {
     pub status: String,
     pub message: String,
     pub timestamp: String,
 }
 
-fn ensure_webhook_authorized(headers: &HeaderMap, state: &AppState) -> Result<(), StatusCode> {
-    let Some(expected) = state.webhook_token.as_deref() else {
+/// Permissão exigida de API keys usadas para chamar webhooks
+const WEBHOOK_PERMISSION: &str = "webhooks.write";
+
+/// Header opcional que identifica a entrega; reenvios com o mesmo valor são ignorados
+const WEBHOOK_ID_HEADER: &str = "x-webhook-id";
+
+async fn ensure_webhook_authorized(
+    headers: &HeaderMap,
+    state: &AppState,
+    source: &str,
+) -> Result<(), StatusCode> {
+    // API key (Authorization: ApiKey ...) com a permissão de webhooks
+    if let Some(key) = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("ApiKey "))
+    {
+        let principal = crate::presentation::auth::authenticate_api_key(key)
+            .await
+            .inspect_err(|_| {
+                metrics::record_webhook_signature_failure(source, "invalid_api_key")
+            })?;
+        if !principal.has_permission(WEBHOOK_PERMISSION) {
+            warn!(subject = %principal.subject, "Webhook rejected: API key lacks webhooks.write");
+            metrics::record_webhook_signature_failure(source, "forbidden_api_key");
+            return Err(StatusCode::FORBIDDEN);
+        }
+        debug!("Webhook authentication passed (API key)");
+        return Ok(());
+    }
+
+    if !state.secrets.is_configured(SecretKind::WebhookSharedSecret) {
         warn!("Webhook rejected: shared secret not configured");
         return Err(StatusCode::SERVICE_UNAVAILABLE);
-    };
+    }
 
     let Some(provided_raw) = headers.get("x-webhook-token").and_then(|v| v.to_str().ok()) else {
         warn!("Webhook rejected: missing X-Webhook-Token header");
+        metrics::record_webhook_signature_failure(source, "missing_token");
         return Err(StatusCode::UNAUTHORIZED);
     };
     let provided = provided_raw.trim();
     if provided.is_empty() {
         warn!("Webhook rejected: empty X-Webhook-Token header");
+        metrics::record_webhook_signature_failure(source, "missing_token");
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+    // Durante a carência de uma rotação, o segredo anterior também é aceito
+    if !state
+        .secrets
+        .matches(SecretKind::WebhookSharedSecret, provided.as_bytes())
+    {
         warn!("Webhook rejected: invalid shared secret");
+        metrics::record_webhook_signature_failure(source, "invalid_token");
         return Err(StatusCode::UNAUTHORIZED);
     }
 
@@ -70,35 +116,196 @@ fn ensure_webhook_authorized(headers: &HeaderMap, state: &AppState) -> Result<()
     Ok(())
 }
 
-fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
+/// Autentica, descarta reenvios (`X-Webhook-Id`) e processa um evento de ingestão,
+/// contabilizando o resultado por fonte em `sut_ingestion_events_total`
+async fn ingest<F, Fut>(
+    state: &AppState,
+    headers: &HeaderMap,
+    source: &str,
+    process: F,
+) -> Result<Json<WebhookResponse>, StatusCode>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String, StatusCode>>,
+{
+    if let Err(status) = ensure_webhook_authorized(headers, state, source).await {
+        metrics::record_ingestion(source, IngestionOutcome::Rejected);
+        return Err(status);
+    }
+
+    let delivery_id = headers
+        .get(WEBHOOK_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+    if let Some(id) = delivery_id {
+        let receipt = WebhookReceipt::new(
+            Source {
+                value: source.to_string(),
+            },
+            Nonce {
+                value: id.to_string(),
+            },
+        );
+        let first = match state.webhook_receipt_repository.save(&receipt).await {
+            Ok(_) => true,
+            // Violação de (source, nonce): a entrega já foi recebida
+            Err(DomainError::Conflict(_)) => false,
+            Err(e) => {
+                error!(error = ?e, source, "Falha ao registrar recebimento do webhook");
+                metrics::record_ingestion(source, IngestionOutcome::Rejected);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+        if !first {
+            info!(source, delivery_id = id, "Webhook duplicado ignorado");
+            metrics::record_ingestion(source, IngestionOutcome::Duplicate);
+            return Ok(Json(WebhookResponse {
+                status: "duplicate".to_string(),
+                message: format!("Entrega {} já processada", id),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            }));
+        }
+    }
+
+    match process().await {
+        Ok(message) => {
+            metrics::record_ingestion(source, IngestionOutcome::Accepted);
+            Ok(Json(WebhookResponse {
+                status: "success".to_string(),
+                message,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            }))
+        }
+        Err(status) => {
+            metrics::record_ingestion(source, IngestionOutcome::Rejected);
+            // O emissor reenviará a mesma entrega
+            if let Some(id) = delivery_id {
+                if let Err(e) = state.webhook_receipt_repository.delete(source, id).await {
+                    error!(error = ?e, source, "Falha ao liberar recebimento do webhook");
+                }
+            }
+            Err(status)
+        }
+    }
+}
+
+/// O evento se refere ao secret KV da aplicação? (`sut`, `kv/data/sut`, `kv/sut/`)
+fn is_app_secret_path(event_path: Option<&str>, app_path: &str) -> bool {
+    let Some(event_path) = event_path else {
+        // Sem path no evento: recarregar é inofensivo (valores iguais não rotacionam)
+        return true;
+    };
+    let normalize = |path: &str| {
+        let path = path.trim_matches('/');
+        let path = path
+            .strip_prefix("kv/data/")
+            .or_else(|| path.strip_prefix("kv/"))
+            .unwrap_or(path);
+        path.trim_matches('/').to_string()
+    };
+    normalize(event_path) == normalize(app_path)
+}
+
+/// Recarrega os segredos da aplicação do Vault e registra a rotação na auditoria
+async fn rotate_app_secrets(
+    state: &AppState,
+    payload: &VaultWebhookPayload,
+) -> Result<String, StatusCode> {
+    let (Some(vault_client), Some(app_path)) =
+        (state.vault.as_ref(), state.vault_secret_path.as_deref())
+    else {
+        warn!("Rotação de secret recebida sem Vault configurado; ignorando");
+        return Ok("Vault não configurado; nenhum segredo recarregado".to_string());
+    };
+    if !is_app_secret_path(payload.secret_path.as_deref(), app_path) {
+        info!(
+            "Secret rotacionado fora do path da aplicação: {:?}",
+            payload.secret_path
+        );
+        return Ok("Secret não utilizado pela aplicação".to_string());
     }
 
-    let mut diff = 0u8;
-    for (&x, &y) in a.iter().zip(b.iter()) {
-        diff |= x ^ y;
+    // Descarta os valores em cache para buscar os novos
+    vault_client.clear_cache().await;
+    let rotated = state
+        .secrets
+        .refresh(vault_client, app_path)
+        .await
+        .map_err(|e| {
+            // 500 faz o emissor reenviar o evento
+            error!(error = ?e, "Falha ao recarregar segredos do Vault");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let rotated: Vec<&str> = rotated.iter().map(|kind| kind.vault_key()).collect();
+    info!(rotated = ?rotated, "Segredos da aplicação recarregados do Vault");
+
+    // Auditoria: apenas os nomes dos segredos, nunca os valores
+    let grace_secs = state.secrets.grace().as_secs();
+    if let Err(status) = crate::infra::audit::log_audit(
+        state,
+        None,
+        "secret_rotated",
+        "vault_secret",
+        app_path,
+        None,
+        Some(serde_json::json!({
+            "rotated": rotated,
+            "grace_secs": grace_secs,
+            "event_timestamp": payload.timestamp,
+        })),
+    )
+    .await
+    {
+        // A rotação já foi aplicada; a falha de auditoria não deve gerar reenvio
+        error!(status = %status, "Falha ao registrar auditoria da rotação de segredos");
     }
 
-    diff == 0
+    Ok(if rotated.is_empty() {
+        "Nenhum segredo alterado".to_string()
+    } else {
+        format!("Segredos rotacionados: {}", rotated.join(", "))
+    })
 }
 
 /// Handler para webhooks do Vault
+#[utoipa::path(
+    post,
+    path = "/vault-alerts",
+    tag = "webhooks",
+    summary = "Eventos do Vault (rotação de segredos)",
+    params(("X-Webhook-Id" = Option<String>, Header, description = "Identificador da entrega; reenvios são ignorados")),
+    request_body = VaultWebhookPayload,
+    responses(
+        (status = 200, description = "Evento processado (ou reenvio ignorado)", body = WebhookResponse),
+        (status = 401, description = "X-Webhook-Token ausente ou inválido"),
+        (status = 403, description = "API key sem a permissão webhooks.write"),
+        (status = 503, description = "Segredo compartilhado não configurado"),
+    ),
+    security(("webhook_token" = []), ("api_key" = ["webhooks.write"]))
+)]
 pub async fn vault_webhook_handler(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Json(payload): Json<VaultWebhookPayload>,
 ) -> Result<Json<WebhookResponse>, StatusCode> {
-    ensure_webhook_authorized(&headers, state.as_ref())?;
+    ingest(state.as_ref(), &headers, "vault", || {
+        process_vault_event(state.as_ref(), payload)
+    })
+    .await
+}
+
+async fn process_vault_event(
+    state: &AppState,
+    payload: VaultWebhookPayload,
+) -> Result<String, StatusCode> {
     info!("Webhook do Vault recebido: {:?}", payload);
 
+    let mut message = "Webhook processado com sucesso".to_string();
     match payload.event_type.as_str() {
         "secret_rotated" => {
             info!("Secret rotacionado: {:?}", payload.secret_path);
-            // TODO: Implementar lógica de rotação de secrets
-            // - Invalidar cache
-            // - Notificar serviços dependentes
-            // - Atualizar configurações
+            message = rotate_app_secrets(state, &payload).await?;
         }
         "secret_created" => {
             info!("Novo secret criado: {:?}", payload.secret_path);
@@ -124,41 +331,79 @@ pub async fn vault_webhook_handler(
         vault_client.clear_cache().await;
     }
 
-    Ok(Json(WebhookResponse {
-        status: "success".to_string(),
-        message: "Webhook processado com sucesso".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-    }))
+    Ok(message)
 }
 
 /// Handler para webhooks do Keycloak
+#[utoipa::path(
+    post,
+    path = "/keycloak-events",
+    tag = "webhooks",
+    summary = "Eventos do Keycloak (logout, exclusão, alteração de senha)",
+    params(("X-Webhook-Id" = Option<String>, Header, description = "Identificador da entrega; reenvios são ignorados")),
+    request_body = KeycloakWebhookPayload,
+    responses(
+        (status = 200, description = "Evento processado (ou reenvio ignorado)", body = WebhookResponse),
+        (status = 401, description = "X-Webhook-Token ausente ou inválido"),
+        (status = 403, description = "API key sem a permissão webhooks.write"),
+        (status = 503, description = "Segredo compartilhado não configurado"),
+    ),
+    security(("webhook_token" = []), ("api_key" = ["webhooks.write"]))
+)]
 pub async fn keycloak_webhook_handler(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Json(payload): Json<KeycloakWebhookPayload>,
 ) -> Result<Json<WebhookResponse>, StatusCode> {
-    ensure_webhook_authorized(&headers, state.as_ref())?;
-    info!("Webhook do Keycloak recebido: {:?}", payload);
+    ingest(state.as_ref(), &headers, "keycloak", || {
+        process_keycloak_event(payload)
+    })
+    .await
+}
+
+async fn process_keycloak_event(payload: KeycloakWebhookPayload) -> Result<String, StatusCode> {
+    // username e details podem conter dados pessoais (LOG_REDACT_FIELDS)
+    info!(
+        event_type = %payload.event_type,
+        user_id = ?payload.user_id,
+        details = %access_log::redact_json(&serde_json::json!({
+            "username": payload.username,
+            "details": payload.details,
+        })),
+        "Webhook do Keycloak recebido"
+    );
 
     match payload.event_type.as_str() {
         "LOGIN" => {
-            info!("Usuário fez login: {:?}", payload.username);
+            info!("Usuário fez login: {:?}", payload.user_id);
             // TODO: Implementar auditoria de login
         }
         "LOGOUT" => {
-            info!("Usuário fez logout: {:?}", payload.username);
-            // TODO: Implementar auditoria de logout
+            info!("Usuário fez logout: {:?}", payload.user_id);
+            // Revoga os tokens ainda válidos da sessão encerrada
+            if let Some(session_id) = payload.session_id.as_deref() {
+                revoke_from_webhook(RevocationKind::Sid, session_id, "keycloak LOGOUT").await?;
+            } else {
+                warn!("Evento LOGOUT sem session_id; nenhum token revogado");
+            }
         }
         "REGISTER" => {
-            info!("Novo usuário registrado: {:?}", payload.username);
+            info!("Novo usuário registrado: {:?}", payload.user_id);
             // TODO: Implementar lógica de registro
         }
         "UPDATE_PASSWORD" => {
-            info!("Senha atualizada para usuário: {:?}", payload.username);
+            info!("Senha atualizada para usuário: {:?}", payload.user_id);
             // TODO: Implementar auditoria de mudança de senha
         }
         "DELETE_ACCOUNT" => {
-            warn!("Conta deletada: {:?}", payload.username);
+            warn!("Conta deletada: {:?}", payload.user_id);
+            // Revoga todos os tokens do usuário removido
+            if let Some(user_id) = payload.user_id.as_deref() {
+                revoke_from_webhook(RevocationKind::Sub, user_id, "keycloak DELETE_ACCOUNT")
+                    .await?;
+            } else {
+                warn!("Evento DELETE_ACCOUNT sem user_id; nenhum token revogado");
+            }
             // TODO: Implementar lógica de remoção de dados
         }
         _ => {
@@ -166,47 +411,80 @@ pub async fn keycloak_webhook_handler(
         }
     }
 
-    Ok(Json(WebhookResponse {
-        status: "success".to_string(),
-        message: "Webhook processado com sucesso".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-    }))
+    Ok("Webhook processado com sucesso".to_string())
+}
+
+/// Revoga tokens a partir de um evento externo; falhas retornam 500 para que o
+/// emissor reenvie o evento
+async fn revoke_from_webhook(
+    kind: RevocationKind,
+    value: &str,
+    reason: &str,
+) -> Result<(), StatusCode> {
+    auth::revoke(kind, value, None, Some(reason.to_string()))
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            error!(error = ?e, "Falha ao revogar tokens: {}", reason);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
 }
 
 /// Handler genérico para webhooks
+#[utoipa::path(
+    post,
+    path = "/{service}",
+    tag = "webhooks",
+    summary = "Eventos de outros serviços",
+    params(
+        ("service" = String, Path, description = "Serviço de origem"),
+        ("X-Webhook-Id" = Option<String>, Header, description = "Identificador da entrega; reenvios são ignorados"),
+    ),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Evento processado (ou reenvio ignorado)", body = WebhookResponse),
+        (status = 401, description = "X-Webhook-Token ausente ou inválido"),
+        (status = 403, description = "API key sem a permissão webhooks.write"),
+        (status = 503, description = "Segredo compartilhado não configurado"),
+    ),
+    security(("webhook_token" = []), ("api_key" = ["webhooks.write"]))
+)]
 pub async fn generic_webhook_handler(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(service): Path<String>,
     Json(payload): Json<serde_json::Value>,
 ) -> Result<Json<WebhookResponse>, StatusCode> {
-    ensure_webhook_authorized(&headers, state.as_ref())?;
-    info!("Webhook genérico recebido de {}: {:?}", service, payload);
-
-    // TODO: Implementar processamento genérico de webhooks
-    // - Validação de payload
-    // - Roteamento baseado no serviço
-    // - Logging estruturado
-    // - Métricas
-
-    Ok(Json(WebhookResponse {
-        status: "success".to_string(),
-        message: format!("Webhook de {} processado", service),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-    }))
+    ingest(state.as_ref(), &headers, &service, || async {
+        info!(
+            "Webhook genérico recebido de {}: {}",
+            service,
+            access_log::redact_json(&payload)
+        );
+
+        // TODO: Implementar processamento genérico de webhooks
+        // - Validação de payload
+        // - Roteamento baseado no serviço
+
+        Ok(format!("Webhook de {} processado", service))
+    })
+    .await
 }
 
 /// Configurar rotas de webhooks
-pub fn webhook_routes() -> Router<Arc<AppState>> {
-    Router::new()
-        .route("/vault-alerts", post(vault_webhook_handler))
-        .route("/keycloak-events", post(keycloak_webhook_handler))
-        .route("/{service}", post(generic_webhook_handler))
+pub fn webhook_routes() -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(vault_webhook_handler))
+        .routes(routes!(keycloak_webhook_handler))
+        .routes(routes!(generic_webhook_handler))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::repositories::WebhookReceiptRepository;
+    use crate::infrastructure::memory::InMemoryDatabase;
+    use crate::shared::secrets::RuntimeSecrets;
     use axum::{
         body::Body,
         http::{Request, StatusCode},
@@ -214,16 +492,125 @@ mod tests {
     use serde_json::json;
     use tower::ServiceExt;
 
+    #[test]
+    fn test_is_app_secret_path() {
+        assert!(is_app_secret_path(Some("sut"), "sut"));
+        assert!(is_app_secret_path(Some("kv/data/sut"), "sut"));
+        assert!(is_app_secret_path(Some("/kv/sut/"), "sut"));
+        assert!(is_app_secret_path(None, "sut"));
+        assert!(!is_app_secret_path(Some("sut/keycloak"), "sut"));
+    }
+
+    const SECRET: &str = "webhook-secret";
+
+    fn app(database: &InMemoryDatabase) -> axum::Router {
+        let state = AppState {
+            secrets: Arc::new(RuntimeSecrets::new(
+                Some(SECRET.to_string()),
+                None,
+                std::time::Duration::ZERO,
+            )),
+            ..AppState::in_memory(database)
+        };
+        let (router, _) = webhook_routes().split_for_parts();
+        router.with_state(Arc::new(state))
+    }
+
+    async fn post(
+        app: &axum::Router,
+        path: &str,
+        token: Option<&str>,
+        delivery_id: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::post(path)
+            .header("content-type", "application/json")
+            .header(WEBHOOK_ID_HEADER, delivery_id);
+        if let Some(token) = token {
+            request = request.header("x-webhook-token", token);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
     #[tokio::test]
     async fn test_vault_webhook_handler() {
-        // TODO: Implementar testes para webhook handlers
-        // - Mock do AppState
-        // - Teste de diferentes tipos de eventos
-        // - Validação de respostas
+        let database = InMemoryDatabase::new();
+        let app = app(&database);
+        let event = json!({ "event_type": "secret_created", "secret_path": "sut", "timestamp": "2026-10-18T12:00:00Z" });
+
+        let (status, body) = post(
+            &app,
+            "/vault-alerts",
+            Some(SECRET),
+            "delivery-1",
+            event.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "success");
+
+        // Reenvio da mesma entrega é ignorado
+        let (status, body) = post(
+            &app,
+            "/vault-alerts",
+            Some(SECRET),
+            "delivery-1",
+            event.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "duplicate");
+        assert!(database
+            .webhook_receipts()
+            .exists("vault", "delivery-1")
+            .await
+            .unwrap());
+
+        let (status, _) = post(&app, "/vault-alerts", None, "delivery-2", event.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = post(&app, "/vault-alerts", Some("wrong"), "delivery-2", event).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(!database
+            .webhook_receipts()
+            .exists("vault", "delivery-2")
+            .await
+            .unwrap());
     }
 
     #[tokio::test]
     async fn test_keycloak_webhook_handler() {
-        // TODO: Implementar testes para webhook handlers
+        let database = InMemoryDatabase::new();
+        let app = app(&database);
+        let event = json!({
+            "event_type": "LOGIN",
+            "user_id": "user-1",
+            "username": "ana",
+            "timestamp": "2026-10-18T12:00:00Z"
+        });
+
+        let (status, body) = post(
+            &app,
+            "/keycloak-events",
+            Some(SECRET),
+            "event-1",
+            event.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "success");
+
+        // O mesmo id em outra fonte é uma entrega diferente
+        let (status, body) = post(&app, "/custom", Some(SECRET), "event-1", event).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "success");
     }
 }
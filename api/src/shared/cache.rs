@@ -0,0 +1,166 @@
+// ============================================================================
+// CACHE EM MEMÓRIA - IMPLEMENTAÇÃO DO TRAIT `Cache`
+// ============================================================================
+// Cache local ao processo, com expiração por TTL e limite de entradas
+// Indicado para resultados caros que toleram pequena defasagem (ex.: estatísticas)
+
+use crate::shared::base_traits::Cache;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Entrada armazenada com seu instante de expiração
+struct CacheEntry<T> {
+    value: T,
+    expires_at: Option<Instant>,
+}
+
+impl<T> CacheEntry<T> {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+/// Cache em memória com TTL por entrada
+///
+/// Quando o limite de entradas é atingido, as expiradas são descartadas e,
+/// se ainda necessário, a entrada mais próxima de expirar é removida.
+pub struct InMemoryCache<T> {
+    entries: RwLock<HashMap<String, CacheEntry<T>>>,
+    default_ttl: Option<Duration>,
+    max_entries: usize,
+}
+
+impl<T> InMemoryCache<T> {
+    /// Cria um cache com TTL padrão (usado quando `set` não informa TTL)
+    pub fn new(default_ttl: Option<Duration>, max_entries: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            default_ttl,
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    fn evict(entries: &mut HashMap<String, CacheEntry<T>>, max_entries: usize) {
+        let now = Instant::now();
+        entries.retain(|_, entry| !entry.is_expired(now));
+
+        while entries.len() >= max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| (entry.expires_at.is_none(), entry.expires_at))
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Cache<T> for InMemoryCache<T>
+where
+    T: Clone + Send + Sync,
+{
+    async fn get(&self, key: &str) -> Result<Option<T>, String> {
+        let entries = self.entries.read().map_err(|e| e.to_string())?;
+        Ok(entries
+            .get(key)
+            .filter(|entry| !entry.is_expired(Instant::now()))
+            .map(|entry| entry.value.clone()))
+    }
+
+    async fn set(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<(), String> {
+        let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+        if !entries.contains_key(key) {
+            Self::evict(&mut entries, self.max_entries);
+        }
+
+        let expires_at = ttl.or(self.default_ttl).map(|ttl| Instant::now() + ttl);
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                value: value.clone(),
+                expires_at,
+            },
+        );
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+        entries.remove(key);
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), String> {
+        let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+        entries.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_and_get() {
+        let cache = InMemoryCache::new(None, 10);
+        cache.set("a", &1, None).await.unwrap();
+
+        assert_eq!(cache.get("a").await.unwrap(), Some(1));
+        assert_eq!(cache.get("b").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire_after_ttl() {
+        let cache = InMemoryCache::new(Some(Duration::from_millis(20)), 10);
+        cache.set("a", &1, None).await.unwrap();
+        cache
+            .set("b", &2, Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(cache.get("a").await.unwrap(), None);
+        assert_eq!(cache.get("b").await.unwrap(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_max_entries_evicts_soonest_to_expire() {
+        let cache = InMemoryCache::new(None, 2);
+        cache
+            .set("short", &1, Some(Duration::from_secs(1)))
+            .await
+            .unwrap();
+        cache
+            .set("long", &2, Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+        cache
+            .set("new", &3, Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get("short").await.unwrap(), None);
+        assert_eq!(cache.get("long").await.unwrap(), Some(2));
+        assert_eq!(cache.get("new").await.unwrap(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_clear() {
+        let cache = InMemoryCache::new(None, 10);
+        cache.set("a", &1, None).await.unwrap();
+        cache.set("b", &2, None).await.unwrap();
+
+        cache.delete("a").await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap(), None);
+
+        cache.clear().await.unwrap();
+        assert_eq!(cache.get("b").await.unwrap(), None);
+    }
+}
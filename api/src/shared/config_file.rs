@@ -0,0 +1,174 @@
+// ============================================================================
+// CONFIG FILE - LEITURA DO ARQUIVO DE CONFIGURAÇÃO (JSON, TOML OU YAML)
+// ============================================================================
+// Converte o arquivo de configuração em um `serde_json::Value`, que é mesclado
+// sobre os valores padrão do `AppConfig`. O formato é escolhido pela extensão;
+// TOML e YAML são lidos com os crates `toml` e `serde_yaml`.
+
+use serde_json::Value;
+use std::path::Path;
+
+/// Formatos aceitos para o arquivo de configuração
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detecta o formato pela extensão do arquivo
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Lê e converte o arquivo de configuração
+pub fn read(path: &Path) -> Result<Value, String> {
+    let format = ConfigFormat::from_path(path).ok_or_else(|| {
+        "unsupported extension (expected .json, .toml, .yaml or .yml)".to_string()
+    })?;
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse(&content, format)
+}
+
+/// Converte o conteúdo do arquivo; a raiz precisa ser um objeto
+pub fn parse(content: &str, format: ConfigFormat) -> Result<Value, String> {
+    let value: Value = match format {
+        ConfigFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string())?,
+        ConfigFormat::Toml => toml::from_str(content).map_err(|e| e.to_string())?,
+        ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string())?,
+    };
+    if !value.is_object() {
+        return Err("the configuration root must be a table/mapping".to_string());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::config::AppConfig;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_toml_sections_and_values() {
+        let value = parse(
+            r#"
+environment = "production" # comentário
+[server]
+port = 8_080
+
+[auth]
+audiences = ["sut-frontend", "sut-etl",]
+[auth.local]
+enabled = true
+[auth.role_permissions]
+"ldap-editors" = ['directory.write']
+"#,
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "environment": "production",
+                "server": { "port": 8080 },
+                "auth": {
+                    "audiences": ["sut-frontend", "sut-etl"],
+                    "local": { "enabled": true },
+                    "role_permissions": { "ldap-editors": ["directory.write"] }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_toml_errors_report_line() {
+        let err = parse("[server]\nport = 80\nport = 81", ConfigFormat::Toml).unwrap_err();
+        assert!(
+            err.contains("line 3") && err.contains("duplicate key"),
+            "{}",
+            err
+        );
+        let err = parse("host = localhost", ConfigFormat::Toml).unwrap_err();
+        assert!(err.contains("line 1"), "{}", err);
+    }
+
+    #[test]
+    fn test_toml_floats_reach_the_config() {
+        let value = parse("[telemetry]\nsample_ratio = 0.25", ConfigFormat::Toml).unwrap();
+        assert_eq!(value, json!({ "telemetry": { "sample_ratio": 0.25 } }));
+        let config = AppConfig::from_layers(Some(value), &HashMap::new()).unwrap();
+        assert_eq!(config.telemetry.sample_ratio, 0.25);
+    }
+
+    #[test]
+    fn test_yaml_mappings_and_sequences() {
+        let value = parse(
+            r#"
+---
+environment: production
+server:
+  host: "0.0.0.0"   # todas as interfaces
+  port: 8080
+auth:
+  jwks_uri: http://keycloak:8080/realms/sut/protocol/openid-connect/certs
+  audiences: [sut-frontend, 'sut-etl']
+  permission_clients:
+  - sut-frontend
+  role_permissions:
+    ldap-editors:
+      - directory.write
+vault: ~
+"#,
+            ConfigFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "environment": "production",
+                "server": { "host": "0.0.0.0", "port": 8080 },
+                "auth": {
+                    "jwks_uri": "http://keycloak:8080/realms/sut/protocol/openid-connect/certs",
+                    "audiences": ["sut-frontend", "sut-etl"],
+                    "permission_clients": ["sut-frontend"],
+                    "role_permissions": { "ldap-editors": ["directory.write"] }
+                },
+                "vault": null
+            })
+        );
+    }
+
+    #[test]
+    fn test_yaml_errors_report_line() {
+        let err = parse("server:\n  port: 1\n    host: x", ConfigFormat::Yaml).unwrap_err();
+        assert!(err.contains("line 3"), "{}", err);
+        let err = parse("- a\n- b", ConfigFormat::Yaml).unwrap_err();
+        assert!(err.contains("table/mapping"), "{}", err);
+    }
+
+    #[test]
+    fn test_yaml_floats_reach_the_config() {
+        let value = parse("telemetry:\n  sample_ratio: 0.25", ConfigFormat::Yaml).unwrap();
+        assert_eq!(value, json!({ "telemetry": { "sample_ratio": 0.25 } }));
+        let config = AppConfig::from_layers(Some(value), &HashMap::new()).unwrap();
+        assert_eq!(config.telemetry.sample_ratio, 0.25);
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("/etc/sut/config.YML")),
+            Some(ConfigFormat::Yaml)
+        );
+        assert_eq!(ConfigFormat::from_path(Path::new("config.ini")), None);
+    }
+}
@@ -0,0 +1,487 @@
+// ============================================================================
+// HEALTH - VERIFICAÇÕES DE SAÚDE E HEARTBEATS
+// ============================================================================
+// Verificações plugáveis (`HealthCheck`) executadas em paralelo, cada uma com
+// timeout próprio, e agregadas em um relatório JSON com status e duração por
+// verificação
+//
+// O relatório fica em cache por alguns segundos e só uma execução ocorre por vez:
+// probes concorrentes aguardam a execução em andamento em vez de repetir as
+// consultas ao banco. A liveness não executa verificações.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+/// Status de uma verificação ou do relatório
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Resultado produzido por uma verificação
+#[derive(Debug, Clone)]
+pub struct CheckOutcome {
+    pub status: HealthStatus,
+    /// Detalhes da dependência (latência, versão, idade das chaves...)
+    pub details: Value,
+    /// Detalhes internos (usuários, versões, erros brutos), omitidos no `/ready`
+    pub diagnostics: Value,
+    pub error: Option<String>,
+}
+
+impl CheckOutcome {
+    pub fn pass(details: Value) -> Self {
+        Self {
+            status: HealthStatus::Pass,
+            details,
+            diagnostics: Value::Null,
+            error: None,
+        }
+    }
+
+    pub fn warn(details: Value, error: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Warn,
+            details,
+            diagnostics: Value::Null,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn fail(details: Value, error: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Fail,
+            details,
+            diagnostics: Value::Null,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn with_diagnostics(mut self, diagnostics: Value) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+}
+
+/// Verificação de uma dependência
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Nome da verificação no relatório
+    fn name(&self) -> &str;
+
+    /// Falha de uma verificação crítica torna a instância não pronta (503);
+    /// as demais apenas degradam o relatório (`warn`)
+    fn critical(&self) -> bool {
+        true
+    }
+
+    async fn check(&self) -> CheckOutcome;
+}
+
+/// Resultado de uma verificação no relatório
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CheckReport {
+    pub status: HealthStatus,
+    pub critical: bool,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub details: Value,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub diagnostics: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Relatório de prontidão
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checked_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Verdadeiro quando o relatório veio do cache
+    pub cached: bool,
+    pub checks: BTreeMap<String, CheckReport>,
+}
+
+impl HealthReport {
+    /// Pronta enquanto nenhuma verificação crítica falhar
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Fail
+    }
+
+    /// Relatório sem os diagnósticos internos, para as probes públicas
+    pub fn public(mut self) -> Self {
+        for check in self.checks.values_mut() {
+            check.diagnostics = Value::Null;
+        }
+        self
+    }
+}
+
+/// Resposta da liveness (não consulta dependências)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LivenessReport {
+    pub status: HealthStatus,
+    pub uptime_secs: u64,
+    pub version: &'static str,
+}
+
+/// Conjunto de verificações com cache do último relatório
+pub struct HealthRegistry {
+    checks: Vec<Arc<dyn HealthCheck>>,
+    cache_ttl: Duration,
+    check_timeout: Duration,
+    started_at: Instant,
+    last: Mutex<Option<(Instant, HealthReport)>>,
+}
+
+impl HealthRegistry {
+    pub fn new(cache_ttl: Duration, check_timeout: Duration) -> Self {
+        Self {
+            checks: Vec::new(),
+            cache_ttl,
+            check_timeout,
+            started_at: Instant::now(),
+            last: Mutex::new(None),
+        }
+    }
+
+    pub fn with_check(mut self, check: impl HealthCheck + 'static) -> Self {
+        self.checks.push(Arc::new(check));
+        self
+    }
+
+    pub fn liveness(&self) -> LivenessReport {
+        LivenessReport {
+            status: HealthStatus::Pass,
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+
+    /// Relatório de prontidão (do cache, quando ainda válido)
+    pub async fn report(&self) -> HealthReport {
+        // O lock serializa as execuções: quem chega durante uma execução
+        // recebe o resultado dela
+        let mut last = self.last.lock().await;
+        if let Some((at, report)) = last.as_ref() {
+            if at.elapsed() < self.cache_ttl {
+                return HealthReport {
+                    cached: true,
+                    ..report.clone()
+                };
+            }
+        }
+
+        let report = self.run().await;
+        *last = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    async fn run(&self) -> HealthReport {
+        let started = Instant::now();
+        let mut tasks = tokio::task::JoinSet::new();
+        for check in &self.checks {
+            let check = check.clone();
+            let timeout = self.check_timeout;
+            tasks.spawn(async move {
+                let started = Instant::now();
+                let outcome = match tokio::time::timeout(timeout, check.check()).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => CheckOutcome::fail(
+                        Value::Null,
+                        format!("timed out after {}ms", timeout.as_millis()),
+                    ),
+                };
+                let report = CheckReport {
+                    status: outcome.status,
+                    critical: check.critical(),
+                    duration_ms: started.elapsed().as_millis() as u64,
+                    details: outcome.details,
+                    diagnostics: outcome.diagnostics,
+                    error: outcome.error,
+                };
+                (check.name().to_string(), report)
+            });
+        }
+
+        let mut checks = BTreeMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((name, report)) => {
+                    checks.insert(name, report);
+                }
+                Err(e) => tracing::error!(error = ?e, "health check panicked"),
+            }
+        }
+
+        let status = checks
+            .values()
+            .map(|check| match (check.status, check.critical) {
+                // Falhas não críticas degradam, mas não tiram a instância do balanceador
+                (HealthStatus::Fail, false) => HealthStatus::Warn,
+                (status, _) => status,
+            })
+            .max()
+            .unwrap_or(HealthStatus::Pass);
+        if status != HealthStatus::Pass {
+            tracing::warn!(status = ?status, "readiness degraded");
+        }
+
+        HealthReport {
+            status,
+            checked_at: Utc::now(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            cached: false,
+            checks,
+        }
+    }
+}
+
+// ============================================================================
+// HEARTBEATS DE TAREFAS EM BACKGROUND
+// ============================================================================
+
+struct JobState {
+    max_interval: Duration,
+    registered_at: Instant,
+    last_beat: Option<Instant>,
+}
+
+/// Último sinal de vida de cada tarefa em background
+#[derive(Default)]
+pub struct Heartbeats {
+    jobs: RwLock<BTreeMap<String, JobState>>,
+}
+
+impl Heartbeats {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Registra uma tarefa que deve sinalizar ao menos a cada `max_interval`
+    pub fn register(self: &Arc<Self>, name: &str, max_interval: Duration) -> Heartbeat {
+        self.jobs.write().insert(
+            name.to_string(),
+            JobState {
+                max_interval,
+                registered_at: Instant::now(),
+                last_beat: None,
+            },
+        );
+        Heartbeat {
+            name: name.to_string(),
+            jobs: self.clone(),
+        }
+    }
+
+    fn beat(&self, name: &str) {
+        if let Some(job) = self.jobs.write().get_mut(name) {
+            job.last_beat = Some(Instant::now());
+        }
+    }
+}
+
+/// Handle usado pela tarefa para sinalizar cada ciclo
+#[derive(Clone)]
+pub struct Heartbeat {
+    name: String,
+    jobs: Arc<Heartbeats>,
+}
+
+impl Heartbeat {
+    pub fn beat(&self) {
+        self.jobs.beat(&self.name);
+    }
+}
+
+/// Tarefas sem sinal há mais do que o intervalo permitido (não crítica)
+pub struct HeartbeatCheck {
+    heartbeats: Arc<Heartbeats>,
+}
+
+impl HeartbeatCheck {
+    pub fn new(heartbeats: Arc<Heartbeats>) -> Self {
+        Self { heartbeats }
+    }
+}
+
+#[async_trait]
+impl HealthCheck for HeartbeatCheck {
+    fn name(&self) -> &str {
+        "background_jobs"
+    }
+
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> CheckOutcome {
+        let jobs = self.heartbeats.jobs.read();
+        let mut details = serde_json::Map::new();
+        let mut stale = Vec::new();
+        for (name, job) in jobs.iter() {
+            // Antes do primeiro sinal, conta desde o registro
+            let age = job.last_beat.unwrap_or(job.registered_at).elapsed();
+            if age > job.max_interval {
+                stale.push(name.clone());
+            }
+            details.insert(
+                name.clone(),
+                serde_json::json!({
+                    "last_beat_secs_ago": job.last_beat.map(|at| at.elapsed().as_secs()),
+                    "max_interval_secs": job.max_interval.as_secs(),
+                    "stale": age > job.max_interval,
+                }),
+            );
+        }
+
+        if stale.is_empty() {
+            CheckOutcome::pass(Value::Object(details))
+        } else {
+            CheckOutcome::fail(
+                Value::Object(details),
+                format!("stale background jobs: {}", stale.join(", ")),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct StaticCheck {
+        name: &'static str,
+        critical: bool,
+        status: HealthStatus,
+        runs: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    impl StaticCheck {
+        fn new(name: &'static str, critical: bool, status: HealthStatus) -> Self {
+            Self {
+                name,
+                critical,
+                status,
+                runs: Arc::new(AtomicUsize::new(0)),
+                delay: Duration::ZERO,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheck for StaticCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn critical(&self) -> bool {
+            self.critical
+        }
+
+        async fn check(&self) -> CheckOutcome {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            CheckOutcome {
+                status: self.status,
+                details: Value::Null,
+                diagnostics: serde_json::json!({ "runs": self.runs.load(Ordering::SeqCst) }),
+                error: None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_critical_failure_only_degrades() {
+        let registry = HealthRegistry::new(Duration::ZERO, Duration::from_secs(1))
+            .with_check(StaticCheck::new("db", true, HealthStatus::Pass))
+            .with_check(StaticCheck::new("vault", false, HealthStatus::Fail));
+        let report = registry.report().await;
+        assert_eq!(report.status, HealthStatus::Warn);
+        assert!(report.is_ready());
+        assert_eq!(report.checks["vault"].status, HealthStatus::Fail);
+
+        let registry = HealthRegistry::new(Duration::ZERO, Duration::from_secs(1))
+            .with_check(StaticCheck::new("db", true, HealthStatus::Fail));
+        assert!(!registry.report().await.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_slow_check_times_out() {
+        let mut slow = StaticCheck::new("slow", true, HealthStatus::Pass);
+        slow.delay = Duration::from_secs(5);
+        let registry =
+            HealthRegistry::new(Duration::ZERO, Duration::from_millis(20)).with_check(slow);
+
+        let report = registry.report().await;
+        assert_eq!(report.checks["slow"].status, HealthStatus::Fail);
+        assert_eq!(
+            report.checks["slow"].error.as_deref(),
+            Some("timed out after 20ms")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_probes_share_cached_report() {
+        let check = StaticCheck::new("db", true, HealthStatus::Pass);
+        let runs = check.runs.clone();
+        let registry = Arc::new(
+            HealthRegistry::new(Duration::from_secs(60), Duration::from_secs(1)).with_check(check),
+        );
+
+        let probes: Vec<_> = (0..10)
+            .map(|_| {
+                let registry = registry.clone();
+                tokio::spawn(async move { registry.report().await })
+            })
+            .collect();
+        let mut cached = 0;
+        for probe in probes {
+            cached += usize::from(probe.await.unwrap().cached);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(cached, 9);
+    }
+
+    #[tokio::test]
+    async fn test_public_report_omits_diagnostics() {
+        let registry = HealthRegistry::new(Duration::ZERO, Duration::from_secs(1))
+            .with_check(StaticCheck::new("db", true, HealthStatus::Pass));
+        let report = registry.report().await;
+        assert_eq!(report.checks["db"].diagnostics["runs"], 1);
+
+        let public = serde_json::to_value(report.public()).unwrap();
+        assert_eq!(public["checks"]["db"]["status"], "pass");
+        assert!(public["checks"]["db"].get("diagnostics").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stale_heartbeats_are_reported() {
+        let heartbeats = Heartbeats::new();
+        let fresh = heartbeats.register("jwks_refresh", Duration::from_secs(60));
+        heartbeats.register("contact_purge", Duration::ZERO);
+        fresh.beat();
+
+        let outcome = HeartbeatCheck::new(heartbeats).check().await;
+        assert_eq!(outcome.status, HealthStatus::Fail);
+        assert_eq!(
+            outcome.error.as_deref(),
+            Some("stale background jobs: contact_purge")
+        );
+        assert_eq!(outcome.details["jwks_refresh"]["stale"], false);
+    }
+}
@@ -0,0 +1,267 @@
+// ============================================================================
+// METRICS - MÉTRICAS DE DOMÍNIO E INFRAESTRUTURA
+// ============================================================================
+// Contadores e gauges além das métricas HTTP do axum-prometheus, registrados no
+// mesmo recorder global e expostos no endpoint protegido `/metrics`
+//
+// Contadores são incrementados no ponto do evento (webhooks, refresh do JWKS).
+// Gauges que dependem de consultas (contatos, candidatos a merge, pool, cache do
+// Vault) são atualizados periodicamente pelo `MetricsCollector`, nunca durante o
+// scrape, para que o Prometheus não gere carga no banco.
+
+use async_trait::async_trait;
+use axum_prometheus::metrics::{counter, describe_counter, describe_gauge, gauge, Unit};
+use std::sync::Arc;
+
+const CONTACTS: &str = "sut_contacts";
+const MERGE_CANDIDATES_PENDING: &str = "sut_merge_candidates_pending";
+const INGESTION_EVENTS: &str = "sut_ingestion_events_total";
+const WEBHOOK_SIGNATURE_FAILURES: &str = "sut_webhook_signature_failures_total";
+const JWKS_REFRESH_FAILURES: &str = "sut_jwks_refresh_failures_total";
+const JWKS_KEYS: &str = "sut_jwks_keys";
+const JWKS_KEY_AGE: &str = "sut_jwks_key_age_seconds";
+const VAULT_CACHE_HIT_RATIO: &str = "sut_vault_cache_hit_ratio";
+const VAULT_CACHE_ENTRIES: &str = "sut_vault_cache_entries";
+const DB_POOL_CONNECTIONS: &str = "sut_db_pool_connections";
+const DB_POOL_MAX_CONNECTIONS: &str = "sut_db_pool_max_connections";
+
+/// Registra as descrições (`# HELP`) das métricas; chamar após instalar o recorder
+pub fn describe() {
+    describe_gauge!(CONTACTS, "Contatos não excluídos por status e tipo");
+    describe_gauge!(
+        MERGE_CANDIDATES_PENDING,
+        "Candidatos a merge ainda sem decisão"
+    );
+    describe_counter!(
+        INGESTION_EVENTS,
+        "Eventos de ingestão recebidos por fonte e resultado (accepted, duplicate, rejected)"
+    );
+    describe_counter!(
+        WEBHOOK_SIGNATURE_FAILURES,
+        "Webhooks rejeitados na autenticação por fonte e motivo"
+    );
+    describe_counter!(JWKS_REFRESH_FAILURES, "Falhas ao atualizar o JWKS remoto");
+    describe_gauge!(JWKS_KEYS, "Chaves de assinatura carregadas por origem");
+    describe_gauge!(
+        JWKS_KEY_AGE,
+        Unit::Seconds,
+        "Tempo desde o último refresh bem-sucedido do JWKS"
+    );
+    describe_gauge!(
+        VAULT_CACHE_HIT_RATIO,
+        "Fração das leituras de secrets atendidas pelo cache do cliente Vault"
+    );
+    describe_gauge!(
+        VAULT_CACHE_ENTRIES,
+        "Entradas do cache do cliente Vault por estado (fresh, expired)"
+    );
+    describe_gauge!(
+        DB_POOL_CONNECTIONS,
+        "Conexões do pool do PostgreSQL por estado (idle, in_use)"
+    );
+    describe_gauge!(
+        DB_POOL_MAX_CONNECTIONS,
+        "Tamanho máximo do pool do PostgreSQL"
+    );
+}
+
+// ===== CONTADORES =====
+
+/// Resultado de um evento de ingestão (webhook)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestionOutcome {
+    /// Evento autenticado e processado
+    Accepted,
+    /// Reenvio de um evento já recebido (mesmo `X-Webhook-Id`)
+    Duplicate,
+    /// Evento recusado (autenticação ou falha no processamento)
+    Rejected,
+}
+
+impl IngestionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionOutcome::Accepted => "accepted",
+            IngestionOutcome::Duplicate => "duplicate",
+            IngestionOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+/// Rótulo `source` dos webhooks: o nome vem do path (`/v1/webhooks/{service}`), que é
+/// público, então nomes desconhecidos caem em `other` para manter as séries limitadas
+fn webhook_source(source: &str) -> &'static str {
+    match source {
+        "keycloak" => "keycloak",
+        "vault" => "vault",
+        _ => "other",
+    }
+}
+
+pub fn record_ingestion(source: &str, outcome: IngestionOutcome) {
+    counter!(
+        INGESTION_EVENTS,
+        "source" => webhook_source(source),
+        "outcome" => outcome.as_str()
+    )
+    .increment(1);
+}
+
+pub fn record_webhook_signature_failure(source: &str, reason: &'static str) {
+    counter!(
+        WEBHOOK_SIGNATURE_FAILURES,
+        "source" => webhook_source(source),
+        "reason" => reason
+    )
+    .increment(1);
+}
+
+pub fn record_jwks_refresh_failure() {
+    counter!(JWKS_REFRESH_FAILURES).increment(1);
+}
+
+// ===== GAUGES =====
+
+pub fn set_contacts(status: &str, contact_type: &str, count: i64) {
+    gauge!(
+        CONTACTS,
+        "status" => status.to_string(),
+        "type" => contact_type.to_string()
+    )
+    .set(count as f64);
+}
+
+pub fn set_pending_merge_candidates(count: i64) {
+    gauge!(MERGE_CANDIDATES_PENDING).set(count as f64);
+}
+
+/// Chaves remotas e locais e idade do JWKS (`None` antes do primeiro refresh)
+pub fn set_jwks(remote_keys: usize, trusted_keys: usize, age_secs: Option<f64>) {
+    gauge!(JWKS_KEYS, "origin" => "remote").set(remote_keys as f64);
+    gauge!(JWKS_KEYS, "origin" => "trusted").set(trusted_keys as f64);
+    if let Some(age) = age_secs {
+        gauge!(JWKS_KEY_AGE).set(age);
+    }
+}
+
+/// Entradas do cache do Vault e taxa de acerto (`None` antes da primeira leitura)
+pub fn set_vault_cache(entries: usize, expired: usize, hit_ratio: Option<f64>) {
+    gauge!(VAULT_CACHE_ENTRIES, "state" => "fresh").set(entries.saturating_sub(expired) as f64);
+    gauge!(VAULT_CACHE_ENTRIES, "state" => "expired").set(expired as f64);
+    if let Some(ratio) = hit_ratio {
+        gauge!(VAULT_CACHE_HIT_RATIO).set(ratio);
+    }
+}
+
+pub fn set_db_pool(size: u32, idle: u32, max: u32) {
+    gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(f64::from(idle));
+    gauge!(DB_POOL_CONNECTIONS, "state" => "in_use").set(f64::from(size.saturating_sub(idle)));
+    gauge!(DB_POOL_MAX_CONNECTIONS).set(f64::from(max));
+}
+
+// ===== COLETA PERIÓDICA =====
+
+/// Fonte de gauges atualizada pelo `MetricsCollector`
+#[async_trait]
+pub trait MetricsSource: Send + Sync {
+    /// Nome da fonte nos logs
+    fn name(&self) -> &str;
+
+    async fn collect(&self) -> anyhow::Result<()>;
+}
+
+/// Executa as fontes registradas; uma fonte com falha mantém os últimos valores
+#[derive(Default)]
+pub struct MetricsCollector {
+    sources: Vec<Arc<dyn MetricsSource>>,
+}
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_source(mut self, source: impl MetricsSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Atualiza todas as fontes, retornando quantas falharam
+    pub async fn collect(&self) -> usize {
+        let mut failures = 0;
+        for source in &self.sources {
+            if let Err(e) = source.collect().await {
+                tracing::warn!(source = source.name(), error = ?e, "metrics collection failed");
+                failures += 1;
+            }
+        }
+        failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_prometheus::metrics::with_local_recorder;
+    use axum_prometheus::metrics_exporter_prometheus::PrometheusBuilder;
+
+    struct Fixed(Option<&'static str>);
+
+    #[async_trait]
+    impl MetricsSource for Fixed {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn collect(&self) -> anyhow::Result<()> {
+            match self.0 {
+                Some(error) => Err(anyhow::anyhow!(error)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_metrics_are_rendered_with_labels() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        with_local_recorder(&recorder, || {
+            describe();
+            record_ingestion("keycloak", IngestionOutcome::Accepted);
+            record_ingestion("keycloak", IngestionOutcome::Accepted);
+            record_ingestion("vault", IngestionOutcome::Duplicate);
+            record_webhook_signature_failure("vault", "invalid_token");
+            record_webhook_signature_failure("made-up-1", "missing_token");
+            record_webhook_signature_failure("made-up-2", "missing_token");
+            set_contacts("ACTIVE", "PERSON", 7);
+            set_db_pool(8, 3, 10);
+            set_vault_cache(4, 1, Some(0.75));
+        });
+
+        let rendered = handle.render();
+        for line in [
+            r#"sut_ingestion_events_total{source="keycloak",outcome="accepted"} 2"#,
+            r#"sut_ingestion_events_total{source="vault",outcome="duplicate"} 1"#,
+            r#"sut_webhook_signature_failures_total{source="vault",reason="invalid_token"} 1"#,
+            r#"sut_webhook_signature_failures_total{source="other",reason="missing_token"} 2"#,
+            r#"sut_contacts{status="ACTIVE",type="PERSON"} 7"#,
+            r#"sut_db_pool_connections{state="in_use"} 5"#,
+            r#"sut_db_pool_max_connections 10"#,
+            r#"sut_vault_cache_entries{state="fresh"} 3"#,
+            r#"sut_vault_cache_hit_ratio 0.75"#,
+        ] {
+            assert!(rendered.contains(line), "missing {line} in:\n{rendered}");
+        }
+        assert!(!rendered.contains("made-up"));
+        assert!(rendered.contains("# HELP sut_contacts Contatos não excluídos por status e tipo"));
+    }
+
+    #[tokio::test]
+    async fn test_collector_counts_failed_sources() {
+        let collector = MetricsCollector::new()
+            .with_source(Fixed(None))
+            .with_source(Fixed(Some("database unavailable")))
+            .with_source(Fixed(None));
+        assert_eq!(collector.collect().await, 1);
+    }
+}
@@ -3,16 +3,25 @@
 // ============================================================================
 // Sistema unificado para gerenciamento de middleware
 // Elimina redundância e centraliza toda a lógica de middleware
+//
+// O `MiddlewareSystem` é um `tower::Layer`: cada requisição percorre todos os
+// middlewares registrados em ordem de prioridade (menor primeiro), pulando os
+// que não se aplicam à rota (`should_execute`). Um middleware pode encerrar a
+// cadeia retornando sem chamar `next.run`.
 
+use crate::presentation::error_mapper::Problem;
 use axum::{
-    extract::Request,
-    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
-    middleware::Next,
-    response::Response,
+    extract::{MatchedPath, Request},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
 };
-use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-use tracing::{info_span, Span};
+use std::task::{Context, Poll};
+use tower::{util::BoxCloneSyncService, Layer, Service, ServiceExt};
+use tracing::{info_span, Instrument, Span};
 
 // ============================================================================
 // TRAIT BASE PARA MIDDLEWARE
@@ -32,29 +41,113 @@ pub trait Middleware: Send + Sync {
     }
 
     /// Se o middleware deve ser executado para esta rota
-    fn should_execute(&self, method: &Method, path: &str) -> bool {
+    fn should_execute(&self, _method: &Method, _path: &str) -> bool {
         true
     }
 }
 
+// ============================================================================
+// FILTRO DE ROTAS
+// ============================================================================
+
+/// Predicado de método/caminho usado por `Middleware::should_execute`
+///
+/// Caminhos casam por segmento: `/v1/webhooks` cobre `/v1/webhooks/keycloak-events`,
+/// mas não `/v1/webhooksx`. Sem métodos informados, todos são aceitos.
+#[derive(Debug, Clone, Default)]
+pub struct RouteFilter {
+    methods: Vec<Method>,
+    skip_paths: Vec<String>,
+}
+
+impl RouteFilter {
+    /// Filtro que aceita todas as rotas
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Restringe aos métodos informados
+    pub fn methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.methods.extend(methods);
+        self
+    }
+
+    /// Exclui os caminhos (e subcaminhos) informados
+    pub fn skip_paths<S: Into<String>>(mut self, paths: impl IntoIterator<Item = S>) -> Self {
+        self.skip_paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        (self.methods.is_empty() || self.methods.contains(method))
+            && !self.skip_paths.iter().any(|p| path_matches(p, path))
+    }
+}
+
+fn path_matches(prefix: &str, path: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || prefix.is_empty(),
+        None => false,
+    }
+}
+
+// ============================================================================
+// CADEIA DE EXECUÇÃO
+// ============================================================================
+
+/// Restante da cadeia: próximos middlewares aplicáveis e, ao final, o handler
+pub struct Next {
+    chain: Arc<[Arc<dyn Middleware>]>,
+    index: usize,
+    inner: BoxCloneSyncService<Request, Response, Infallible>,
+}
+
+impl Next {
+    /// Executa o próximo middleware aplicável (ou o handler, ao fim da cadeia)
+    pub async fn run(mut self, request: Request) -> Response {
+        while let Some(middleware) = self.chain.get(self.index).cloned() {
+            self.index += 1;
+            if middleware.should_execute(request.method(), request.uri().path()) {
+                return match middleware.execute(request, self).await {
+                    Ok(response) => response,
+                    Err(status) => Problem::from_status(status).into_response(),
+                };
+            }
+        }
+
+        match self.inner.oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        }
+    }
+}
+
 // ============================================================================
 // MIDDLEWARE DE LOGGING
 // ============================================================================
 
+/// Abre o span `http.request` (método, rota, request id e, após a autenticação,
+/// o subject), continuando o `traceparent` recebido, e escreve o log de acesso
+/// (ver `presentation::access_log`); a resposta leva `traceparent` e `X-Request-Id`
 pub struct LoggingMiddleware {
-    pub include_body: bool,
-    pub include_headers: bool,
+    filter: RouteFilter,
 }
 
 impl LoggingMiddleware {
-    pub fn new(include_body: bool, include_headers: bool) -> Self {
+    pub fn new() -> Self {
         Self {
-            include_body,
-            include_headers,
+            filter: RouteFilter::all(),
         }
     }
 }
 
+impl Default for LoggingMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl Middleware for LoggingMiddleware {
     fn name(&self) -> &str {
@@ -65,31 +158,36 @@ impl Middleware for LoggingMiddleware {
         10
     }
 
-    async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
-        let method = request.method().clone();
-        let path = request.uri().path().to_string();
-        let span = info_span!("http.request", "http.method"=%method, "http.route"=%path);
-        let _enter = span.enter();
-
-        // Log da requisição
-        tracing::info!(
-            method = %method,
-            path = %path,
-            "Processing request"
-        );
-
-        // Executa o próximo middleware/handler
-        let response = next.run(request).await;
+    fn should_execute(&self, method: &Method, path: &str) -> bool {
+        self.filter.matches(method, path)
+    }
 
-        // Log da resposta
-        tracing::info!(
-            method = %method,
-            path = %path,
-            status = %response.status(),
-            "Request completed"
+    async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
+        // Template da rota (ex.: /v1/contacts/{id}); o path bruto pode conter dados pessoais
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or("unmatched", |matched| matched.as_str())
+            .to_string();
+        let span = info_span!(
+            "http.request",
+            "http.method" = %request.method(),
+            "http.route" = %route,
+            "request_id" = tracing::field::Empty,
+            "enduser.id" = tracing::field::Empty,
+            "otel.kind" = "server"
         );
-
-        Ok(response)
+        // Continua o trace do chamador (`traceparent`), quando houver
+        crate::shared::telemetry::set_remote_parent(&span, request.headers());
+
+        async move {
+            let mut response =
+                crate::presentation::access_log::record(request, |request| next.run(request)).await;
+            crate::shared::telemetry::inject(&tracing::Span::current(), response.headers_mut());
+            Ok(response)
+        }
+        .instrument(span)
+        .await
     }
 }
 
@@ -97,18 +195,25 @@ impl Middleware for LoggingMiddleware {
 // MIDDLEWARE DE AUTENTICAÇÃO
 // ============================================================================
 
+/// Valida o bearer token ou a API key (ver `presentation::auth::authenticate`)
+/// e insere o `Principal` na requisição
 pub struct AuthMiddleware {
     pub required_roles: Vec<String>,
-    pub skip_paths: Vec<String>,
+    filter: RouteFilter,
 }
 
 impl AuthMiddleware {
     pub fn new(required_roles: Vec<String>, skip_paths: Vec<String>) -> Self {
         Self {
             required_roles,
-            skip_paths,
+            filter: RouteFilter::all().skip_paths(skip_paths),
         }
     }
+
+    pub fn with_filter(mut self, filter: RouteFilter) -> Self {
+        self.filter = filter;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -121,34 +226,32 @@ impl Middleware for AuthMiddleware {
         20
     }
 
-    fn should_execute(&self, _method: &Method, path: &str) -> bool {
-        !self
-            .skip_paths
-            .iter()
-            .any(|skip_path| path.starts_with(skip_path))
+    fn should_execute(&self, method: &Method, path: &str) -> bool {
+        self.filter.matches(method, path)
     }
 
-    async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
-        // Verificar token JWT
-        let auth_header = request.headers().get("Authorization");
-
-        match auth_header {
-            Some(header) => {
-                if let Ok(token) = header.to_str() {
-                    if token.starts_with("Bearer ") {
-                        // Validar token (implementação simplificada)
-                        tracing::debug!("Token found, proceeding");
-                        return Ok(next.run(request).await);
-                    }
-                }
-            }
-            None => {
-                tracing::warn!("No authorization header found");
-                return Err(StatusCode::UNAUTHORIZED);
-            }
+    async fn execute(&self, mut request: Request, next: Next) -> Result<Response, StatusCode> {
+        crate::presentation::auth::authenticate(&mut request).await?;
+
+        let principal = request
+            .extensions()
+            .get::<crate::domain::value_objects::Principal>()
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        if !self.required_roles.is_empty() && !principal.has_any_permission(&self.required_roles) {
+            tracing::warn!(
+                user_id = %principal.subject,
+                required_roles = ?self.required_roles,
+                "Access denied: insufficient permissions"
+            );
+            return Err(StatusCode::FORBIDDEN);
         }
+        Span::current().record("enduser.id", tracing::field::display(&principal.subject));
+        let principal = principal.clone();
 
-        Err(StatusCode::UNAUTHORIZED)
+        // Subject disponível para o log de acesso
+        let mut response = next.run(request).await;
+        response.extensions_mut().insert(principal);
+        Ok(response)
     }
 }
 
@@ -156,18 +259,31 @@ impl Middleware for AuthMiddleware {
 // MIDDLEWARE DE RATE LIMITING
 // ============================================================================
 
+/// Token bucket por classe de rota e identidade (ver `presentation::rate_limit`)
+///
+/// Limites, proxies confiáveis e armazenamento vêm de `presentation::rate_limit::init`.
+/// Executa depois do `AuthMiddleware` para usar o subject autenticado.
 pub struct RateLimitMiddleware {
-    pub requests_per_minute: u32,
-    pub requests: Arc<tokio::sync::Mutex<HashMap<String, (u32, std::time::Instant)>>>,
+    filter: RouteFilter,
 }
 
 impl RateLimitMiddleware {
-    pub fn new(requests_per_minute: u32) -> Self {
+    pub fn new() -> Self {
         Self {
-            requests_per_minute,
-            requests: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            filter: RouteFilter::all(),
         }
     }
+
+    pub fn with_filter(mut self, filter: RouteFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+impl Default for RateLimitMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait::async_trait]
@@ -180,30 +296,12 @@ impl Middleware for RateLimitMiddleware {
         30
     }
 
-    async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
-        let client_ip = request
-            .headers()
-            .get("x-forwarded-for")
-            .or_else(|| request.headers().get("x-real-ip"))
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("unknown");
-
-        let mut requests = self.requests.lock().await;
-        let now = std::time::Instant::now();
-
-        // Limpar entradas antigas
-        requests.retain(|_, (_, time)| now.duration_since(*time).as_secs() < 60);
-
-        let (count, _) = requests.entry(client_ip.to_string()).or_insert((0, now));
-
-        if *count >= self.requests_per_minute {
-            tracing::warn!(client_ip = %client_ip, "Rate limit exceeded");
-            return Err(StatusCode::TOO_MANY_REQUESTS);
-        }
-
-        *count += 1;
+    fn should_execute(&self, method: &Method, path: &str) -> bool {
+        self.filter.matches(method, path)
+    }
 
-        Ok(next.run(request).await)
+    async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
+        Ok(crate::presentation::rate_limit::limit(request, |request| next.run(request)).await)
     }
 }
 
@@ -211,13 +309,22 @@ impl Middleware for RateLimitMiddleware {
 // MIDDLEWARE DE CORS
 // ============================================================================
 
+/// CORS: responde aos preflights e adiciona os headers `Access-Control-*` quando
+/// a origem é permitida (`*` aceita qualquer origem)
+///
+/// Requisições de origens não permitidas seguem sem os headers (o navegador as
+/// bloqueia); preflights dessas origens recebem 403.
 pub struct CorsMiddleware {
     pub allowed_origins: Vec<String>,
     pub allowed_methods: Vec<Method>,
     pub allowed_headers: Vec<HeaderName>,
+    filter: RouteFilter,
 }
 
 impl CorsMiddleware {
+    /// Validade do preflight em cache no navegador (segundos)
+    const MAX_AGE: &'static str = "3600";
+
     pub fn new(
         allowed_origins: Vec<String>,
         allowed_methods: Vec<Method>,
@@ -227,8 +334,53 @@ impl CorsMiddleware {
             allowed_origins,
             allowed_methods,
             allowed_headers,
+            filter: RouteFilter::all(),
         }
     }
+
+    fn is_allowed_origin(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == origin)
+    }
+
+    fn join<T: AsRef<str>>(values: &[T]) -> HeaderValue {
+        let joined = values
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ");
+        HeaderValue::try_from(joined).unwrap_or_else(|_| HeaderValue::from_static(""))
+    }
+
+    fn preflight(&self, request: &Request, origin: &HeaderValue) -> Response {
+        let requested_method = request
+            .headers()
+            .get(header::ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<Method>().ok());
+        if !requested_method.is_some_and(|method| self.allowed_methods.contains(&method)) {
+            return Problem::from_status(StatusCode::FORBIDDEN)
+                .with_detail("CORS preflight method not allowed")
+                .into_response();
+        }
+
+        let methods: Vec<&str> = self.allowed_methods.iter().map(Method::as_str).collect();
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, Self::join(&methods));
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            Self::join(&self.allowed_headers),
+        );
+        headers.insert(
+            header::ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from_static(Self::MAX_AGE),
+        );
+        headers.append(header::VARY, HeaderValue::from_static("origin"));
+        response
+    }
 }
 
 #[async_trait::async_trait]
@@ -241,45 +393,39 @@ impl Middleware for CorsMiddleware {
         5
     }
 
+    fn should_execute(&self, method: &Method, path: &str) -> bool {
+        self.filter.matches(method, path)
+    }
+
     async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
-        let origin = request.headers().get("origin").cloned();
-        let method = request.method().clone();
-
-        // Verificar origem
-        if let Some(origin_header) = &origin {
-            if let Ok(origin_str) = origin_header.to_str() {
-                if !self.allowed_origins.contains(&origin_str.to_string())
-                    && !self.allowed_origins.contains(&"*".to_string())
-                {
-                    return Err(StatusCode::FORBIDDEN);
-                }
+        // Sem Origin não é uma requisição CORS
+        let Some(origin) = request.headers().get(header::ORIGIN).cloned() else {
+            return Ok(next.run(request).await);
+        };
+        let allowed = origin
+            .to_str()
+            .is_ok_and(|origin| self.is_allowed_origin(origin));
+
+        let is_preflight = request.method() == Method::OPTIONS
+            && request
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+        if is_preflight {
+            if !allowed {
+                return Err(StatusCode::FORBIDDEN);
             }
-        }
-
-        // Verificar método
-        if !self.allowed_methods.contains(&method) {
-            return Err(StatusCode::METHOD_NOT_ALLOWED);
+            return Ok(self.preflight(&request, &origin));
         }
 
         let mut response = next.run(request).await;
 
         // Adicionar headers CORS
         let headers = response.headers_mut();
-
-        if let Some(origin_header) = origin {
-            headers.insert("Access-Control-Allow-Origin", origin_header);
+        headers.append(header::VARY, HeaderValue::from_static("origin"));
+        if allowed {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
         }
 
-        headers.insert(
-            "Access-Control-Allow-Methods",
-            HeaderValue::from_static("GET, POST, PUT, PATCH, DELETE, OPTIONS"),
-        );
-
-        headers.insert(
-            "Access-Control-Allow-Headers",
-            HeaderValue::from_static("Content-Type, Authorization"),
-        );
-
         Ok(response)
     }
 }
@@ -288,42 +434,76 @@ impl Middleware for CorsMiddleware {
 // SISTEMA DE MIDDLEWARE
 // ============================================================================
 
+/// Cadeia ordenada de middlewares, aplicada como `tower::Layer`
+#[derive(Clone, Default)]
 pub struct MiddlewareSystem {
-    middlewares: Vec<Box<dyn Middleware>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
 }
 
 impl MiddlewareSystem {
     pub fn new() -> Self {
-        Self {
-            middlewares: Vec::new(),
-        }
+        Self::default()
     }
 
     pub fn add_middleware(&mut self, middleware: Box<dyn Middleware>) {
-        self.middlewares.push(middleware);
-        // Ordenar por prioridade
+        self.middlewares.push(Arc::from(middleware));
+        // Ordenar por prioridade (estável: empates mantêm a ordem de registro)
         self.middlewares.sort_by_key(|m| m.priority());
     }
 
-    pub async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
-        // Por simplicidade, executar apenas o primeiro middleware que se aplica
-        // Em uma implementação mais robusta, seria necessário implementar uma cadeia completa
-        for middleware in &self.middlewares {
-            let method = request.method().clone();
-            let path = request.uri().path().to_string();
+    /// Variante encadeável de `add_middleware`
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.add_middleware(Box::new(middleware));
+        self
+    }
 
-            if middleware.should_execute(&method, &path) {
-                return middleware.execute(request, next).await;
-            }
-        }
+    /// Nomes dos middlewares na ordem de execução
+    pub fn names(&self) -> Vec<&str> {
+        self.middlewares.iter().map(|m| m.name()).collect()
+    }
+}
 
-        Ok(next.run(request).await)
+impl<S> Layer<S> for MiddlewareSystem
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + Sync + 'static,
+    S::Future: Send + 'static,
+{
+    type Service = MiddlewareService;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MiddlewareService {
+            chain: self.middlewares.clone().into(),
+            inner: BoxCloneSyncService::new(inner),
+        }
     }
 }
 
-impl Default for MiddlewareSystem {
-    fn default() -> Self {
-        Self::new()
+/// Serviço produzido pelo `MiddlewareSystem`
+#[derive(Clone)]
+pub struct MiddlewareService {
+    chain: Arc<[Arc<dyn Middleware>]>,
+    inner: BoxCloneSyncService<Request, Response, Infallible>,
+}
+
+impl Service<Request> for MiddlewareService {
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Usa o serviço que ficou pronto e deixa um clone no lugar
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        let next = Next {
+            chain: self.chain.clone(),
+            index: 0,
+            inner,
+        };
+        Box::pin(async move { Ok(next.run(request).await) })
     }
 }
 
@@ -332,6 +512,9 @@ impl Default for MiddlewareSystem {
 // ============================================================================
 
 /// Macro para criar middleware simples
+///
+/// `$execute` recebe `(Request, Next)` e retorna uma future de
+/// `Result<Response, StatusCode>`.
 #[macro_export]
 macro_rules! create_middleware {
     ($name:ident, $priority:expr, $execute:expr) => {
@@ -349,10 +532,10 @@ macro_rules! create_middleware {
 
             async fn execute(
                 &self,
-                request: &mut axum::extract::Request,
-                next: axum::middleware::Next,
+                request: axum::extract::Request,
+                next: $crate::shared::middleware_system::Next,
             ) -> Result<axum::response::Response, axum::http::StatusCode> {
-                $execute(request, next).await
+                ($execute)(request, next).await
             }
         }
     };
@@ -361,17 +544,80 @@ macro_rules! create_middleware {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::{body::Body, http::Request, response::Response};
+    use axum::{body::Body, routing::get, Router};
+    use parking_lot::Mutex;
+
+    /// Registra a ordem de execução
+    struct Recorder {
+        name: &'static str,
+        priority: i32,
+        filter: RouteFilter,
+        log: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn should_execute(&self, method: &Method, path: &str) -> bool {
+            self.filter.matches(method, path)
+        }
+
+        async fn execute(&self, request: Request, next: Next) -> Result<Response, StatusCode> {
+            self.log.lock().push(self.name);
+            Ok(next.run(request).await)
+        }
+    }
+
+    fn recorder(
+        name: &'static str,
+        priority: i32,
+        filter: RouteFilter,
+        log: &Arc<Mutex<Vec<&'static str>>>,
+    ) -> Recorder {
+        Recorder {
+            name,
+            priority,
+            filter,
+            log: log.clone(),
+        }
+    }
+
+    fn app(system: MiddlewareSystem) -> Router {
+        Router::new()
+            .route("/v1/items", get(|| async { "ok" }).post(|| async { "ok" }))
+            .route("/public", get(|| async { "ok" }))
+            .layer(system)
+    }
+
+    fn request(method: Method, uri: &str) -> Request {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
 
     #[tokio::test]
     async fn test_middleware_system() {
         let mut system = MiddlewareSystem::new();
 
-        let logging = LoggingMiddleware::new(false, false);
+        let logging = LoggingMiddleware::new();
         system.add_middleware(Box::new(logging));
 
         // Teste básico - sistema deve compilar e executar
         assert_eq!(system.middlewares.len(), 1);
+        let response = app(system)
+            .oneshot(request(Method::GET, "/public"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
     }
 
     #[test]
@@ -379,7 +625,7 @@ mod tests {
         let mut system = MiddlewareSystem::new();
 
         let auth = AuthMiddleware::new(vec![], vec![]);
-        let logging = LoggingMiddleware::new(false, false);
+        let logging = LoggingMiddleware::new();
 
         system.add_middleware(Box::new(auth));
         system.add_middleware(Box::new(logging));
@@ -388,4 +634,155 @@ mod tests {
         assert_eq!(system.middlewares[0].priority(), 10);
         assert_eq!(system.middlewares[1].priority(), 20);
     }
+
+    #[tokio::test]
+    async fn test_every_applicable_middleware_runs_in_priority_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let system = MiddlewareSystem::new()
+            .with(recorder("third", 30, RouteFilter::all(), &log))
+            .with(recorder("first", 10, RouteFilter::all(), &log))
+            .with(recorder(
+                "writes_only",
+                15,
+                RouteFilter::all().methods([Method::POST]),
+                &log,
+            ))
+            .with(recorder(
+                "not_public",
+                20,
+                RouteFilter::all().skip_paths(["/public"]),
+                &log,
+            ));
+        assert_eq!(
+            system.names(),
+            vec!["first", "writes_only", "not_public", "third"]
+        );
+        let app = app(system);
+
+        app.clone()
+            .oneshot(request(Method::GET, "/v1/items"))
+            .await
+            .unwrap();
+        assert_eq!(*log.lock(), vec!["first", "not_public", "third"]);
+
+        log.lock().clear();
+        app.oneshot(request(Method::POST, "/public")).await.unwrap();
+        assert_eq!(*log.lock(), vec!["first", "writes_only", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_auth_short_circuits_protected_routes() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let system = MiddlewareSystem::new()
+            .with(AuthMiddleware::new(vec![], vec!["/public".to_string()]))
+            .with(recorder("after_auth", 50, RouteFilter::all(), &log));
+        let app = app(system);
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::GET, "/v1/items"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(log.lock().is_empty());
+
+        let response = app.oneshot(request(Method::GET, "/public")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*log.lock(), vec!["after_auth"]);
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_and_origin_headers() {
+        let system = MiddlewareSystem::new()
+            .with(CorsMiddleware::new(
+                vec!["https://app.example.com".to_string()],
+                vec![Method::GET, Method::POST],
+                vec![header::AUTHORIZATION, header::CONTENT_TYPE],
+            ))
+            .with(AuthMiddleware::new(vec![], vec![]));
+        let app = app(system);
+        let preflight = |origin: &str, method: &str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/v1/items")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Preflight respondido antes da autenticação
+        let response = app
+            .clone()
+            .oneshot(preflight("https://app.example.com", "POST"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_METHODS],
+            "GET, POST"
+        );
+
+        for (origin, method) in [
+            ("https://evil.example.com", "GET"),
+            ("https://app.example.com", "DELETE"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(preflight(origin, method))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/items")
+                    .header(header::ORIGIN, "https://app.example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+    }
+
+    crate::create_middleware!(TaggingMiddleware, 1, |request, next: Next| async move {
+        let mut response = next.run(request).await;
+        response
+            .headers_mut()
+            .insert("x-tagged", HeaderValue::from_static("1"));
+        Ok(response)
+    });
+
+    #[tokio::test]
+    async fn test_create_middleware_macro() {
+        let system = MiddlewareSystem::new().with(TaggingMiddleware);
+        assert_eq!(system.names(), vec!["TaggingMiddleware"]);
+
+        let response = app(system)
+            .oneshot(request(Method::GET, "/public"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-tagged"], "1");
+    }
+
+    #[test]
+    fn test_route_filter_matches_whole_segments() {
+        let filter = RouteFilter::all().skip_paths(["/v1/webhooks", "/docs/"]);
+        assert!(!filter.matches(&Method::POST, "/v1/webhooks"));
+        assert!(!filter.matches(&Method::POST, "/v1/webhooks/keycloak-events"));
+        assert!(!filter.matches(&Method::GET, "/docs/openapi.yaml"));
+        assert!(filter.matches(&Method::POST, "/v1/webhooksx"));
+        assert!(filter.matches(&Method::GET, "/v1/contacts"));
+    }
 }
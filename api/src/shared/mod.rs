@@ -6,123 +6,17 @@
 
 // ===== CORE UTILITIES =====
 pub mod base_traits; // Traits base para eliminar redundância
+pub mod cache; // Cache em memória com TTL
 pub mod config; // Sistema de configuração centralizado
+pub mod config_file; // Leitura do arquivo de configuração (JSON, TOML, YAML)
+pub mod health; // Verificações de saúde plugáveis e heartbeats de tarefas
+pub mod metrics; // Métricas Prometheus de domínio e infraestrutura
 pub mod middleware_system; // Sistema de middleware centralizado
+pub mod secrets; // Segredos substituíveis em tempo de execução (rotação via Vault)
+pub mod telemetry; // Tracing distribuído (OpenTelemetry, W3C Trace Context)
 
 // ===== UTILITY FUNCTIONS =====
 
-/// Verifica se o usuário tem o escopo/permissão necessário
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-/// * `scope` - Escopo/permissão a ser verificado
-///
-/// # Retorna
-/// `true` se o usuário tem o escopo, `false` caso contrário
-pub fn has_scope(claims: &serde_json::Value, scope: &str) -> bool {
-    // Verificar scope direto
-    if let Some(s) = claims.get("scope").and_then(|x| x.as_str()) {
-        if s.split_whitespace().any(|t| t == scope) {
-            return true;
-        }
-    }
-
-    // Verificar roles do realm
-    if let Some(roles) = claims
-        .get("realm_access")
-        .and_then(|r| r.get("roles"))
-        .and_then(|r| r.as_array())
-    {
-        if roles.iter().any(|r| r.as_str() == Some(scope)) {
-            return true;
-        }
-    }
-
-    false
-}
-
-/// Extrai o ID do usuário dos claims JWT
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-///
-/// # Retorna
-/// ID do usuário se encontrado, `None` caso contrário
-pub fn extract_user_id(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("sub")
-        .and_then(|s| s.as_str())
-        .map(|s| s.to_string())
-}
-
-/// Extrai o nome do usuário dos claims JWT
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-///
-/// # Retorna
-/// Nome do usuário se encontrado, `None` caso contrário
-pub fn extract_username(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("preferred_username")
-        .and_then(|s| s.as_str())
-        .map(|s| s.to_string())
-}
-
-/// Extrai o email do usuário dos claims JWT
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-///
-/// # Retorna
-/// Email do usuário se encontrado, `None` caso contrário
-pub fn extract_email(claims: &serde_json::Value) -> Option<String> {
-    claims
-        .get("email")
-        .and_then(|s| s.as_str())
-        .map(|s| s.to_string())
-}
-
-/// Extrai as roles do usuário dos claims JWT
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-///
-/// # Retorna
-/// Lista de roles do usuário
-pub fn extract_roles(claims: &serde_json::Value) -> Vec<String> {
-    claims
-        .get("realm_access")
-        .and_then(|r| r.get("roles"))
-        .and_then(|r| r.as_array())
-        .map(|roles| {
-            roles
-                .iter()
-                .filter_map(|r| r.as_str())
-                .map(|s| s.to_string())
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Valida se um token JWT não expirou
-///
-/// # Argumentos
-/// * `claims` - Claims JWT do usuário
-///
-/// # Retorna
-/// `true` se o token não expirou, `false` caso contrário
-pub fn is_token_valid(claims: &serde_json::Value) -> bool {
-    if let Some(exp) = claims.get("exp").and_then(|e| e.as_i64()) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-        return exp > now;
-    }
-    false
-}
-
 /// Gera um ID único para rastreamento de requisições
 ///
 /// # Retorna
@@ -178,74 +72,6 @@ pub fn format_bytes(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
-
-    #[test]
-    fn test_has_scope() {
-        let claims = json!({
-            "scope": "read write",
-            "realm_access": {
-                "roles": ["user", "admin"]
-            }
-        });
-
-        assert!(has_scope(&claims, "read"));
-        assert!(has_scope(&claims, "write"));
-        assert!(has_scope(&claims, "user"));
-        assert!(has_scope(&claims, "admin"));
-        assert!(!has_scope(&claims, "delete"));
-    }
-
-    #[test]
-    fn test_extract_user_id() {
-        let claims = json!({
-            "sub": "user123",
-            "preferred_username": "john.doe"
-        });
-
-        assert_eq!(extract_user_id(&claims), Some("user123".to_string()));
-    }
-
-    #[test]
-    fn test_extract_username() {
-        let claims = json!({
-            "sub": "user123",
-            "preferred_username": "john.doe"
-        });
-
-        assert_eq!(extract_username(&claims), Some("john.doe".to_string()));
-    }
-
-    #[test]
-    fn test_extract_roles() {
-        let claims = json!({
-            "realm_access": {
-                "roles": ["user", "admin"]
-            }
-        });
-
-        let roles = extract_roles(&claims);
-        assert_eq!(roles, vec!["user", "admin"]);
-    }
-
-    #[test]
-    fn test_is_token_valid() {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
-        let valid_claims = json!({
-            "exp": now + 3600 // 1 hora no futuro
-        });
-
-        let expired_claims = json!({
-            "exp": now - 3600 // 1 hora no passado
-        });
-
-        assert!(is_token_valid(&valid_claims));
-        assert!(!is_token_valid(&expired_claims));
-    }
 
     #[test]
     fn test_format_duration() {
@@ -0,0 +1,355 @@
+// ============================================================================
+// RUNTIME SECRETS - SEGREDOS SUBSTITUÍVEIS EM TEMPO DE EXECUÇÃO
+// ============================================================================
+// Segredo compartilhado dos webhooks, token de métricas e chave de criptografia,
+// recarregados do Vault quando o webhook de rotação é recebido
+//
+// Após uma rotação o valor anterior continua aceito durante o período de carência,
+// para que clientes (Prometheus, emissores de webhooks) troquem o segredo sem falhas
+// e dados cifrados com a chave anterior ainda possam ser lidos
+
+use crate::infra::vault::VaultClient;
+use parking_lot::RwLock;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Segredos gerenciados em tempo de execução
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecretKind {
+    WebhookSharedSecret,
+    MetricsToken,
+    EncryptionKey,
+}
+
+impl SecretKind {
+    pub const ALL: [SecretKind; 3] = [
+        SecretKind::WebhookSharedSecret,
+        SecretKind::MetricsToken,
+        SecretKind::EncryptionKey,
+    ];
+
+    /// Chave no secret KV do Vault
+    pub fn vault_key(self) -> &'static str {
+        match self {
+            SecretKind::WebhookSharedSecret => "webhook_shared_secret",
+            SecretKind::MetricsToken => "metrics_token",
+            SecretKind::EncryptionKey => "encryption_key",
+        }
+    }
+}
+
+/// Valor atual e, durante a carência, o valor anterior
+#[derive(Debug, Default)]
+struct RotatingSecret {
+    current: Option<String>,
+    previous: Option<(String, Instant)>,
+}
+
+impl RotatingSecret {
+    /// Valor anterior, enquanto a carência não terminou
+    fn previous_at(&self, now: Instant) -> Option<&str> {
+        self.previous
+            .as_ref()
+            .filter(|(_, until)| now < *until)
+            .map(|(value, _)| value.as_str())
+    }
+}
+
+/// Conjunto de segredos compartilhado pelo `AppState`
+pub struct RuntimeSecrets {
+    secrets: RwLock<HashMap<SecretKind, RotatingSecret>>,
+    grace: Duration,
+}
+
+impl RuntimeSecrets {
+    /// Cria com os valores resolvidos na inicialização (ver `AppConfig::resolve_secrets`)
+    pub fn new(
+        webhook_shared_secret: Option<String>,
+        metrics_token: Option<String>,
+        grace: Duration,
+    ) -> Self {
+        let mut secrets: HashMap<SecretKind, RotatingSecret> = SecretKind::ALL
+            .into_iter()
+            .map(|kind| (kind, RotatingSecret::default()))
+            .collect();
+        secrets
+            .entry(SecretKind::WebhookSharedSecret)
+            .or_default()
+            .current = webhook_shared_secret;
+        secrets.entry(SecretKind::MetricsToken).or_default().current = metrics_token;
+        Self {
+            secrets: RwLock::new(secrets),
+            grace,
+        }
+    }
+
+    /// Período em que o valor anterior continua aceito
+    pub fn grace(&self) -> Duration {
+        self.grace
+    }
+
+    /// Há um valor atual configurado
+    pub fn is_configured(&self, kind: SecretKind) -> bool {
+        self.secrets
+            .read()
+            .get(&kind)
+            .is_some_and(|secret| secret.current.is_some())
+    }
+
+    /// Valor atual
+    pub fn current(&self, kind: SecretKind) -> Option<String> {
+        self.secrets
+            .read()
+            .get(&kind)
+            .and_then(|secret| secret.current.clone())
+    }
+
+    /// Valores aceitos: o atual e, durante a carência, o anterior
+    /// (ex.: decifrar dados gravados com a chave anterior)
+    pub fn accepted(&self, kind: SecretKind) -> Vec<String> {
+        let now = Instant::now();
+        let secrets = self.secrets.read();
+        let Some(secret) = secrets.get(&kind) else {
+            return Vec::new();
+        };
+        secret
+            .current
+            .iter()
+            .map(String::as_str)
+            .chain(secret.previous_at(now))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Cifra com a chave de criptografia atual (AES-256-GCM)
+    ///
+    /// Retorna `None` quando não há chave configurada.
+    pub fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(key) = self.current(SecretKind::EncryptionKey) else {
+            return Ok(None);
+        };
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow::anyhow!("failed to generate nonce"))?;
+        let mut sealed = plaintext.to_vec();
+        cipher(&key)
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow::anyhow!("encryption failed"))?;
+        Ok(Some([ENCRYPTED_PREFIX, &nonce, &sealed].concat()))
+    }
+
+    /// Decifra dados de `encrypt` com a chave atual ou, durante a carência, com a anterior
+    pub fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let data = data.strip_prefix(ENCRYPTED_PREFIX)?;
+        if data.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, sealed) = data.split_at(NONCE_LEN);
+        self.accepted(SecretKind::EncryptionKey)
+            .iter()
+            .find_map(|key| {
+                let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+                let mut buffer = sealed.to_vec();
+                let plaintext = cipher(key)
+                    .open_in_place(nonce, Aad::empty(), &mut buffer)
+                    .ok()?;
+                Some(plaintext.to_vec())
+            })
+    }
+
+    /// Compara em tempo constante com o valor atual e, durante a carência, com o anterior
+    pub fn matches(&self, kind: SecretKind, provided: &[u8]) -> bool {
+        let now = Instant::now();
+        let secrets = self.secrets.read();
+        let Some(secret) = secrets.get(&kind) else {
+            return false;
+        };
+        let current = secret
+            .current
+            .as_deref()
+            .is_some_and(|expected| constant_time_eq(provided, expected.as_bytes()));
+        let previous = secret
+            .previous_at(now)
+            .is_some_and(|expected| constant_time_eq(provided, expected.as_bytes()));
+        current | previous
+    }
+
+    /// Substitui o valor atual; o anterior permanece aceito durante a carência
+    ///
+    /// Retorna `false` quando o valor não mudou.
+    pub fn rotate(&self, kind: SecretKind, value: String) -> bool {
+        let mut secrets = self.secrets.write();
+        let secret = secrets.entry(kind).or_default();
+        if secret.current.as_deref() == Some(value.as_str()) {
+            return false;
+        }
+        secret.previous = secret
+            .current
+            .replace(value)
+            .map(|old| (old, Instant::now() + self.grace));
+        true
+    }
+
+    /// Relê os segredos do Vault (`path`) e rotaciona os que mudaram
+    ///
+    /// Chaves ausentes no Vault mantêm o valor atual. O cache do cliente deve ser
+    /// limpo antes, para que os valores novos sejam buscados.
+    pub async fn refresh(
+        &self,
+        vault: &VaultClient,
+        path: &str,
+    ) -> anyhow::Result<Vec<SecretKind>> {
+        let values = vault.get_secret(path).await?;
+        Ok(self.apply(&values))
+    }
+
+    fn apply(&self, values: &HashMap<String, Value>) -> Vec<SecretKind> {
+        SecretKind::ALL
+            .into_iter()
+            .filter(|kind| {
+                values
+                    .get(kind.vault_key())
+                    .and_then(Value::as_str)
+                    .filter(|value| !value.trim().is_empty())
+                    .is_some_and(|value| self.rotate(*kind, value.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Prefixo dos dados cifrados por `RuntimeSecrets::encrypt` (seguido do nonce)
+const ENCRYPTED_PREFIX: &[u8] = b"sut:aes-256-gcm:";
+
+/// Dados cifrados por `RuntimeSecrets::encrypt`
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Chave AES-256: o valor no Vault tem formato livre, e o SHA-256 deriva os 32 bytes
+fn cipher(key: &str) -> LessSafeKey {
+    let key = UnboundKey::new(&AES_256_GCM, &Sha256::digest(key.as_bytes()))
+        .expect("SHA-256 digest is a valid AES-256 key");
+    LessSafeKey::new(key)
+}
+
+/// Comparação em tempo constante (não revela o prefixo correto pelo tempo de resposta)
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_previous_secret_is_accepted_during_grace() {
+        let secrets = RuntimeSecrets::new(Some("old".to_string()), None, Duration::from_secs(300));
+        assert!(secrets.rotate(SecretKind::WebhookSharedSecret, "new".to_string()));
+        assert!(!secrets.rotate(SecretKind::WebhookSharedSecret, "new".to_string()));
+
+        assert!(secrets.matches(SecretKind::WebhookSharedSecret, b"new"));
+        assert!(secrets.matches(SecretKind::WebhookSharedSecret, b"old"));
+        assert!(!secrets.matches(SecretKind::WebhookSharedSecret, b"other"));
+        assert!(!secrets.is_configured(SecretKind::MetricsToken));
+        assert!(!secrets.matches(SecretKind::MetricsToken, b""));
+    }
+
+    #[test]
+    fn test_previous_secret_is_rejected_after_grace() {
+        let secrets = RuntimeSecrets::new(Some("old".to_string()), None, Duration::ZERO);
+        secrets.rotate(SecretKind::WebhookSharedSecret, "new".to_string());
+
+        assert!(secrets.matches(SecretKind::WebhookSharedSecret, b"new"));
+        assert!(!secrets.matches(SecretKind::WebhookSharedSecret, b"old"));
+    }
+
+    #[test]
+    fn test_data_encrypted_with_previous_key_is_readable_during_grace() {
+        let secrets = RuntimeSecrets::new(None, None, Duration::from_secs(300));
+        assert!(secrets.encrypt(b"plain").unwrap().is_none());
+
+        secrets.rotate(SecretKind::EncryptionKey, "key-1".to_string());
+        let sealed = secrets.encrypt(b"{\"document\":\"123\"}").unwrap().unwrap();
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.windows(8).any(|w| w == b"document"));
+
+        secrets.rotate(SecretKind::EncryptionKey, "key-2".to_string());
+        assert_eq!(
+            secrets.accepted(SecretKind::EncryptionKey),
+            vec!["key-2", "key-1"]
+        );
+        assert_eq!(
+            secrets.decrypt(&sealed).as_deref(),
+            Some(&b"{\"document\":\"123\"}"[..])
+        );
+
+        let expired = RuntimeSecrets::new(None, None, Duration::ZERO);
+        expired.rotate(SecretKind::EncryptionKey, "key-1".to_string());
+        let sealed = expired.encrypt(b"data").unwrap().unwrap();
+        expired.rotate(SecretKind::EncryptionKey, "key-2".to_string());
+        assert!(expired.decrypt(&sealed).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_changed_secrets_from_vault() {
+        let mut server = mockito::Server::new_async().await;
+        let _m = server
+            .mock("GET", "/v1/kv/data/sut")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data": {"data": {"webhook_shared_secret": "hook-2", "metrics_token": "metrics-1", "encryption_key": "key-2"}}}"#)
+            .create_async()
+            .await;
+        let vault = VaultClient::new(crate::infra::vault::VaultConfig {
+            addr: server.url(),
+            token: "test-token".to_string(),
+            timeout: Duration::from_secs(5),
+            max_retries: 1,
+            retry_delay: Duration::from_millis(10),
+        })
+        .unwrap();
+        let secrets = RuntimeSecrets::new(
+            Some("hook-1".to_string()),
+            Some("metrics-1".to_string()),
+            Duration::from_secs(300),
+        );
+
+        let rotated = secrets.refresh(&vault, "sut").await.unwrap();
+
+        assert_eq!(
+            rotated,
+            vec![SecretKind::WebhookSharedSecret, SecretKind::EncryptionKey]
+        );
+        assert_eq!(
+            secrets.current(SecretKind::EncryptionKey).as_deref(),
+            Some("key-2")
+        );
+        assert!(secrets.matches(SecretKind::WebhookSharedSecret, b"hook-1"));
+        assert!(secrets.matches(SecretKind::WebhookSharedSecret, b"hook-2"));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+}
@@ -0,0 +1,218 @@
+// ============================================================================
+// TELEMETRY - TRACING DISTRIBUÍDO (OPENTELEMETRY)
+// ============================================================================
+// Spans do `tracing` convertidos em spans OpenTelemetry e, quando há um coletor
+// configurado (`OTEL_EXPORTER_OTLP_ENDPOINT`), exportados via OTLP/HTTP
+//
+// O contexto W3C (`traceparent`/`tracestate`) é extraído das requisições
+// recebidas (ver `LoggingMiddleware`), devolvido nas respostas e injetado nas
+// chamadas de saída (Keycloak, Vault). Sem coletor os spans continuam sendo
+// criados, para que o trace id seja propagado, mas não são exportados.
+
+use crate::shared::config::TelemetryConfig;
+use axum::http::HeaderMap;
+use opentelemetry::global;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+
+/// Provider de traces; `shutdown` exporta os spans pendentes
+pub struct Telemetry {
+    provider: SdkTracerProvider,
+}
+
+impl Telemetry {
+    /// Cria o provider (com exportador OTLP quando configurado) e registra o
+    /// propagador W3C como global
+    pub fn init(config: &TelemetryConfig) -> anyhow::Result<Self> {
+        install_propagator();
+
+        let sampler =
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sample_ratio)));
+        let mut builder = SdkTracerProvider::builder()
+            .with_sampler(sampler)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(config.service_name.clone())
+                    .build(),
+            );
+        if let Some(endpoint) = &config.otlp_endpoint {
+            let exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_endpoint(traces_endpoint(endpoint))
+                .build()?;
+            builder = builder.with_batch_exporter(exporter);
+        }
+        Ok(Self {
+            provider: builder.build(),
+        })
+    }
+
+    /// Layer do `tracing_subscriber` que converte os spans
+    pub fn layer<S>(&self) -> OpenTelemetryLayer<S, SdkTracer>
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(self.provider.tracer("sut-api"))
+    }
+
+    /// Exporta os spans pendentes e encerra o exportador
+    pub fn shutdown(self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!(error = %e, "telemetry shutdown failed");
+        }
+    }
+}
+
+/// Registra o propagador W3C Trace Context
+pub fn install_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// Endpoint de traces a partir do endpoint base do coletor (`.../v1/traces`)
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}/v1/traces")
+    }
+}
+
+/// Define o span remoto do `traceparent` recebido como pai do span
+pub fn set_remote_parent(span: &tracing::Span, headers: &HeaderMap) {
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    if parent.span().span_context().is_valid() {
+        // Falha apenas quando o layer OpenTelemetry não está instalado
+        let _ = span.set_parent(parent);
+    }
+}
+
+/// Injeta o contexto do span em `headers` (`traceparent` e `tracestate`), em
+/// requisições de saída e nas respostas
+pub fn inject(span: &tracing::Span, headers: &mut HeaderMap) {
+    let cx = span.context();
+    if cx.span().span_context().is_valid() {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut HeaderInjector(headers))
+        });
+    }
+}
+
+/// Trace id do span (hexadecimal), quando há um contexto válido
+pub fn trace_id(span: &tracing::Span) -> Option<String> {
+    let cx = span.context();
+    let span_context = cx.span().span_context().clone();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+/// Propaga o trace atual em uma chamada HTTP de saída
+pub fn propagate(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let mut headers = HeaderMap::new();
+    inject(&tracing::Span::current(), &mut headers);
+    request.headers(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::access_log::REQUEST_ID_HEADER;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn config(otlp_endpoint: Option<String>) -> TelemetryConfig {
+        TelemetryConfig {
+            otlp_endpoint,
+            service_name: "sut-api-test".to_string(),
+            sample_ratio: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_traces_endpoint() {
+        assert_eq!(
+            traces_endpoint("http://collector:4318"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://collector:4318/v1/traces/"),
+            "http://collector:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_spans_are_exported_to_collector() {
+        // Coletor OTLP/HTTP substituído por um servidor local
+        let mut collector = mockito::Server::new();
+        let traces = collector
+            .mock("POST", "/v1/traces")
+            .match_header("content-type", "application/x-protobuf")
+            .with_status(200)
+            .expect_at_least(1)
+            .create();
+
+        let telemetry = Telemetry::init(&config(Some(collector.url()))).unwrap();
+        let subscriber = tracing_subscriber::registry().with(telemetry.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("http.request", "http.route" = "/v1/contacts");
+            let _entered = span.enter();
+            tracing::info_span!("ContactRepository.find_all").in_scope(|| {});
+        });
+        telemetry.shutdown();
+
+        traces.assert();
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_is_continued_and_propagated() {
+        let telemetry = Telemetry::init(&config(None)).unwrap();
+        let subscriber = tracing_subscriber::registry().with(telemetry.layer());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // Handler que registra os headers que seriam enviados em uma chamada de saída
+        let app = Router::new().route(
+            "/outbound",
+            get(|| async {
+                let mut headers = HeaderMap::new();
+                inject(&tracing::Span::current(), &mut headers);
+                headers["traceparent"].to_str().unwrap().to_string()
+            }),
+        );
+        let app = app.layer(
+            crate::shared::middleware_system::MiddlewareSystem::new()
+                .with(crate::shared::middleware_system::LoggingMiddleware::new()),
+        );
+
+        let response = app
+            .oneshot(
+                Request::get("/outbound")
+                    .header("traceparent", format!("00-{TRACE_ID}-00f067aa0ba902b7-01"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Sem X-Request-Id do cliente, o request id é o trace id
+        assert_eq!(response.headers()[&REQUEST_ID_HEADER], TRACE_ID);
+        let traceparent = response.headers()["traceparent"].to_str().unwrap();
+        assert!(traceparent.starts_with(&format!("00-{TRACE_ID}-")));
+        assert!(!traceparent.contains("00f067aa0ba902b7"));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let outbound = String::from_utf8(body.to_vec()).unwrap();
+        assert!(outbound.starts_with(&format!("00-{TRACE_ID}-")));
+    }
+}
@@ -211,7 +211,10 @@ export function handleApiError(error: any): ApiError {
     const data = error.response?.data;
     
     let message = error.message;
-    if (data?.error) {
+    if (data?.detail) {
+      // application/problem+json
+      message = data.detail;
+    } else if (data?.error) {
       message = data.error;
     } else if (data?.message) {
       message = data.message;
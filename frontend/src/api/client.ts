@@ -120,11 +120,6 @@ export function useAuthedClient(){
       headers.set('X-Requested-With', 'XMLHttpRequest')
       headers.set('X-Client-Version', '1.0.0')
       
-      // Fallback para desenvolvimento: adicionar X-Dev-User header
-      if (process.env.NODE_ENV === 'development' && token) {
-        headers.set('X-Dev-User', user?.username || 'admin')
-      }
-
       // Ensure we have a resolved base URL (with a short timeout)
       let base = 'http://localhost:8080'
       try{
@@ -33,7 +33,8 @@ export class AxiosApiClient implements IApiClient {
       (error) => {
         // Transform axios error to our error format
         const apiError = {
-          message: error.response?.data?.message || error.message,
+          // Erros da API seguem application/problem+json (detail, code, errors)
+          message: error.response?.data?.detail || error.response?.data?.message || error.message,
           status: error.response?.status || 500,
           data: error.response?.data
         }